@@ -0,0 +1,11 @@
+fn main() {
+    // Keeps `include/cellular_automata.h` in sync with `src/ffi.rs`
+    #[cfg(feature = "c-header")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        cbindgen::generate(&crate_dir)
+            .expect("Unable to generate the C header")
+            .write_to_file(format!("{crate_dir}/include/cellular_automata.h"));
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+    }
+}
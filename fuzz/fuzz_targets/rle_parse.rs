@@ -0,0 +1,11 @@
+#![no_main]
+
+use cellular_automata::rle;
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary text must either be rejected or survive a write/parse roundtrip
+fuzz_target!(|text: &str| {
+    if let Ok(grid) = rle::parse(text) {
+        assert_eq!(rle::parse(&rle::write(&grid)), Ok(grid));
+    }
+});
@@ -0,0 +1,28 @@
+#![no_main]
+
+use cellular_automata::{rle, Cell};
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+
+/// Rectangular pattern of at least one Cell, `true` being alive
+#[derive(Arbitrary, Debug)]
+struct Pattern {
+    col_count: u8,
+    cells: Vec<bool>,
+}
+
+fuzz_target!(|pattern: Pattern| {
+    let col_count = usize::from(pattern.col_count.max(1));
+    let grid = pattern
+        .cells
+        .chunks_exact(col_count)
+        .map(|row| {
+            row.iter()
+                .map(|&alive| if alive { Cell::Alive } else { Cell::Dead })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    if grid.is_empty() {
+        return;
+    }
+    assert_eq!(rle::parse(&rle::write(&grid)), Ok(grid));
+});
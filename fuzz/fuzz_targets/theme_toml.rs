@@ -0,0 +1,8 @@
+#![no_main]
+
+use cellular_automata::Theme;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = Theme::from_toml(text);
+});
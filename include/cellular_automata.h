@@ -0,0 +1,76 @@
+#ifndef CELLULAR_AUTOMATA_H
+#define CELLULAR_AUTOMATA_H
+
+/* Generated by cbindgen from src/ffi.rs, `cargo build --features c-header` */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Automaton Automaton;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Conway's Game of Life on a grid where every Cell is alive with probability `density`.
+// Returns null for an empty grid or a `density` outside of `0..=1`.
+Automaton *ca_automaton_new(uintptr_t rows, uintptr_t cols, double density);
+
+// # Safety
+// `automaton` has to be null or created by `ca_automaton_new` and not freed before
+void ca_automaton_free(Automaton *automaton);
+
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`
+void ca_automaton_step(Automaton *automaton);
+
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`
+void ca_automaton_advance(Automaton *automaton, uintptr_t generations);
+
+// Generation of the automaton, `0` for null
+//
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`
+uint64_t ca_automaton_generation(const Automaton *automaton);
+
+// Number of rows, `0` for null
+//
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`
+uintptr_t ca_automaton_rows(const Automaton *automaton);
+
+// Number of columns, `0` for null
+//
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`
+uintptr_t ca_automaton_cols(const Automaton *automaton);
+
+// State number of the Cell at (`row`, `col`), `-1` outside of the grid or for null
+//
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`
+int64_t ca_automaton_get_cell(const Automaton *automaton, uintptr_t row, uintptr_t col);
+
+// Sets the Cell at (`row`, `col`) to the state `state`,
+// returns whether it was inside of the grid
+//
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`
+bool ca_automaton_set_cell(Automaton *automaton, uintptr_t row, uintptr_t col, uintptr_t state);
+
+// Switches to the rules in B/S notation, e.g. `"B36/S23"` or `"B2/S/C3"`.
+// Returns whether `rules` could be parsed, the rules are unchanged otherwise.
+//
+// # Safety
+// `automaton` has to be null or a live pointer from `ca_automaton_new`,
+// `rules` has to be null or a nul terminated string
+bool ca_automaton_set_rules(Automaton *automaton, const char *rules);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* CELLULAR_AUTOMATA_H */
@@ -0,0 +1,90 @@
+use crate::Grid;
+use itertools::izip;
+
+/// Per Cell measure of how often it changed recently
+///
+/// Every observed change adds `1` to the heat of a Cell,
+/// which then fades by the factor `decay` with every observation.
+#[derive(Debug, Clone)]
+pub struct Activity {
+    heat: Vec<Vec<f32>>,
+    /// Whether the Cell changed during the last observation
+    changed: Vec<Vec<bool>>,
+    last: Grid,
+    decay: f32,
+}
+
+impl Activity {
+    /// `decay` is clamped to `0.0..1.0`, values close to `1.0` remember changes longer
+    pub fn new(decay: f32) -> Self {
+        Self {
+            heat: Vec::new(),
+            changed: Vec::new(),
+            last: Grid::new(),
+            decay: decay.clamp(0.0, 1.0 - f32::EPSILON),
+        }
+    }
+
+    /// Compares `grid` with the previously observed one and updates the heat.
+    /// A `grid` of different dimensions resets all heat.
+    pub fn observe(&mut self, grid: &Grid) {
+        let same_size = self.last.len() == grid.len()
+            && izip!(&self.last, grid).all(|(last, row)| last.len() == row.len());
+        if !same_size {
+            self.heat = grid.iter().map(|row| vec![0.0; row.len()]).collect();
+            self.changed = grid.iter().map(|row| vec![false; row.len()]).collect();
+            self.last.clone_from(grid);
+            return;
+        }
+        let rows = izip!(&mut self.heat, &mut self.changed, &mut self.last, grid);
+        for (heat_row, changed_row, last_row, row) in rows {
+            for (heat, changed, last, cell) in izip!(heat_row, changed_row, last_row, row) {
+                *heat *= self.decay;
+                *changed = last != cell;
+                if *changed {
+                    *heat += 1.0;
+                    last.clone_from(cell);
+                }
+            }
+        }
+    }
+
+    pub fn changed(&self, row: usize, col: usize) -> bool {
+        self.changed
+            .get(row)
+            .and_then(|changed_row| changed_row.get(col))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Heat of the Cell relative to one that changes every observation, from `0.0` to `1.0`
+    pub fn intensity(&self, row: usize, col: usize) -> f32 {
+        self.heat
+            .get(row)
+            .and_then(|heat_row| heat_row.get(col))
+            .map_or(0.0, |heat| (heat * (1.0 - self.decay)).min(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Activity, Cell};
+
+    #[test]
+    fn heat_fades() {
+        let mut activity = Activity::new(0.5);
+        let still = vec![vec![Cell::Dead, Cell::Alive]];
+        let blinking = vec![vec![Cell::Dead, Cell::Dead]];
+        activity.observe(&still);
+        activity.observe(&blinking);
+        assert!(activity.intensity(0, 1) > 0.0);
+        assert!(activity.intensity(0, 0) < f32::EPSILON);
+        assert!(activity.changed(0, 1));
+        assert!(!activity.changed(0, 0));
+
+        let heat = activity.intensity(0, 1);
+        activity.observe(&blinking);
+        assert!(activity.intensity(0, 1) < heat);
+        assert!(!activity.changed(0, 1));
+    }
+}
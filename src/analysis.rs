@@ -0,0 +1,487 @@
+//! Offline analysis of patterns, used by `no_bevy_2d analyze`
+
+use crate::{grid, rle, Automaton, Cell, Generation, Grid, PackedGrid};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+
+/// Repetition of the grid found by `find_period`
+/// - `start` => First generation of the cycle
+/// - `period` => Generations until the grid repeats, `1` for still lifes and extinction
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Period {
+    pub start: Generation,
+    pub period: u64,
+}
+
+/// Steps `automaton` until its grid repeats or `max_generations` passed.
+///
+/// Spaceships never repeat in place, the edges of the grid don't wrap around
+/// so they only settle into a period after crashing into one.
+pub fn find_period(automaton: &mut Automaton, max_generations: u64) -> Option<Period> {
+    let mut seen: HashMap<PackedGrid, Generation> = HashMap::new();
+    for _ in 0..=max_generations {
+        let packed = PackedGrid::from(&automaton.grid);
+        if let Some(&start) = seen.get(&packed) {
+            return Some(Period {
+                start,
+                period: automaton.generation - start,
+            });
+        }
+        seen.insert(packed, automaton.generation);
+        automaton.step();
+    }
+    None
+}
+
+/// Dead Cells `find_cycle` keeps between the pattern and the edges of the grid
+const CYCLE_MARGIN: usize = 8;
+
+/// Repetition of the alive Cells, wherever they are on the grid, found by `find_cycle`
+/// - `start` => First generation of the cycle
+/// - `period` => Generations until the pattern repeats, `1` for still lifes and extinction
+/// - `displacement` => Rows and columns the pattern travels per period,
+///   positive downwards and to the right and `(0, 0)` unless it's a spaceship
+/// - `phases` => The pattern of every generation of the cycle, cropped to its bounding box
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle {
+    pub start: Generation,
+    pub period: u64,
+    pub displacement: (i64, i64),
+    pub phases: Vec<Grid>,
+}
+
+impl Cycle {
+    /// See `velocity`, `None` unless the pattern moves
+    pub fn velocity(&self) -> Option<String> {
+        (self.displacement != (0, 0)).then(|| velocity(self.displacement, self.period))
+    }
+
+    /// All phases side by side, aligned at the top and separated by a dead column
+    pub fn strip(&self) -> Grid {
+        let row_count = self.phases.iter().map(Vec::len).max().unwrap_or_default();
+        let mut strip = vec![Vec::new(); row_count];
+        for (index, phase) in self.phases.iter().enumerate() {
+            let col_count = phase.first().map_or(0, Vec::len);
+            for (row, cells) in strip.iter_mut().enumerate() {
+                if index > 0 {
+                    cells.push(Cell::Dead);
+                }
+                match phase.get(row) {
+                    Some(phase_row) => cells.extend_from_slice(phase_row),
+                    None => cells.extend(std::iter::repeat_n(Cell::Dead, col_count)),
+                }
+            }
+        }
+        strip
+    }
+
+    /// Every phase as an RLE pattern preceded by a `#C` comment naming the phase
+    pub fn to_rle(&self) -> String {
+        let mut text = String::new();
+        for (index, phase) in self.phases.iter().enumerate() {
+            let _ = writeln!(text, "#C Phase {index}");
+            text.push_str(&rle::write(phase));
+        }
+        text
+    }
+}
+
+/// Steps `automaton` until its alive Cells repeat in the same shape, possibly moved, or
+/// `max_generations` passed.
+///
+/// Unlike `find_period` the grid grows with the pattern, so it's never clipped by the edges and
+/// spaceships are found as well.
+pub fn find_cycle(automaton: &mut Automaton, max_generations: u64) -> Option<Cycle> {
+    // Position of the first row and column of the grid relative to the initial one
+    let mut origin = (0_i64, 0_i64);
+    let mut seen: HashMap<PackedGrid, usize> = HashMap::new();
+    let mut history: Vec<(Generation, (i64, i64), PackedGrid)> = Vec::new();
+    for _ in 0..=max_generations {
+        let growth = automaton.grow(CYCLE_MARGIN);
+        origin.0 -= i64::try_from(growth.top).unwrap_or(i64::MAX);
+        origin.1 -= i64::try_from(growth.left).unwrap_or(i64::MAX);
+        let (position, pattern) = automaton.bounding_box().map_or_else(
+            || (origin, Grid::new()),
+            |bounds| {
+                let pattern =
+                    automaton.region(bounds.top..=bounds.bottom, bounds.left..=bounds.right);
+                let offset = |index: usize| i64::try_from(index).unwrap_or(i64::MAX);
+                (
+                    (
+                        origin.0 + offset(bounds.top),
+                        origin.1 + offset(bounds.left),
+                    ),
+                    pattern,
+                )
+            },
+        );
+        let pattern = PackedGrid::from(&pattern);
+        if let Some(&index) = seen.get(&pattern) {
+            let (start, first_position, _) = history[index];
+            return Some(Cycle {
+                start,
+                period: automaton.generation - start,
+                displacement: (position.0 - first_position.0, position.1 - first_position.1),
+                phases: history
+                    .drain(index..)
+                    .map(|(_, _, phase)| phase.to_grid())
+                    .collect(),
+            });
+        }
+        seen.insert(pattern.clone(), history.len());
+        history.push((automaton.generation, position, pattern));
+        automaton.step();
+    }
+    None
+}
+
+/// Cells at most this many Cells apart belong to the same object. Alive Cells with a single
+/// dead Cell between them share a neighbor and interact, and ships like the lightweight
+/// spaceship have parts that only touch that way.
+const OBJECT_REACH: usize = 2;
+
+/// Groups of alive Cells within `OBJECT_REACH` of each other, each cropped to its bounding box
+pub fn objects(grid: &[Vec<Cell>]) -> Vec<Grid> {
+    located_objects(grid)
+        .into_iter()
+        .map(|(_, _, object)| object)
+        .collect()
+}
+
+/// `objects` along with the row and column of the top left corner of their bounding boxes
+pub fn located_objects(grid: &[Vec<Cell>]) -> Vec<(usize, usize, Grid)> {
+    let mut visited = vec![vec![false; grid.first().map_or(0, Vec::len)]; grid.len()];
+    let mut objects = Vec::new();
+    for (row, cells) in grid.iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            if *cell != Cell::Alive || visited[row][col] {
+                continue;
+            }
+            let mut members = Vec::new();
+            let mut pending = vec![(row, col)];
+            visited[row][col] = true;
+            while let Some((row, col)) = pending.pop() {
+                members.push((row, col));
+                let neighbors = (row.saturating_sub(OBJECT_REACH)..=row + OBJECT_REACH).flat_map(
+                    |neighbor_row| {
+                        (col.saturating_sub(OBJECT_REACH)..=col + OBJECT_REACH)
+                            .map(move |neighbor_col| (neighbor_row, neighbor_col))
+                    },
+                );
+                for (neighbor_row, neighbor_col) in neighbors {
+                    let alive = grid
+                        .get(neighbor_row)
+                        .and_then(|cells| cells.get(neighbor_col))
+                        == Some(&Cell::Alive);
+                    if alive && !visited[neighbor_row][neighbor_col] {
+                        visited[neighbor_row][neighbor_col] = true;
+                        pending.push((neighbor_row, neighbor_col));
+                    }
+                }
+            }
+            objects.push(crop(&members));
+        }
+    }
+    objects
+}
+
+/// Bounding box of the alive `members` and its top left corner
+fn crop(members: &[(usize, usize)]) -> (usize, usize, Grid) {
+    let min_row = members
+        .iter()
+        .map(|&(row, _)| row)
+        .min()
+        .unwrap_or_default();
+    let min_col = members
+        .iter()
+        .map(|&(_, col)| col)
+        .min()
+        .unwrap_or_default();
+    let max_row = members
+        .iter()
+        .map(|&(row, _)| row)
+        .max()
+        .unwrap_or_default();
+    let max_col = members
+        .iter()
+        .map(|&(_, col)| col)
+        .max()
+        .unwrap_or_default();
+    let mut object = vec![vec![Cell::Dead; max_col - min_col + 1]; max_row - min_row + 1];
+    for &(row, col) in members {
+        object[row - min_row][col - min_col] = Cell::Alive;
+    }
+    (min_row, min_col, object)
+}
+
+/// The same representative of all rotations and reflections of `object`
+pub(crate) fn canonical(object: &[Vec<Cell>]) -> Grid {
+    let mirrored = object.iter().rev().cloned().collect::<Grid>();
+    [object.to_vec(), mirrored]
+        .into_iter()
+        .flat_map(|object| {
+            let mut rotations = vec![object];
+            for _ in 0..3 {
+                rotations.push(grid::rotate(rotations.last().unwrap()));
+            }
+            rotations
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// Number of objects of every shape on the grid, keyed by the RLE of the shape.
+/// Rotated and reflected copies count as the same shape.
+pub fn census(grid: &[Vec<Cell>]) -> BTreeMap<String, usize> {
+    let mut census = BTreeMap::new();
+    for object in objects(grid) {
+        let shape = rle::write(&canonical(&object));
+        let body = shape.lines().skip(1).collect::<String>();
+        *census.entry(body).or_default() += 1;
+    }
+    census
+}
+
+/// 64 bit FNV-1a of the size and the `Cell::state_byte`s of the grid.
+///
+/// Unlike `std::hash::DefaultHasher` it's the same on every platform and Rust version,
+/// so it can be compared across engines and releases.
+pub fn checksum(grid: &[Vec<Cell>]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    checksum_bytes(grid).fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// 128 bit FNV-1a of the same bytes as `checksum`, for histories long enough that
+/// 64 bit collisions become likely
+pub fn checksum128(grid: &[Vec<Cell>]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+    checksum_bytes(grid).fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// The row and column count as little endian `u64`s followed by the state of every Cell
+fn checksum_bytes(grid: &[Vec<Cell>]) -> impl Iterator<Item = u8> + '_ {
+    let col_count = grid.first().map_or(0, Vec::len);
+    let size = [grid.len(), col_count]
+        .into_iter()
+        .flat_map(|length| (length as u64).to_le_bytes());
+    size.chain(grid.iter().flatten().map(Cell::state_byte))
+}
+
+/// Longest period `find_spaceships` is usually asked for,
+/// covers the gliders, spaceships and knightships of Life up to period 8
+pub const SHIP_PERIOD: u64 = 8;
+
+/// Object that reappears in the same phase at another position, found by `find_spaceships`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Spaceship {
+    /// Canonical RLE of the object, the same as its key in the `census`
+    pub shape: String,
+    /// Top left corner of the bounding box when it was detected
+    pub row: usize,
+    pub col: usize,
+    pub row_count: usize,
+    pub col_count: usize,
+    pub period: u64,
+    /// Rows and columns travelled per period, positive downwards and to the right
+    pub displacement: (i64, i64),
+}
+
+impl Spaceship {
+    /// See `velocity`
+    pub fn velocity(&self) -> String {
+        velocity(self.displacement, self.period)
+    }
+}
+
+/// Speed and direction class of a pattern travelling `displacement` every `period` generations,
+/// e.g. `c/4 diagonal`, `c/2 orthogonal` or `(2,1)c/6 oblique` with the displacement of knightships
+pub fn velocity(displacement: (i64, i64), period: u64) -> String {
+    let (rows, cols) = (displacement.0.unsigned_abs(), displacement.1.unsigned_abs());
+    let speed = |distance: u64| {
+        let divisor = gcd(distance, period);
+        match (distance / divisor, period / divisor) {
+            (1, 1) => "c".to_owned(),
+            (1, period) => format!("c/{period}"),
+            (distance, 1) => format!("{distance}c"),
+            (distance, period) => format!("{distance}c/{period}"),
+        }
+    };
+    if rows == 0 || cols == 0 {
+        format!("{} orthogonal", speed(rows.max(cols)))
+    } else if rows == cols {
+        format!("{} diagonal", speed(rows))
+    } else {
+        format!("({},{})c/{period} oblique", rows.max(cols), rows.min(cols))
+    }
+}
+
+const fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Objects of the grid of `automaton` that reappear unchanged, but moved,
+/// within `max_period` generations.
+///
+/// An object can't travel faster than one Cell per generation, ships that collide with
+/// something or the edge of the grid before completing a period aren't found.
+pub fn find_spaceships(automaton: &Automaton, max_period: u64) -> Vec<Spaceship> {
+    let start = located_objects(&automaton.grid);
+    let mut automaton = automaton.clone();
+    let mut ships: Vec<Option<Spaceship>> = vec![None; start.len()];
+    for period in 1..=max_period {
+        if ships.iter().all(Option::is_some) {
+            break;
+        }
+        automaton.step();
+        let current = located_objects(&automaton.grid);
+        let reach = i64::try_from(period).unwrap_or(i64::MAX);
+        for ((row, col, object), ship) in start.iter().zip(&mut ships) {
+            if ship.is_some() {
+                continue;
+            }
+            let offset = |&(moved_row, moved_col, _): &(usize, usize, Grid)| {
+                let offset = |to: usize, from: usize| {
+                    i64::try_from(to).unwrap_or(i64::MAX) - i64::try_from(from).unwrap_or(i64::MAX)
+                };
+                (offset(moved_row, *row), offset(moved_col, *col))
+            };
+            let moved = current
+                .iter()
+                .filter(|(_, _, moved)| moved == object)
+                .map(offset)
+                .filter(|&displacement| displacement != (0, 0))
+                .find(|(rows, cols)| rows.abs() <= reach && cols.abs() <= reach);
+            if let Some(displacement) = moved {
+                let shape = rle::write(&canonical(object));
+                *ship = Some(Spaceship {
+                    shape: shape.lines().skip(1).collect(),
+                    row: *row,
+                    col: *col,
+                    row_count: object.len(),
+                    col_count: object.first().map_or(0, Vec::len),
+                    period,
+                    displacement,
+                });
+            }
+        }
+    }
+    ships.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analysis::{
+            census, checksum, checksum128, find_cycle, find_period, find_spaceships, objects,
+            Period,
+        },
+        grid, rle, Automaton, Cell, Generation,
+    };
+
+    #[test]
+    fn census_and_period() {
+        // Two blocks and two differently oriented blinkers, far enough apart not to interact
+        let block = rle::parse("2o$2o!").unwrap();
+        let blinker = rle::parse("3o!").unwrap();
+        let mut automaton = Automaton::builder()
+            .row_count(16)
+            .col_count(16)
+            .grid(vec![vec![Cell::Dead; 16]; 16])
+            .build();
+        automaton.stamp(&block, 1, 1);
+        automaton.stamp(&block, 1, 10);
+        automaton.stamp(&blinker, 12, 9);
+        automaton.stamp(&grid::rotate(&blinker), 8, 2);
+        assert_eq!(objects(&automaton.grid).len(), 4);
+        let census = census(&automaton.grid);
+        assert_eq!(census.len(), 2);
+        assert_eq!(census.get("2o$2o!"), Some(&2));
+        assert_eq!(census.get("o$o$o!"), Some(&2));
+
+        let before = checksum(&automaton.grid);
+        automaton.step();
+        assert_ne!(checksum(&automaton.grid), before);
+        automaton.step();
+        assert_eq!(checksum(&automaton.grid), before);
+        assert_eq!(
+            checksum(&rle::parse("bo$2bo$3o!").unwrap()),
+            0x16b6_fbdf_1ab1_e31a
+        );
+        assert_eq!(
+            checksum128(&rle::parse("bo$2bo$3o!").unwrap()),
+            0xc38d_7239_3a17_aca5_f138_a95f_eaf5_fc2a
+        );
+
+        automaton.step();
+        assert_eq!(
+            find_period(&mut automaton, 10),
+            Some(Period {
+                start: Generation(3),
+                period: 2
+            })
+        );
+    }
+
+    #[test]
+    fn spaceships() {
+        let mut automaton = Automaton::builder()
+            .row_count(24)
+            .col_count(24)
+            .grid(vec![vec![Cell::Dead; 24]; 24])
+            .build();
+        automaton.stamp(&rle::parse("bo$2bo$3o!").unwrap(), 2, 2);
+        automaton.stamp(&rle::parse("bo2bo$o4b$o3bo$4o!").unwrap(), 14, 14);
+        automaton.stamp(&rle::parse("3o!").unwrap(), 20, 2);
+        let ships = find_spaceships(&automaton, 4);
+        assert_eq!(ships.len(), 2);
+        assert_eq!((ships[0].row, ships[0].col), (2, 2));
+        assert_eq!(ships[0].displacement, (1, 1));
+        assert_eq!(ships[0].velocity(), "c/4 diagonal");
+        assert_eq!(ships[1].displacement, (0, -2));
+        assert_eq!(ships[1].velocity(), "c/2 orthogonal");
+    }
+
+    #[test]
+    fn cycles() {
+        let pattern = |rle: &str| {
+            let pattern = rle::parse(rle).unwrap();
+            let (row_count, col_count) = (pattern.len() + 2, pattern[0].len() + 2);
+            let mut automaton = Automaton::builder()
+                .row_count(row_count)
+                .col_count(col_count)
+                .grid(vec![vec![Cell::Dead; col_count]; row_count])
+                .build();
+            automaton.stamp(&pattern, 1, 1);
+            automaton
+        };
+
+        // The glider would crash into the edges of its tiny grid without growing it
+        let glider = find_cycle(&mut pattern("bo$2bo$3o!"), 10).unwrap();
+        assert_eq!((glider.start, glider.period), (Generation(0), 4));
+        assert_eq!(glider.displacement, (1, 1));
+        assert_eq!(glider.velocity().as_deref(), Some("c/4 diagonal"));
+        assert_eq!(glider.phases.len(), 4);
+        assert_eq!(glider.phases[0], rle::parse("bo$2bo$3o!").unwrap());
+
+        let blinker = find_cycle(&mut pattern("3o!"), 10).unwrap();
+        assert_eq!((blinker.period, blinker.displacement), (2, (0, 0)));
+        assert_eq!(blinker.velocity(), None);
+        assert_eq!(blinker.strip(), rle::parse("3obo$4bo$4bo!").unwrap());
+        assert_eq!(blinker.to_rle().matches("#C Phase").count(), 2);
+
+        // The R-pentomino takes over a thousand generations to settle
+        assert_eq!(find_cycle(&mut pattern("b2o$2o$bo!"), 50), None);
+    }
+}
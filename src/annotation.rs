@@ -0,0 +1,228 @@
+//! Text labels attached to Cells, documenting what the parts of a construction do.
+//! Saved with the session, floated over the grid by the app and listed in a legend by exports.
+
+use crate::{grid::BoundingBox, theme::Rgb};
+use image::RgbImage;
+use serde::{Deserialize, Serialize};
+use std::{error, fmt, fmt::Write, str::FromStr};
+
+/// Color of the markers outlining annotated Cells in exports
+pub const MARKER_COLOR: Rgb = [255, 190, 0];
+/// Font size of the legend and marker numbers in SVG exports
+const FONT_SIZE: usize = 12;
+/// Height of a line of the legend in SVG exports
+const LINE_HEIGHT: usize = 16;
+
+/// Errors that can occur while parsing an `Annotation`
+/// - `Format` => The text isn't `ROW,COL: TEXT`
+/// - `Coordinate` => The row or column isn't a number
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AnnotationError {
+    Format(String),
+    Coordinate(String),
+}
+
+impl fmt::Display for AnnotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format(text) => write!(f, "Annotation {text:?} isn't ROW,COL: TEXT"),
+            Self::Coordinate(coordinate) => write!(f, "Invalid coordinate {coordinate:?}"),
+        }
+    }
+}
+
+impl error::Error for AnnotationError {}
+
+/// A label on the Cell at (`row`, `col`)
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub row: usize,
+    pub col: usize,
+    pub text: String,
+}
+
+impl Annotation {
+    pub fn new(row: usize, col: usize, text: impl Into<String>) -> Self {
+        Self {
+            row,
+            col,
+            text: text.into(),
+        }
+    }
+
+    /// The annotation on the grid `grid::crop` makes of `bounds` and `margin`,
+    /// `None` if its Cell is cropped off
+    pub fn cropped(&self, bounds: BoundingBox, margin: usize) -> Option<Self> {
+        let row = (self.row + margin).checked_sub(bounds.top)?;
+        let col = (self.col + margin).checked_sub(bounds.left)?;
+        (row < bounds.row_count() + 2 * margin && col < bounds.col_count() + 2 * margin)
+            .then(|| Self::new(row, col, self.text.clone()))
+    }
+}
+
+/// `ROW,COL: TEXT`, the way annotations are written into the comments of pattern files
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}: {}", self.row, self.col, self.text)
+    }
+}
+
+/// Parses the `ROW,COL: TEXT` written by `Display`
+impl FromStr for Annotation {
+    type Err = AnnotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = || AnnotationError::Format(s.to_owned());
+        let (coordinates, text) = s.split_once(':').ok_or_else(format)?;
+        let (row, col) = coordinates.split_once(',').ok_or_else(format)?;
+        let coordinate = |coordinate: &str| {
+            coordinate
+                .trim()
+                .parse()
+                .map_err(|_| AnnotationError::Coordinate(coordinate.trim().to_owned()))
+        };
+        Ok(Self::new(coordinate(row)?, coordinate(col)?, text.trim()))
+    }
+}
+
+/// The annotations of a grid cropped with `grid::crop`
+pub fn crop(annotations: &[Annotation], bounds: BoundingBox, margin: usize) -> Vec<Annotation> {
+    annotations
+        .iter()
+        .filter_map(|annotation| annotation.cropped(bounds, margin))
+        .collect()
+}
+
+/// Numbered lines listing the annotations, the numbers match the markers of exports
+pub fn legend(annotations: &[Annotation]) -> Vec<String> {
+    annotations
+        .iter()
+        .enumerate()
+        .map(|(index, annotation)| format!("{}. {annotation}", index + 1))
+        .collect()
+}
+
+/// Outlines the annotated Cells of an image rendered with `scale` pixels per Cell
+#[allow(clippy::cast_possible_truncation)]
+pub fn mark(image: &mut RgbImage, annotations: &[Annotation], scale: u32) {
+    let scale = scale.max(1);
+    for annotation in annotations {
+        let (left, top) = (annotation.col as u32 * scale, annotation.row as u32 * scale);
+        for offset in 0..scale {
+            for (x, y) in [
+                (left + offset, top),
+                (left + offset, top + scale - 1),
+                (left, top + offset),
+                (left + scale - 1, top + offset),
+            ] {
+                if x < image.width() && y < image.height() {
+                    image.put_pixel(x, y, image::Rgb(MARKER_COLOR));
+                }
+            }
+        }
+    }
+}
+
+/// Height of the legend below the grid in SVG exports
+pub(crate) const fn legend_height(annotations: &[Annotation]) -> usize {
+    if annotations.is_empty() {
+        0
+    } else {
+        annotations.len() * LINE_HEIGHT + LINE_HEIGHT / 2
+    }
+}
+
+/// Writes numbered markers outlining the annotated Cells and the legend starting at `top`
+pub(crate) fn write_svg(svg: &mut String, annotations: &[Annotation], scale: usize, top: usize) {
+    let color = format!(
+        "#{:02x}{:02x}{:02x}",
+        MARKER_COLOR[0], MARKER_COLOR[1], MARKER_COLOR[2]
+    );
+    for (index, annotation) in annotations.iter().enumerate() {
+        let (x, y) = (annotation.col * scale, annotation.row * scale);
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{scale}\" height=\"{scale}\" fill=\"none\" \
+             stroke=\"{color}\"/>"
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{y}\" font-size=\"{FONT_SIZE}\" fill=\"{color}\">{}</text>",
+            x + scale,
+            index + 1
+        );
+    }
+    for (index, line) in legend(annotations).iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            "<text x=\"4\" y=\"{}\" font-size=\"{FONT_SIZE}\" fill=\"{color}\">{}</text>",
+            top + (index + 1) * LINE_HEIGHT,
+            escape(line)
+        );
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        annotation::{self, Annotation, AnnotationError},
+        grid::{self, BoundingBox},
+        Cell, Theme,
+    };
+
+    #[test]
+    fn parsing_and_cropping() {
+        let annotation: Annotation = "3, 4: Gun fires <here>".parse().unwrap();
+        assert_eq!(annotation, Annotation::new(3, 4, "Gun fires <here>"));
+        assert_eq!(annotation.to_string().parse(), Ok(annotation.clone()));
+        assert_eq!(
+            "3: gun".parse::<Annotation>(),
+            Err(AnnotationError::Format("3: gun".into()))
+        );
+        assert_eq!(
+            "x,4: gun".parse::<Annotation>(),
+            Err(AnnotationError::Coordinate("x".into()))
+        );
+
+        let bounds = BoundingBox {
+            top: 2,
+            left: 4,
+            bottom: 3,
+            right: 6,
+        };
+        assert_eq!(
+            annotation.cropped(bounds, 1),
+            Some(Annotation::new(2, 1, "Gun fires <here>"))
+        );
+        assert_eq!(
+            annotation.cropped(bounds, 0),
+            Some(Annotation::new(1, 0, "Gun fires <here>"))
+        );
+        assert_eq!(Annotation::new(0, 4, "").cropped(bounds, 1), None);
+        assert_eq!(Annotation::new(3, 8, "").cropped(bounds, 1), None);
+
+        let annotations = [annotation, Annotation::new(0, 0, "corner")];
+        assert_eq!(annotation::legend(&annotations)[1], "2. 0,0: corner");
+        let svg = grid::to_annotated_svg(
+            &vec![vec![Cell::Dead; 8]; 6],
+            &Theme::classic(),
+            0,
+            2,
+            &annotations,
+        );
+        assert!(svg.contains("height=\"52\""));
+        assert!(svg.contains("1. 3,4: Gun fires &lt;here&gt;"));
+
+        let mut image = grid::to_image(&vec![vec![Cell::Dead; 8]; 6], &Theme::classic(), 0, 3);
+        annotation::mark(&mut image, &annotations, 3);
+        assert_eq!(image.get_pixel(12, 9).0, annotation::MARKER_COLOR);
+        assert_eq!(image.get_pixel(13, 10).0, Theme::classic().dead);
+    }
+}
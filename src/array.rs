@@ -0,0 +1,97 @@
+//! `ndarray` interop for numerical analysis of the simulation state, e.g. convolutions or FFTs.
+//! A `PackedGrid` is viewed as an `Array2<u8>` of `Cell::state_byte`s without copying,
+//! and `next_array` steps such arrays directly.
+
+use crate::{Cell, Neighborhood, PackedGrid, RuleTable};
+use ndarray::{Array2, ArrayView2, ArrayViewMut2};
+
+impl PackedGrid {
+    /// The `Cell::state_byte`s as rows x columns array, sharing the memory of the grid
+    pub fn view(&self) -> ArrayView2<'_, u8> {
+        ArrayView2::from_shape((self.row_count(), self.col_count()), self.as_bytes())
+            .expect("The bytes of a packed grid are rows times columns")
+    }
+
+    /// Like `PackedGrid::view`, writing to the array edits the grid
+    pub fn view_mut(&mut self) -> ArrayViewMut2<'_, u8> {
+        let shape = (self.row_count(), self.col_count());
+        ArrayViewMut2::from_shape(shape, self.as_bytes_mut())
+            .expect("The bytes of a packed grid are rows times columns")
+    }
+}
+
+/// Takes over the buffer of the grid
+impl From<PackedGrid> for Array2<u8> {
+    fn from(grid: PackedGrid) -> Self {
+        let shape = (grid.row_count(), grid.col_count());
+        Self::from_shape_vec(shape, grid.into_bytes())
+            .expect("The bytes of a packed grid are rows times columns")
+    }
+}
+
+/// Takes over the buffer of arrays in standard layout, others are copied first
+impl From<Array2<u8>> for PackedGrid {
+    fn from(array: Array2<u8>) -> Self {
+        let col_count = array.ncols();
+        let array = if array.is_standard_layout() {
+            array
+        } else {
+            array.as_standard_layout().into_owned()
+        };
+        Self::from_bytes(col_count, array.into_raw_vec()).expect("An array is rows times columns")
+    }
+}
+
+/// Array of `Cell::state_byte`s of the next generation according to the rules compiled
+/// into `table`, the edges don't wrap around like in `transition`
+pub fn next_array(
+    cells: ArrayView2<'_, u8>,
+    neighborhood: Neighborhood,
+    table: &RuleTable,
+) -> Array2<u8> {
+    let (row_count, col_count) = cells.dim();
+    let cell = |row: usize, col: usize| Cell::from_state_number(usize::from(cells[[row, col]]));
+    Array2::from_shape_fn((row_count, col_count), |(row, col)| {
+        let neighbors = (row.saturating_sub(1)..=(row + 1).min(row_count - 1))
+            .flat_map(|irow| {
+                (col.saturating_sub(1)..=(col + 1).min(col_count - 1)).map(move |icol| (irow, icol))
+            })
+            .filter(|&(irow, icol)| match neighborhood {
+                Neighborhood::Moore => irow != row || icol != col,
+                Neighborhood::VonNeumann => (irow == row) != (icol == col),
+            })
+            .map(|(irow, icol)| cell(irow, icol))
+            .collect::<Vec<_>>();
+        table.next_state(&cell(row, col), &neighbors).state_byte()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::next_array, rle, transition, Cell, Neighborhood, PackedGrid, RuleSet, RuleTable,
+    };
+    use ndarray::Array2;
+
+    #[test]
+    fn interop() {
+        let glider = rle::parse("5b$2bo2b$3bob$b3ob$5b$5b!").unwrap();
+        let mut packed = PackedGrid::from(&glider);
+        assert_eq!(packed.view().dim(), (6, 5));
+        assert_eq!(packed.view()[[1, 2]], Cell::Alive.state_byte());
+        packed.view_mut()[[0, 0]] = Cell::Alive.state_byte();
+        assert_eq!(packed.as_bytes()[0], Cell::Alive.state_byte());
+        packed.view_mut()[[0, 0]] = Cell::Dead.state_byte();
+
+        let table = RuleTable::new(&RuleSet::default());
+        let mut array = Array2::from(packed);
+        for _ in 0..4 {
+            array = next_array(array.view(), Neighborhood::Moore, &table);
+        }
+        let mut grid = glider;
+        for _ in 0..4 {
+            grid = transition::next_grid(&grid, Neighborhood::Moore, &RuleSet::default());
+        }
+        assert_eq!(PackedGrid::from(array).to_grid(), grid);
+    }
+}
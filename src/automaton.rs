@@ -0,0 +1,410 @@
+use crate::{
+    analysis,
+    grid::{self, BoundingBox},
+    metrics::Metrics,
+    transition, tui, Cell, Grid, NeighborCounts, Neighborhood, PackedGrid, RuleSet, RuleTable,
+    Seeding,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, iter,
+    ops::{AddAssign, RangeInclusive, Sub},
+};
+
+/// Number of generations an `Automaton` was stepped
+#[derive(
+    Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize,
+)]
+pub struct Generation(pub u64);
+
+impl AddAssign<u64> for Generation {
+    fn add_assign(&mut self, generations: u64) {
+        self.0 += generations;
+    }
+}
+
+/// Generations between two `Generation`s, `0` if `rhs` is the later one
+impl Sub for Generation {
+    type Output = u64;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.saturating_sub(rhs.0)
+    }
+}
+
+impl fmt::Display for Generation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Dead rows and columns `Automaton::grow` added on each side of the grid
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Growth {
+    pub top: usize,
+    pub bottom: usize,
+    pub left: usize,
+    pub right: usize,
+}
+
+impl Growth {
+    pub const fn is_empty(&self) -> bool {
+        self.top == 0 && self.bottom == 0 && self.left == 0 && self.right == 0
+    }
+}
+
+/// A Cell that changed during the last generation, see `Automaton::changes`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Change {
+    pub row: usize,
+    pub col: usize,
+    /// State of the Cell before the generation, the new one is in the grid
+    pub before: Cell,
+}
+
+#[derive(typed_builder::TypedBuilder, Debug, Clone)]
+#[builder(field_defaults(default))]
+pub struct Automaton {
+    pub generation: Generation,
+    pub row_count: usize,
+    pub col_count: usize,
+    pub seeding: Seeding,
+    /// Populated according to `seeding` unless set explicitly
+    #[builder(default = seeding.populate(row_count, col_count))]
+    pub grid: Grid,
+    pub neighborhood_type: Neighborhood,
+    pub rule_set: RuleSet,
+    /// `rule_set` compiled for stepping, recompiled whenever `rule_set` changed
+    #[builder(setter(skip), default = RuleTable::new(&rule_set))]
+    pub(crate) rule_table: RuleTable,
+    /// Cells that became `Cell::Alive` during the last generation
+    #[builder(setter(skip))]
+    pub births: usize,
+    /// Cells that stopped being `Cell::Alive` during the last generation
+    #[builder(setter(skip))]
+    pub deaths: usize,
+    /// Whether stepping records the `changes`, which costs memory per changed Cell
+    pub track_changes: bool,
+    /// Every Cell that changed during the last generation if `track_changes` is set,
+    /// e.g. for updating a `zobrist::Zobrist` hash
+    #[builder(setter(skip))]
+    pub changes: Vec<Change>,
+}
+
+impl Default for Automaton {
+    fn default() -> Self {
+        const ROW_COUNT: usize = 20;
+        const COL_COUNT: usize = 20;
+        Self {
+            row_count: ROW_COUNT,
+            col_count: COL_COUNT,
+            seeding: Seeding::default(),
+            grid: Seeding::default().populate(ROW_COUNT, COL_COUNT),
+            generation: Generation::default(),
+            neighborhood_type: Neighborhood::default(),
+            rule_set: RuleSet::default(),
+            rule_table: RuleTable::new(&RuleSet::default()),
+            births: 0,
+            deaths: 0,
+            track_changes: false,
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl Automaton {
+    /// Copies `pattern` onto the grid with its top left corner at (`row`, `col`).
+    /// Parts of the pattern that lie outside of the grid are clipped.
+    pub fn stamp(&mut self, pattern: &[Vec<Cell>], row: usize, col: usize) {
+        for (pattern_row, grid_row) in pattern.iter().zip(self.grid.iter_mut().skip(row)) {
+            for (cell, target) in pattern_row.iter().zip(grid_row.iter_mut().skip(col)) {
+                *target = cell.clone();
+            }
+        }
+    }
+
+    /// Copies the Cells within the given bounds out of the grid
+    pub fn region(&self, rows: RangeInclusive<usize>, cols: RangeInclusive<usize>) -> Grid {
+        self.grid
+            .get(rows)
+            .unwrap_or_default()
+            .iter()
+            .map(|row| row.get(cols.clone()).unwrap_or_default().to_vec())
+            .collect()
+    }
+
+    /// Number of `Cell::Alive` Cells
+    pub fn population(&self) -> usize {
+        self.grid
+            .iter()
+            .flatten()
+            .filter(|&cell| *cell == Cell::Alive)
+            .count()
+    }
+
+    /// Stable 64 bit hash of the grid, equal for equal grids on every platform and release,
+    /// see `analysis::checksum` and `state_history::StateHistory`
+    pub fn state_hash(&self) -> u64 {
+        analysis::checksum(&self.grid)
+    }
+
+    /// Like `state_hash` with 128 bits, see `analysis::checksum128`
+    pub fn state_hash128(&self) -> u128 {
+        analysis::checksum128(&self.grid)
+    }
+
+    /// A copy of the grid with one byte per Cell, see `PackedGrid`
+    pub fn packed(&self) -> PackedGrid {
+        PackedGrid::from(&self.grid)
+    }
+
+    /// Smallest rectangle containing every `Cell::Alive`, `None` if no Cell is alive
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        grid::bounding_box(&self.grid)
+    }
+
+    /// Adds `margin` dead rows or columns on every side that an alive Cell is closer than
+    /// `margin` Cells to, so growing patterns aren't clipped by the edges of the grid.
+    /// `row_count` and `col_count` are set to the size of the grown grid.
+    pub fn grow(&mut self, margin: usize) -> Growth {
+        let Some(bounds) = self.bounding_box() else {
+            return Growth::default();
+        };
+        // The counts may not match the grid, e.g. when only the grid was passed to the builder
+        let (row_count, col_count) = (self.grid.len(), self.grid.first().map_or(0, Vec::len));
+        let pad = |distance: usize| if distance < margin { margin } else { 0 };
+        let growth = Growth {
+            top: pad(bounds.top),
+            bottom: pad(row_count - 1 - bounds.bottom),
+            left: pad(bounds.left),
+            right: pad(col_count - 1 - bounds.right),
+        };
+        if growth.is_empty() {
+            return growth;
+        }
+        self.col_count = col_count + growth.left + growth.right;
+        for row in &mut self.grid {
+            row.splice(0..0, iter::repeat_n(Cell::Dead, growth.left));
+            row.extend(iter::repeat_n(Cell::Dead, growth.right));
+        }
+        let empty_row = vec![Cell::Dead; self.col_count];
+        self.grid
+            .splice(0..0, iter::repeat_n(empty_row.clone(), growth.top));
+        self.grid.extend(iter::repeat_n(empty_row, growth.bottom));
+        self.row_count = row_count + growth.top + growth.bottom;
+        growth
+    }
+
+    /// Entropy, complexity and activity of the current generation
+    pub fn metrics(&self) -> Metrics {
+        self.into()
+    }
+
+    /// Steps `generations` times
+    pub fn advance(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Iterator over snapshots of the grid of every following generation,
+    /// stepping the automaton in place.
+    /// The first item is the grid of generation `self.generation + 1`.
+    pub const fn generations(&mut self) -> Generations<'_> {
+        Generations { automaton: self }
+    }
+
+    /// Computes the next generation in place
+    pub fn step(&mut self) {
+        if self.rule_table.rule_set() != &self.rule_set {
+            self.rule_table = RuleTable::new(&self.rule_set);
+        }
+        let next =
+            transition::compiled_next_grid(&self.grid, self.neighborhood_type, &self.rule_table);
+        self.replace_grid(next);
+    }
+
+    /// Computes the next generation in place with a custom transition instead of the `rule_set`.
+    /// If the transition fails for any Cell, the automaton is left unchanged.
+    pub fn try_step_with<E>(
+        &mut self,
+        next_state: impl FnMut(&Cell, &NeighborCounts) -> Result<Cell, E>,
+    ) -> Result<(), E> {
+        let next = transition::try_next_grid(&self.grid, self.neighborhood_type, next_state)?;
+        self.replace_grid(next);
+        Ok(())
+    }
+
+    /// Advances to the generation of `next`, counting the births and deaths
+    pub(crate) fn replace_grid(&mut self, next: Grid) {
+        self.generation += 1;
+        (self.births, self.deaths) = self.grid.iter().flatten().zip(next.iter().flatten()).fold(
+            (0, 0),
+            |(births, deaths), (before, after)| match (
+                *before == Cell::Alive,
+                *after == Cell::Alive,
+            ) {
+                (false, true) => (births + 1, deaths),
+                (true, false) => (births, deaths + 1),
+                _ => (births, deaths),
+            },
+        );
+        self.changes.clear();
+        if self.track_changes {
+            for (row, (before, after)) in self.grid.iter().zip(&next).enumerate() {
+                self.changes.extend(
+                    before
+                        .iter()
+                        .zip(after)
+                        .enumerate()
+                        .filter(|(_, (before, after))| before != after)
+                        .map(|(col, (before, _))| Change {
+                            row,
+                            col,
+                            before: before.clone(),
+                        }),
+                );
+            }
+        }
+        self.grid = next;
+    }
+}
+
+/// See `Automaton::generations`
+#[derive(Debug)]
+pub struct Generations<'a> {
+    automaton: &'a mut Automaton,
+}
+
+impl Iterator for Generations<'_> {
+    type Item = Grid;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.automaton.step();
+        Some(self.automaton.grid.clone())
+    }
+}
+
+impl fmt::Display for Automaton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // ~ PLAIN TEXT
+        /* writeln!(f, "NeighborhoodType: {:?}", self.neighborhood_type)?;
+        writeln!(f, "Generation: {}", self.generation.0)?;
+        writeln!(f, "Grid:")?;
+        for idx in 0..self.col_count {
+            write!(f, " {idx:^8} ")?;
+        }
+        writeln!(f)?;
+        for (idx, row) in self.grid.iter().enumerate() {
+            write!(f, "{idx:<2}[")?;
+            for col in row {
+                write!(f, "{:<8}, ", format!("{}", col))?;
+            }
+            writeln!(f, "]")?;
+        } */
+        // ~ UNICODE
+        writeln!(f, "NeighborhoodType: {:?}", self.neighborhood_type)?;
+        writeln!(f, "Generation: {}", self.generation)?;
+        writeln!(f, "Grid:")?;
+        f.write_str(&tui::render_emoji(self, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{automaton::Growth, Automaton, Cell, Generation, RuleSet};
+
+    #[test]
+    fn primitive_test_1() {
+        let grid = vec![vec![Cell::Dead, Cell::Alive, Cell::Dead]; 3];
+        let mut automaton = Automaton::builder()
+            .row_count(3)
+            .col_count(3)
+            .grid(grid.clone())
+            .build();
+
+        let mut generations = automaton.generations();
+        assert_ne!(generations.next().unwrap(), grid);
+        assert_eq!(generations.next().unwrap(), grid);
+        assert_eq!(automaton.generation, Generation(2));
+    }
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed")]
+    fn primitive_test_2() {
+        let grid = vec![vec![Cell::Dead, Cell::Alive, Cell::Dead]; 3];
+        let mut automaton = Automaton::builder()
+            .row_count(3)
+            .col_count(3)
+            .grid(grid.clone())
+            .build();
+
+        let mut generations = automaton.generations();
+        assert_eq!(generations.next().unwrap(), grid);
+        assert_eq!(generations.next().unwrap(), grid);
+    }
+
+    #[test]
+    fn decay() {
+        let grid = vec![vec![Cell::Dead, Cell::Alive, Cell::Dead]; 3];
+        let mut automaton = Automaton::builder()
+            .row_count(3)
+            .col_count(3)
+            .rule_set(RuleSet::life_like(vec![3], vec![2, 3], 2))
+            .grid(grid)
+            .build();
+
+        let dying = Cell::Dying {
+            ticks_till_death: 2,
+        };
+        automaton.step();
+        assert_eq!(automaton.grid[0][1], dying);
+        assert_eq!(automaton.grid[1][1], Cell::Alive);
+    }
+
+    #[test]
+    fn growth() {
+        let mut grid = vec![vec![Cell::Dead; 6]; 6];
+        grid[1][4] = Cell::Alive;
+        grid[2][4] = Cell::Alive;
+        let mut automaton = Automaton::builder()
+            .row_count(6)
+            .col_count(6)
+            .grid(grid)
+            .build();
+
+        let growth = automaton.grow(2);
+        assert_eq!(
+            growth,
+            Growth {
+                top: 2,
+                bottom: 0,
+                left: 0,
+                right: 2,
+            }
+        );
+        assert_eq!((automaton.row_count, automaton.col_count), (8, 8));
+        assert_eq!(automaton.grid.len(), 8);
+        assert!(automaton.grid.iter().all(|row| row.len() == 8));
+        assert_eq!(automaton.grid[3][4], Cell::Alive);
+        assert_eq!(automaton.population(), 2);
+        assert!(automaton.grow(2).is_empty());
+
+        automaton.grid = vec![vec![Cell::Dead; 8]; 8];
+        assert!(automaton.grow(2).is_empty());
+
+        // Without counts the builder leaves them at 0, the grid tells the size
+        let mut automaton = Automaton::builder()
+            .grid(vec![vec![Cell::Alive; 3]; 2])
+            .build();
+        assert_eq!(
+            automaton.grow(1),
+            Growth {
+                top: 1,
+                bottom: 1,
+                left: 1,
+                right: 1,
+            }
+        );
+        assert_eq!((automaton.row_count, automaton.col_count), (4, 5));
+    }
+}
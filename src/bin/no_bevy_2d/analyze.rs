@@ -0,0 +1,628 @@
+//! Facts about pattern files
+
+use crate::{
+    args::PatternArgs,
+    failure::Failure,
+    files::{load_pattern, write_table},
+};
+use cellular_automata::{
+    analysis::{self, Cycle, Spaceship},
+    collision::{self, Collision},
+    enumeration,
+    format::Format,
+    grid, identification, mean_field,
+    predecessor::{self, Boundary, Search},
+    reversibility::{self, Verdict},
+    rle,
+    table::{Table, Values},
+    Neighborhood, RuleSet, Symmetry, Theme,
+};
+use clap::{Args, Subcommand};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    thread,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum Analysis {
+    /// Counts the objects of every shape after some generations, with the velocity of spaceships
+    Census(SnapshotArgs),
+    /// Steps until the pattern repeats, possibly moved, and prints the period and displacement
+    Period(PeriodArgs),
+    /// Lists the spaceships after some generations with their period, displacement and velocity
+    Ships(ShipsArgs),
+    /// Prints a hash of the grid after some generations that is stable across platforms,
+    /// engines and versions, to check that runs are deterministic
+    Checksum(SnapshotArgs),
+    /// Prints the largest symmetry group of the alive Cells after some generations,
+    /// `C1` if they aren't symmetric
+    Symmetry(SnapshotArgs),
+    /// Lists every still life and period 2 oscillator fitting into a box as RLE,
+    /// each once regardless of position, rotation, reflection and phase
+    Enumerate(EnumerateArgs),
+    /// Searches for a pattern that becomes the given one in one generation, printed as RLE.
+    /// The `--padding` around the pattern has to stay dead, keep it small
+    /// as the search is exponential in the size of the grid.
+    Predecessor(PredecessorArgs),
+    /// Collides the second pattern with the first at every offset and phase and lists how each
+    /// collision ended, new spaceships and clean constructions of a single object first
+    Collide(CollideArgs),
+    /// Infers the Life-like or Generations rules most likely to have produced consecutive
+    /// generations saved uncropped, e.g. with `convert --full`
+    Identify(IdentifyArgs),
+    /// Checks whether no two grids of a small torus share a successor, i.e. the rules are
+    /// reversible there, and prints two grids that do otherwise
+    Reversibility(ReversibilityArgs),
+    /// Prints the density of alive Cells in the next generation as a function of the current
+    /// one in the mean-field approximation, and the densities it keeps unchanged
+    Meanfield(MeanfieldArgs),
+}
+
+/// A pattern after some generations
+#[derive(Args, Debug)]
+pub struct SnapshotArgs {
+    #[command(flatten)]
+    pub pattern: PatternArgs,
+    #[arg(long, default_value_t = 0)]
+    pub generations: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct PeriodArgs {
+    #[command(flatten)]
+    pub pattern: PatternArgs,
+    /// Gives up after this many generations
+    #[arg(long, default_value_t = 1000)]
+    pub max_generations: u64,
+    /// Writes every phase to `.rle` as one pattern each, or side by side to an image
+    #[arg(long)]
+    pub phases: Option<PathBuf>,
+    /// Pixels per Cell of the phase image
+    #[arg(long, default_value_t = 8)]
+    pub scale: u32,
+}
+
+#[derive(Args, Debug)]
+pub struct ShipsArgs {
+    #[command(flatten)]
+    pub snapshot: SnapshotArgs,
+    /// Longest period searched for
+    #[arg(long, default_value_t = analysis::SHIP_PERIOD)]
+    pub max_period: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct EnumerateArgs {
+    #[arg(long)]
+    pub rows: usize,
+    #[arg(long)]
+    pub cols: usize,
+    /// Rules in B/S notation
+    #[arg(long, default_value = "B3/S23")]
+    pub rules: RuleSet,
+    #[arg(long, value_enum, default_value_t)]
+    pub neighborhood: Neighborhood,
+    /// Threads searching at the same time, by default one per CPU
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct PredecessorArgs {
+    #[command(flatten)]
+    pub pattern: PatternArgs,
+    /// `free` proves the pattern is a Garden of Eden if there's no predecessor
+    #[arg(long, value_enum, default_value_t)]
+    pub boundary: Boundary,
+    /// Gives up after assigning this many Cells
+    #[arg(long, default_value_t = 1 << 24)]
+    pub max_nodes: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct CollideArgs {
+    pub first: PathBuf,
+    pub second: PathBuf,
+    /// Rules in B/S notation
+    #[arg(long, default_value = "B3/S23")]
+    pub rules: RuleSet,
+    /// Cells beyond touching the second pattern is placed away from the first
+    #[arg(long, default_value_t = 4)]
+    pub reach: usize,
+    /// Phases of the second pattern tried, by default the period of its cycle
+    #[arg(long)]
+    pub phases: Option<u64>,
+    /// Generations each collision runs before it's classified
+    #[arg(long, default_value_t = 256)]
+    pub max_generations: u64,
+    /// Only lists the new spaceships and clean constructions
+    #[arg(long)]
+    pub interesting: bool,
+    /// Writes every collision to a `.csv` or `.parquet` table as well
+    #[arg(long)]
+    pub table: Option<PathBuf>,
+    /// Threads colliding at the same time, by default one per CPU
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct IdentifyArgs {
+    #[arg(num_args = 2.., required = true)]
+    pub grids: Vec<PathBuf>,
+    /// Format of the pattern files, by default detected from their contents
+    #[arg(long, value_enum)]
+    pub from: Option<Format>,
+    /// Only tries this neighborhood instead of both
+    #[arg(long, value_enum)]
+    pub neighborhood: Option<Neighborhood>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReversibilityArgs {
+    /// Rules in B/S notation
+    #[arg(long, default_value = "B3/S23")]
+    pub rules: RuleSet,
+    #[arg(long, value_enum, default_value_t)]
+    pub neighborhood: Neighborhood,
+    #[arg(long, default_value_t = 4)]
+    pub rows: usize,
+    #[arg(long, default_value_t = 4)]
+    pub cols: usize,
+    /// Checks this many random grids instead of all of them, for larger tori
+    #[arg(long)]
+    pub samples: Option<usize>,
+    /// Seed of the random grids
+    #[arg(long, default_value_t = 0, requires = "samples")]
+    pub seed: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct MeanfieldArgs {
+    /// Rules in B/S notation
+    #[arg(long, default_value = "B3/S23")]
+    pub rules: RuleSet,
+    #[arg(long, value_enum, default_value_t)]
+    pub neighborhood: Neighborhood,
+    /// Evenly spaced densities from 0 to 1 the map is printed at
+    #[arg(long, default_value_t = 11)]
+    pub samples: usize,
+    /// Writes the map to a `.csv` or `.parquet` table as well
+    #[arg(long)]
+    pub table: Option<PathBuf>,
+}
+
+/// Prints the analysis to `out`, returning the exit status
+pub fn analyze(analysis: &Analysis, out: &mut impl Write) -> Result<i32, Failure> {
+    match analysis {
+        Analysis::Census(args) => census(args, out),
+        Analysis::Period(args) => period(args, out),
+        Analysis::Ships(args) => ships(args, out),
+        Analysis::Checksum(args) => checksum(args, out),
+        Analysis::Symmetry(args) => symmetry(args, out),
+        Analysis::Enumerate(args) => enumerate(args, out),
+        Analysis::Predecessor(args) => predecessor(args, out),
+        Analysis::Collide(args) => collide(args, out),
+        Analysis::Identify(args) => identify(args, out),
+        Analysis::Reversibility(args) => reversibility(args, out),
+        Analysis::Meanfield(args) => meanfield(args, out),
+    }
+}
+
+/// Threads to use if not given, one per CPU
+fn threads(threads: Option<usize>) -> usize {
+    threads
+        .or_else(|| thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1)
+}
+
+fn census(args: &SnapshotArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let mut automaton = args.pattern.automaton()?;
+    automaton.advance(args.generations);
+    let census = analysis::census(&automaton.grid);
+    let mut counts = census.iter().collect::<Vec<_>>();
+    counts.sort_by_key(|(_, &count)| std::cmp::Reverse(count));
+    writeln!(
+        out,
+        "Generation {}: {} objects",
+        automaton.generation,
+        census.values().sum::<usize>()
+    )?;
+    let ships = analysis::find_spaceships(&automaton, analysis::SHIP_PERIOD);
+    for (shape, count) in counts {
+        match ships.iter().find(|ship| ship.shape == *shape) {
+            Some(ship) => writeln!(out, "{count:>6} {shape} ({})", ship.velocity())?,
+            None => writeln!(out, "{count:>6} {shape}")?,
+        }
+    }
+    Ok(0)
+}
+
+fn period(args: &PeriodArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let max_generations = args.max_generations;
+    let Some(cycle) = analysis::find_cycle(&mut args.pattern.automaton()?, max_generations) else {
+        writeln!(out, "No period within {max_generations} generations")?;
+        return Ok(2);
+    };
+    writeln!(
+        out,
+        "Period {} starting at generation {}",
+        cycle.period, cycle.start
+    )?;
+    if let Some(velocity) = cycle.velocity() {
+        writeln!(
+            out,
+            "Moves by {:?} per period, {velocity}",
+            cycle.displacement
+        )?;
+    }
+    if let Some(path) = &args.phases {
+        write_phases(&cycle, path, args.scale).map_err(|e| Failure::Write(path.clone(), e))?;
+    }
+    Ok(0)
+}
+
+fn ships(args: &ShipsArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let mut automaton = args.snapshot.pattern.automaton()?;
+    automaton.advance(args.snapshot.generations);
+    let ships = analysis::find_spaceships(&automaton, args.max_period);
+    writeln!(
+        out,
+        "Generation {}: {} spaceships",
+        automaton.generation,
+        ships.len()
+    )?;
+    for ship in ships {
+        writeln!(
+            out,
+            "{} at row {}, col {}: period {}, displacement {:?}, {}",
+            ship.shape,
+            ship.row,
+            ship.col,
+            ship.period,
+            ship.displacement,
+            ship.velocity()
+        )?;
+    }
+    Ok(0)
+}
+
+fn checksum(args: &SnapshotArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let mut automaton = args.pattern.automaton()?;
+    automaton.advance(args.generations);
+    writeln!(out, "{:016x}", analysis::checksum(&automaton.grid))?;
+    Ok(0)
+}
+
+fn symmetry(args: &SnapshotArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let mut automaton = args.pattern.automaton()?;
+    automaton.advance(args.generations);
+    match Symmetry::detect(&automaton.grid) {
+        Some(symmetry) => writeln!(out, "{symmetry}")?,
+        None => writeln!(out, "C1")?,
+    }
+    Ok(0)
+}
+
+fn enumerate(args: &EnumerateArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let (rows, cols) = (args.rows, args.cols);
+    let threads = threads(args.threads);
+    let found = enumeration::enumerate(rows, cols, args.neighborhood, &args.rules, threads)
+        .map_err(Failure::other)?;
+    for found in &found {
+        let rle = rle::write(&found.pattern);
+        let body = rle.lines().skip(1).collect::<String>();
+        writeln!(out, "{} ({} Cells): {body}", found.kind, found.population)?;
+    }
+    writeln!(out, "{} patterns in {rows} x {cols}", found.len())?;
+    Ok(0)
+}
+
+fn predecessor(args: &PredecessorArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let automaton = args.pattern.automaton()?;
+    match predecessor::find_predecessor(
+        &automaton.grid,
+        automaton.neighborhood_type,
+        &automaton.rule_set,
+        args.boundary,
+        args.max_nodes,
+    ) {
+        Search::Found(predecessor) => {
+            write!(out, "{}", rle::write(&predecessor))?;
+            Ok(0)
+        }
+        Search::None if args.boundary == Boundary::Free => {
+            writeln!(out, "No predecessor, the pattern is a Garden of Eden")?;
+            Ok(2)
+        }
+        Search::None => {
+            writeln!(out, "No predecessor within the bounds of the pattern")?;
+            Ok(2)
+        }
+        Search::GaveUp => {
+            writeln!(out, "Gave up after {} Cells", args.max_nodes)?;
+            Ok(3)
+        }
+    }
+}
+
+fn collide(args: &CollideArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let search = collision::Search {
+        first: load_pattern(&args.first, None)?,
+        second: load_pattern(&args.second, None)?,
+        rule_set: args.rules.clone(),
+        reach: args.reach,
+        phases: args.phases,
+        max_generations: args.max_generations,
+    };
+    let collisions = search.run(threads(args.threads));
+    if let Some(path) = &args.table {
+        write_table(&collision::table(&collisions), path)?;
+    }
+    print_collisions(&collisions, args.interesting, out)?;
+    Ok(0)
+}
+
+fn identify(args: &IdentifyArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let grids = args
+        .grids
+        .iter()
+        .map(|path| load_pattern(path, args.from))
+        .collect::<Result<Vec<_>, _>>()?;
+    let identified = args
+        .neighborhood
+        .map_or_else(
+            || identification::identify_any(&grids),
+            |neighborhood| identification::identify(&grids, neighborhood),
+        )
+        .map_err(Failure::other)?;
+    writeln!(
+        out,
+        "{} ({:?})",
+        identified.rule_set, identified.neighborhood
+    )?;
+    writeln!(
+        out,
+        "Explains {} of {} transitions ({:.2}%)",
+        identified.matching,
+        identified.observed,
+        identified.accuracy() * 100.0
+    )?;
+    let counts = |counts: &[usize]| counts.iter().map(ToString::to_string).collect::<String>();
+    if !identified.unobserved_birth.is_empty() {
+        writeln!(
+            out,
+            "Unknown whether these counts give birth: {}",
+            counts(&identified.unobserved_birth)
+        )?;
+    }
+    if !identified.unobserved_survival.is_empty() {
+        writeln!(
+            out,
+            "Unknown whether these counts let Cells survive: {}",
+            counts(&identified.unobserved_survival)
+        )?;
+    }
+    Ok(0)
+}
+
+fn reversibility(args: &ReversibilityArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let (rows, cols) = (args.rows, args.cols);
+    let check = reversibility::Check {
+        row_count: rows,
+        col_count: cols,
+        rule_set: args.rules.clone(),
+        neighborhood: args.neighborhood,
+    };
+    let method = args
+        .samples
+        .map_or(reversibility::Method::Exhaustive, |samples| {
+            reversibility::Method::Random {
+                samples,
+                seed: args.seed,
+            }
+        });
+    match check.run(method) {
+        Ok(Verdict::Reversible(grids)) => {
+            writeln!(
+                out,
+                "Reversible on a {rows} x {cols} torus, \
+                 the successors of all {grids} grids differ"
+            )?;
+        }
+        Ok(Verdict::Irreversible(counterexample)) => {
+            writeln!(
+                out,
+                "Not reversible on a {rows} x {cols} torus, these two grids"
+            )?;
+            writeln!(out, "{}", rle::write(&counterexample.first).trim_end())?;
+            writeln!(out, "{}", rle::write(&counterexample.second).trim_end())?;
+            writeln!(out, "have the same successor")?;
+            writeln!(out, "{}", rle::write(&counterexample.successor).trim_end())?;
+        }
+        Ok(Verdict::Inconclusive(samples)) => writeln!(
+            out,
+            "No two of {samples} random grids and their variations share a successor, \
+             which doesn't prove reversibility"
+        )?,
+        Err(e) => return Err(Failure::other(e)),
+    }
+    Ok(0)
+}
+
+fn meanfield(args: &MeanfieldArgs, out: &mut impl Write) -> Result<i32, Failure> {
+    let curve = mean_field::curve(&args.rules, args.neighborhood, args.samples);
+    if let Some(path) = &args.table {
+        let column = |index: usize| Values::Float(curve.iter().map(|point| point[index]).collect());
+        let table = Table::default()
+            .column("density", column(0))
+            .column("next_density", column(1));
+        write_table(&table, path)?;
+    }
+    for [density, next] in &curve {
+        writeln!(out, "{density:.3} -> {next:.3}")?;
+    }
+    for fixed_point in mean_field::fixed_points(&args.rules, args.neighborhood) {
+        let stability = if fixed_point.stable {
+            "stable"
+        } else {
+            "unstable"
+        };
+        writeln!(out, "Fixed point {:.4} ({stability})", fixed_point.density)?;
+    }
+    Ok(0)
+}
+
+/// Counts of every outcome followed by one line per collision, the interesting ones first
+fn print_collisions(
+    collisions: &[Collision],
+    interesting_only: bool,
+    out: &mut impl Write,
+) -> Result<(), Failure> {
+    let mut outcomes = BTreeMap::new();
+    for collision in collisions {
+        *outcomes.entry(collision.outcome).or_insert(0) += 1;
+    }
+    let counts = outcomes
+        .iter()
+        .map(|(outcome, count)| format!("{count} {outcome}"))
+        .collect::<Vec<_>>();
+    writeln!(
+        out,
+        "{} collisions: {}",
+        collisions.len(),
+        counts.join(", ")
+    )?;
+
+    let mut listed = collisions
+        .iter()
+        .filter(|collision| !interesting_only || collision.is_interesting())
+        .collect::<Vec<_>>();
+    listed.sort_by_key(|collision| !collision.is_interesting());
+    writeln!(
+        out,
+        "offset    phase reaction outcome      population notes"
+    )?;
+    for collision in listed {
+        let mut notes = collision
+            .new_ships
+            .iter()
+            .map(|ship| format!("new {} ship {}", ship.velocity(), ship.shape))
+            .collect::<Vec<_>>();
+        if collision.is_clean() {
+            notes.extend(
+                collision
+                    .census
+                    .keys()
+                    .map(|shape| format!("clean {shape}")),
+            );
+        } else if collision.new_ships.is_empty() {
+            notes.extend(collision.ships.iter().map(Spaceship::velocity));
+        }
+        writeln!(
+            out,
+            "{:>4},{:<4} {:>5} {:>8} {:<12} {:>10} {}",
+            collision.offset.0,
+            collision.offset.1,
+            collision.phase,
+            collision.reaction,
+            collision.outcome.to_string(),
+            collision.population,
+            notes.join(", ")
+        )?;
+    }
+    Ok(())
+}
+
+/// RLE for `.rle` files, a strip of all phases in the classic theme otherwise
+fn write_phases(cycle: &Cycle, path: &Path, scale: u32) -> Result<(), Box<dyn Error>> {
+    let rle = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("rle"));
+    if rle {
+        fs::write(path, cycle.to_rle())?;
+    } else {
+        grid::to_image(&cycle.strip(), &Theme::classic(), 0, scale).save(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{analyze::analyze, command, glider, Command};
+    use cellular_automata::{grid, rle, Automaton, RuleSet};
+
+    /// The exit status and output of `no_bevy_2d analyze` followed by `args`
+    fn run(args: &[&str]) -> (i32, String) {
+        let Command::Analyze { analysis } = command(&[&["analyze"], args].concat()) else {
+            unreachable!()
+        };
+        let mut out = Vec::new();
+        let status = analyze(&analysis, &mut out).unwrap();
+        (status, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn period() {
+        let path = glider("period");
+        let pattern = path.to_str().unwrap();
+        assert_eq!(
+            run(&["period", "--pattern", pattern]),
+            (
+                0,
+                "Period 4 starting at generation 0\nMoves by (1, 1) per period, c/4 diagonal\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            run(&["period", "--pattern", pattern, "--max-generations", "2"]),
+            (2, "No period within 2 generations\n".to_string())
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn predecessor() {
+        let path = glider("predecessor");
+        let pattern = path.to_str().unwrap();
+        let (status, out) = run(&["predecessor", "--pattern", pattern, "--padding", "1"]);
+        assert_eq!(status, 0);
+        let predecessor = rle::parse(&out).unwrap();
+        let mut automaton = Automaton::builder()
+            .row_count(predecessor.len())
+            .col_count(predecessor[0].len())
+            .grid(predecessor)
+            .rule_set(RuleSet::default())
+            .build();
+        automaton.step();
+        assert_eq!(
+            grid::trim(&automaton.grid, 0),
+            rle::parse("bo$2bo$3o!").unwrap()
+        );
+
+        assert_eq!(
+            run(&["predecessor", "--pattern", pattern, "--max-nodes", "1"]),
+            (3, "Gave up after 1 Cells\n".to_string())
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn enumerate() {
+        assert_eq!(
+            run(&["enumerate", "--rows", "2", "--cols", "2"]),
+            (
+                0,
+                "still life (4 Cells): 2o$2o!\n1 patterns in 2 x 2\n".to_string()
+            )
+        );
+        let (status, out) = run(&["enumerate", "--rows", "3", "--cols", "3", "--threads", "2"]);
+        assert_eq!(status, 0);
+        assert!(out.contains("p2 oscillator (3 Cells): o$o$o!\n"));
+        assert!(out.ends_with("5 patterns in 3 x 3\n"));
+    }
+}
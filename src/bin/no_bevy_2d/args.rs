@@ -0,0 +1,252 @@
+//! Arguments shared by several subcommands
+
+use crate::{
+    failure::Failure,
+    files::{load_annotated, load_pattern},
+};
+use cellular_automata::{
+    annotation::{self, Annotation},
+    format::Format,
+    grid,
+    plugin::Registry,
+    Automaton, Cell, Grid, RuleSet, Seeding, Symmetry,
+};
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+
+/// Seeding strategies selectable from the command line
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum SeedingKind {
+    #[default]
+    Uniform,
+    Perlin,
+    Simplex,
+    Radial,
+    Symmetric,
+    Scatter,
+}
+
+/// A pattern file placed on an otherwise empty grid
+#[derive(Args, Debug)]
+pub struct PatternArgs {
+    #[arg(long)]
+    pub pattern: PathBuf,
+    /// Format of the pattern file, by default detected from its contents
+    #[arg(long, value_enum)]
+    pub from: Option<Format>,
+    /// Dead Cells around the pattern, the edges of the grid don't wrap around
+    #[arg(long, default_value_t = 16)]
+    pub padding: usize,
+    /// Rules in B/S notation
+    #[arg(long, default_value = "B3/S23")]
+    pub rules: RuleSet,
+}
+
+impl PatternArgs {
+    pub fn automaton(&self) -> Result<Automaton, Failure> {
+        Ok(self.annotated()?.0)
+    }
+
+    /// The automaton with the annotations in the comments of the pattern file
+    pub fn annotated(&self) -> Result<(Automaton, Vec<Annotation>), Failure> {
+        let (pattern, annotations) = load_annotated(&self.pattern, self.from)?;
+        let col_count = pattern.first().map_or(0, Vec::len) + 2 * self.padding;
+        let row_count = pattern.len() + 2 * self.padding;
+        let mut automaton = Automaton::builder()
+            .row_count(row_count)
+            .col_count(col_count)
+            .grid(vec![vec![Cell::Dead; col_count]; row_count])
+            .rule_set(self.rules.clone())
+            .build();
+        automaton.stamp(&pattern, self.padding, self.padding);
+        let annotations = annotations
+            .into_iter()
+            .map(|annotation| {
+                Annotation::new(
+                    annotation.row + self.padding,
+                    annotation.col + self.padding,
+                    annotation.text,
+                )
+            })
+            .collect();
+        Ok((automaton, annotations))
+    }
+}
+
+/// How much of the grid is exported
+#[derive(Args, Debug)]
+pub struct TrimArgs {
+    /// Export the whole grid instead of cropping it to the alive Cells
+    #[arg(long)]
+    pub full: bool,
+    /// Dead Cells kept around the alive ones on every side when cropping
+    #[arg(long, default_value_t = 0, conflicts_with = "full")]
+    pub margin: usize,
+}
+
+impl TrimArgs {
+    /// The grid and its annotations moved along with the Cells
+    pub fn apply(&self, grid: Grid, annotations: Vec<Annotation>) -> (Grid, Vec<Annotation>) {
+        if self.full {
+            return (grid, annotations);
+        }
+        grid::bounding_box(&grid).map_or_else(
+            || (grid::trim(&grid, self.margin), Vec::new()),
+            |bounds| {
+                (
+                    grid::crop(&grid, bounds, self.margin),
+                    annotation::crop(&annotations, bounds, self.margin),
+                )
+            },
+        )
+    }
+}
+
+/// The initial grid of a simulation
+#[derive(Args, Debug)]
+pub struct GridArgs {
+    #[arg(long, default_value_t = 20)]
+    pub rows: usize,
+    #[arg(long, default_value_t = 20)]
+    pub cols: usize,
+    /// How the initial grid is populated
+    #[arg(long, value_enum, default_value_t)]
+    pub seeding: SeedingKind,
+    /// Probability of a Cell being alive (at the center for radial seeding)
+    #[arg(long, default_value_t = 0.5)]
+    pub density: f64,
+    /// Probability of a Cell being alive in the corners for radial seeding
+    #[arg(long, default_value_t = 0.0)]
+    pub edge_density: f64,
+    /// Sampling step between neighboring Cells for noise seeding
+    #[arg(long, default_value_t = 0.1)]
+    pub noise_scale: f64,
+    /// Noise value above which a Cell is alive, noise ranges from -1 to 1
+    #[arg(long, default_value_t = 0.0)]
+    pub noise_threshold: f64,
+    /// Seed for noise seeding, random if omitted
+    #[arg(long)]
+    pub noise_seed: Option<u32>,
+    #[arg(long, value_enum, default_value_t)]
+    pub symmetry: Symmetry,
+    /// Number of library patterns dropped for scatter seeding
+    #[arg(long, default_value_t = 20)]
+    pub scatter_count: usize,
+    /// Rules and neighborhood of a registered automaton family, e.g. `HighLife`
+    #[arg(long)]
+    pub preset: Option<String>,
+    /// Pattern file in the center of an otherwise empty grid, which grows to fit it
+    #[arg(long)]
+    pub pattern: Option<PathBuf>,
+    /// Format of the pattern file, by default detected from its contents
+    #[arg(long, value_enum, requires = "pattern")]
+    pub from: Option<Format>,
+}
+
+impl GridArgs {
+    pub fn seeding(&self) -> Seeding {
+        let seed = self.noise_seed.unwrap_or_else(rand::random);
+        match self.seeding {
+            SeedingKind::Uniform => Seeding::Uniform {
+                density: self.density,
+            },
+            SeedingKind::Perlin => Seeding::Perlin {
+                scale: self.noise_scale,
+                threshold: self.noise_threshold,
+                seed,
+            },
+            SeedingKind::Simplex => Seeding::Simplex {
+                scale: self.noise_scale,
+                threshold: self.noise_threshold,
+                seed,
+            },
+            SeedingKind::Radial => Seeding::Radial {
+                center: self.density,
+                edge: self.edge_density,
+            },
+            SeedingKind::Symmetric => Seeding::Symmetric {
+                density: self.density,
+                symmetry: self.symmetry,
+            },
+            SeedingKind::Scatter => Seeding::Scatter {
+                count: self.scatter_count,
+            },
+        }
+    }
+}
+
+impl GridArgs {
+    pub fn automaton(&self) -> Result<Automaton, Failure> {
+        let mut automaton = Automaton::builder()
+            .row_count(self.rows)
+            .col_count(self.cols)
+            .seeding(self.seeding())
+            .build();
+        if let Some(name) = &self.preset {
+            let registry = Registry::default();
+            let Some((_, preset)) = registry.preset(name) else {
+                let names = registry.presets().map(|(_, preset)| preset.name);
+                return Err(Failure::UnknownPreset(name.clone(), names.collect()));
+            };
+            preset.apply(&mut automaton);
+        }
+        if let Some(path) = &self.pattern {
+            let pattern = load_pattern(path, self.from)?;
+            let pattern_cols = pattern.first().map_or(0, Vec::len);
+            automaton.row_count = automaton.row_count.max(pattern.len());
+            automaton.col_count = automaton.col_count.max(pattern_cols);
+            automaton.grid = vec![vec![Cell::Dead; automaton.col_count]; automaton.row_count];
+            let row = (automaton.row_count - pattern.len()) / 2;
+            let col = (automaton.col_count - pattern_cols) / 2;
+            automaton.stamp(&pattern, row, col);
+        }
+        Ok(automaton)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{analyze::Analysis, command, glider, Command};
+    use cellular_automata::{Neighborhood, RuleSet};
+
+    #[test]
+    fn grid_defaults_to_conway() {
+        let Command::Coordinate { grid, .. } = command(&["coordinate", "--workers", "worker:9100"])
+        else {
+            unreachable!()
+        };
+        let automaton = grid.automaton().unwrap();
+        assert_eq!((automaton.row_count, automaton.col_count), (20, 20));
+        assert_eq!(automaton.rule_set, RuleSet::default());
+        assert_eq!(automaton.neighborhood_type, Neighborhood::Moore);
+
+        let Command::Coordinate { grid, .. } = command(&[
+            "coordinate",
+            "--preset",
+            "HighLife",
+            "--workers",
+            "worker:9100",
+        ]) else {
+            unreachable!()
+        };
+        assert_eq!(grid.automaton().unwrap().rule_set.to_string(), "B36/S23");
+    }
+
+    #[test]
+    fn pattern_padded() {
+        let path = glider("pattern_padded");
+        let Command::Analyze {
+            analysis: Analysis::Checksum(args),
+        } = command(&["analyze", "checksum", "--pattern", path.to_str().unwrap()])
+        else {
+            unreachable!()
+        };
+        let automaton = args.pattern.automaton().unwrap();
+        assert_eq!((automaton.row_count, automaton.col_count), (35, 35));
+        assert_eq!(
+            automaton.rule_set.to_string(),
+            RuleSet::default().to_string()
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+//! Converting patterns between formats
+
+use crate::{args::TrimArgs, failure::Failure, files::load_annotated};
+use cellular_automata::{annotation::Annotation, format::Format};
+use clap::Args;
+use std::{fs, io::Write, path::PathBuf};
+
+#[derive(Args, Debug)]
+pub struct ConvertArgs {
+    pub input: PathBuf,
+    /// Printed to stdout if omitted
+    pub output: Option<PathBuf>,
+    /// Format of the input, by default detected from its contents
+    #[arg(long, value_enum)]
+    pub from: Option<Format>,
+    /// Format of the output, by default going by its extension or RLE
+    #[arg(long, value_enum)]
+    pub to: Option<Format>,
+    #[command(flatten)]
+    pub trim: TrimArgs,
+    /// Label as `ROW,COL: TEXT` written into the comments, in addition to the ones of the input
+    #[arg(long = "annotation")]
+    pub annotations: Vec<Annotation>,
+}
+
+/// Writes the converted pattern to `--output`, or to `out` if omitted
+pub fn convert(args: &ConvertArgs, out: &mut impl Write) -> Result<(), Failure> {
+    let (grid, mut annotations) = load_annotated(&args.input, args.from)?;
+    annotations.extend(args.annotations.iter().cloned());
+    let format = args
+        .to
+        .or_else(|| args.output.as_ref().and_then(Format::from_path))
+        .unwrap_or_default();
+    let (grid, annotations) = args.trim.apply(grid, annotations);
+    let text = format.write_annotated(&grid, &annotations);
+    if let Some(path) = &args.output {
+        fs::write(path, text).map_err(|e| Failure::Write(path.clone(), e.into()))
+    } else {
+        Ok(out.write_all(text.as_bytes())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{command, convert::convert, glider, Command};
+
+    #[test]
+    fn to_stdout() {
+        let path = glider("to_stdout");
+        let Command::Convert(args) =
+            command(&["convert", path.to_str().unwrap(), "--to", "plaintext"])
+        else {
+            unreachable!()
+        };
+        let mut out = Vec::new();
+        convert(&args, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), ".O\n..O\nOOO\n");
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+//! Stepping across worker processes
+
+use crate::failure::Failure;
+use cellular_automata::{
+    distributed::{self, Coordinator, DistributedError},
+    Automaton, Cell,
+};
+use std::net::TcpListener;
+
+/// Serves coordinators one after another until the process is stopped
+pub fn worker(address: &str) -> Result<(), Failure> {
+    let listener =
+        TcpListener::bind(address).map_err(|e| Failure::Listen(address.to_owned(), e))?;
+    loop {
+        if let Err(e) = distributed::work(&listener) {
+            eprintln!("{e}");
+        }
+    }
+}
+
+/// Steps `automaton` split across `workers` until the process is stopped or a worker fails
+pub fn coordinate(
+    automaton: &Automaton,
+    workers: &[String],
+    snapshot_every: usize,
+) -> Result<(), DistributedError> {
+    let mut coordinator = connect(automaton, workers)?;
+    loop {
+        println!("{}", snapshot(&mut coordinator, snapshot_every)?);
+    }
+}
+
+fn connect(automaton: &Automaton, workers: &[String]) -> Result<Coordinator, DistributedError> {
+    Coordinator::connect(workers.iter().map(String::as_str), automaton)
+}
+
+/// Advances by `generations` and describes the assembled grid
+fn snapshot(coordinator: &mut Coordinator, generations: usize) -> Result<String, DistributedError> {
+    coordinator.advance(generations.max(1))?;
+    let grid = coordinator.snapshot()?;
+    let population = grid
+        .iter()
+        .flatten()
+        .filter(|&cell| *cell == Cell::Alive)
+        .count();
+    Ok(format!(
+        "Generation: {} | Population: {population}",
+        coordinator.generation
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        command,
+        distribute::{connect, snapshot},
+        Command,
+    };
+    use cellular_automata::distributed;
+    use std::{net::TcpListener, thread};
+
+    #[test]
+    fn coordinates_the_default_rules() {
+        let listeners = (0..2)
+            .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+            .collect::<Vec<_>>();
+        let addresses = listeners
+            .iter()
+            .map(|listener| listener.local_addr().unwrap().to_string())
+            .collect::<Vec<_>>();
+        let workers = listeners
+            .into_iter()
+            .map(|listener| thread::spawn(move || distributed::work(&listener)))
+            .collect::<Vec<_>>();
+
+        let Command::Coordinate {
+            grid,
+            workers: addresses,
+            ..
+        } = command(&[
+            "coordinate",
+            "--rows",
+            "8",
+            "--cols",
+            "8",
+            "--density",
+            "0",
+            "--workers",
+            &addresses.join(","),
+        ])
+        else {
+            unreachable!()
+        };
+        let mut coordinator = connect(&grid.automaton().unwrap(), &addresses).unwrap();
+        assert_eq!(
+            snapshot(&mut coordinator, 3).unwrap(),
+            "Generation: 3 | Population: 0"
+        );
+        coordinator.shutdown().unwrap();
+        for worker in workers {
+            worker.join().unwrap().unwrap();
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Parameter sweeps
+
+use crate::{failure::Failure, files::write_table};
+use cellular_automata::{
+    experiment::{self, Experiment},
+    localization::Localizer,
+};
+use std::{path::Path, thread};
+
+pub fn sweep(
+    path: &Path,
+    output: &Path,
+    threads: Option<usize>,
+    localizer: &Localizer,
+) -> Result<(), Failure> {
+    let experiment = Experiment::load(path).map_err(Failure::other)?;
+    let runs = experiment.runs().map_err(Failure::other)?;
+    let threads = threads
+        .or_else(|| thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1);
+    eprintln!(
+        "{}",
+        localizer.format(
+            "experiment-runs",
+            [("runs", runs.len().into()), ("threads", threads.into())]
+        )
+    );
+    let outcomes = experiment::execute(&runs, threads);
+    write_table(&experiment::table(&outcomes), output)
+}
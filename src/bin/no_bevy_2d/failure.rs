@@ -0,0 +1,117 @@
+//! Errors ending a command, described in the language of the user
+
+use cellular_automata::{
+    format::{Format, FormatError},
+    localization::Localizer,
+};
+use std::{error::Error, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum Failure {
+    Read(PathBuf, io::Error),
+    /// Neither the contents nor the extension of a pattern file tell its format
+    Detect(PathBuf, FormatError),
+    Parse(PathBuf, Format, FormatError),
+    /// A `--preset` no plugin provides, with the names of the ones they do
+    UnknownPreset(String, Vec<String>),
+    Create(PathBuf, Box<dyn Error>),
+    Write(PathBuf, Box<dyn Error>),
+    /// A table ending in `.parquet` without the parquet feature
+    Parquet,
+    Render(PathBuf, Box<dyn Error>),
+    Listen(String, io::Error),
+    /// Writing to stdout, e.g. into a closed pipe
+    Output(io::Error),
+    /// An error of the library, which describes itself
+    Other(Box<dyn Error>),
+}
+
+impl Failure {
+    pub fn other(error: impl Into<Box<dyn Error>>) -> Self {
+        Self::Other(error.into())
+    }
+
+    pub fn localize(&self, localizer: &Localizer) -> String {
+        let file = |path: &PathBuf| ("path", path.display().to_string());
+        let (id, args) = match self {
+            Self::Read(path, e) => ("error-read", vec![file(path), ("error", e.to_string())]),
+            Self::Detect(path, e) => ("error-detect", vec![file(path), ("error", e.to_string())]),
+            Self::Parse(path, format, e) => (
+                "error-parse",
+                vec![
+                    file(path),
+                    ("format", format!("{format:?}")),
+                    ("error", e.to_string()),
+                ],
+            ),
+            Self::UnknownPreset(name, available) => (
+                "error-preset",
+                vec![("name", name.clone()), ("available", available.join(", "))],
+            ),
+            Self::Create(path, e) => ("error-create", vec![file(path), ("error", e.to_string())]),
+            Self::Write(path, e) => ("error-write", vec![file(path), ("error", e.to_string())]),
+            Self::Parquet => ("error-parquet", vec![]),
+            Self::Render(path, e) => ("error-render", vec![file(path), ("error", e.to_string())]),
+            Self::Listen(address, e) => (
+                "error-listen",
+                vec![("address", address.clone()), ("error", e.to_string())],
+            ),
+            Self::Output(e) => ("error-output", vec![("error", e.to_string())]),
+            Self::Other(e) => return e.to_string(),
+        };
+        localizer.format(
+            id,
+            args.iter()
+                .map(|(name, value)| (*name, value.as_str().into())),
+        )
+    }
+
+    /// Prints the failure without ending the command
+    pub fn warn(&self, localizer: &Localizer) {
+        eprintln!("{}", self.localize(localizer));
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(error: io::Error) -> Self {
+        Self::Output(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{command, failure::Failure, files::load_pattern, Command};
+    use cellular_automata::localization::{Language, Localizer};
+    use std::path::Path;
+
+    #[test]
+    fn localized() {
+        let Command::Coordinate { grid, .. } = command(&[
+            "coordinate",
+            "--preset",
+            "Lowlife",
+            "--workers",
+            "worker:9100",
+        ]) else {
+            unreachable!()
+        };
+        let failure = grid.automaton().unwrap_err();
+        let Failure::UnknownPreset(name, available) = &failure else {
+            panic!("{failure:?}")
+        };
+        assert_eq!(name, "Lowlife");
+        assert!(available.iter().any(|name| name == "HighLife"));
+        assert!(failure
+            .localize(&Localizer::default())
+            .starts_with("Unknown preset \"Lowlife\", available are: "));
+        assert!(failure
+            .localize(&Localizer::new(Language::German))
+            .starts_with("Unbekannte Vorlage „Lowlife“, verfügbar sind: "));
+
+        let failure = load_pattern(Path::new("no/such/pattern.rle"), None).unwrap_err();
+        assert!(matches!(failure, Failure::Read(..)));
+        assert!(failure
+            .localize(&Localizer::new(Language::Japanese))
+            .starts_with("no/such/pattern.rle を読み込めませんでした: "));
+    }
+}
@@ -0,0 +1,57 @@
+//! Reading patterns and writing tables
+
+use crate::failure::Failure;
+use cellular_automata::{
+    annotation::Annotation,
+    format::Format,
+    table::{Table, TableFormat},
+    theme::LoadError,
+    Grid, Theme,
+};
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+};
+
+pub fn theme(name: &str) -> Result<Theme, LoadError> {
+    Theme::named(name).map_or_else(|| Theme::load(name), Ok)
+}
+
+/// Reads the pattern at `path` as `format`, or as the format its contents look like,
+/// falling back to its extension
+pub fn load_pattern(path: &Path, format: Option<Format>) -> Result<Grid, Failure> {
+    Ok(load_annotated(path, format)?.0)
+}
+
+/// `load_pattern` with the annotations in the comments of the file
+pub fn load_annotated(
+    path: &Path,
+    format: Option<Format>,
+) -> Result<(Grid, Vec<Annotation>), Failure> {
+    let text = fs::read_to_string(path).map_err(|e| Failure::Read(path.to_owned(), e))?;
+    let format = format.map_or_else(
+        || Format::detect(&text).or_else(|e| Format::from_path(path).ok_or(e)),
+        Ok,
+    );
+    let format = format.map_err(|e| Failure::Detect(path.to_owned(), e))?;
+    let grid = format
+        .parse(&text)
+        .map_err(|e| Failure::Parse(path.to_owned(), format, e))?;
+    Ok((grid, format.annotations(&text)))
+}
+
+/// Writes `table` in the format going by the extension of `path`
+pub fn write_table(table: &Table, path: &Path) -> Result<(), Failure> {
+    let file = File::create(path).map_err(|e| Failure::Create(path.to_owned(), e.into()))?;
+    let file = BufWriter::new(file);
+    let written: Result<(), Box<dyn Error>> = match TableFormat::from_path(path) {
+        TableFormat::Csv => table.write_csv(file).map_err(Into::into),
+        #[cfg(feature = "parquet")]
+        TableFormat::Parquet => table.write_parquet(file).map_err(Into::into),
+        #[cfg(not(feature = "parquet"))]
+        TableFormat::Parquet => return Err(Failure::Parquet),
+    };
+    written.map_err(|e| Failure::Write(path.to_owned(), e))
+}
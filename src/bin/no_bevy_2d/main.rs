@@ -0,0 +1,189 @@
+#![warn(
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+    // clippy::cargo
+)]
+
+mod analyze;
+mod args;
+mod convert;
+mod distribute;
+mod experiment;
+mod failure;
+mod files;
+mod render;
+mod run;
+#[cfg(feature = "server")]
+mod serve;
+
+use analyze::{analyze, Analysis};
+use args::GridArgs;
+use cellular_automata::localization::{Language, Localizer};
+#[cfg(feature = "fetch")]
+use cellular_automata::{
+    fetch::{Fetcher, Query},
+    rle,
+};
+use clap::{Parser, Subcommand};
+use convert::{convert, ConvertArgs};
+use distribute::{coordinate, worker};
+use experiment::sweep;
+use failure::Failure;
+use render::{render, RenderArgs};
+use run::{run, RunArgs};
+#[cfg(feature = "server")]
+use serve::{serve, ServeArgs};
+use std::{io, path::PathBuf, process};
+
+#[derive(Parser, Debug)]
+#[command(about = "Runs, converts, analyzes and renders cellular automata")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Language of the terminal output, detected from `LANG` by default
+    #[arg(long, global = true, value_enum)]
+    language: Option<Language>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Runs a simulation in the terminal, or headless without rendering
+    Run(Box<RunArgs>),
+    /// Converts a pattern file to another format
+    Convert(ConvertArgs),
+    /// Prints facts about a pattern file
+    Analyze {
+        #[command(subcommand)]
+        analysis: Analysis,
+    },
+    /// Renders a pattern file as PNG, SVG or an animated GIF
+    Render(RenderArgs),
+    /// Runs headless and streams the grid to WebSocket clients, see `cellular_automata::remote`
+    #[cfg(feature = "server")]
+    Serve {
+        #[command(flatten)]
+        grid: GridArgs,
+        #[command(flatten)]
+        serve: ServeArgs,
+    },
+    /// Owns a strip of a distributed grid for a coordinator, see `cellular_automata::distributed`
+    Worker {
+        #[arg(long, default_value = "0.0.0.0:9100")]
+        address: String,
+    },
+    /// Splits the grid across workers and steps it, printing the population periodically
+    Coordinate {
+        #[command(flatten)]
+        grid: GridArgs,
+        /// Addresses of the workers, from the top strip to the bottom one
+        #[arg(long, value_delimiter = ',', required = true)]
+        workers: Vec<String>,
+        /// Generations between assembling snapshots of the whole grid
+        #[arg(long, default_value_t = 100)]
+        snapshot_every: usize,
+    },
+    /// Runs every combination of the parameters in a TOML experiment in parallel
+    /// and writes one row of outcome metrics per run, see `cellular_automata::experiment`
+    Experiment {
+        experiment: PathBuf,
+        /// CSV file, or Parquet if it ends in `.parquet`
+        #[arg(long)]
+        output: PathBuf,
+        /// Runs at the same time, by default one per CPU
+        #[arg(long)]
+        threads: Option<usize>,
+    },
+    /// Downloads a pattern by name from LifeWiki or decodes an apgcode of Catagolue,
+    /// prints it as RLE and caches it for `--pattern` and the pattern browser of the app
+    #[cfg(feature = "fetch")]
+    Fetch {
+        /// e.g. `Gosper glider gun` or `xq4_153`
+        query: String,
+        #[arg(long, default_value = "patterns")]
+        cache: PathBuf,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let localizer = Localizer::new(cli.language.unwrap_or_else(Language::detect));
+    match execute(cli.command, &localizer) {
+        Ok(0) => {}
+        Ok(status) => process::exit(status),
+        Err(failure) => {
+            failure.warn(&localizer);
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs `command`, returning the exit status
+fn execute(command: Command, localizer: &Localizer) -> Result<i32, Failure> {
+    match command {
+        Command::Run(args) => run(&args, localizer),
+        Command::Convert(args) => convert(&args, &mut io::stdout().lock()).map(|()| 0),
+        Command::Analyze { analysis } => analyze(&analysis, &mut io::stdout().lock()),
+        Command::Render(args) => render(&args).map(|()| 0),
+        #[cfg(feature = "server")]
+        Command::Serve { grid, serve: args } => serve(grid.automaton()?, &args, localizer),
+        Command::Worker { address } => worker(&address).map(|()| 0),
+        Command::Coordinate {
+            grid,
+            workers,
+            snapshot_every,
+        } => {
+            coordinate(&grid.automaton()?, &workers, snapshot_every).map_err(Failure::other)?;
+            Ok(0)
+        }
+        Command::Experiment {
+            experiment: path,
+            output,
+            threads,
+        } => sweep(&path, &output, threads, localizer).map(|()| 0),
+        #[cfg(feature = "fetch")]
+        Command::Fetch { query, cache } => {
+            let fetcher = Fetcher::new(cache);
+            let query = Query::parse(&query);
+            let pattern = fetcher.fetch(&query).map_err(Failure::other)?;
+            let path = fetcher.cached_path(&query).display().to_string();
+            eprintln!(
+                "{}",
+                localizer.format("fetch-cached", [("path", path.into())])
+            );
+            print!("{}", rle::write(&pattern.grid));
+            Ok(0)
+        }
+    }
+}
+
+/// The command of `no_bevy_2d` followed by `args`
+#[cfg(test)]
+fn command(args: &[&str]) -> Command {
+    let args = std::iter::once("no_bevy_2d").chain(args.iter().copied());
+    Cli::try_parse_from(args).unwrap().command
+}
+
+/// A glider in an RLE file named after the test writing it, tests run in parallel
+#[cfg(test)]
+fn glider(test: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("no_bevy_2d_{test}.rle"));
+    std::fs::write(&path, "x = 3, y = 3\nbo$2bo$3o!\n").unwrap();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn arguments() {
+        Cli::command().debug_assert();
+    }
+}
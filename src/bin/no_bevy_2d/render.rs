@@ -0,0 +1,152 @@
+//! Rendering patterns to images and meshes
+
+use crate::{
+    args::{PatternArgs, TrimArgs},
+    failure::Failure,
+    files::theme,
+};
+use cellular_automata::{
+    annotation::{self, Annotation},
+    grid::{self, BoundingBox},
+    mesh::{self, Volume},
+    Automaton, Grid,
+};
+use clap::Args;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, DynamicImage, Frame,
+};
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+#[derive(Args, Debug)]
+pub struct RenderArgs {
+    #[command(flatten)]
+    pub pattern: PatternArgs,
+    /// `.png`, `.svg`, `.gif` or `.obj`
+    #[arg(long)]
+    pub output: PathBuf,
+    /// Generations stepped before rendering, animated in a GIF or stacked on top of each other
+    /// in an OBJ mesh of the alive Cells
+    #[arg(long, default_value_t = 0)]
+    pub generations: usize,
+    /// Pixels per Cell in both directions, or edge length of a Cell in an OBJ mesh
+    #[arg(long, default_value_t = 4)]
+    pub scale: u32,
+    /// Built in theme or path to a TOML theme
+    #[arg(long, default_value = "Classic")]
+    pub theme: String,
+    /// Milliseconds between the frames of a GIF
+    #[arg(long, default_value_t = 100)]
+    pub delay: u32,
+    /// GIF frames and OBJ layers are all cropped to the alive Cells of every one, so they
+    /// keep their size
+    #[command(flatten)]
+    pub trim: TrimArgs,
+    /// Label as `ROW,COL: TEXT` of the padded grid, in addition to the ones of the pattern.
+    /// Annotated Cells are outlined, the legend is listed below SVGs and written next to
+    /// PNGs and GIFs as `.legend.txt`.
+    #[arg(long = "annotation")]
+    pub annotations: Vec<Annotation>,
+}
+
+pub fn render(args: &RenderArgs) -> Result<(), Failure> {
+    let (automaton, annotations) = args.pattern.annotated()?;
+    draw(args, automaton, annotations).map_err(|e| Failure::Render(args.output.clone(), e))
+}
+
+fn draw(
+    args: &RenderArgs,
+    mut automaton: Automaton,
+    mut annotations: Vec<Annotation>,
+) -> Result<(), Box<dyn Error>> {
+    annotations.extend(args.annotations.iter().cloned());
+    let theme = theme(&args.theme)?;
+    let decay = automaton.rule_set.decay;
+    let extension = args
+        .output
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("gif") => {
+            let mut encoder = GifEncoder::new(File::create(&args.output)?);
+            encoder.set_repeat(Repeat::Infinite)?;
+            for grid in frames(&mut automaton, args, &mut annotations) {
+                let mut image = grid::to_image(&grid, &theme, decay, args.scale);
+                annotation::mark(&mut image, &annotations, args.scale);
+                encoder.encode_frame(Frame::from_parts(
+                    DynamicImage::ImageRgb8(image).into_rgba8(),
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(args.delay, 1),
+                ))?;
+            }
+            write_legend(&args.output, &annotations)?;
+        }
+        Some("obj") => {
+            let frames = frames(&mut automaton, args, &mut annotations);
+            let mesh = mesh::greedy_mesh(&Volume::stack(&frames));
+            #[allow(clippy::cast_precision_loss)]
+            let scale = args.scale as f32;
+            mesh.write_obj(BufWriter::new(File::create(&args.output)?), scale)?;
+        }
+        Some("svg") => {
+            automaton.advance(args.generations);
+            let (grid, annotations) = args.trim.apply(automaton.grid, annotations);
+            let svg = grid::to_annotated_svg(&grid, &theme, decay, args.scale, &annotations);
+            fs::write(&args.output, svg)?;
+        }
+        _ => {
+            automaton.advance(args.generations);
+            let (grid, annotations) = args.trim.apply(automaton.grid, annotations);
+            let mut image = grid::to_image(&grid, &theme, decay, args.scale);
+            annotation::mark(&mut image, &annotations, args.scale);
+            image.save(&args.output)?;
+            write_legend(&args.output, &annotations)?;
+        }
+    }
+    Ok(())
+}
+
+/// The grid and the `generations` after it, all cropped to the alive Cells of every one of them
+/// along with the `annotations` unless the whole grid is rendered
+fn frames(
+    automaton: &mut Automaton,
+    args: &RenderArgs,
+    annotations: &mut Vec<Annotation>,
+) -> Vec<Grid> {
+    let mut frames: Vec<_> = std::iter::once(automaton.grid.clone())
+        .chain(automaton.generations().take(args.generations))
+        .collect();
+    if !args.trim.full {
+        let bounds = frames
+            .iter()
+            .filter_map(|grid| grid::bounding_box(grid))
+            .reduce(BoundingBox::union);
+        for grid in &mut frames {
+            *grid = bounds.map_or_else(
+                || grid::trim(grid, args.trim.margin),
+                |bounds| grid::crop(grid, bounds, args.trim.margin),
+            );
+        }
+        *annotations = bounds.map_or_else(Vec::new, |bounds| {
+            annotation::crop(annotations, bounds, args.trim.margin)
+        });
+    }
+    frames
+}
+
+/// Lists the annotations marked in the image at `path` next to it, images can't hold text
+fn write_legend(path: &Path, annotations: &[Annotation]) -> io::Result<()> {
+    if annotations.is_empty() {
+        return Ok(());
+    }
+    let mut legend = annotation::legend(annotations).join("\n");
+    legend.push('\n');
+    fs::write(path.with_extension("legend.txt"), legend)
+}
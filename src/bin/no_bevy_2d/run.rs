@@ -0,0 +1,719 @@
+//! Stepping in the terminal
+
+use crate::{
+    args::GridArgs,
+    failure::Failure,
+    files::{theme, write_table},
+};
+#[cfg(feature = "led-matrix")]
+use cellular_automata::led::{LedConfig, LedDisplay};
+#[cfg(feature = "scripting")]
+use cellular_automata::script::Script;
+#[cfg(feature = "shared-memory")]
+use cellular_automata::shared_memory::SharedGrid;
+#[cfg(feature = "midi")]
+use cellular_automata::sonification::MidiPlayer;
+#[cfg(feature = "led-matrix")]
+use cellular_automata::theme::LoadError;
+use cellular_automata::{
+    localization::Localizer,
+    schedule::Schedule,
+    sonification::{self, Scale, SonificationConfig, Sonifier, WavWriter},
+    stats::{self, TimeSeries},
+    stop::{StopCondition, Stopper},
+    table::TableFormat,
+    timelapse::Timelapse,
+    tui,
+    zone::Zones,
+    Automaton, Divergence, Neighborhood, RuleSet, Stats, Theme, Trail,
+};
+use clap::Args;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+/// Generations shown in the population sparkline
+const HISTORY_LENGTH: usize = 60;
+
+/// Sample rate of `--audio` recordings
+const SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub grid: GridArgs,
+    /// Steps as fast as possible without rendering, printing the status every K-th generation
+    #[arg(long, conflicts_with_all = ["color", "compare"])]
+    pub headless: bool,
+    /// Stops after this many generations
+    #[arg(long)]
+    pub generations: Option<u64>,
+    /// Stops once one of these conditions holds, exiting with a status depending on which:
+    /// generation=N => 0, extinction => 3, stable => 4, period<=K => 5, population>X => 6
+    #[arg(
+        long,
+        value_name = "CONDITION",
+        value_delimiter = ',',
+        requires = "headless"
+    )]
+    pub stop_on: Vec<StopCondition>,
+    /// TOML file of rule changes during the run, e.g. switching to B3/S23 at generation 500
+    #[arg(long, value_name = "PATH", conflicts_with = "compare")]
+    pub schedule: Option<PathBuf>,
+    /// Seed of the random mixing of the rules while a scheduled change ramps in
+    #[arg(long, default_value_t = 0, requires = "schedule")]
+    pub schedule_seed: u64,
+    /// TOML file of zones of the grid following rules of their own
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["compare", "schedule"])]
+    pub zones: Option<PathBuf>,
+    /// Writes the population, births, deaths, `Metrics` and bounding box of generations to
+    /// this CSV file while running, or Parquet once the run ends if it ends in `.parquet`
+    #[arg(long, value_name = "PATH")]
+    pub stats: Option<PathBuf>,
+    /// Only every K-th generation is written to `--stats`
+    #[arg(long, value_name = "K", default_value_t = 1, requires = "stats")]
+    pub stats_every: u64,
+    /// Records generations as an animated GIF if this ends in `.gif`,
+    /// otherwise as numbered PNG frames in this directory
+    #[arg(long, value_name = "PATH")]
+    pub timelapse: Option<PathBuf>,
+    /// Only every K-th generation is recorded by `--timelapse`
+    #[arg(long, value_name = "K", default_value_t = 1, requires = "timelapse")]
+    pub timelapse_every: u64,
+    /// Pixels per Cell of the recorded frames
+    #[arg(long, default_value_t = 4, requires = "timelapse")]
+    pub timelapse_scale: u32,
+    /// Built in theme or path to a TOML theme the recorded frames are colored with
+    #[arg(long, default_value = "Classic", requires = "timelapse")]
+    pub timelapse_theme: String,
+    /// Milliseconds each frame of a recorded GIF is shown
+    #[arg(long, value_name = "MS", default_value_t = 100, requires = "timelapse")]
+    pub timelapse_delay: u32,
+    /// Only every K-th generation is printed, the ones in between are skipped
+    #[arg(long, value_name = "K", default_value_t = 1)]
+    pub render_every: usize,
+    #[command(flatten)]
+    pub display: DisplayArgs,
+    #[command(flatten)]
+    pub comparison: CompareArgs,
+    /// Rhai script providing `next_state`, `seed` and `on_generation` functions
+    #[cfg(feature = "scripting")]
+    #[arg(long, conflicts_with = "compare")]
+    pub script: Option<PathBuf>,
+    #[cfg(feature = "led-matrix")]
+    #[command(flatten)]
+    pub led_matrix: LedArgs,
+    /// Publishes every rendered generation to this file for other processes, e.g. in `/dev/shm`
+    #[cfg(feature = "shared-memory")]
+    #[arg(long, value_name = "PATH")]
+    pub shared_memory: Option<PathBuf>,
+    /// Plays births as notes on the MIDI output port containing this name, or the first one
+    #[cfg(feature = "midi")]
+    #[arg(long, value_name = "PORT", num_args = 0..=1, default_missing_value = "")]
+    pub midi: Option<String>,
+    /// MIDI channel from 0 to 15
+    #[cfg(feature = "midi")]
+    #[arg(long, default_value_t = 0, requires = "midi")]
+    pub midi_channel: u8,
+    /// Records births as synthesized notes into this WAV file
+    #[arg(long, value_name = "PATH")]
+    pub audio: Option<PathBuf>,
+    /// Scale the columns are mapped to when sonifying
+    #[arg(long, value_enum, default_value_t)]
+    pub scale: Scale,
+    /// MIDI key of the leftmost column when sonifying, 48 is C3
+    #[arg(long, default_value_t = 48)]
+    pub root: u8,
+    /// Octaves the columns are spread over when sonifying
+    #[arg(long, default_value_t = 3)]
+    pub octaves: u8,
+}
+
+/// How generations are drawn in the terminal
+#[derive(Args, Debug)]
+pub struct DisplayArgs {
+    /// Renders with 24 bit terminal colors instead of emoji, monochrome if `NO_COLOR` is set
+    #[arg(long)]
+    pub color: bool,
+    /// Renders with shaded block glyphs instead of colors or emoji
+    #[arg(long, conflicts_with = "color")]
+    pub monochrome: bool,
+    /// Recently dead Cells fade out over this many rendered frames
+    #[arg(long, value_name = "FRAMES", default_value_t = 0, requires = "color")]
+    pub trail: usize,
+    /// Built in theme ("Classic", "High Contrast", "Ocean", "Deuteranopia", "Protanopia")
+    /// or path to a TOML theme.
+    /// Entering another one while running switches to it.
+    #[arg(long, default_value = "Classic", requires = "color")]
+    pub theme: String,
+    /// Labels rows and columns with their indices
+    #[arg(long)]
+    pub axes: bool,
+}
+
+/// A second automaton stepped alongside the first one
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// Steps a second automaton in lockstep and shows where it diverges from the first one
+    #[arg(long)]
+    pub compare: bool,
+    /// Neighbor counts giving birth in the compared automaton
+    #[arg(long, value_delimiter = ',', default_values_t = [3], requires = "compare")]
+    pub compare_birth: Vec<usize>,
+    /// Neighbor counts letting Cells survive in the compared automaton
+    #[arg(long, value_delimiter = ',', default_values_t = [2, 3], requires = "compare")]
+    pub compare_survival: Vec<usize>,
+    #[arg(long, value_enum, default_value_t, requires = "compare")]
+    pub compare_neighborhood: Neighborhood,
+}
+
+/// An RGB LED matrix mirroring the terminal
+#[cfg(feature = "led-matrix")]
+#[derive(Args, Debug)]
+pub struct LedArgs {
+    /// Also shows every rendered generation on an RGB LED matrix attached to a Raspberry Pi
+    #[arg(long)]
+    pub led: bool,
+    /// Rows of a single LED panel
+    #[arg(long, default_value_t = 32, requires = "led")]
+    pub led_rows: u32,
+    /// Columns of a single LED panel
+    #[arg(long, default_value_t = 32, requires = "led")]
+    pub led_cols: u32,
+    /// Number of daisy chained LED panels
+    #[arg(long, default_value_t = 1, requires = "led")]
+    pub led_chain: u32,
+    /// LED brightness in percent
+    #[arg(long, default_value_t = 50, requires = "led")]
+    pub led_brightness: u8,
+    /// Wiring of the LED panel, e.g. "regular" or "adafruit-hat"
+    #[arg(long, default_value = "regular", requires = "led")]
+    pub led_mapping: String,
+    /// Built in theme or path to a TOML theme the LEDs are colored with
+    #[arg(long, default_value = "High Contrast", requires = "led")]
+    pub led_theme: String,
+}
+
+#[cfg(feature = "led-matrix")]
+impl LedArgs {
+    pub fn config(&self) -> Result<LedConfig, LoadError> {
+        Ok(LedConfig {
+            rows: self.led_rows,
+            cols: self.led_cols,
+            chain_length: self.led_chain,
+            brightness: self.led_brightness,
+            hardware_mapping: self.led_mapping.clone(),
+            theme: theme(&self.led_theme)?,
+        })
+    }
+}
+
+/// Prints the XOR difference of both automata every rendered generation
+pub fn compare(args: &RunArgs, automaton: Automaton) -> ! {
+    let compared = Automaton::builder()
+        .neighborhood_type(args.comparison.compare_neighborhood)
+        .rule_set(RuleSet::life_like(
+            args.comparison.compare_birth.clone(),
+            args.comparison.compare_survival.clone(),
+            0,
+        ))
+        .build();
+    let mut divergence = Divergence::new(automaton, compared);
+    loop {
+        println!(
+            "Generation: {} | {} vs {} | Differing: {}",
+            divergence.left.generation,
+            divergence.left.rule_set,
+            divergence.right.rule_set,
+            divergence.differing()
+        );
+        print!(
+            "{}",
+            tui::render_difference(&divergence.difference(), args.display.axes)
+        );
+        thread::sleep(Duration::from_secs(1));
+        for _ in 0..args.render_every.max(1) {
+            divergence.step();
+        }
+    }
+}
+
+/// `--stats` of a run
+pub struct StatsExport {
+    pub series: TimeSeries,
+    pub path: PathBuf,
+    /// Appended to after every recorded generation, `None` for Parquet
+    pub csv: Option<BufWriter<File>>,
+}
+
+impl StatsExport {
+    pub fn create(args: &RunArgs) -> Result<Option<Self>, Failure> {
+        let Some(path) = args.stats.clone() else {
+            return Ok(None);
+        };
+        let csv = match TableFormat::from_path(&path) {
+            TableFormat::Csv => Some(BufWriter::new(
+                File::create(&path).map_err(|e| Failure::Create(path.clone(), e.into()))?,
+            )),
+            TableFormat::Parquet => None,
+        };
+        Ok(Some(Self {
+            series: TimeSeries::new(args.stats_every),
+            path,
+            csv,
+        }))
+    }
+
+    pub fn record(&mut self, automaton: &Automaton, localizer: &Localizer) {
+        self.series.record(automaton);
+        if let Some(csv) = &mut self.csv {
+            if let Err(e) = self.series.write_csv(csv) {
+                Failure::Write(self.path.clone(), e.into()).warn(localizer);
+            }
+        }
+    }
+
+    pub fn finish(&self, localizer: &Localizer) {
+        if self.csv.is_none() {
+            if let Err(failure) = write_table(&self.series.table(), &self.path) {
+                failure.warn(localizer);
+            }
+        }
+    }
+}
+
+impl RunArgs {
+    pub fn timelapse(&self) -> Result<Option<Timelapse>, Failure> {
+        let Some(path) = &self.timelapse else {
+            return Ok(None);
+        };
+        let theme = theme(&self.timelapse_theme).map_err(Failure::other)?;
+        let timelapse = Timelapse::create(
+            path,
+            self.timelapse_every,
+            theme,
+            self.timelapse_scale,
+            self.timelapse_delay,
+        );
+        timelapse
+            .map(Some)
+            .map_err(|e| Failure::Create(path.clone(), e.into()))
+    }
+
+    /// Fails if the `--schedule` or `--zones` can't be loaded
+    pub fn stepping(&self, automaton: &Automaton) -> Result<Stepping, Failure> {
+        if let Some(path) = &self.schedule {
+            return Ok(Stepping::Scheduled {
+                schedule: Schedule::load(path).map_err(Failure::other)?,
+                initial: automaton.rule_set.clone(),
+                rng: Box::new(StdRng::seed_from_u64(self.schedule_seed)),
+            });
+        }
+        if let Some(path) = &self.zones {
+            return Ok(Stepping::Zoned(Zones::load(path).map_err(Failure::other)?));
+        }
+        Ok(Stepping::Plain)
+    }
+}
+
+/// How `run` steps the automaton
+/// - `Plain` => With its own rules
+/// - `Scheduled` => Along the `--schedule`, starting from the rules the run started with
+/// - `Zoned` => Every Cell with the rules of its zone of the `--zones`
+pub enum Stepping {
+    Plain,
+    Scheduled {
+        schedule: Schedule,
+        initial: RuleSet,
+        rng: Box<StdRng>,
+    },
+    Zoned(Zones),
+}
+
+impl Stepping {
+    pub fn advance(&mut self, automaton: &mut Automaton, generations: usize) {
+        match self {
+            Self::Plain => automaton.advance(generations),
+            Self::Scheduled {
+                schedule,
+                initial,
+                rng,
+            } => {
+                for _ in 0..generations {
+                    schedule.step(automaton, initial, rng);
+                }
+            }
+            Self::Zoned(zones) => zones.advance(automaton, generations),
+        }
+    }
+}
+
+/// Captures the generation of `automaton` if it's due
+pub fn record_timelapse(
+    timelapse: &mut Option<Timelapse>,
+    automaton: &Automaton,
+    localizer: &Localizer,
+) {
+    if let Some(timelapse) = timelapse {
+        if let Err(e) = timelapse.capture(automaton) {
+            let message = localizer.format(
+                "warning-record",
+                [
+                    ("generation", automaton.generation.0.into()),
+                    ("error", e.to_string().into()),
+                ],
+            );
+            eprintln!("{message}");
+        }
+    }
+}
+
+/// Steps without rendering or waiting until `--generations` passed or a `--stop-on` condition
+/// holds, returning the exit status
+pub fn headless(
+    args: &RunArgs,
+    mut automaton: Automaton,
+    out: &mut impl Write,
+    localizer: &Localizer,
+) -> Result<i32, Failure> {
+    let every = u64::try_from(args.render_every.max(1)).unwrap_or(u64::MAX);
+    let mut stopper = Stopper::new(args.stop_on.clone());
+    // Lets the stopper hash the grid incrementally, nothing edits it between generations
+    automaton.track_changes = true;
+    let mut export = StatsExport::create(args)?;
+    let mut timelapse = args.timelapse()?;
+    let mut stepping = args.stepping(&automaton)?;
+    loop {
+        if let Some(export) = &mut export {
+            export.record(&automaton, localizer);
+        }
+        record_timelapse(&mut timelapse, &automaton, localizer);
+        let done = args
+            .generations
+            .is_some_and(|generations| automaton.generation.0 >= generations);
+        let stop = stopper.check(&automaton);
+        if done || stop.is_some() || automaton.generation.0.is_multiple_of(every) {
+            // Not localized, headless output is meant to be parsed by scripts
+            writeln!(out, "{}", tui::status_line(&automaton))?;
+        }
+        if done || stop.is_some() {
+            if let Some(export) = &export {
+                export.finish(localizer);
+            }
+        }
+        if let Some(stop) = stop {
+            writeln!(out, "{stop}")?;
+            return Ok(stop.condition.exit_code());
+        }
+        if done {
+            return Ok(0);
+        }
+        stepping.advance(&mut automaton, 1);
+    }
+}
+
+/// Where rendered generations go besides the terminal
+struct Outputs {
+    sonifier: Sonifier,
+    wav: Option<WavWriter>,
+    #[cfg(feature = "midi")]
+    midi: Option<MidiPlayer>,
+    #[cfg(feature = "led-matrix")]
+    led: Option<LedDisplay>,
+    #[cfg(feature = "shared-memory")]
+    shared: Option<SharedGrid>,
+}
+
+impl Outputs {
+    /// Fails if one of the outputs can't be opened
+    fn create(args: &RunArgs, automaton: &Automaton) -> Result<Self, Failure> {
+        #[cfg(not(feature = "shared-memory"))]
+        let _ = automaton;
+        Ok(Self {
+            sonifier: Sonifier::new(SonificationConfig {
+                scale: args.scale,
+                root: args.root,
+                octaves: args.octaves,
+                ..SonificationConfig::default()
+            }),
+            wav: args
+                .audio
+                .as_ref()
+                .map(|path| WavWriter::create(path, SAMPLE_RATE))
+                .transpose()
+                .map_err(Failure::other)?,
+            #[cfg(feature = "midi")]
+            midi: args
+                .midi
+                .as_deref()
+                .map(|port| {
+                    let port = Some(port).filter(|port| !port.is_empty());
+                    MidiPlayer::connect(port, args.midi_channel)
+                })
+                .transpose()
+                .map_err(Failure::other)?,
+            #[cfg(feature = "led-matrix")]
+            led: if args.led_matrix.led {
+                let config = args.led_matrix.config().map_err(Failure::other)?;
+                Some(LedDisplay::new(config).map_err(Failure::other)?)
+            } else {
+                None
+            },
+            #[cfg(feature = "shared-memory")]
+            shared: args
+                .shared_memory
+                .as_ref()
+                .map(|path| SharedGrid::create(path, automaton.row_count, automaton.col_count))
+                .transpose()
+                .map_err(Failure::other)?,
+        })
+    }
+
+    const fn sonifying(&self) -> bool {
+        #[cfg(feature = "midi")]
+        if self.midi.is_some() {
+            return true;
+        }
+        self.wav.is_some()
+    }
+
+    /// How long a rendered generation is shown, depending on its births when sonifying
+    fn beat(&self, automaton: &Automaton) -> Duration {
+        if self.sonifying() {
+            self.sonifier.beat(automaton)
+        } else {
+            Duration::from_secs(1)
+        }
+    }
+
+    /// Shows the generation of `automaton` on the LEDs and plays its births for `beat`
+    fn show(&mut self, automaton: &Automaton, beat: Duration) {
+        #[cfg(feature = "led-matrix")]
+        if let Some(led) = &mut self.led {
+            led.show(automaton);
+        }
+        #[cfg(feature = "shared-memory")]
+        if let Some(shared) = &mut self.shared {
+            shared.publish(automaton.generation, &automaton.grid);
+        }
+        if !self.sonifying() {
+            return;
+        }
+        let notes = self.sonifier.observe(automaton);
+        if let Some(wav) = &mut self.wav {
+            let samples = sonification::synthesize(&notes, beat, SAMPLE_RATE);
+            if let Err(e) = wav.write(&samples) {
+                eprintln!("{e}");
+            }
+        }
+        #[cfg(feature = "midi")]
+        if let Some(midi) = &mut self.midi {
+            if let Err(e) = midi.play(&notes) {
+                eprintln!("{e}");
+            }
+        }
+    }
+}
+
+/// Names of themes entered while running
+fn theme_names() -> mpsc::Receiver<String> {
+    let (sender, names) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    names
+}
+
+/// Prints the grid of `automaton` with emoji, colors or shaded blocks
+fn print_grid(args: &RunArgs, automaton: &Automaton, theme: &Theme, trail: &mut Trail) {
+    let display = &args.display;
+    // See https://no-color.org
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if display.monochrome || (display.color && no_color) {
+        print!("{}", tui::render_monochrome(automaton, display.axes));
+    } else if display.color {
+        trail.observe(&automaton.grid);
+        print!(
+            "{}",
+            tui::render_color(automaton, theme, Some(trail), display.axes)
+        );
+    } else {
+        print!("{}", tui::render_emoji(automaton, display.axes));
+    }
+}
+
+/// Steps in the terminal until the process is stopped or `--generations` passed,
+/// returning the exit status
+pub fn run(args: &RunArgs, localizer: &Localizer) -> Result<i32, Failure> {
+    // Only seeded by scripts
+    #[cfg_attr(not(feature = "scripting"), allow(unused_mut))]
+    let mut automaton = args.grid.automaton()?;
+    if args.headless {
+        return headless(args, automaton, &mut io::stdout().lock(), localizer);
+    }
+    if args.comparison.compare {
+        compare(args, automaton);
+    }
+    #[cfg(feature = "scripting")]
+    let script = match &args.script {
+        Some(path) => {
+            let script = Script::load(path).map_err(Failure::other)?;
+            if script.defines("seed", 2) {
+                automaton.grid = script
+                    .seed(automaton.row_count, automaton.col_count)
+                    .map_err(Failure::other)?;
+            }
+            Some(script)
+        }
+        None => None,
+    };
+    let mut stepping = args.stepping(&automaton)?;
+    let step = |automaton: &mut Automaton| {
+        #[cfg(feature = "scripting")]
+        if let Some(script) = &script {
+            return script.step(automaton).map_err(Failure::other);
+        }
+        stepping.advance(automaton, 1);
+        Ok(())
+    };
+    terminal(args, automaton, step, localizer)?;
+    Ok(0)
+}
+
+/// Renders every `--render-every` generation of `automaton` stepped by `step` until
+/// `--generations` passed
+fn terminal(
+    args: &RunArgs,
+    mut automaton: Automaton,
+    mut step: impl FnMut(&mut Automaton) -> Result<(), Failure>,
+    localizer: &Localizer,
+) -> Result<(), Failure> {
+    let mut outputs = Outputs::create(args, &automaton)?;
+    let mut history = Stats::with_capacity(HISTORY_LENGTH);
+    let mut export = StatsExport::create(args)?;
+    let mut timelapse = args.timelapse()?;
+    let mut trail = Trail::new(args.display.trail);
+    let mut current_theme = theme(&args.display.theme).unwrap_or_else(|e| {
+        let error = e.to_string();
+        eprintln!(
+            "{}",
+            localizer.format("warning-theme", [("error", error.into())])
+        );
+        Theme::classic()
+    });
+    let theme_names = theme_names();
+    if let Some(export) = &mut export {
+        export.record(&automaton, localizer);
+    }
+    record_timelapse(&mut timelapse, &automaton, localizer);
+    let running = |automaton: &Automaton| {
+        args.generations
+            .is_none_or(|generations| automaton.generation.0 < generations)
+    };
+    while running(&automaton) {
+        for name in theme_names.try_iter() {
+            match theme(name.trim()) {
+                Ok(theme) => current_theme = theme,
+                Err(e) => eprintln!("{e}"),
+            }
+        }
+        history.record(&automaton);
+        let beat = outputs.beat(&automaton);
+        // `render_every` generations pass per beat
+        let speed = f64::from(u32::try_from(args.render_every.max(1)).unwrap_or(u32::MAX))
+            / beat.as_secs_f64();
+        println!(
+            "{} | {speed:.1} gen/s",
+            tui::localized_status_line(&automaton, localizer),
+        );
+        print_grid(args, &automaton, &current_theme, &mut trail);
+        outputs.show(&automaton, beat);
+        if let Some(sample) = history.latest() {
+            println!(
+                "{}",
+                localizer.format(
+                    "status-population",
+                    [
+                        ("population", sample.population.into()),
+                        ("births", sample.births.into()),
+                        ("deaths", sample.deaths.into()),
+                    ],
+                )
+            );
+        }
+        println!(
+            "{}",
+            stats::sparkline(history.samples().map(|sample| sample.population))
+        );
+        thread::sleep(beat);
+        // Steps one generation at a time so the stats and the timelapse see every one of them,
+        // not just the rendered
+        for _ in 0..args.render_every.max(1) {
+            if !running(&automaton) {
+                break;
+            }
+            step(&mut automaton)?;
+            if let Some(export) = &mut export {
+                export.record(&automaton, localizer);
+            }
+            record_timelapse(&mut timelapse, &automaton, localizer);
+        }
+    }
+    if let Some(export) = &export {
+        export.finish(localizer);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{command, run::headless, Command};
+    use cellular_automata::localization::Localizer;
+
+    /// The exit status and output of `no_bevy_2d run --headless` followed by `args`
+    fn run(args: &[&str]) -> (i32, String) {
+        let Command::Run(args) = command(&[&["run", "--headless"], args].concat()) else {
+            unreachable!()
+        };
+        let mut out = Vec::new();
+        let automaton = args.grid.automaton().unwrap();
+        let status = headless(&args, automaton, &mut out, &Localizer::default()).unwrap();
+        (status, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn headless_with_default_rules() {
+        let (status, out) = run(&["--rows", "8", "--cols", "8", "--generations", "3"]);
+        assert_eq!(status, 0);
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        for (generation, line) in lines.iter().enumerate() {
+            assert!(line.starts_with(&format!("Generation: {generation} | ")));
+            assert!(line.ends_with(" | Rules: B3/S23"));
+        }
+    }
+
+    #[test]
+    fn headless_stops() {
+        assert_eq!(
+            run(&["--density", "0", "--stop-on", "extinction"]),
+            (
+                3,
+                "Generation: 0 | Population: 0 | Rules: B3/S23\n\
+                 Stopped at generation 0 (population 0): extinction fired\n"
+                    .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Streaming to WebSocket clients and chat
+
+use crate::failure::Failure;
+use cellular_automata::{
+    localization::Localizer,
+    server::{self, ChatConfig, Moderation, Session},
+    Automaton,
+};
+use clap::Args;
+use std::{
+    process,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    #[arg(long, default_value = "127.0.0.1:9001")]
+    pub address: String,
+    /// Milliseconds between generations
+    #[arg(long, default_value_t = 100)]
+    pub interval: u64,
+    /// Also serves the HTTP API on this address, e.g. 127.0.0.1:8080
+    #[cfg(feature = "http-api")]
+    #[arg(long)]
+    pub http: Option<std::net::SocketAddr>,
+    /// Twitch channel, or IRC channel without `#`, whose viewers can `!spawn` patterns
+    #[arg(long)]
+    pub chat: Option<String>,
+    #[arg(long, default_value = "irc.chat.twitch.tv:6667", requires = "chat")]
+    pub chat_server: String,
+    /// Read only on Twitch unless a `--chat-token` is given
+    #[arg(long, default_value = "justinfan31415", requires = "chat")]
+    pub chat_nick: String,
+    /// `oauth:` token for replying to viewers on Twitch
+    #[arg(long, requires = "chat")]
+    pub chat_token: Option<String>,
+    /// Viewers who may also change the rules and pause, and aren't rate limited
+    #[arg(long, value_delimiter = ',', requires = "chat")]
+    pub chat_allow: Vec<String>,
+    /// Seconds viewers have to wait between commands
+    #[arg(long, default_value_t = 10, requires = "chat")]
+    pub chat_cooldown: u64,
+}
+
+/// Steps `automaton` for WebSocket clients until the process is stopped
+pub fn serve(automaton: Automaton, args: &ServeArgs, localizer: &Localizer) -> ! {
+    let language = localizer.language();
+    let session = Arc::new(Mutex::new(Session::new(automaton)));
+    let listener = Arc::clone(&session);
+    let address = args.address.clone();
+    thread::spawn(move || {
+        if let Err(e) = server::listen(&address, &listener) {
+            Failure::Listen(address, e).warn(&Localizer::new(language));
+            process::exit(1);
+        }
+    });
+    let address = format!("ws://{}", args.address);
+    println!(
+        "{}",
+        localizer.format("serve-websocket", [("address", address.into())])
+    );
+    #[cfg(feature = "http-api")]
+    if let Some(address) = args.http {
+        let listener = Arc::clone(&session);
+        thread::spawn(move || {
+            if let Err(e) = server::listen_http(address, &listener) {
+                Failure::Listen(address.to_string(), e).warn(&Localizer::new(language));
+                process::exit(1);
+            }
+        });
+        let address = format!("http://{address}");
+        println!(
+            "{}",
+            localizer.format("serve-http", [("address", address.into())])
+        );
+    }
+    if let Some(channel) = &args.chat {
+        let config = ChatConfig {
+            server: args.chat_server.clone(),
+            nick: args.chat_nick.clone(),
+            password: args.chat_token.clone(),
+            channel: channel.clone(),
+        };
+        let mut moderation = Moderation::new(
+            args.chat_allow.iter().map(String::as_str),
+            Duration::from_secs(args.chat_cooldown),
+        );
+        let listener = Arc::clone(&session);
+        thread::spawn(move || {
+            if let Err(e) = server::listen_chat(&config, &mut moderation, &listener) {
+                let channel = config.channel.as_str();
+                let message = Localizer::new(language).format(
+                    "warning-chat",
+                    [("channel", channel.into()), ("error", e.to_string().into())],
+                );
+                eprintln!("{message}");
+            }
+        });
+        let channel = channel.as_str();
+        println!(
+            "{}",
+            localizer.format("serve-chat", [("channel", channel.into())])
+        );
+    }
+    server::run(&session, Duration::from_millis(args.interval))
+}
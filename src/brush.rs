@@ -0,0 +1,136 @@
+//! Brushes painting many Cells at once, see the brush tool of the editor
+
+use rand::Rng;
+
+/// Cells a brush covers around the Cell it's centered on
+/// - `Circle` => Cells whose center is at most `radius` away
+/// - `Square` => Cells at most `radius` away along both axes
+/// - `Line` => Cells at most `radius` away in the same row
+/// - `Spray` => Every Cell of the circle with probability `density`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Shape {
+    Circle,
+    Square,
+    Line,
+    Spray { density: f64 },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Brush {
+    pub shape: Shape,
+    /// `0` paints a single Cell
+    pub radius: usize,
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self {
+            shape: Shape::Circle,
+            radius: 2,
+        }
+    }
+}
+
+impl Brush {
+    /// Row and column offsets from the center the brush may paint
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn footprint(&self) -> Vec<(isize, isize)> {
+        let radius = self.radius as isize;
+        let offsets = -radius..=radius;
+        match self.shape {
+            Shape::Line => offsets.map(|col| (0, col)).collect(),
+            Shape::Square => offsets
+                .clone()
+                .flat_map(|row| offsets.clone().map(move |col| (row, col)))
+                .collect(),
+            Shape::Circle | Shape::Spray { .. } => offsets
+                .clone()
+                .flat_map(|row| offsets.clone().map(move |col| (row, col)))
+                .filter(|(row, col)| row * row + col * col <= radius * radius)
+                .collect(),
+        }
+    }
+
+    /// Cells painted by one dab centered on (`row`, `col`), clipped to the grid
+    pub fn dab(
+        &self,
+        row: usize,
+        col: usize,
+        row_count: usize,
+        col_count: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<(usize, usize)> {
+        self.footprint()
+            .into_iter()
+            .filter(|_| match self.shape {
+                Shape::Spray { density } => rng.gen_bool(density.clamp(0.0, 1.0)),
+                _ => true,
+            })
+            .filter_map(|(row_offset, col_offset)| {
+                let row = row.checked_add_signed(row_offset)?;
+                let col = col.checked_add_signed(col_offset)?;
+                (row < row_count && col < col_count).then_some((row, col))
+            })
+            .collect()
+    }
+}
+
+/// Cells on the line from `from` to `to`, both included, so strokes dragged faster
+/// than one Cell per frame have no gaps
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+pub fn stroke(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let (mut row, mut col) = (from.0 as isize, from.1 as isize);
+    let (to_row, to_col) = (to.0 as isize, to.1 as isize);
+    let (row_distance, col_distance) = ((to_row - row).abs(), -(to_col - col).abs());
+    let (row_step, col_step) = ((to_row - row).signum(), (to_col - col).signum());
+    let mut error = row_distance + col_distance;
+    let mut cells = vec![(row as usize, col as usize)];
+    while (row, col) != (to_row, to_col) {
+        let doubled = 2 * error;
+        if doubled >= col_distance {
+            error += col_distance;
+            row += row_step;
+        }
+        if doubled <= row_distance {
+            error += row_distance;
+            col += col_step;
+        }
+        cells.push((row as usize, col as usize));
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::brush::{stroke, Brush, Shape};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn shapes_and_strokes() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let brush = |shape, radius| Brush { shape, radius };
+        assert_eq!(brush(Shape::Circle, 0).footprint(), vec![(0, 0)]);
+        assert_eq!(brush(Shape::Circle, 1).footprint().len(), 5);
+        assert_eq!(brush(Shape::Circle, 2).footprint().len(), 13);
+        assert_eq!(brush(Shape::Square, 2).footprint().len(), 25);
+        assert_eq!(brush(Shape::Line, 2).footprint().len(), 5);
+
+        // Clipped at the top left corner
+        let dab = brush(Shape::Square, 1).dab(0, 0, 10, 10, &mut rng);
+        assert_eq!(dab, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+        let none = brush(Shape::Spray { density: 0.0 }, 3).dab(5, 5, 10, 10, &mut rng);
+        assert!(none.is_empty());
+        let all = brush(Shape::Spray { density: 1.0 }, 3).dab(5, 5, 10, 10, &mut rng);
+        assert_eq!(all, brush(Shape::Circle, 3).dab(5, 5, 10, 10, &mut rng));
+
+        assert_eq!(stroke((2, 2), (2, 2)), vec![(2, 2)]);
+        assert_eq!(stroke((0, 0), (0, 3)), vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+        assert_eq!(stroke((3, 3), (0, 0)), vec![(3, 3), (2, 2), (1, 1), (0, 0)]);
+        let steep = stroke((0, 1), (4, 0));
+        assert_eq!(steep.len(), 5);
+        assert_eq!((steep[0], steep[4]), ((0, 1), (4, 0)));
+        assert!(steep
+            .windows(2)
+            .all(|pair| pair[0].0.abs_diff(pair[1].0) <= 1 && pair[0].1.abs_diff(pair[1].1) <= 1));
+    }
+}
@@ -0,0 +1,132 @@
+use core::fmt;
+
+/// Represents The current State of the Cell
+/// - `Dead` => The Cell is dead
+/// - `Alive` => The Cell is alive
+/// - `Dying` => The Cell is currently dying with the state counter `ticks_till_death`
+///   representing the remaining generations until the Cell is dead
+///   i.e. Changes to the `Dead` state
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cell {
+    #[default]
+    Dead,
+    Alive,
+    Dying {
+        ticks_till_death: usize,
+    },
+}
+
+impl Cell {
+    pub const fn is_dead(&self) -> bool {
+        matches!(self, Self::Dead)
+    }
+    /// Whether the Cell isn't `Cell::Dead`, which includes `Cell::Dying`.
+    /// How neighbors count for the rules is up to `RuleSet::counting`.
+    pub const fn is_alive(&self) -> bool {
+        !self.is_dead()
+    }
+    pub const fn is_dying(&self) -> bool {
+        matches!(
+            self,
+            Self::Dying {
+                ticks_till_death: _
+            }
+        )
+    }
+
+    /// Quantizes a luminance value into one of `levels` Cell states.
+    /// Fewer than 2 levels can't distinguish anything and yield `Cell::Dead`.
+    #[cfg(feature = "std")]
+    pub(crate) fn from_darkness(luma: u8, levels: u8) -> Self {
+        if levels < 2 {
+            return Self::Dead;
+        }
+        let darkness = usize::from(u8::MAX - luma);
+        let level = darkness * usize::from(levels) / (usize::from(u8::MAX) + 1);
+        match level {
+            0 => Self::Dead,
+            l if l == usize::from(levels) - 1 => Self::Alive,
+            ticks_till_death => Self::Dying { ticks_till_death },
+        }
+    }
+
+    /// Number identifying the state for bindings to other languages
+    /// - `0` => `Cell::Dead`
+    /// - `1` => `Cell::Alive`
+    /// - `n >= 2` => `Cell::Dying` with `n - 1` ticks till death
+    ///
+    /// `Cell::Dying` with 0 ticks dies next generation just like with 1 tick, so it's numbered
+    /// like that one rather than like `Cell::Alive`
+    pub const fn state_number(&self) -> usize {
+        match self {
+            Self::Dead => 0,
+            Self::Alive => 1,
+            Self::Dying {
+                ticks_till_death: 0,
+            } => 2,
+            Self::Dying { ticks_till_death } => ticks_till_death.saturating_add(1),
+        }
+    }
+
+    /// `Cell::state_number` capped at `u8::MAX`, for compact binary formats
+    pub fn state_byte(&self) -> u8 {
+        u8::try_from(self.state_number()).unwrap_or(u8::MAX)
+    }
+
+    /// Inverse of `Cell::state_number`
+    pub const fn from_state_number(state: usize) -> Self {
+        match state {
+            0 => Self::Dead,
+            1 => Self::Alive,
+            state => Self::Dying {
+                ticks_till_death: state - 1,
+            },
+        }
+    }
+
+    pub const fn dying_cell() -> Self {
+        const TICKS_TILL_DEATH: usize = 3;
+        Self::Dying {
+            ticks_till_death: TICKS_TILL_DEATH,
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dead => write!(f, "Dead"),
+            Self::Alive => write!(f, "Alive"),
+            Self::Dying { ticks_till_death } => write!(f, "Death {ticks_till_death}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Cell;
+
+    #[test]
+    fn darkness_levels() {
+        assert_eq!(Cell::from_darkness(u8::MAX, 4), Cell::Dead);
+        assert_eq!(Cell::from_darkness(0, 4), Cell::Alive);
+        assert_eq!(
+            Cell::from_darkness(100, 4),
+            Cell::Dying {
+                ticks_till_death: 2
+            }
+        );
+        assert_eq!(Cell::from_darkness(0, 1), Cell::Dead);
+    }
+
+    #[test]
+    fn state_numbers() {
+        for cell in [Cell::Dead, Cell::Alive, Cell::dying_cell()] {
+            assert_eq!(Cell::from_state_number(cell.state_number()), cell);
+        }
+        assert_eq!(Cell::dying_cell().state_number(), 4);
+        let dying = |ticks_till_death| Cell::Dying { ticks_till_death };
+        assert_eq!(Cell::from_state_number(dying(0).state_number()), dying(1));
+    }
+}
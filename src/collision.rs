@@ -0,0 +1,384 @@
+//! Systematic collisions of two patterns, the way reactions for guns, eaters and glider
+//! syntheses are searched for, run by `no_bevy_2d analyze collide`.
+//!
+//! The second pattern is placed at every offset around the first in every phase of its cycle,
+//! and each collision runs until it settles and is classified.
+
+use crate::{
+    analysis::{self, Spaceship},
+    table::{Table, Values},
+    Automaton, Cell, Grid, RuleSet,
+};
+use itertools::iproduct;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Dead Cells kept between a reaction and the edges of its growing grid while it runs,
+/// nothing grows faster than a Cell per generation
+const MARGIN: usize = 2;
+/// Dead Cells around the result when it's classified,
+/// so ships can complete a period before `find_spaceships` would see them hit an edge
+#[allow(clippy::cast_possible_truncation)]
+const SHIP_MARGIN: usize = 2 * analysis::SHIP_PERIOD as usize;
+/// Patterns starting closer than this many Cells interact right away and aren't collided
+const MIN_DISTANCE: usize = 3;
+
+/// How a collision ended
+/// - `Annihilation` => Nothing is left
+/// - `Settled` => Only still lifes and oscillators are left
+/// - `Ships` => Spaceships fly off, possibly leaving debris behind
+/// - `Unsettled` => Still evolving after the last generation
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Outcome {
+    Annihilation,
+    Settled,
+    Ships,
+    Unsettled,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Annihilation => write!(f, "annihilation"),
+            Self::Settled => write!(f, "settled"),
+            Self::Ships => write!(f, "ships"),
+            Self::Unsettled => write!(f, "unsettled"),
+        }
+    }
+}
+
+/// Collision of the second pattern with the first
+/// - `offset` => Rows and columns of the top left corner of the second pattern relative to the
+///   one of the first, positive downwards and to the right
+/// - `phase` => Generations the second pattern ran on its own before it was placed
+/// - `reaction` => Generation the patterns started interacting at
+/// - `census` => Objects left after the last generation, see `analysis::census`
+/// - `new_ships` => Spaceships left whose shape isn't one of the collided patterns
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Collision {
+    pub offset: (i64, i64),
+    pub phase: u64,
+    pub reaction: u64,
+    pub outcome: Outcome,
+    pub population: usize,
+    pub census: BTreeMap<String, usize>,
+    pub ships: Vec<Spaceship>,
+    pub new_ships: Vec<Spaceship>,
+}
+
+impl Collision {
+    /// Whether the collision left a single still life or oscillator, which makes it a
+    /// candidate for constructing that object with the colliding patterns
+    pub fn is_clean(&self) -> bool {
+        self.outcome == Outcome::Settled && self.census.values().sum::<usize>() == 1
+    }
+
+    /// New ships and clean constructions stand out among the usual debris
+    pub fn is_interesting(&self) -> bool {
+        !self.new_ships.is_empty() || self.is_clean()
+    }
+}
+
+/// A pattern with the population and pattern of each of its generations on an unbounded grid
+struct Evolution {
+    populations: Vec<usize>,
+    phases: Vec<Grid>,
+    /// Canonical shapes of every object in every generation
+    shapes: BTreeSet<String>,
+}
+
+impl Evolution {
+    fn new(pattern: &[Vec<Cell>], rule_set: &RuleSet, generations: u64) -> Self {
+        let mut automaton = placed(pattern, rule_set);
+        let mut evolution = Self {
+            populations: Vec::new(),
+            phases: Vec::new(),
+            shapes: BTreeSet::new(),
+        };
+        for _ in 0..=generations {
+            automaton.grow(MARGIN);
+            evolution.populations.push(automaton.population());
+            evolution
+                .phases
+                .push(automaton.bounding_box().map_or_else(Grid::new, |bounds| {
+                    automaton.region(bounds.top..=bounds.bottom, bounds.left..=bounds.right)
+                }));
+            evolution
+                .shapes
+                .extend(analysis::census(&automaton.grid).into_keys());
+            automaton.step();
+        }
+        evolution
+    }
+}
+
+/// `pattern` with `MARGIN` dead Cells around it
+fn placed(pattern: &[Vec<Cell>], rule_set: &RuleSet) -> Automaton {
+    let row_count = pattern.len() + 2 * MARGIN;
+    let col_count = pattern.first().map_or(0, Vec::len) + 2 * MARGIN;
+    let mut automaton = Automaton::builder()
+        .row_count(row_count)
+        .col_count(col_count)
+        .grid(vec![vec![Cell::Dead; col_count]; row_count])
+        .rule_set(rule_set.clone())
+        .build();
+    automaton.stamp(pattern, MARGIN, MARGIN);
+    automaton
+}
+
+/// Collisions of `second` with `first` at every offset at most `reach` Cells beyond touching
+/// - `phases` => Phases of `second` tried, by default the period of its cycle
+/// - `max_generations` => Generations each collision runs before it's classified
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Search {
+    pub first: Grid,
+    pub second: Grid,
+    pub rule_set: RuleSet,
+    pub reach: usize,
+    pub phases: Option<u64>,
+    pub max_generations: u64,
+}
+
+impl Search {
+    /// Collisions of every offset and phase in which the patterns interact, patterns that miss
+    /// each other aren't listed. On `threads` threads, ordered by phase and offset.
+    pub fn run(&self, threads: usize) -> Vec<Collision> {
+        let phases = self.phases.unwrap_or_else(|| {
+            let mut automaton = placed(&self.second, &self.rule_set);
+            analysis::find_cycle(&mut automaton, self.max_generations)
+                .map_or(1, |cycle| cycle.period)
+        });
+        let first = Evolution::new(&self.first, &self.rule_set, self.max_generations);
+        let second = Evolution::new(&self.second, &self.rule_set, self.max_generations + phases);
+        let extent = |pattern: &Grid| pattern.len().max(pattern.first().map_or(0, Vec::len));
+        let reach = i64::try_from(
+            self.reach + MIN_DISTANCE + extent(&self.first).max(extent(&self.second)),
+        )
+        .unwrap_or(i64::MAX);
+        let placements: Vec<_> = iproduct!(0..phases, -reach..=reach, -reach..=reach).collect();
+
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..threads.clamp(1, placements.len().max(1)) {
+                let sender = sender.clone();
+                let (next, placements) = (&next, &placements);
+                let (first, second) = (&first, &second);
+                scope.spawn(move || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&(phase, rows, cols)) = placements.get(index) else {
+                        break;
+                    };
+                    let collision = self.collide(first, second, phase, (rows, cols));
+                    if sender.send((index, collision)).is_err() {
+                        break;
+                    }
+                });
+            }
+        });
+        drop(sender);
+        let mut collisions = receiver
+            .into_iter()
+            .filter_map(|(index, collision)| Some((index, collision?)))
+            .collect::<Vec<_>>();
+        collisions.sort_by_key(|(index, _)| *index);
+        collisions
+            .into_iter()
+            .map(|(_, collision)| collision)
+            .collect()
+    }
+
+    /// `None` if the patterns start too close or never interact
+    #[allow(clippy::cast_possible_truncation)]
+    fn collide(
+        &self,
+        first: &Evolution,
+        second: &Evolution,
+        phase: u64,
+        offset: (i64, i64),
+    ) -> Option<Collision> {
+        let second_phase = &second.phases[phase as usize];
+        let (top, left) = (
+            offset.0.min(0).unsigned_abs(),
+            offset.1.min(0).unsigned_abs(),
+        );
+        let first_corner = (MARGIN + top as usize, MARGIN + left as usize);
+        let second_corner = (
+            first_corner.0.checked_add_signed(offset.0 as isize)?,
+            first_corner.1.checked_add_signed(offset.1 as isize)?,
+        );
+        let row_count =
+            (first_corner.0 + self.first.len()).max(second_corner.0 + second_phase.len()) + MARGIN;
+        let col_count = (first_corner.1 + self.first.first().map_or(0, Vec::len))
+            .max(second_corner.1 + second_phase.first().map_or(0, Vec::len))
+            + MARGIN;
+        let mut automaton = Automaton::builder()
+            .row_count(row_count)
+            .col_count(col_count)
+            .grid(vec![vec![Cell::Dead; col_count]; row_count])
+            .rule_set(self.rule_set.clone())
+            .build();
+        automaton.stamp(&self.first, first_corner.0, first_corner.1);
+        if too_close(&automaton.grid, second_phase, second_corner) {
+            return None;
+        }
+        automaton.stamp(second_phase, second_corner.0, second_corner.1);
+
+        // Patterns that miss each other add up to the populations they have on their own
+        let mut reaction = None;
+        for generation in 0..=self.max_generations {
+            automaton.grow(MARGIN);
+            let apart = first.populations[generation as usize]
+                + second.populations[(generation + phase) as usize];
+            if reaction.is_none() && automaton.population() != apart {
+                reaction = Some(generation);
+            }
+            if reaction.is_some() && automaton.population() == 0 {
+                break;
+            }
+            if generation < self.max_generations {
+                automaton.step();
+            }
+        }
+        let reaction = reaction?;
+
+        automaton.grow(SHIP_MARGIN);
+        let ships = analysis::find_spaceships(&automaton, analysis::SHIP_PERIOD);
+        let outcome = if automaton.population() == 0 {
+            Outcome::Annihilation
+        } else if !ships.is_empty() {
+            Outcome::Ships
+        } else if analysis::find_period(&mut automaton.clone(), analysis::SHIP_PERIOD).is_some() {
+            Outcome::Settled
+        } else {
+            Outcome::Unsettled
+        };
+        let new_ships = ships
+            .iter()
+            .filter(|ship| !first.shapes.contains(&ship.shape))
+            .filter(|ship| !second.shapes.contains(&ship.shape))
+            .cloned()
+            .collect();
+        Some(Collision {
+            offset,
+            phase,
+            reaction,
+            outcome,
+            population: automaton.population(),
+            census: analysis::census(&automaton.grid),
+            ships,
+            new_ships,
+        })
+    }
+}
+
+/// Whether an alive Cell of `pattern` placed at `corner` is within `MIN_DISTANCE` of an alive
+/// Cell of `grid`
+fn too_close(grid: &[Vec<Cell>], pattern: &[Vec<Cell>], corner: (usize, usize)) -> bool {
+    let alive = |grid: &[Vec<Cell>], row: usize, col: usize| {
+        grid.get(row)
+            .and_then(|cells| cells.get(col))
+            .is_some_and(|cell| *cell == Cell::Alive)
+    };
+    iproduct!(0..pattern.len(), 0..pattern.first().map_or(0, Vec::len))
+        .filter(|&(row, col)| alive(pattern, row, col))
+        .any(|(row, col)| {
+            let (row, col) = (corner.0 + row, corner.1 + col);
+            iproduct!(
+                row.saturating_sub(MIN_DISTANCE)..=row + MIN_DISTANCE,
+                col.saturating_sub(MIN_DISTANCE)..=col + MIN_DISTANCE
+            )
+            .any(|(row, col)| alive(grid, row, col))
+        })
+}
+
+/// Velocities of `ships` separated by spaces
+fn velocities(ships: &[Spaceship]) -> String {
+    ships
+        .iter()
+        .map(Spaceship::velocity)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One row per collision, the ships as their velocities and the census as
+/// `count x shape` separated by spaces
+#[allow(clippy::cast_possible_wrap)]
+pub fn table(collisions: &[Collision]) -> Table {
+    let int = |value: fn(&Collision) -> i64| Values::Int(collisions.iter().map(value).collect());
+    let text =
+        |value: fn(&Collision) -> String| Values::Text(collisions.iter().map(value).collect());
+    Table::default()
+        .column("row_offset", int(|collision| collision.offset.0))
+        .column("col_offset", int(|collision| collision.offset.1))
+        .column("phase", int(|collision| collision.phase as i64))
+        .column("reaction", int(|collision| collision.reaction as i64))
+        .column("outcome", text(|collision| collision.outcome.to_string()))
+        .column("population", int(|collision| collision.population as i64))
+        .column(
+            "census",
+            text(|collision| {
+                collision
+                    .census
+                    .iter()
+                    .map(|(shape, count)| format!("{count}x{shape}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }),
+        )
+        .column("ships", text(|collision| velocities(&collision.ships)))
+        .column(
+            "new_ships",
+            text(|collision| velocities(&collision.new_ships)),
+        )
+        .column("clean", int(|collision| i64::from(collision.is_clean())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collision::{self, Outcome, Search},
+        rle, RuleSet,
+    };
+
+    #[test]
+    fn glider_collisions() {
+        let glider = rle::parse("bo$2bo$3o!").unwrap();
+        // Travelling up and left, towards the first glider
+        let opposite = rle::parse("3o$o$bo!").unwrap();
+        let search = Search {
+            first: glider,
+            second: opposite,
+            rule_set: RuleSet::default(),
+            reach: 0,
+            phases: None,
+            max_generations: 24,
+        };
+        let collisions = search.run(4);
+        assert!(!collisions.is_empty());
+        assert!(collisions.iter().all(|collision| collision.phase < 4));
+        // Head on gliders annihilate, turn into blocks, blinkers and more
+        assert!(collisions
+            .iter()
+            .any(|collision| collision.outcome == Outcome::Annihilation));
+        assert!(collisions
+            .iter()
+            .any(|collision| collision.outcome == Outcome::Settled));
+        // The gliders only approach each other if the second starts further down and right
+        assert!(collisions
+            .iter()
+            .all(|collision| collision.offset.0 + collision.offset.1 > 0));
+        assert!(collisions
+            .iter()
+            .filter(|collision| collision.outcome == Outcome::Ships)
+            .all(|collision| !collision.ships.is_empty()));
+        assert_eq!(collision::table(&collisions).row_count(), collisions.len());
+    }
+}
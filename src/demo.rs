@@ -0,0 +1,204 @@
+//! Unattended demo cycling through presets and random interesting rules, reseeding the grid
+//! with a soup whenever its activity dies down, for kiosk displays and screensavers
+
+use crate::{
+    plugin::Preset,
+    seeding,
+    stop::{StopCondition, Stopped, Stopper},
+    Automaton, Generation, RuleSet,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Density of the soups scenes start from
+const SOUP_DENSITY: f64 = 0.35;
+/// The grid counts as quiet once it repeats with at most this period, e.g. blinkers
+const QUIET_PERIOD: u64 = 2;
+/// Size of the soup a random rule is tried on
+const TRIAL_SIZE: usize = 48;
+const TRIAL_GENERATIONS: u64 = 150;
+/// Fraction of the trial soup allowed to be alive at its end, above it the rule just fills it
+const MAX_TRIAL_FILL: f64 = 0.6;
+/// Random rules tried for a scene before falling back to the next preset
+const RULE_ATTEMPTS: usize = 20;
+
+/// What `Demo::tick` did to the automaton
+/// - `Scene` => Switched to the rules of the preset and reseeded
+/// - `Reseeded` => Reseeded because the grid went quiet in the way it stopped
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Event {
+    Scene(Preset),
+    Reseeded(Stopped),
+}
+
+/// Alternates between the given presets in order and random interesting rules,
+/// showing each for `scene_length` generations
+pub struct Demo {
+    presets: Vec<Preset>,
+    next_preset: usize,
+    scene_length: u64,
+    /// `None` until the first scene started
+    scene_start: Option<Generation>,
+    /// Whether the next scene is a random rule rather than a preset
+    random_next: bool,
+    quiet: Stopper,
+    rng: StdRng,
+}
+
+impl Demo {
+    pub fn new(presets: Vec<Preset>, scene_length: u64, seed: u64) -> Self {
+        Self {
+            presets,
+            next_preset: 0,
+            scene_length: scene_length.max(1),
+            scene_start: None,
+            random_next: false,
+            quiet: quiet_stopper(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Starts the next scene if the current one ran long enough, or reseeds if the grid went
+    /// quiet, to be called once per generation
+    pub fn tick(&mut self, automaton: &mut Automaton) -> Option<Event> {
+        let scene_over = self
+            .scene_start
+            .is_none_or(|start| automaton.generation - start >= self.scene_length);
+        if scene_over {
+            let preset = self.next_scene();
+            preset.apply(automaton);
+            self.reseed(automaton);
+            self.scene_start = Some(automaton.generation);
+            return Some(Event::Scene(preset));
+        }
+        let stopped = self.quiet.check(automaton)?;
+        self.reseed(automaton);
+        Some(Event::Reseeded(stopped))
+    }
+
+    fn next_scene(&mut self) -> Preset {
+        let random = self.random_next;
+        self.random_next = !random;
+        if random || self.presets.is_empty() {
+            if let Some(rule_set) = interesting_rule(&mut self.rng, RULE_ATTEMPTS) {
+                return Preset::new(rule_set.to_string(), rule_set);
+            }
+        }
+        let Some(preset) = self.presets.get(self.next_preset) else {
+            return Preset::new("Conway", RuleSet::default());
+        };
+        self.next_preset = (self.next_preset + 1) % self.presets.len();
+        preset.clone()
+    }
+
+    /// Replaces the grid with a fresh soup of the same size, e.g. after resizing it
+    pub fn reseed(&mut self, automaton: &mut Automaton) {
+        automaton.grid = seeding::soup(
+            self.rng.gen(),
+            automaton.row_count,
+            automaton.col_count,
+            SOUP_DENSITY,
+        );
+        self.quiet = quiet_stopper();
+    }
+}
+
+fn quiet_stopper() -> Stopper {
+    Stopper::new(vec![
+        StopCondition::Extinction,
+        StopCondition::Period(QUIET_PERIOD),
+    ])
+}
+
+/// Random life-like rule without `B0`, whose soups would flash, and with a decay now and then
+pub fn random_rule(rng: &mut impl Rng) -> RuleSet {
+    let mut birth = (1..=8).filter(|_| rng.gen_bool(0.3)).collect::<Vec<_>>();
+    if birth.is_empty() {
+        birth.push(rng.gen_range(1..=8));
+    }
+    let survival = (0..=8).filter(|_| rng.gen_bool(0.35)).collect();
+    let decay = if rng.gen_bool(0.25) {
+        rng.gen_range(1..=3)
+    } else {
+        0
+    };
+    RuleSet::life_like(birth, survival, decay)
+}
+
+/// Whether a soup under `rule_set` neither dies out, fills the grid nor goes quiet
+/// within the trial generations
+#[allow(clippy::cast_precision_loss)]
+pub fn is_interesting(rule_set: &RuleSet, seed: u64) -> bool {
+    let mut automaton = Automaton::builder()
+        .row_count(TRIAL_SIZE)
+        .col_count(TRIAL_SIZE)
+        .grid(seeding::soup(seed, TRIAL_SIZE, TRIAL_SIZE, SOUP_DENSITY))
+        .rule_set(rule_set.clone())
+        .build();
+    let mut quiet = quiet_stopper();
+    for _ in 0..TRIAL_GENERATIONS {
+        if quiet.check(&automaton).is_some() {
+            return false;
+        }
+        automaton.step();
+    }
+    automaton.population() as f64 <= (TRIAL_SIZE * TRIAL_SIZE) as f64 * MAX_TRIAL_FILL
+}
+
+/// The first of `attempts` random rules that `is_interesting`
+pub fn interesting_rule(rng: &mut impl Rng, attempts: usize) -> Option<RuleSet> {
+    (0..attempts).find_map(|_| {
+        let rule_set = random_rule(rng);
+        is_interesting(&rule_set, rng.gen()).then_some(rule_set)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        demo::{is_interesting, Demo, Event},
+        plugin::Preset,
+        Automaton, Cell, RuleSet,
+    };
+
+    #[test]
+    fn scenes_and_reseeding() {
+        assert!(is_interesting(&RuleSet::default(), 7));
+        // Births need all 8 neighbors alive, so the soup dies out
+        assert!(!is_interesting(&RuleSet::life_like(vec![8], vec![], 0), 7));
+
+        let presets = vec![
+            Preset::new("Conway", RuleSet::default()),
+            Preset::new("HighLife", RuleSet::life_like(vec![3, 6], vec![2, 3], 0)),
+        ];
+        let mut demo = Demo::new(presets, 50, 1);
+        let mut automaton = Automaton::builder()
+            .row_count(32)
+            .col_count(32)
+            .grid(vec![vec![Cell::Dead; 32]; 32])
+            .build();
+        let Some(Event::Scene(first)) = demo.tick(&mut automaton) else {
+            panic!("the first tick starts a scene");
+        };
+        assert_eq!(first.name, "Conway");
+        assert!(automaton.population() > 0);
+
+        automaton.grid = vec![vec![Cell::Dead; 32]; 32];
+        automaton.step();
+        assert!(matches!(
+            demo.tick(&mut automaton),
+            Some(Event::Reseeded(_))
+        ));
+        assert!(automaton.population() > 0);
+
+        let mut scenes = Vec::new();
+        for _ in 0..200 {
+            automaton.step();
+            if let Some(Event::Scene(preset)) = demo.tick(&mut automaton) {
+                scenes.push(preset.name);
+            }
+        }
+        // Every other scene is a random rule, unless none of the tried ones was interesting
+        assert_eq!(scenes.len(), 4);
+        assert!(scenes.contains(&"HighLife".to_owned()));
+    }
+}
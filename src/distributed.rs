@@ -0,0 +1,578 @@
+//! Simulation of grids too large for a single machine.
+//!
+//! The grid is split into horizontal strips, each owned by a worker process (see `work`).
+//! Every generation the `Coordinator` relays the first and last row of every strip
+//! to its neighbors as halos, and it assembles snapshots out of all strips on demand.
+//!
+//! Messages are prefixed with their length as u32 and encoded like the ones of `remote`.
+//! Rules are sent in B/S notation, so only rules expressible in it can be distributed,
+//! `Coordinator::connect` refuses others rather than letting workers step different rules.
+
+use crate::{
+    remote::{ProtocolError, Reader},
+    rules::NotationError,
+    transition, Automaton, Cell, Generation, Grid, Neighborhood, RuleTable,
+};
+use std::{
+    error, fmt,
+    io::{self, BufReader, BufWriter, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+/// Largest message accepted, protects against allocating for corrupt length prefixes
+const MAX_MESSAGE_LEN: usize = 1 << 30;
+
+/// Errors that can occur between a coordinator and its workers
+/// - `Io` => A connection failed
+/// - `Protocol` => A message couldn't be decoded
+/// - `Rules` => The rules sent to a worker aren't valid B/S notation
+/// - `Inexpressible` => The rules can't be written in B/S notation without changing how they
+///   step Cells, e.g. rules counting dying neighbors or a particular state
+/// - `Unexpected` => A valid message arrived at the wrong time
+/// - `NoWorkers` => A coordinator needs at least one worker
+#[derive(Debug)]
+pub enum DistributedError {
+    Io(io::Error),
+    Protocol(ProtocolError),
+    Rules(NotationError),
+    Inexpressible(String),
+    Unexpected(&'static str),
+    NoWorkers,
+}
+
+impl fmt::Display for DistributedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Connection failed: {e}"),
+            Self::Protocol(e) => write!(f, "Invalid message: {e}"),
+            Self::Rules(e) => write!(f, "Invalid rules: {e}"),
+            Self::Inexpressible(notation) => write!(
+                f,
+                "The rules can't be sent to workers, in B/S notation they'd become {notation}"
+            ),
+            Self::Unexpected(message) => write!(f, "Unexpected message: {message}"),
+            Self::NoWorkers => write!(f, "There are no workers"),
+        }
+    }
+}
+
+impl error::Error for DistributedError {}
+
+impl From<io::Error> for DistributedError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ProtocolError> for DistributedError {
+    fn from(value: ProtocolError) -> Self {
+        Self::Protocol(value)
+    }
+}
+
+impl From<NotationError> for DistributedError {
+    fn from(value: NotationError) -> Self {
+        Self::Rules(value)
+    }
+}
+
+/// Consecutive rows of the grid owned by a worker, together with how to step them
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Strip {
+    pub cells: Grid,
+    /// Compiled once when the strip is assigned rather than every generation
+    pub rule_table: RuleTable,
+    pub neighborhood: Neighborhood,
+}
+
+impl Strip {
+    /// Steps the strip given the last row of the strip `above` and the first row of the one `below`,
+    /// which are `None` at the edges of the grid
+    pub fn step(&mut self, above: Option<&[Cell]>, below: Option<&[Cell]>) {
+        let mut padded = Vec::with_capacity(self.cells.len() + 2);
+        padded.extend(above.map(<[Cell]>::to_vec));
+        padded.append(&mut self.cells);
+        padded.extend(below.map(<[Cell]>::to_vec));
+        let mut next = transition::compiled_next_grid(&padded, self.neighborhood, &self.rule_table);
+        if below.is_some() {
+            next.pop();
+        }
+        if above.is_some() {
+            next.remove(0);
+        }
+        self.cells = next;
+    }
+
+    /// First and last row, which are the halos of the neighboring strips
+    fn edges(&self) -> (Vec<Cell>, Vec<Cell>) {
+        (
+            self.cells.first().cloned().unwrap_or_default(),
+            self.cells.last().cloned().unwrap_or_default(),
+        )
+    }
+}
+
+/// Splits the grid of `automaton` into up to `count` strips whose heights differ by at most 1
+pub fn split(automaton: &Automaton, count: usize) -> Vec<Strip> {
+    let row_count = automaton.grid.len();
+    let count = count.clamp(1, row_count.max(1));
+    let (height, taller) = (row_count / count, row_count % count);
+    let mut rows = automaton.grid.iter();
+    let rule_table = RuleTable::new(&automaton.rule_set);
+    (0..count)
+        .map(|index| Strip {
+            cells: rows
+                .by_ref()
+                .take(height + usize::from(index < taller))
+                .cloned()
+                .collect(),
+            rule_table: rule_table.clone(),
+            neighborhood: automaton.neighborhood_type,
+        })
+        .collect()
+}
+
+/// Message from the coordinator
+/// - `Assign` => The strip the worker owns from now on
+/// - `Step` => Steps the strip with the halos of its neighbors
+/// - `Snapshot` => Asks for the whole strip
+/// - `Shutdown` => Stops the worker
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum ToWorker {
+    Assign(Strip),
+    Step {
+        above: Option<Vec<Cell>>,
+        below: Option<Vec<Cell>>,
+    },
+    Snapshot,
+    Shutdown,
+}
+
+/// Message from a worker
+/// - `Edges` => First and last row of the strip after assigning or stepping it
+/// - `Strip` => The whole strip, answering `ToWorker::Snapshot`
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum ToCoordinator {
+    Edges { top: Vec<Cell>, bottom: Vec<Cell> },
+    Strip(Grid),
+}
+
+impl ToWorker {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Self::Assign(strip) => {
+                bytes.push(0);
+                write_slice(
+                    &mut bytes,
+                    strip.rule_table.rule_set().to_string().as_bytes(),
+                );
+                bytes.push(match strip.neighborhood {
+                    Neighborhood::Moore => 0,
+                    Neighborhood::VonNeumann => 1,
+                });
+                write_grid(&mut bytes, &strip.cells);
+            }
+            Self::Step { above, below } => {
+                bytes.push(1);
+                for halo in [above, below] {
+                    bytes.push(u8::from(halo.is_some()));
+                    write_row(&mut bytes, halo.as_deref().unwrap_or_default());
+                }
+            }
+            Self::Snapshot => bytes.push(2),
+            Self::Shutdown => bytes.push(3),
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, DistributedError> {
+        let mut reader = Reader(bytes);
+        let message = match reader.u8()? {
+            0 => {
+                let len = reader.u32()? as usize;
+                let rule_set = String::from_utf8_lossy(reader.slice(len)?).parse()?;
+                let rule_table = RuleTable::new(&rule_set);
+                let neighborhood = match reader.u8()? {
+                    0 => Neighborhood::Moore,
+                    _ => Neighborhood::VonNeumann,
+                };
+                Self::Assign(Strip {
+                    rule_table,
+                    neighborhood,
+                    cells: read_grid(&mut reader)?,
+                })
+            }
+            1 => {
+                let mut halo = || -> Result<_, ProtocolError> {
+                    let present = reader.u8()? != 0;
+                    let row = read_row(&mut reader)?;
+                    Ok(present.then_some(row))
+                };
+                Self::Step {
+                    above: halo()?,
+                    below: halo()?,
+                }
+            }
+            2 => Self::Snapshot,
+            3 => Self::Shutdown,
+            tag => return Err(ProtocolError::UnknownTag(tag).into()),
+        };
+        Ok(reader.finish(message)?)
+    }
+}
+
+impl ToCoordinator {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Self::Edges { top, bottom } => {
+                bytes.push(0);
+                write_row(&mut bytes, top);
+                write_row(&mut bytes, bottom);
+            }
+            Self::Strip(cells) => {
+                bytes.push(1);
+                write_grid(&mut bytes, cells);
+            }
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        let mut reader = Reader(bytes);
+        let message = match reader.u8()? {
+            0 => Self::Edges {
+                top: read_row(&mut reader)?,
+                bottom: read_row(&mut reader)?,
+            },
+            1 => Self::Strip(read_grid(&mut reader)?),
+            tag => return Err(ProtocolError::UnknownTag(tag)),
+        };
+        reader.finish(message)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_slice(bytes: &mut Vec<u8>, slice: &[u8]) {
+    bytes.extend((slice.len() as u32).to_le_bytes());
+    bytes.extend(slice);
+}
+
+fn write_row(bytes: &mut Vec<u8>, row: &[Cell]) {
+    write_slice(bytes, &row.iter().map(Cell::state_byte).collect::<Vec<_>>());
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_grid(bytes: &mut Vec<u8>, grid: &[Vec<Cell>]) {
+    bytes.extend((grid.len() as u32).to_le_bytes());
+    for row in grid {
+        write_row(bytes, row);
+    }
+}
+
+fn read_row(reader: &mut Reader<'_>) -> Result<Vec<Cell>, ProtocolError> {
+    let len = reader.u32()? as usize;
+    Ok(reader
+        .slice(len)?
+        .iter()
+        .map(|&state| Cell::from_state_number(state.into()))
+        .collect())
+}
+
+fn read_grid(reader: &mut Reader<'_>) -> Result<Grid, ProtocolError> {
+    (0..reader.u32()?).map(|_| read_row(reader)).collect()
+}
+
+/// Both directions of a connection with length prefixed messages
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        // Halos are small and latency bound
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: BufWriter::new(stream),
+        })
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn send(&mut self, message: &[u8]) -> io::Result<()> {
+        self.writer
+            .write_all(&(message.len() as u32).to_le_bytes())?;
+        self.writer.write_all(message)?;
+        self.writer.flush()
+    }
+
+    fn receive(&mut self) -> io::Result<Vec<u8>> {
+        let mut len = [0; 4];
+        self.reader.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_MESSAGE_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Message is too long",
+            ));
+        }
+        let mut message = vec![0; len];
+        self.reader.read_exact(&mut message)?;
+        Ok(message)
+    }
+}
+
+/// Serves a single coordinator connecting to `listener`,
+/// until it shuts the worker down or disconnects
+pub fn work(listener: &TcpListener) -> Result<(), DistributedError> {
+    let (stream, _) = listener.accept()?;
+    let mut connection = Connection::new(stream)?;
+    let mut strip = None;
+    loop {
+        let message = match connection.receive() {
+            Ok(message) => message,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let reply = match ToWorker::decode(&message)? {
+            ToWorker::Assign(assigned) => strip.insert(assigned).edges(),
+            ToWorker::Step { above, below } => {
+                let strip = strip.as_mut().ok_or(DistributedError::Unexpected(
+                    "Step before a strip was assigned",
+                ))?;
+                strip.step(above.as_deref(), below.as_deref());
+                strip.edges()
+            }
+            ToWorker::Snapshot => {
+                let strip = strip.as_ref().ok_or(DistributedError::Unexpected(
+                    "Snapshot before a strip was assigned",
+                ))?;
+                connection.send(&ToCoordinator::Strip(strip.cells.clone()).encode())?;
+                continue;
+            }
+            ToWorker::Shutdown => return Ok(()),
+        };
+        let (top, bottom) = reply;
+        connection.send(&ToCoordinator::Edges { top, bottom }.encode())?;
+    }
+}
+
+/// A worker as seen by the coordinator
+struct Worker {
+    connection: Connection,
+    /// First row of its strip
+    top: Vec<Cell>,
+    /// Last row of its strip
+    bottom: Vec<Cell>,
+}
+
+impl Worker {
+    fn receive_edges(&mut self) -> Result<(), DistributedError> {
+        match ToCoordinator::decode(&self.connection.receive()?)? {
+            ToCoordinator::Edges { top, bottom } => {
+                (self.top, self.bottom) = (top, bottom);
+                Ok(())
+            }
+            ToCoordinator::Strip(_) => Err(DistributedError::Unexpected("Strip instead of edges")),
+        }
+    }
+}
+
+/// Steps a grid split across workers, ordered from the top strip to the bottom one
+pub struct Coordinator {
+    workers: Vec<Worker>,
+    pub generation: Generation,
+}
+
+impl Coordinator {
+    /// Connects to the workers listening on `addresses` and assigns each one a strip of `automaton`.
+    /// Surplus workers, if the grid has fewer rows than there are workers, are disconnected.
+    pub fn connect<A: ToSocketAddrs>(
+        addresses: impl IntoIterator<Item = A>,
+        automaton: &Automaton,
+    ) -> Result<Self, DistributedError> {
+        let notation = automaton.rule_set.to_string();
+        let sent = notation.parse().map(|rule_set| RuleTable::new(&rule_set));
+        if sent != Ok(RuleTable::new(&automaton.rule_set)) {
+            return Err(DistributedError::Inexpressible(notation));
+        }
+        let streams = addresses
+            .into_iter()
+            .map(TcpStream::connect)
+            .collect::<io::Result<Vec<_>>>()?;
+        if streams.is_empty() {
+            return Err(DistributedError::NoWorkers);
+        }
+        let strips = split(automaton, streams.len());
+        let mut workers = Vec::with_capacity(strips.len());
+        for (stream, strip) in streams.into_iter().zip(strips) {
+            let mut connection = Connection::new(stream)?;
+            connection.send(&ToWorker::Assign(strip).encode())?;
+            workers.push(Worker {
+                connection,
+                top: Vec::new(),
+                bottom: Vec::new(),
+            });
+        }
+        for worker in &mut workers {
+            worker.receive_edges()?;
+        }
+        Ok(Self {
+            workers,
+            generation: automaton.generation,
+        })
+    }
+
+    /// Steps every strip once, all workers step in parallel
+    pub fn step(&mut self) -> Result<(), DistributedError> {
+        let halos = (0..self.workers.len())
+            .map(|index| {
+                let above = index
+                    .checked_sub(1)
+                    .map(|above| self.workers[above].bottom.clone());
+                let below = self.workers.get(index + 1).map(|below| below.top.clone());
+                ToWorker::Step { above, below }.encode()
+            })
+            .collect::<Vec<_>>();
+        for (worker, message) in self.workers.iter_mut().zip(halos) {
+            worker.connection.send(&message)?;
+        }
+        for worker in &mut self.workers {
+            worker.receive_edges()?;
+        }
+        self.generation += 1;
+        Ok(())
+    }
+
+    pub fn advance(&mut self, generations: usize) -> Result<(), DistributedError> {
+        for _ in 0..generations {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Assembles the whole grid out of the strips of all workers
+    pub fn snapshot(&mut self) -> Result<Grid, DistributedError> {
+        for worker in &mut self.workers {
+            worker.connection.send(&ToWorker::Snapshot.encode())?;
+        }
+        let mut grid = Vec::new();
+        for worker in &mut self.workers {
+            match ToCoordinator::decode(&worker.connection.receive()?)? {
+                ToCoordinator::Strip(mut strip) => grid.append(&mut strip),
+                ToCoordinator::Edges { .. } => {
+                    return Err(DistributedError::Unexpected("Edges instead of a strip"))
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Stops all workers
+    pub fn shutdown(mut self) -> Result<(), DistributedError> {
+        for worker in &mut self.workers {
+            worker.connection.send(&ToWorker::Shutdown.encode())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        distributed::{split, work, Coordinator, DistributedError},
+        Automaton, Counting, Neighborhood, RuleSet, Seeding,
+    };
+    use std::{net::TcpListener, thread};
+
+    fn soup(rule_set: RuleSet) -> Automaton {
+        Automaton::builder()
+            .row_count(10)
+            .col_count(8)
+            .seeding(Seeding::Uniform { density: 0.4 })
+            .rule_set(rule_set)
+            .neighborhood_type(Neighborhood::Moore)
+            .build()
+    }
+
+    fn brians_brain() -> Automaton {
+        soup(RuleSet::life_like(vec![2], vec![], 1))
+    }
+
+    #[test]
+    fn strips_step_like_the_whole_grid() {
+        let mut automaton = brians_brain();
+        let mut strips = split(&automaton, 3);
+        assert_eq!(
+            strips
+                .iter()
+                .map(|strip| strip.cells.len())
+                .collect::<Vec<_>>(),
+            vec![4, 3, 3]
+        );
+        assert_eq!(split(&automaton, 20).len(), 10);
+        for _ in 0..5 {
+            let edges = strips
+                .iter()
+                .map(|strip| (strip.cells[0].clone(), strip.cells.last().unwrap().clone()))
+                .collect::<Vec<_>>();
+            for (index, strip) in strips.iter_mut().enumerate() {
+                let above = index.checked_sub(1).map(|above| edges[above].1.as_slice());
+                let below = edges.get(index + 1).map(|below| below.0.as_slice());
+                strip.step(above, below);
+            }
+            automaton.step();
+            let cells = strips
+                .iter()
+                .flat_map(|strip| strip.cells.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(cells, automaton.grid);
+        }
+    }
+
+    /// Steps `automaton` on three workers and locally, comparing the results
+    fn distribute(automaton: &mut Automaton) {
+        let listeners = (0..3)
+            .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+            .collect::<Vec<_>>();
+        let addresses = listeners
+            .iter()
+            .map(|listener| listener.local_addr().unwrap())
+            .collect::<Vec<_>>();
+        let workers = listeners
+            .into_iter()
+            .map(|listener| thread::spawn(move || work(&listener)))
+            .collect::<Vec<_>>();
+
+        let mut coordinator = Coordinator::connect(&addresses, automaton).unwrap();
+        coordinator.advance(4).unwrap();
+        automaton.advance(4);
+        assert_eq!(coordinator.snapshot().unwrap(), automaton.grid);
+        assert_eq!(coordinator.generation, automaton.generation);
+
+        coordinator.shutdown().unwrap();
+        for worker in workers {
+            worker.join().unwrap().unwrap();
+        }
+    }
+
+    #[test]
+    fn over_tcp() {
+        distribute(&mut brians_brain());
+        // Conway's Game of Life written with ranges, which B/S notation doesn't preserve
+        distribute(&mut soup(RuleSet::default()));
+
+        // B/S notation only counts alive neighbors, workers would step other rules
+        let automaton = soup(RuleSet {
+            counting: Counting::AliveAndDying,
+            ..RuleSet::life_like(vec![2], vec![], 1)
+        });
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        assert!(matches!(
+            Coordinator::connect([listener.local_addr().unwrap()], &automaton),
+            Err(DistributedError::Inexpressible(_))
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{Automaton, Cell};
+use itertools::izip;
+
+/// Two automata stepped in lockstep from the same initial grid,
+/// to compare rules or to validate one engine against another
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub left: Automaton,
+    pub right: Automaton,
+}
+
+impl Divergence {
+    /// `right` starts from the grid and generation of `left`,
+    /// only its rules and neighborhood are kept
+    pub fn new(left: Automaton, right: Automaton) -> Self {
+        let right = Automaton {
+            generation: left.generation,
+            row_count: left.row_count,
+            col_count: left.col_count,
+            grid: left.grid.clone(),
+            ..right
+        };
+        Self { left, right }
+    }
+
+    pub fn step(&mut self) {
+        self.left.step();
+        self.right.step();
+    }
+
+    /// Cells whose states differ between both grids
+    pub fn difference(&self) -> Vec<Vec<bool>> {
+        difference(&self.left.grid, &self.right.grid)
+    }
+
+    /// Number of Cells whose states differ between both grids
+    pub fn differing(&self) -> usize {
+        differing(&self.left.grid, &self.right.grid)
+    }
+}
+
+/// XOR of two grids, `true` where the Cells differ.
+/// Cells outside of the smaller grid count as `Cell::Dead`.
+pub fn difference(left: &[Vec<Cell>], right: &[Vec<Cell>]) -> Vec<Vec<bool>> {
+    let dead = Vec::new();
+    (0..left.len().max(right.len()))
+        .map(|row| {
+            let left = left.get(row).unwrap_or(&dead);
+            let right = right.get(row).unwrap_or(&dead);
+            (0..left.len().max(right.len()))
+                .map(|col| {
+                    left.get(col).unwrap_or(&Cell::Dead) != right.get(col).unwrap_or(&Cell::Dead)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Which of two compared grids a Cell is alive in, dying Cells count as alive
+/// - `Neither` => Dead in both
+/// - `OnlyLeft` => Alive only in the left grid
+/// - `OnlyRight` => Alive only in the right grid
+/// - `Both` => Alive in both
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Presence {
+    Neither,
+    OnlyLeft,
+    OnlyRight,
+    Both,
+}
+
+/// Presence of every Cell in either grid, aligned at their top left corners.
+/// Cells outside of the smaller grid count as `Cell::Dead`, the result covers both grids.
+pub fn overlay(left: &[Vec<Cell>], right: &[Vec<Cell>]) -> Vec<Vec<Presence>> {
+    let col_count = left.iter().chain(right).map(Vec::len).max().unwrap_or(0);
+    let alive = |grid: &[Vec<Cell>], row: usize, col: usize| {
+        grid.get(row)
+            .and_then(|cells| cells.get(col))
+            .is_some_and(|cell| *cell != Cell::Dead)
+    };
+    (0..left.len().max(right.len()))
+        .map(|row| {
+            (0..col_count)
+                .map(
+                    |col| match (alive(left, row, col), alive(right, row, col)) {
+                        (false, false) => Presence::Neither,
+                        (true, false) => Presence::OnlyLeft,
+                        (false, true) => Presence::OnlyRight,
+                        (true, true) => Presence::Both,
+                    },
+                )
+                .collect()
+        })
+        .collect()
+}
+
+/// Number of differing Cells of two grids of the same dimensions
+pub fn differing(left: &[Vec<Cell>], right: &[Vec<Cell>]) -> usize {
+    izip!(left.iter().flatten(), right.iter().flatten())
+        .filter(|(left, right)| left != right)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        divergence::{self, Presence},
+        rle, Automaton, Cell, Divergence, RuleSet,
+    };
+
+    #[test]
+    fn lockstep() {
+        // The center has 6 neighbors, HighLife gives birth to it while Conway doesn't
+        let grid = vec![
+            vec![Cell::Alive, Cell::Alive, Cell::Alive],
+            vec![Cell::Dead, Cell::Dead, Cell::Dead],
+            vec![Cell::Alive, Cell::Alive, Cell::Alive],
+        ];
+        let conway = Automaton::builder()
+            .row_count(3)
+            .col_count(3)
+            .grid(grid)
+            .build();
+        let highlife = Automaton::builder()
+            .rule_set(RuleSet::life_like(vec![3, 6], vec![2, 3], 0))
+            .build();
+
+        let mut same = Divergence::new(conway.clone(), conway.clone());
+        same.step();
+        assert_eq!(same.differing(), 0);
+
+        let mut divergence = Divergence::new(conway, highlife);
+        assert_eq!(divergence.differing(), 0);
+        divergence.step();
+        assert_eq!(divergence.differing(), 1);
+        assert!(divergence.difference()[1][1]);
+    }
+
+    #[test]
+    fn difference_of_different_sizes() {
+        let left = vec![vec![Cell::Alive, Cell::Dead]];
+        let right = vec![vec![Cell::Alive], vec![Cell::Alive]];
+        assert_eq!(
+            divergence::difference(&left, &right),
+            vec![vec![false, false], vec![true]]
+        );
+    }
+
+    #[test]
+    fn overlay() {
+        let original = rle::parse("3o!").unwrap();
+        let edited = rle::parse("b2o$bo!").unwrap();
+        let overlay = divergence::overlay(&original, &edited);
+        assert_eq!(
+            overlay,
+            vec![
+                vec![Presence::OnlyLeft, Presence::Both, Presence::Both],
+                vec![Presence::Neither, Presence::OnlyRight, Presence::Neither],
+            ]
+        );
+        assert_eq!(divergence::overlay(&[], &[]), Vec::<Vec<Presence>>::new());
+    }
+}
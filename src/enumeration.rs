@@ -0,0 +1,280 @@
+//! Exhaustive search for still lifes and period 2 oscillators in small boxes,
+//! used by `no_bevy_2d analyze enumerate`
+
+use crate::{
+    analysis, grid, transition, Cell, Grid, NeighborCounts, Neighborhood, RuleSet, RuleTable,
+};
+use itertools::iproduct;
+use std::{
+    collections::BTreeSet,
+    error, fmt, iter,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+};
+
+/// Largest number of Cells of a box `enumerate` accepts, each one doubles the search space
+pub const MAX_CELLS: usize = 36;
+/// Patterns a thread checks before taking the next batch
+const BATCH_SIZE: u64 = 1 << 12;
+
+/// Errors that can occur while enumerating patterns
+/// - `TooLarge` => The box has more than `MAX_CELLS` Cells
+/// - `Empty` => The box has no Cells
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EnumerationError {
+    TooLarge(usize),
+    Empty,
+}
+
+impl fmt::Display for EnumerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge(cells) => write!(
+                f,
+                "A box of {cells} Cells is too large to enumerate, at most {MAX_CELLS} are supported"
+            ),
+            Self::Empty => write!(f, "The box has no Cells"),
+        }
+    }
+}
+
+impl error::Error for EnumerationError {}
+
+/// Kinds of patterns `enumerate` finds
+/// - `StillLife` => Never changes
+/// - `Oscillator` => Alternates between two phases
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Kind {
+    StillLife,
+    Oscillator,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StillLife => write!(f, "still life"),
+            Self::Oscillator => write!(f, "p2 oscillator"),
+        }
+    }
+}
+
+/// A pattern found by `enumerate`
+/// - `population` => Alive Cells, of the smaller phase for oscillators
+/// - `pattern` => The same representative of all rotations, reflections
+///   and for oscillators phases, cropped to its bounding box
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Found {
+    pub kind: Kind,
+    pub population: usize,
+    pub pattern: Grid,
+}
+
+/// Every still life and period 2 oscillator fitting into a `row_count` x `col_count` box,
+/// each once regardless of its position, rotation, reflection and phase, on `threads` threads.
+///
+/// Patterns made of several separate objects count as well, sorted by kind and population.
+pub fn enumerate(
+    row_count: usize,
+    col_count: usize,
+    neighborhood: Neighborhood,
+    rule_set: &RuleSet,
+    threads: usize,
+) -> Result<Vec<Found>, EnumerationError> {
+    let cell_count = row_count * col_count;
+    if cell_count == 0 {
+        return Err(EnumerationError::Empty);
+    }
+    if cell_count > MAX_CELLS {
+        return Err(EnumerationError::TooLarge(cell_count));
+    }
+    let stepper = Stepper::new(row_count, col_count, neighborhood, rule_set);
+    let total = 1_u64 << cell_count;
+    let next = AtomicU64::new(1);
+    let found = thread::scope(|scope| {
+        let workers = (0..threads.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut found = BTreeSet::new();
+                    loop {
+                        let start = next.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                        if start >= total {
+                            break found;
+                        }
+                        for mask in start..(start + BATCH_SIZE).min(total) {
+                            found.extend(stepper.check(mask));
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect::<BTreeSet<_>>()
+    });
+    Ok(found.into_iter().collect())
+}
+
+/// Steps boxes given as bit masks, with a ring of dead Cells around them
+/// so births just outside of the box are noticed
+struct Stepper {
+    row_count: usize,
+    col_count: usize,
+    neighborhood: Neighborhood,
+    /// The full rules candidates are confirmed with, compiled once for all of them
+    rule_table: RuleTable,
+    /// Whether a Cell is alive next generation, by its own state and its alive neighbors
+    alive_next: [Vec<bool>; 2],
+}
+
+impl Stepper {
+    fn new(
+        row_count: usize,
+        col_count: usize,
+        neighborhood: Neighborhood,
+        rule_set: &RuleSet,
+    ) -> Self {
+        Self {
+            row_count,
+            col_count,
+            neighborhood,
+            rule_table: RuleTable::new(rule_set),
+            alive_next: [Cell::Dead, Cell::Alive].map(|current| {
+                (0..=8)
+                    .map(|count| {
+                        let neighbors =
+                            iter::repeat_n(&Cell::Alive, count).collect::<NeighborCounts>();
+                        rule_set.next_state(&current, &neighbors) == Cell::Alive
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    /// The pattern of `mask`, skipped unless it touches the top and the left edge of the box
+    /// as other patterns are translated copies of those that do
+    fn check(&self, mask: u64) -> Option<Found> {
+        let top_row = (1_u64 << self.col_count) - 1;
+        let left_col = (0..self.row_count).fold(0, |col, row| col | 1 << (row * self.col_count));
+        if mask & top_row == 0 || mask & left_col == 0 {
+            return None;
+        }
+        let (rows, cols) = (self.row_count + 2, self.col_count + 2);
+        let mut cells = vec![false; rows * cols];
+        for bit in (0..self.row_count * self.col_count).filter(|bit| mask >> bit & 1 == 1) {
+            cells[(bit / self.col_count + 1) * cols + bit % self.col_count + 1] = true;
+        }
+        let next = self.step(&cells);
+        let kind = if next == cells {
+            Kind::StillLife
+        } else if self.step(&next) == cells {
+            Kind::Oscillator
+        } else {
+            return None;
+        };
+        self.confirm(kind, &cells)
+    }
+
+    /// Steps the unpacked Cells of a box including its ring
+    fn step(&self, cells: &[bool]) -> Vec<bool> {
+        let (rows, cols) = (self.row_count + 2, self.col_count + 2);
+        iproduct!(0..rows, 0..cols)
+            .map(|(row, col)| {
+                let count = iproduct!(
+                    row.saturating_sub(1)..=(row + 1).min(rows - 1),
+                    col.saturating_sub(1)..=(col + 1).min(cols - 1)
+                )
+                .filter(|&(neighbor_row, neighbor_col)| match self.neighborhood {
+                    Neighborhood::Moore => neighbor_row != row || neighbor_col != col,
+                    Neighborhood::VonNeumann => (neighbor_row == row) != (neighbor_col == col),
+                })
+                .filter(|&(neighbor_row, neighbor_col)| cells[neighbor_row * cols + neighbor_col])
+                .count();
+                self.alive_next[usize::from(cells[row * cols + col])][count]
+            })
+            .collect()
+    }
+
+    /// Checks a candidate with the full `rule_set`, which may have more than two states,
+    /// and canonicalizes it
+    fn confirm(&self, kind: Kind, cells: &[bool]) -> Option<Found> {
+        let grid = cells
+            .chunks(self.col_count + 2)
+            .map(|row| {
+                row.iter()
+                    .map(|&alive| if alive { Cell::Alive } else { Cell::Dead })
+                    .collect()
+            })
+            .collect::<Grid>();
+        let next = transition::compiled_next_grid(&grid, self.neighborhood, &self.rule_table);
+        let phases = match kind {
+            Kind::StillLife if next == grid => vec![grid],
+            Kind::Oscillator
+                if transition::compiled_next_grid(&next, self.neighborhood, &self.rule_table)
+                    == grid =>
+            {
+                vec![grid, next]
+            }
+            _ => return None,
+        };
+        phases
+            .iter()
+            .filter_map(|phase| {
+                let bounds = grid::bounding_box(phase)?;
+                let cropped = phase[bounds.top..=bounds.bottom]
+                    .iter()
+                    .map(|row| row[bounds.left..=bounds.right].to_vec())
+                    .collect::<Grid>();
+                let population = cropped
+                    .iter()
+                    .flatten()
+                    .filter(|cell| **cell == Cell::Alive);
+                Some((population.count(), analysis::canonical(&cropped)))
+            })
+            .min()
+            .map(|(population, pattern)| Found {
+                kind,
+                population,
+                pattern,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        enumeration::{enumerate, EnumerationError, Kind},
+        rle, Neighborhood, RuleSet,
+    };
+
+    #[test]
+    fn small_boxes() {
+        let life = RuleSet::default();
+        let found = enumerate(3, 3, Neighborhood::Moore, &life, 2).unwrap();
+        let still_lifes = found
+            .iter()
+            .filter(|found| found.kind == Kind::StillLife)
+            .collect::<Vec<_>>();
+        let oscillators = found
+            .iter()
+            .filter(|found| found.kind == Kind::Oscillator)
+            .collect::<Vec<_>>();
+        // Tub, block, boat and ship
+        let populations = still_lifes.iter().map(|found| found.population);
+        assert_eq!(populations.collect::<Vec<_>>(), vec![4, 4, 5, 6]);
+        assert_eq!(still_lifes[1].pattern, rle::parse("2o$2o!").unwrap());
+        // Only the blinker fits
+        assert_eq!(oscillators.len(), 1);
+        assert_eq!(oscillators[0].population, 3);
+
+        // The same on a single thread
+        assert_eq!(
+            enumerate(3, 3, Neighborhood::Moore, &life, 1).unwrap(),
+            found
+        );
+        assert_eq!(
+            enumerate(7, 7, Neighborhood::Moore, &life, 1),
+            Err(EnumerationError::TooLarge(49))
+        );
+    }
+}
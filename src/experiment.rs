@@ -0,0 +1,360 @@
+//! Parameter sweeps run by `no_bevy_2d experiment`
+
+use crate::{
+    rules::NotationError,
+    schedule::{RuleChange, Schedule},
+    seeding,
+    stop::{StopCondition, StopConditionError, Stopper},
+    table::{Table, Values},
+    Automaton, Generation, RuleSet,
+};
+use itertools::iproduct;
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Deserialize;
+use std::{
+    error, fmt, fs, io,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Errors that can occur while loading an `Experiment`
+/// - `Io` => The file couldn't be read
+/// - `Toml` => The file isn't a valid TOML experiment
+/// - `Rules` => One of the `rules` isn't valid B/S notation
+/// - `StopOn` => One of the `stop_on` conditions is invalid
+/// - `Density` => One of the `densities` isn't a probability between 0 and 1
+#[derive(Debug)]
+pub enum ExperimentError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Rules(NotationError),
+    StopOn(StopConditionError),
+    Density(f64),
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't read experiment: {e}"),
+            Self::Toml(e) => write!(f, "Invalid experiment: {e}"),
+            Self::Rules(e) => write!(f, "Invalid rules: {e}"),
+            Self::StopOn(e) => write!(f, "Invalid stop condition: {e}"),
+            Self::Density(density) => {
+                write!(f, "Invalid density {density}, it has to be between 0 and 1")
+            }
+        }
+    }
+}
+
+impl error::Error for ExperimentError {}
+
+impl From<io::Error> for ExperimentError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<toml::de::Error> for ExperimentError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Toml(value)
+    }
+}
+
+impl From<NotationError> for ExperimentError {
+    fn from(value: NotationError) -> Self {
+        Self::Rules(value)
+    }
+}
+
+impl From<StopConditionError> for ExperimentError {
+    fn from(value: StopConditionError) -> Self {
+        Self::StopOn(value)
+    }
+}
+
+/// Every combination of the listed parameters is run once, loadable from TOML:
+///
+/// ```toml
+/// rules = ["B3/S23", "B36/S23"]
+/// densities = [0.2, 0.35, 0.5]
+/// seeds = [1, 2, 3, 4]
+/// sizes = [[64, 64], [128, 128]]
+/// generations = 2000
+/// stop_on = ["extinction", "period<=2"]
+///
+/// [[changes]]
+/// generation = 500
+/// rules = "B36/S23"
+/// ramp = 100
+/// ```
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct Experiment {
+    /// B/S notation
+    pub rules: Vec<String>,
+    /// Probabilities of the Cells of the initial soup being alive
+    pub densities: Vec<f64>,
+    /// Seeds of the random soups, the same seed always yields the same soup
+    pub seeds: Vec<u64>,
+    /// Rows and columns
+    pub sizes: Vec<[usize; 2]>,
+    /// Runs stop after this many generations at the latest
+    pub generations: u64,
+    /// Conditions stopping a run earlier, see `StopCondition`
+    #[serde(default)]
+    pub stop_on: Vec<String>,
+    /// Rule changes during every run, see `Schedule`
+    #[serde(default)]
+    pub changes: Vec<RuleChange>,
+}
+
+impl Experiment {
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ExperimentError> {
+        Ok(Self::from_toml(&fs::read_to_string(path)?)?)
+    }
+
+    /// All combinations of rules, densities, seeds and sizes in that order
+    pub fn runs(&self) -> Result<Vec<Run>, ExperimentError> {
+        // Seeding would panic on the worker threads otherwise, taking the whole sweep down
+        if let Some(&density) = self
+            .densities
+            .iter()
+            .find(|density| !(0.0..=1.0).contains(*density))
+        {
+            return Err(ExperimentError::Density(density));
+        }
+        let rule_sets = self
+            .rules
+            .iter()
+            .map(|rules| rules.parse())
+            .collect::<Result<Vec<RuleSet>, _>>()?;
+        let stop_on = self
+            .stop_on
+            .iter()
+            .map(|condition| condition.parse())
+            .collect::<Result<Vec<StopCondition>, _>>()?;
+        let schedule = Schedule::new(self.changes.clone());
+        Ok(
+            iproduct!(&rule_sets, &self.densities, &self.seeds, &self.sizes)
+                .map(|(rule_set, &density, &seed, &[row_count, col_count])| Run {
+                    rule_set: rule_set.clone(),
+                    density,
+                    seed,
+                    row_count,
+                    col_count,
+                    generations: self.generations,
+                    stop_on: stop_on.clone(),
+                    schedule: schedule.clone(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A single combination of the parameters of an `Experiment`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Run {
+    pub rule_set: RuleSet,
+    pub density: f64,
+    pub seed: u64,
+    pub row_count: usize,
+    pub col_count: usize,
+    pub generations: u64,
+    pub stop_on: Vec<StopCondition>,
+    /// Rule changes starting from `rule_set`, ramps are mixed by `seed`
+    pub schedule: Schedule,
+}
+
+impl Run {
+    /// Steps a soup of `seeding::soup` until a stop condition fires or `generations` passed
+    pub fn execute(&self) -> Outcome {
+        let mut automaton = Automaton::builder()
+            .row_count(self.row_count)
+            .col_count(self.col_count)
+            .grid(seeding::soup(
+                self.seed,
+                self.row_count,
+                self.col_count,
+                self.density,
+            ))
+            .rule_set(self.rule_set.clone())
+            .build();
+        let initial_population = automaton.population();
+        let mut max_population = initial_population;
+        let mut stopper = Stopper::new(self.stop_on.clone());
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let ending = loop {
+            let fired = stopper.check(&automaton);
+            if fired.is_some() || automaton.generation.0 >= self.generations {
+                break fired;
+            }
+            self.schedule.step(&mut automaton, &self.rule_set, &mut rng);
+            max_population = max_population.max(automaton.population());
+        };
+        Outcome {
+            run: self.clone(),
+            generation: automaton.generation,
+            stopped_by: ending.map(|stopped| stopped.condition),
+            initial_population,
+            population: automaton.population(),
+            max_population,
+            period: ending
+                .and_then(|stopped| stopped.period)
+                .map(|period| period.period),
+        }
+    }
+}
+
+/// Metrics of a finished `Run`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Outcome {
+    pub run: Run,
+    /// Generation the run stopped at
+    pub generation: Generation,
+    /// `None` if the run lasted all of its generations
+    pub stopped_by: Option<StopCondition>,
+    pub initial_population: usize,
+    /// Population of the last generation
+    pub population: usize,
+    pub max_population: usize,
+    /// Period of the grid if a `StopCondition::Stable` or `StopCondition::Period` fired
+    pub period: Option<u64>,
+}
+
+/// Executes all `runs` on `threads` threads, the outcomes are in the same order as the runs
+pub fn execute(runs: &[Run], threads: usize) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, runs.len().max(1)) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(run) = runs.get(index) else {
+                    break;
+                };
+                if sender.send((index, run.execute())).is_err() {
+                    break;
+                }
+            });
+        }
+    });
+    drop(sender);
+    let mut outcomes = receiver.into_iter().collect::<Vec<_>>();
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+/// One row per outcome, a `period` of `0` means none was detected
+/// and an empty `stopped_by` means the run lasted all of its generations
+#[allow(clippy::cast_possible_wrap)]
+pub fn table(outcomes: &[Outcome]) -> Table {
+    let int = |value: fn(&Outcome) -> u64| {
+        Values::Int(
+            outcomes
+                .iter()
+                .map(|outcome| value(outcome) as i64)
+                .collect(),
+        )
+    };
+    Table::default()
+        .column(
+            "rules",
+            Values::Text(
+                outcomes
+                    .iter()
+                    .map(|outcome| outcome.run.rule_set.to_string())
+                    .collect(),
+            ),
+        )
+        .column(
+            "density",
+            Values::Float(outcomes.iter().map(|outcome| outcome.run.density).collect()),
+        )
+        .column("seed", int(|outcome| outcome.run.seed))
+        .column("rows", int(|outcome| outcome.run.row_count as u64))
+        .column("cols", int(|outcome| outcome.run.col_count as u64))
+        .column("generation", int(|outcome| outcome.generation.0))
+        .column(
+            "stopped_by",
+            Values::Text(
+                outcomes
+                    .iter()
+                    .map(|outcome| {
+                        outcome
+                            .stopped_by
+                            .map(|condition| condition.to_string())
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+            ),
+        )
+        .column(
+            "initial_population",
+            int(|outcome| outcome.initial_population as u64),
+        )
+        .column("population", int(|outcome| outcome.population as u64))
+        .column(
+            "max_population",
+            int(|outcome| outcome.max_population as u64),
+        )
+        .column("period", int(|outcome| outcome.period.unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        experiment::{self, Experiment, ExperimentError},
+        stop::StopCondition,
+    };
+
+    #[test]
+    fn sweep() {
+        let experiment = Experiment {
+            rules: vec!["B3/S23".into(), "B/S".into()],
+            densities: vec![0.3, 0.6],
+            seeds: vec![1, 2],
+            sizes: vec![[12, 16]],
+            generations: 50,
+            stop_on: vec!["extinction".into()],
+            changes: Vec::new(),
+        };
+        let runs = experiment.runs().unwrap();
+        assert_eq!(runs.len(), 8);
+        let outcomes = experiment::execute(&runs, 3);
+        assert_eq!(outcomes.len(), 8);
+        for (run, outcome) in runs.iter().zip(&outcomes) {
+            assert_eq!(&outcome.run, run);
+            assert_eq!(outcome, &run.execute());
+        }
+        // Nothing is ever born without birth counts
+        assert!(outcomes[4..]
+            .iter()
+            .all(|outcome| outcome.stopped_by == Some(StopCondition::Extinction)));
+        assert_eq!(experiment::table(&outcomes).row_count(), 8);
+
+        let invalid = Experiment {
+            rules: vec!["B3".into()],
+            ..experiment.clone()
+        };
+        assert!(invalid.runs().is_err());
+        for density in [1.5, f64::NAN] {
+            let invalid = Experiment {
+                densities: vec![0.3, density],
+                ..experiment.clone()
+            };
+            assert!(matches!(
+                invalid.runs(),
+                Err(ExperimentError::Density(invalid)) if invalid.to_bits() == density.to_bits()
+            ));
+        }
+    }
+}
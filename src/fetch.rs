@@ -0,0 +1,237 @@
+//! Downloads patterns from public catalogs by name or apgcode into a local cache.
+//! The cache is a directory of `.rle` files, by default the one the pattern browser lists.
+//!
+//! - Names are looked up in the pattern files of `LifeWiki`, e.g. `Gosper glider gun`
+//! - apgcodes of still lifes, oscillators and spaceships as used by Catagolue,
+//!   e.g. `xq4_153`, encode the pattern itself and are decoded without a request
+
+use crate::{library::Pattern, rle, Cell, Grid};
+use std::{
+    error, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Directory of the pattern files of `LifeWiki`, named after the pattern without spaces
+const LIFEWIKI_URL: &str = "https://conwaylife.com/patterns";
+/// Rows of each strip of an extended Wechsler format apgcode
+const STRIP_HEIGHT: usize = 5;
+
+/// Errors that can occur while fetching a pattern
+/// - `Http` => The catalog couldn't be reached or doesn't have the pattern
+/// - `Io` => The cache couldn't be read or written
+/// - `Rle` => The catalog returned an invalid pattern
+/// - `InvalidApgcode` => The apgcode isn't a valid extended Wechsler format code
+#[derive(Debug)]
+pub enum FetchError {
+    Http(Box<ureq::Error>),
+    Io(io::Error),
+    Rle(rle::ParseError),
+    InvalidApgcode(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(e) => write!(f, "Couldn't download pattern: {e}"),
+            Self::Io(e) => write!(f, "Couldn't access the pattern cache: {e}"),
+            Self::Rle(e) => write!(f, "Downloaded pattern is invalid: {e}"),
+            Self::InvalidApgcode(code) => write!(f, "Invalid apgcode: {code}"),
+        }
+    }
+}
+
+impl error::Error for FetchError {}
+
+impl From<ureq::Error> for FetchError {
+    fn from(value: ureq::Error) -> Self {
+        Self::Http(Box::new(value))
+    }
+}
+
+impl From<io::Error> for FetchError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<rle::ParseError> for FetchError {
+    fn from(value: rle::ParseError) -> Self {
+        Self::Rle(value)
+    }
+}
+
+/// What to fetch
+/// - `Name` => A pattern of `LifeWiki`
+/// - `Apgcode` => A still life (`xs`), oscillator (`xp`) or spaceship (`xq`) of Catagolue
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Query {
+    Name(String),
+    Apgcode(String),
+}
+
+impl Query {
+    /// Anything that looks like an apgcode is one, everything else is a name
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let is_apgcode = text.split_once('_').is_some_and(|(prefix, _)| {
+            prefix.len() > 2
+                && ["xs", "xp", "xq"].contains(&&prefix[..2])
+                && prefix[2..].bytes().all(|byte| byte.is_ascii_digit())
+        });
+        if is_apgcode {
+            Self::Apgcode(text.into())
+        } else {
+            Self::Name(text.into())
+        }
+    }
+
+    /// Name of the cached file without extension, `LifeWiki` names are lowercase without spaces
+    fn file_stem(&self) -> String {
+        match self {
+            Self::Name(name) => name
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .map(|c| c.to_ascii_lowercase())
+                .collect(),
+            Self::Apgcode(code) => code.clone(),
+        }
+    }
+}
+
+/// Fetches patterns into a cache directory, repeated queries are answered from the cache
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    cache: PathBuf,
+}
+
+impl Fetcher {
+    /// The cache directory is created on the first fetch
+    pub fn new(cache: impl Into<PathBuf>) -> Self {
+        Self {
+            cache: cache.into(),
+        }
+    }
+
+    /// Path of the cached file of `query`, whether it was fetched yet or not
+    pub fn cached_path(&self, query: &Query) -> PathBuf {
+        self.cache.join(query.file_stem()).with_extension("rle")
+    }
+
+    /// The cached pattern of `query`, or the one downloaded and written to the cache
+    pub fn fetch(&self, query: &Query) -> Result<Pattern, FetchError> {
+        let path = self.cached_path(query);
+        if !path.exists() {
+            let rle = match query {
+                Query::Name(_) => download(&format!("{LIFEWIKI_URL}/{}.rle", query.file_stem()))?,
+                Query::Apgcode(code) => {
+                    format!("#N {code}\n{}", rle::write(&decode_apgcode(code)?))
+                }
+            };
+            // Don't cache anything that isn't a pattern, like an error page
+            rle::parse(&rle)?;
+            fs::create_dir_all(&self.cache)?;
+            fs::write(&path, rle)?;
+        }
+        load(&path)
+    }
+}
+
+fn download(url: &str) -> Result<String, FetchError> {
+    Ok(ureq::get(url).call()?.into_string()?)
+}
+
+fn load(path: &Path) -> Result<Pattern, FetchError> {
+    Ok(Pattern {
+        name: path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned()),
+        grid: rle::parse(&fs::read_to_string(path)?)?,
+    })
+}
+
+/// Decodes the extended Wechsler format after the `_` of an apgcode:
+/// - `z` starts the next strip of `STRIP_HEIGHT` rows
+/// - `0`-`9` and `a`-`v` => A column of the strip, the lowest bit is its top row
+/// - `w` and `x` => Two and three empty columns
+/// - `y` followed by `0`-`9` or `a`-`z` => 4 to 39 empty columns
+pub fn decode_apgcode(code: &str) -> Result<Grid, FetchError> {
+    let invalid = || FetchError::InvalidApgcode(code.into());
+    let (_, body) = code.split_once('_').ok_or_else(invalid)?;
+    let mut strips: Vec<Vec<u32>> = Vec::new();
+    for strip in body.split('z') {
+        let mut columns = Vec::new();
+        let mut chars = strip.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                'w' => columns.extend([0; 2]),
+                'x' => columns.extend([0; 3]),
+                'y' => {
+                    let count = chars
+                        .next()
+                        .and_then(|c| c.to_digit(36))
+                        .ok_or_else(invalid)?;
+                    columns.extend(std::iter::repeat_n(0, 4 + count as usize));
+                }
+                _ => columns.push(c.to_digit(32).ok_or_else(invalid)?),
+            }
+        }
+        strips.push(columns);
+    }
+    let col_count = strips.iter().map(Vec::len).max().unwrap_or(0);
+    let mut grid = vec![vec![Cell::Dead; col_count]; strips.len() * STRIP_HEIGHT];
+    for (strip, columns) in strips.iter().enumerate() {
+        for (col, bits) in columns.iter().enumerate() {
+            for bit in 0..STRIP_HEIGHT {
+                if bits >> bit & 1 == 1 {
+                    grid[strip * STRIP_HEIGHT + bit][col] = Cell::Alive;
+                }
+            }
+        }
+    }
+    while grid
+        .last()
+        .is_some_and(|row| row.iter().all(|cell| *cell == Cell::Dead))
+    {
+        grid.pop();
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fetch::{decode_apgcode, Fetcher, Query},
+        rle,
+    };
+    use std::fs;
+
+    #[test]
+    fn apgcodes() {
+        assert_eq!(Query::parse("xq4_153"), Query::Apgcode("xq4_153".into()));
+        assert_eq!(
+            Query::parse("Gosper glider gun"),
+            Query::Name("Gosper glider gun".into())
+        );
+        assert_eq!(Query::parse("x_1"), Query::Name("x_1".into()));
+
+        let glider = decode_apgcode("xq4_153").unwrap();
+        assert_eq!(glider, rle::parse("3o$2bo$bo!").unwrap());
+        let pentadecathlon = decode_apgcode("xp15_4r4z4r4").unwrap();
+        assert_eq!((pentadecathlon.len(), pentadecathlon[0].len()), (10, 3));
+        let spread = decode_apgcode("xs2_1y11").unwrap();
+        assert_eq!(spread, rle::parse("o5bo!").unwrap());
+        assert!(decode_apgcode("xs2_1y").is_err());
+        assert!(decode_apgcode("xs2_!").is_err());
+
+        // Apgcodes are decoded without a request, so this only touches the cache
+        let cache = std::env::temp_dir().join("cellular_automata_fetch");
+        let fetcher = Fetcher::new(&cache);
+        let query = Query::parse("xp2_7");
+        let blinker = fetcher.fetch(&query).unwrap();
+        assert_eq!(blinker.name, "xp2_7");
+        assert_eq!(blinker.grid, rle::parse("o$o$o!").unwrap());
+        assert!(fetcher.cached_path(&query).exists());
+        assert_eq!(fetcher.fetch(&query).unwrap(), blinker);
+        fs::remove_dir_all(cache).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+//! C API of the engine, declared in `include/cellular_automata.h`.
+//!
+//! Cells are exchanged as their `Cell::state_number`.
+//! Every `Automaton` created with `ca_automaton_new` has to be released with
+//! `ca_automaton_free`.
+
+use crate::{Automaton, Cell, CellAccess, ColIdx, RowIdx, RuleSet, Seeding};
+use std::{
+    ffi::{c_char, CStr},
+    ptr,
+};
+
+/// Conway's Game of Life on a grid where every Cell is alive with probability `density`.
+/// Returns null for an empty grid or a `density` outside of `0..=1`.
+#[no_mangle]
+pub extern "C" fn ca_automaton_new(rows: usize, cols: usize, density: f64) -> *mut Automaton {
+    if rows == 0 || cols == 0 || !(0.0..=1.0).contains(&density) {
+        return ptr::null_mut();
+    }
+    let automaton = Automaton::builder()
+        .row_count(rows)
+        .col_count(cols)
+        .seeding(Seeding::Uniform { density })
+        .build();
+    Box::into_raw(Box::new(automaton))
+}
+
+/// # Safety
+/// `automaton` has to be null or created by `ca_automaton_new` and not freed before
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_free(automaton: *mut Automaton) {
+    if !automaton.is_null() {
+        drop(Box::from_raw(automaton));
+    }
+}
+
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_step(automaton: *mut Automaton) {
+    if let Some(automaton) = automaton.as_mut() {
+        automaton.step();
+    }
+}
+
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_advance(automaton: *mut Automaton, generations: usize) {
+    if let Some(automaton) = automaton.as_mut() {
+        automaton.advance(generations);
+    }
+}
+
+/// Generation of the automaton, `0` for null
+///
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_generation(automaton: *const Automaton) -> u64 {
+    automaton
+        .as_ref()
+        .map_or(0, |automaton| automaton.generation.0)
+}
+
+/// Number of rows, `0` for null
+///
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_rows(automaton: *const Automaton) -> usize {
+    automaton
+        .as_ref()
+        .map_or(0, |automaton| automaton.row_count)
+}
+
+/// Number of columns, `0` for null
+///
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_cols(automaton: *const Automaton) -> usize {
+    automaton
+        .as_ref()
+        .map_or(0, |automaton| automaton.col_count)
+}
+
+/// State number of the Cell at (`row`, `col`), `-1` outside of the grid or for null
+///
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_get_cell(
+    automaton: *const Automaton,
+    row: usize,
+    col: usize,
+) -> i64 {
+    automaton
+        .as_ref()
+        .and_then(|automaton| automaton.grid.cell(RowIdx(row), ColIdx(col)))
+        .and_then(|cell| i64::try_from(cell.state_number()).ok())
+        .unwrap_or(-1)
+}
+
+/// Sets the Cell at (`row`, `col`) to the state `state`,
+/// returns whether it was inside of the grid
+///
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_set_cell(
+    automaton: *mut Automaton,
+    row: usize,
+    col: usize,
+    state: usize,
+) -> bool {
+    automaton.as_mut().is_some_and(|automaton| {
+        automaton
+            .grid
+            .set_cell(RowIdx(row), ColIdx(col), Cell::from_state_number(state))
+            .is_some()
+    })
+}
+
+/// Switches to the rules in B/S notation, e.g. `"B36/S23"` or `"B2/S/C3"`.
+/// Returns whether `rules` could be parsed, the rules are unchanged otherwise.
+///
+/// # Safety
+/// `automaton` has to be null or a live pointer from `ca_automaton_new`,
+/// `rules` has to be null or a nul terminated string
+#[no_mangle]
+pub unsafe extern "C" fn ca_automaton_set_rules(
+    automaton: *mut Automaton,
+    rules: *const c_char,
+) -> bool {
+    let (Some(automaton), false) = (automaton.as_mut(), rules.is_null()) else {
+        return false;
+    };
+    let rule_set = CStr::from_ptr(rules)
+        .to_str()
+        .ok()
+        .and_then(|rules| rules.parse::<RuleSet>().ok());
+    rule_set.is_some_and(|rule_set| {
+        automaton.rule_set = rule_set;
+        true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ffi::{
+        ca_automaton_free, ca_automaton_generation, ca_automaton_get_cell, ca_automaton_new,
+        ca_automaton_set_cell, ca_automaton_set_rules, ca_automaton_step,
+    };
+    use std::{ffi::CString, ptr};
+
+    #[test]
+    fn blinker() {
+        assert!(ca_automaton_new(0, 5, 0.5).is_null());
+        let automaton = ca_automaton_new(5, 5, 0.0);
+        let invalid = CString::new("B3/S9").unwrap();
+        let brain = CString::new("B3/S2/C3").unwrap();
+        unsafe {
+            for col in 1..=3 {
+                assert!(ca_automaton_set_cell(automaton, 2, col, 1));
+            }
+            assert!(!ca_automaton_set_cell(automaton, 5, 0, 1));
+            assert!(!ca_automaton_set_rules(automaton, invalid.as_ptr()));
+            assert!(ca_automaton_set_rules(automaton, brain.as_ptr()));
+
+            ca_automaton_step(automaton);
+            assert_eq!(ca_automaton_generation(automaton), 1);
+            assert_eq!(ca_automaton_get_cell(automaton, 1, 2), 1);
+            assert_eq!(ca_automaton_get_cell(automaton, 2, 2), 1);
+            assert_eq!(ca_automaton_get_cell(automaton, 2, 1), 2);
+            assert_eq!(ca_automaton_get_cell(automaton, 0, 5), -1);
+            assert_eq!(ca_automaton_get_cell(ptr::null(), 0, 0), -1);
+
+            ca_automaton_free(automaton);
+            ca_automaton_free(ptr::null_mut());
+        }
+    }
+}
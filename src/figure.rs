@@ -0,0 +1,114 @@
+//! Figures spanned between two Cells for constructing containers, conduits and test rigs
+
+use crate::brush::{stroke, Brush, Shape};
+use std::collections::HashSet;
+
+/// - `Line` => The straight line between both Cells
+/// - `Rectangle` => The rectangle with both Cells as opposite corners
+/// - `Circle` => The circle around the first Cell through the second
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Figure {
+    Line,
+    Rectangle,
+    Circle,
+}
+
+impl Figure {
+    /// Cells of the figure spanned from `from` to `to`, clipped to the grid.
+    /// Outlined figures only keep the Cells with an orthogonal neighbor outside of the
+    /// filled figure, lines are the same either way.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn cells(
+        self,
+        from: (usize, usize),
+        to: (usize, usize),
+        filled: bool,
+        row_count: usize,
+        col_count: usize,
+    ) -> Vec<(usize, usize)> {
+        let in_grid = |&(row, col): &(usize, usize)| row < row_count && col < col_count;
+        match self {
+            Self::Line => stroke(from, to).into_iter().filter(in_grid).collect(),
+            Self::Rectangle => {
+                let rows = from.0.min(to.0)..=from.0.max(to.0);
+                let cols = from.1.min(to.1)..=from.1.max(to.1);
+                rows.clone()
+                    .flat_map(|row| cols.clone().map(move |col| (row, col)))
+                    .filter(|&(row, col)| {
+                        filled
+                            || [rows.start(), rows.end()].contains(&&row)
+                            || [cols.start(), cols.end()].contains(&&col)
+                    })
+                    .filter(in_grid)
+                    .collect()
+            }
+            Self::Circle => {
+                let (rows, cols) = (from.0.abs_diff(to.0), from.1.abs_diff(to.1));
+                let radius = ((rows * rows + cols * cols) as f64).sqrt().round() as usize;
+                let disk = Brush {
+                    shape: Shape::Circle,
+                    radius,
+                }
+                .footprint();
+                let covered = disk.iter().copied().collect::<HashSet<_>>();
+                disk.iter()
+                    .filter(|&&(row, col)| {
+                        filled
+                            || [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(
+                                |(row_step, col_step)| {
+                                    !covered.contains(&(row + row_step, col + col_step))
+                                },
+                            )
+                    })
+                    .filter_map(|&(row_offset, col_offset)| {
+                        Some((
+                            from.0.checked_add_signed(row_offset)?,
+                            from.1.checked_add_signed(col_offset)?,
+                        ))
+                    })
+                    .filter(in_grid)
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::figure::Figure;
+
+    #[test]
+    fn figures() {
+        let line = Figure::Line.cells((1, 1), (1, 12), true, 10, 10);
+        assert_eq!(line.len(), 9);
+        assert_eq!(
+            Figure::Line.cells((0, 0), (2, 2), false, 10, 10),
+            vec![(0, 0), (1, 1), (2, 2)]
+        );
+
+        assert_eq!(
+            Figure::Rectangle.cells((4, 5), (1, 1), true, 10, 10).len(),
+            20
+        );
+        let outline = Figure::Rectangle.cells((4, 5), (1, 1), false, 10, 10);
+        assert_eq!(outline.len(), 14);
+        assert!(!outline.contains(&(2, 2)));
+        assert_eq!(
+            Figure::Rectangle.cells((3, 3), (3, 3), false, 10, 10),
+            vec![(3, 3)]
+        );
+
+        // Radius 2
+        let disk = Figure::Circle.cells((5, 5), (5, 7), true, 10, 10);
+        assert_eq!(disk.len(), 13);
+        let ring = Figure::Circle.cells((5, 5), (7, 5), false, 10, 10);
+        assert_eq!(ring.len(), 8);
+        assert!(!ring.contains(&(5, 5)));
+        let clipped = Figure::Circle.cells((0, 0), (0, 2), true, 10, 10);
+        assert_eq!(clipped.len(), 6);
+    }
+}
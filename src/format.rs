@@ -0,0 +1,273 @@
+//! Pattern file formats besides `rle`, and picking one of them by content or file extension
+
+pub mod json;
+pub mod life106;
+pub mod macrocell;
+pub mod plaintext;
+
+use crate::{annotation::Annotation, rle, Cell, Grid};
+use clap::ValueEnum;
+use std::{error, fmt, fmt::Write, path::Path};
+
+/// Errors that can occur while parsing a pattern
+/// - `Rle` => The RLE pattern is malformed
+/// - `InvalidLine` => A line isn't valid in the format
+/// - `Json` => The JSON state is malformed
+/// - `TooLarge` => The pattern has more than `rle::MAX_CELLS` Cells
+/// - `Undetected` => The text doesn't look like any of the formats
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FormatError {
+    Rle(rle::ParseError),
+    InvalidLine(String),
+    Json(String),
+    TooLarge,
+    Undetected,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rle(e) => write!(f, "{e}"),
+            Self::InvalidLine(line) => write!(f, "Invalid line: {line}"),
+            Self::Json(e) => write!(f, "Invalid JSON state: {e}"),
+            Self::TooLarge => write!(f, "Pattern has more than {} Cells", rle::MAX_CELLS),
+            Self::Undetected => write!(
+                f,
+                "Not RLE, plaintext, Life 1.06, macrocell or a JSON state, \
+                 the first lines don't match any of them"
+            ),
+        }
+    }
+}
+
+impl error::Error for FormatError {}
+
+impl From<rle::ParseError> for FormatError {
+    fn from(value: rle::ParseError) -> Self {
+        Self::Rle(value)
+    }
+}
+
+/// Pattern file formats
+/// - `Rle` => Run length encoded, `.rle`
+/// - `Plaintext` => One character per Cell, `.cells`
+/// - `Life106` => Coordinates of the alive Cells, `.lif` or `.life`
+/// - `Macrocell` => Golly's quadtree format for huge sparse patterns, `.mc`
+/// - `Json` => Cells as state numbers, `.json`
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum Format {
+    #[default]
+    Rle,
+    Plaintext,
+    Life106,
+    Macrocell,
+    Json,
+}
+
+impl Format {
+    /// The format of the file at `path` going by its extension
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "rle" => Some(Self::Rle),
+            "cells" | "txt" => Some(Self::Plaintext),
+            "lif" | "life" => Some(Self::Life106),
+            "mc" => Some(Self::Macrocell),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Sniffs the format from the beginning of `text`:
+    /// - `{` => `Json`
+    /// - `[M2]` => `Macrocell`
+    /// - `#Life 1.06`, in any case => `Life106`
+    /// - An `x = ..` header or only run length encoded lines after `#` comments => `Rle`
+    /// - Only `.`, `O` and `*` lines after `!` comments => `Plaintext`
+    pub fn detect(text: &str) -> Result<Self, FormatError> {
+        let text = text.trim_start();
+        let first = text.lines().next().unwrap_or_default().trim();
+        if text.starts_with('{') {
+            return Ok(Self::Json);
+        }
+        if first.starts_with("[M2]") {
+            return Ok(Self::Macrocell);
+        }
+        if life106::is_header(first) {
+            return Ok(Self::Life106);
+        }
+        let lines = || text.lines().map(str::trim).filter(|line| !line.is_empty());
+        let mut rle = lines().filter(|line| !line.starts_with('#')).peekable();
+        let header = rle
+            .peek()
+            .is_some_and(|line| line.starts_with('x') && line.contains('='));
+        let runs = |line: &str| {
+            line.chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, 'b' | 'o' | '$' | '!' | '.' | 'A'))
+        };
+        let plain = |line: &str| line.chars().all(|c| matches!(c, '.' | 'O' | '*'));
+        let mut plaintext = lines().filter(|line| !line.starts_with('!'));
+        if plaintext.all(plain) && lines().any(|line| !line.starts_with('!')) {
+            return Ok(Self::Plaintext);
+        }
+        if header || rle.peek().is_some() && rle.all(runs) {
+            return Ok(Self::Rle);
+        }
+        Err(FormatError::Undetected)
+    }
+
+    pub fn parse(self, text: &str) -> Result<Grid, FormatError> {
+        match self {
+            Self::Rle => Ok(rle::parse(text)?),
+            Self::Plaintext => plaintext::parse(text),
+            Self::Life106 => life106::parse(text),
+            Self::Macrocell => macrocell::parse(text),
+            Self::Json => json::parse(text),
+        }
+    }
+
+    /// Only `Cell::Alive` is written as alive, every other state is written as dead,
+    /// except for `Json`
+    pub fn write(self, grid: &[Vec<Cell>]) -> String {
+        match self {
+            Self::Rle => rle::write(grid),
+            Self::Plaintext => plaintext::write(grid),
+            Self::Life106 => life106::write(grid),
+            Self::Macrocell => macrocell::write(grid),
+            Self::Json => json::write(grid),
+        }
+    }
+
+    /// Prefix of the comment lines `annotations` are written into, `None` for `Json`
+    const fn comment(self) -> Option<&'static str> {
+        match self {
+            Self::Rle | Self::Macrocell => Some("#C "),
+            Self::Plaintext => Some("!"),
+            Self::Life106 => Some("#D "),
+            Self::Json => None,
+        }
+    }
+
+    /// `write` with one comment per annotation, `Json` has no comments and drops them.
+    /// They follow the header of formats that start with one.
+    pub fn write_annotated(self, grid: &[Vec<Cell>], annotations: &[Annotation]) -> String {
+        let text = self.write(grid);
+        let Some(prefix) = self.comment() else {
+            return text;
+        };
+        let mut comments = String::new();
+        for annotation in annotations {
+            let _ = writeln!(comments, "{prefix}{annotation}");
+        }
+        match self {
+            Self::Life106 | Self::Macrocell => match text.split_once('\n') {
+                Some((header, body)) => format!("{header}\n{comments}{body}"),
+                None => text + &comments,
+            },
+            _ => comments + &text,
+        }
+    }
+
+    /// The annotations in the comments of `text` as written by `write_annotated`,
+    /// other comments are skipped
+    pub fn annotations(self, text: &str) -> Vec<Annotation> {
+        let Some(prefix) = self.comment() else {
+            return Vec::new();
+        };
+        text.lines()
+            .filter_map(|line| line.trim().strip_prefix(prefix.trim_end()))
+            .filter_map(|comment| comment.parse().ok())
+            .collect()
+    }
+}
+
+/// Smallest grid containing the `alive` (row, column) coordinates, which may be negative
+fn from_alive(alive: &[(i64, i64)]) -> Result<Grid, FormatError> {
+    let Some(min_row) = alive.iter().map(|&(row, _)| row).min() else {
+        return Ok(Vec::new());
+    };
+    let min_col = alive.iter().map(|&(_, col)| col).min().unwrap_or_default();
+    let max_row = alive.iter().map(|&(row, _)| row).max().unwrap_or_default();
+    let max_col = alive.iter().map(|&(_, col)| col).max().unwrap_or_default();
+    let size = |min: i64, max: i64| usize::try_from(max.abs_diff(min)).ok()?.checked_add(1);
+    let (Some(row_count), Some(col_count)) = (size(min_row, max_row), size(min_col, max_col))
+    else {
+        return Err(FormatError::TooLarge);
+    };
+    let cells = row_count.checked_mul(col_count);
+    if cells.is_none_or(|cells| cells > rle::MAX_CELLS) {
+        return Err(FormatError::TooLarge);
+    }
+    let mut grid = vec![vec![Cell::Dead; col_count]; row_count];
+    for &(row, col) in alive {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (row, col) = ((row - min_row) as usize, (col - min_col) as usize);
+        grid[row][col] = Cell::Alive;
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        annotation::Annotation,
+        format::{Format, FormatError},
+        rle,
+    };
+
+    #[test]
+    fn roundtrips() {
+        let glider = rle::parse("bo$2bo$3o!").unwrap();
+        for format in [
+            Format::Rle,
+            Format::Plaintext,
+            Format::Life106,
+            Format::Macrocell,
+        ] {
+            let text = format.write(&glider);
+            assert_eq!(
+                format.parse(&text),
+                Ok(glider.clone()),
+                "{format:?}:\n{text}"
+            );
+
+            let annotations = [Annotation::new(2, 1, "Tail")];
+            let text = format.write_annotated(&glider, &annotations);
+            assert_eq!(Format::detect(&text), Ok(format), "{text}");
+            assert_eq!(format.parse(&text), Ok(glider.clone()), "{text}");
+            assert_eq!(format.annotations(&text), annotations, "{text}");
+        }
+        assert!(Format::Rle
+            .annotations("#C A glider\nbo$2bo$3o!")
+            .is_empty());
+        assert_eq!(Format::from_path("gun.MC"), Some(Format::Macrocell));
+        assert_eq!(Format::from_path("gun.png"), None);
+    }
+
+    #[test]
+    fn detection() {
+        let glider = rle::parse("bo$2bo$3o!").unwrap();
+        for format in [
+            Format::Rle,
+            Format::Plaintext,
+            Format::Life106,
+            Format::Macrocell,
+            Format::Json,
+        ] {
+            assert_eq!(Format::detect(&format.write(&glider)), Ok(format));
+        }
+        assert_eq!(Format::detect("#N Glider\nbo$2bo$3o!"), Ok(Format::Rle));
+        assert_eq!(
+            Format::detect("!Name: Glider\n.O\n..O\nOOO"),
+            Ok(Format::Plaintext)
+        );
+        let lowercase = "#life 1.06\n0 0\n1 0";
+        assert_eq!(Format::detect(lowercase), Ok(Format::Life106));
+        assert_eq!(
+            Format::Life106.parse(lowercase),
+            Ok(rle::parse("2o!").unwrap())
+        );
+        assert_eq!(Format::detect("hello"), Err(FormatError::Undetected));
+        assert_eq!(Format::detect(""), Err(FormatError::Undetected));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{format::FormatError, rle, Cell, Grid};
+use serde::{Deserialize, Serialize};
+
+/// Cells as their `Cell::state_number`, the same shape as `GET /grid` of the HTTP API.
+/// Other fields, e.g. the generation, are ignored.
+#[derive(Debug, Serialize, Deserialize)]
+struct State {
+    cells: Vec<Vec<usize>>,
+}
+
+/// Parses `{"cells": [[0, 1], [1, 0]]}`, shorter rows are padded with dead Cells
+pub fn parse(text: &str) -> Result<Grid, FormatError> {
+    let state: State = serde_json::from_str(text).map_err(|e| FormatError::Json(e.to_string()))?;
+    let col_count = state.cells.iter().map(Vec::len).max().unwrap_or_default();
+    if state.cells.len().saturating_mul(col_count) > rle::MAX_CELLS {
+        return Err(FormatError::TooLarge);
+    }
+    Ok(state
+        .cells
+        .into_iter()
+        .map(|row| {
+            let mut row = row
+                .into_iter()
+                .map(Cell::from_state_number)
+                .collect::<Vec<_>>();
+            row.resize(col_count, Cell::Dead);
+            row
+        })
+        .collect())
+}
+
+/// Unlike the other formats every state is kept
+pub fn write(grid: &[Vec<Cell>]) -> String {
+    let state = State {
+        cells: grid
+            .iter()
+            .map(|row| row.iter().map(Cell::state_number).collect())
+            .collect(),
+    };
+    serde_json::to_string(&state).unwrap_or_default() + "\n"
+}
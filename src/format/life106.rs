@@ -0,0 +1,50 @@
+use crate::{
+    format::{from_alive, FormatError},
+    Cell, Grid,
+};
+use std::fmt::Write;
+
+const HEADER: &str = "#Life 1.06";
+
+/// Whether `line` is the `#Life 1.06` header, in any case
+pub fn is_header(line: &str) -> bool {
+    line.trim().eq_ignore_ascii_case(HEADER)
+}
+
+/// Parses Life 1.06, the `#Life 1.06` header followed by one `x y` line per alive Cell.
+/// The grid is the bounding box of the alive Cells.
+pub fn parse(text: &str) -> Result<Grid, FormatError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    match lines.next() {
+        Some(line) if is_header(line) => {}
+        line => {
+            return Err(FormatError::InvalidLine(
+                line.unwrap_or_default().to_owned(),
+            ))
+        }
+    }
+    let alive = lines
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let mut coordinates = line.split_whitespace().map(str::parse::<i64>);
+            match (coordinates.next(), coordinates.next(), coordinates.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => Ok((y, x)),
+                _ => Err(FormatError::InvalidLine(line.to_owned())),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    from_alive(&alive)
+}
+
+/// Encodes the alive Cells as Life 1.06, relative to the top left corner of the grid
+pub fn write(grid: &[Vec<Cell>]) -> String {
+    let mut text = format!("{HEADER}\n");
+    for (y, row) in grid.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if *cell == Cell::Alive {
+                let _ = writeln!(text, "{x} {y}");
+            }
+        }
+    }
+    text
+}
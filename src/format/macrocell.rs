@@ -0,0 +1,227 @@
+use crate::{
+    format::{from_alive, FormatError},
+    rle, Cell, Grid,
+};
+use std::{collections::HashMap, fmt::Write};
+
+/// Level of the 8 x 8 leaves
+const LEAF_LEVEL: u32 = 3;
+/// Deeper quadtrees have coordinates that don't fit into an `i64`
+const MAX_LEVEL: u32 = 62;
+
+/// Node of the quadtree, referenced by its 1 based position, `0` is empty
+enum Node {
+    /// Alive (row, column) coordinates within the leaf
+    Leaf(Vec<(i64, i64)>),
+    Inner {
+        level: u32,
+        children: [usize; 4],
+        /// Alive Cells of the whole subtree, saturating
+        population: usize,
+    },
+}
+
+impl Node {
+    const fn level(&self) -> u32 {
+        match self {
+            Self::Leaf(_) => LEAF_LEVEL,
+            Self::Inner { level, .. } => *level,
+        }
+    }
+
+    const fn population(&self) -> usize {
+        match self {
+            Self::Leaf(alive) => alive.len(),
+            Self::Inner { population, .. } => *population,
+        }
+    }
+}
+
+/// Parses two state macrocell as written by Golly. The grid is the bounding box of the alive Cells.
+///
+/// - The first line starts with `[M2]`, `#` lines are treated as comments
+/// - Leaves are 8 x 8 Cells, `.` => `Cell::Dead`, `*` => `Cell::Alive`, `$` ends a row
+/// - `level nw ne sw se` lines combine four earlier nodes of `level - 1` into one
+/// - The last node is the root
+pub fn parse(text: &str) -> Result<Grid, FormatError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    match lines.next() {
+        Some(header) if header.starts_with("[M2]") => {}
+        line => {
+            return Err(FormatError::InvalidLine(
+                line.unwrap_or_default().to_owned(),
+            ))
+        }
+    }
+    let mut nodes = Vec::new();
+    for line in lines.filter(|line| !line.starts_with('#')) {
+        let invalid = || FormatError::InvalidLine(line.to_owned());
+        let node = if line.starts_with(['.', '*', '$']) {
+            let (mut row, mut col) = (0, 0);
+            let mut alive = Vec::new();
+            for c in line.chars() {
+                match c {
+                    '.' => col += 1,
+                    '*' if row < 8 => {
+                        alive.push((row, col));
+                        col += 1;
+                    }
+                    '$' => (row, col) = (row + 1, 0),
+                    _ => return Err(invalid()),
+                }
+                if row > 8 || col > 8 {
+                    return Err(invalid());
+                }
+            }
+            Node::Leaf(alive)
+        } else {
+            let numbers = line
+                .split_whitespace()
+                .map(str::parse::<usize>)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid())?;
+            let &[level, nw, ne, sw, se] = numbers.as_slice() else {
+                return Err(invalid());
+            };
+            let level = u32::try_from(level)
+                .ok()
+                .filter(|level| (LEAF_LEVEL + 1..=MAX_LEVEL).contains(level))
+                .ok_or_else(invalid)?;
+            let children = [nw, ne, sw, se];
+            let valid = |&child: &usize| {
+                child == 0
+                    || child
+                        .checked_sub(1)
+                        .and_then(|index| nodes.get(index))
+                        .is_some_and(|child: &Node| child.level() == level - 1)
+            };
+            if !children.iter().all(valid) {
+                return Err(invalid());
+            }
+            let population = children
+                .iter()
+                .filter_map(|child| child.checked_sub(1))
+                .map(|index| nodes[index].population())
+                .fold(0, usize::saturating_add);
+            Node::Inner {
+                level,
+                children,
+                population,
+            }
+        };
+        nodes.push(node);
+    }
+
+    if nodes
+        .last()
+        .is_some_and(|root| root.population() > rle::MAX_CELLS)
+    {
+        return Err(FormatError::TooLarge);
+    }
+    let mut alive = Vec::new();
+    collect(&nodes, nodes.len(), (0, 0), &mut alive);
+    from_alive(&alive)
+}
+
+/// Pushes the alive Cells of the node at `index` with its top left corner at `origin`,
+/// empty subtrees are skipped so only populated ones are visited
+fn collect(nodes: &[Node], index: usize, origin: (i64, i64), alive: &mut Vec<(i64, i64)>) {
+    let Some(node) = index.checked_sub(1).and_then(|index| nodes.get(index)) else {
+        return;
+    };
+    match node {
+        Node::Leaf(cells) => {
+            alive.extend(
+                cells
+                    .iter()
+                    .map(|(row, col)| (origin.0 + row, origin.1 + col)),
+            );
+        }
+        Node::Inner { population: 0, .. } => {}
+        Node::Inner {
+            level, children, ..
+        } => {
+            let half = 1 << (level - 1);
+            let [nw, ne, sw, se] = *children;
+            collect(nodes, nw, origin, alive);
+            collect(nodes, ne, (origin.0, origin.1 + half), alive);
+            collect(nodes, sw, (origin.0 + half, origin.1), alive);
+            collect(nodes, se, (origin.0 + half, origin.1 + half), alive);
+        }
+    }
+}
+
+/// Encodes the grid as two state macrocell, identical subtrees are written once
+pub fn write(grid: &[Vec<Cell>]) -> String {
+    let col_count = grid.first().map(Vec::len).unwrap_or_default();
+    let size = grid
+        .len()
+        .max(col_count)
+        .max(1 << LEAF_LEVEL)
+        .next_power_of_two();
+    let mut nodes = Nodes::default();
+    let root = nodes.add(grid, (0, 0), size.trailing_zeros());
+    if root == 0 {
+        nodes.lines.push(format!("{} 0 0 0 0", LEAF_LEVEL + 1));
+    }
+    let mut text = String::from("[M2] (cellular_automata)\n");
+    for line in nodes.lines {
+        let _ = writeln!(text, "{line}");
+    }
+    text
+}
+
+/// Lines of the nodes written so far and their 1 based positions
+#[derive(Default)]
+struct Nodes {
+    lines: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+impl Nodes {
+    /// Adds the node of `level` with its top left corner at `origin`, `0` if it's empty
+    fn add(&mut self, grid: &[Vec<Cell>], origin: (usize, usize), level: u32) -> usize {
+        let line = if level == LEAF_LEVEL {
+            let mut leaf = String::new();
+            for row in origin.0..origin.0 + 8 {
+                let cells = (origin.1..origin.1 + 8)
+                    .map(|col| {
+                        let alive =
+                            grid.get(row).and_then(|row| row.get(col)) == Some(&Cell::Alive);
+                        if alive {
+                            '*'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>();
+                leaf.push_str(cells.trim_end_matches('.'));
+                leaf.push('$');
+            }
+            let leaf = leaf.trim_end_matches('$');
+            if leaf.is_empty() {
+                return 0;
+            }
+            format!("{leaf}$")
+        } else {
+            let half = 1 << (level - 1);
+            let children = [
+                self.add(grid, origin, level - 1),
+                self.add(grid, (origin.0, origin.1 + half), level - 1),
+                self.add(grid, (origin.0 + half, origin.1), level - 1),
+                self.add(grid, (origin.0 + half, origin.1 + half), level - 1),
+            ];
+            if children == [0; 4] {
+                return 0;
+            }
+            let [nw, ne, sw, se] = children;
+            format!("{level} {nw} {ne} {sw} {se}")
+        };
+        if let Some(&index) = self.indices.get(&line) {
+            return index;
+        }
+        self.lines.push(line.clone());
+        self.indices.insert(line, self.lines.len());
+        self.lines.len()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{format::FormatError, rle, Cell, Grid};
+
+/// Parses the plaintext format of `LifeWiki`
+///
+/// - `!` lines are treated as comments
+/// - `.` => `Cell::Dead`, `O`/`*` => `Cell::Alive`
+/// - Shorter rows are padded with dead Cells
+pub fn parse(text: &str) -> Result<Grid, FormatError> {
+    let mut grid: Grid = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.starts_with('!'))
+        .map(|line| {
+            line.chars()
+                .map(|c| match c {
+                    '.' => Ok(Cell::Dead),
+                    'O' | '*' => Ok(Cell::Alive),
+                    _ => Err(FormatError::InvalidLine(line.to_owned())),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    while grid.last().is_some_and(Vec::is_empty) {
+        grid.pop();
+    }
+    let col_count = grid.iter().map(Vec::len).max().unwrap_or_default();
+    if grid.len().saturating_mul(col_count) > rle::MAX_CELLS {
+        return Err(FormatError::TooLarge);
+    }
+    for row in &mut grid {
+        row.resize(col_count, Cell::Dead);
+    }
+    Ok(grid)
+}
+
+/// Encodes the grid as plaintext, trailing dead Cells of each row are omitted
+pub fn write(grid: &[Vec<Cell>]) -> String {
+    let mut text = String::new();
+    for row in grid {
+        let line = row
+            .iter()
+            .map(|cell| if *cell == Cell::Alive { 'O' } else { '.' })
+            .collect::<String>();
+        text.push_str(line.trim_end_matches('.'));
+        text.push('\n');
+    }
+    text
+}
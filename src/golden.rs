@@ -0,0 +1,80 @@
+//! Regression tests replaying patterns with well known behavior from `fixtures/`,
+//! guarding that the default rules exactly imitate Conway's Game of Life
+
+use crate::{rle, Automaton, Cell, Grid};
+use std::{fs, path::Path};
+
+/// Empty Cells around a pattern, keeps it away from the edges of the grid
+const MARGIN: usize = 2;
+
+fn fixture(name: &str) -> Grid {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(name);
+    let text = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    rle::parse(&text).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+/// Conway's Game of Life with `pattern` at (`row`, `col`) of an otherwise empty grid
+fn place(
+    pattern: &[Vec<Cell>],
+    row_count: usize,
+    col_count: usize,
+    row: usize,
+    col: usize,
+) -> Automaton {
+    let mut automaton = Automaton::builder()
+        .row_count(row_count)
+        .col_count(col_count)
+        .grid(vec![vec![Cell::Dead; col_count]; row_count])
+        .build();
+    automaton.stamp(pattern, row, col);
+    automaton
+}
+
+#[test]
+fn glider_displacement() {
+    let glider = fixture("glider.rle");
+    let mut automaton = place(&glider, 12, 12, MARGIN, MARGIN);
+    for offset in 1..=4 {
+        automaton.advance(4);
+        let expected = place(&glider, 12, 12, MARGIN + offset, MARGIN + offset);
+        assert_eq!(
+            automaton.grid, expected.grid,
+            "generation {}",
+            automaton.generation
+        );
+    }
+}
+
+#[test]
+fn pulsar_period() {
+    let pulsar = fixture("pulsar.rle");
+    let size = pulsar.len() + 2 * MARGIN;
+    let initial = place(&pulsar, size, size, MARGIN, MARGIN);
+    let mut automaton = initial.clone();
+    for generation in 1..=6 {
+        automaton.step();
+        assert_eq!(
+            automaton.grid == initial.grid,
+            generation % 3 == 0,
+            "generation {generation}"
+        );
+    }
+}
+
+#[test]
+fn gosper_gun_emission_rate() {
+    const PERIOD: usize = 30;
+    const GLIDER_POPULATION: usize = 5;
+
+    let gun = fixture("gosper_glider_gun.rle");
+    let mut automaton = place(&gun, 80, 80, MARGIN, MARGIN);
+    automaton.advance(2 * PERIOD);
+    let mut population = automaton.population();
+    for _ in 0..3 {
+        automaton.advance(PERIOD);
+        assert_eq!(automaton.population(), population + GLIDER_POPULATION);
+        population = automaton.population();
+    }
+}
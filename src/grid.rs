@@ -0,0 +1,529 @@
+use crate::Cell;
+#[cfg(feature = "std")]
+use crate::Theme;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use image::{GrayAlphaImage, ImageResult, Rgb, RgbImage};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+pub type Grid = Vec<Vec<Cell>>;
+
+/// Index of a row within a `Grid`
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct RowIdx(pub usize);
+
+/// Index of a column within a `Grid`
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ColIdx(pub usize);
+
+/// Checked access to single Cells of a `Grid`,
+/// distinct index types keep rows and columns from being mixed up
+pub trait CellAccess {
+    /// The Cell at (`row`, `col`), `None` outside of the grid
+    fn cell(&self, row: RowIdx, col: ColIdx) -> Option<&Cell>;
+
+    fn cell_mut(&mut self, row: RowIdx, col: ColIdx) -> Option<&mut Cell>;
+
+    /// Replaces the Cell at (`row`, `col`), returning the previous one.
+    /// Outside of the grid nothing is changed and `None` is returned.
+    fn set_cell(&mut self, row: RowIdx, col: ColIdx, cell: Cell) -> Option<Cell> {
+        self.cell_mut(row, col)
+            .map(|target| core::mem::replace(target, cell))
+    }
+}
+
+impl CellAccess for [Vec<Cell>] {
+    fn cell(&self, RowIdx(row): RowIdx, ColIdx(col): ColIdx) -> Option<&Cell> {
+        self.get(row)?.get(col)
+    }
+
+    fn cell_mut(&mut self, RowIdx(row): RowIdx, ColIdx(col): ColIdx) -> Option<&mut Cell> {
+        self.get_mut(row)?.get_mut(col)
+    }
+}
+
+/// Fraction of `Cell::Alive` Cells within every `block_size` x `block_size` block,
+/// blocks along the bottom and right edges may be smaller
+#[allow(clippy::cast_precision_loss)]
+pub fn density_tiles(grid: &[Vec<Cell>], block_size: usize) -> Vec<Vec<f32>> {
+    let block_size = block_size.max(1);
+    grid.chunks(block_size)
+        .map(|rows| {
+            let col_count = rows.first().map(Vec::len).unwrap_or_default();
+            (0..col_count)
+                .step_by(block_size)
+                .map(|col| {
+                    let cols = col..(col + block_size).min(col_count);
+                    let area = rows.len() * cols.len();
+                    let alive = rows
+                        .iter()
+                        .flat_map(|row| &row[cols.clone()])
+                        .filter(|&cell| *cell == Cell::Alive)
+                        .count();
+                    alive as f32 / area as f32
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Inclusive rows and columns of the smallest rectangle containing every `Cell::Alive`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BoundingBox {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+
+impl BoundingBox {
+    pub const fn row_count(&self) -> usize {
+        self.bottom - self.top + 1
+    }
+
+    pub const fn col_count(&self) -> usize {
+        self.right - self.left + 1
+    }
+
+    /// Smallest box containing both boxes
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            top: self.top.min(other.top),
+            left: self.left.min(other.left),
+            bottom: self.bottom.max(other.bottom),
+            right: self.right.max(other.right),
+        }
+    }
+}
+
+/// `None` if no Cell is alive
+pub fn bounding_box(grid: &[Vec<Cell>]) -> Option<BoundingBox> {
+    let alive_cols = |row: &Vec<Cell>| {
+        let first = row.iter().position(|cell| *cell == Cell::Alive)?;
+        let last = row.iter().rposition(|cell| *cell == Cell::Alive)?;
+        Some((first, last))
+    };
+    let top = grid.iter().position(|row| alive_cols(row).is_some())?;
+    let bottom = grid.iter().rposition(|row| alive_cols(row).is_some())?;
+    let (left, right) = grid[top..=bottom]
+        .iter()
+        .filter_map(alive_cols)
+        .fold((usize::MAX, 0), |(left, right), (first, last)| {
+            (left.min(first), right.max(last))
+        });
+    Some(BoundingBox {
+        top,
+        left,
+        bottom,
+        right,
+    })
+}
+
+/// The Cells within `bounds` with `margin` dead rows and columns on every side,
+/// Cells beyond the edges of `grid` are dead
+pub fn crop(grid: &[Vec<Cell>], bounds: BoundingBox, margin: usize) -> Grid {
+    let cell = |row: usize, col: usize| {
+        let row = (row + bounds.top).checked_sub(margin)?;
+        let col = (col + bounds.left).checked_sub(margin)?;
+        grid.get(row)?.get(col).cloned()
+    };
+    (0..bounds.row_count() + 2 * margin)
+        .map(|row| {
+            (0..bounds.col_count() + 2 * margin)
+                .map(|col| cell(row, col).unwrap_or(Cell::Dead))
+                .collect()
+        })
+        .collect()
+}
+
+/// `grid` cropped to the `bounding_box` of its alive Cells with `margin` dead rows and columns
+/// on every side, centering the pattern.
+///
+/// A grid without alive Cells is trimmed to a dead square with sides of twice the `margin`.
+pub fn trim(grid: &[Vec<Cell>], margin: usize) -> Grid {
+    bounding_box(grid).map_or_else(
+        || {
+            (0..2 * margin)
+                .map(|_| core::iter::repeat_n(Cell::Dead, 2 * margin).collect())
+                .collect()
+        },
+        |bounds| crop(grid, bounds, margin),
+    )
+}
+
+/// The grid rotated clockwise by 90 degrees, rows become columns
+pub fn rotate(grid: &[Vec<Cell>]) -> Grid {
+    let col_count = grid.first().map(Vec::len).unwrap_or_default();
+    (0..col_count)
+        .map(|col| grid.iter().rev().map(|row| row[col].clone()).collect())
+        .collect()
+}
+
+#[cfg(feature = "std")]
+/// Seeds a `Grid` from an image file
+///
+/// Every pixel maps to exactly one Cell, so the resulting `Grid`
+/// has as many rows as the image is high and as many columns as it is wide.
+/// Fully transparent pixels are always treated as `Cell::Dead`.
+pub trait FromImage: Sized {
+    /// Pixels whose luminance is below `threshold` become `Cell::Alive`,
+    /// every other pixel becomes `Cell::Dead`
+    fn from_image(path: impl AsRef<Path>, threshold: u8) -> ImageResult<Self>;
+
+    /// Splits the luminance into `levels` evenly sized bands
+    /// - The lightest band => `Cell::Dead`
+    /// - The darkest band => `Cell::Alive`
+    /// - Everything in between => `Cell::Dying` with darker pixels
+    ///   having more `ticks_till_death` left
+    fn from_image_levels(path: impl AsRef<Path>, levels: u8) -> ImageResult<Self>;
+}
+
+#[cfg(feature = "std")]
+impl FromImage for Grid {
+    fn from_image(path: impl AsRef<Path>, threshold: u8) -> ImageResult<Self> {
+        let image = image::open(path)?.into_luma_alpha8();
+        Ok(map_pixels(&image, |luma| {
+            if luma < threshold {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            }
+        }))
+    }
+
+    fn from_image_levels(path: impl AsRef<Path>, levels: u8) -> ImageResult<Self> {
+        let image = image::open(path)?.into_luma_alpha8();
+        Ok(map_pixels(&image, |luma| Cell::from_darkness(luma, levels)))
+    }
+}
+
+#[cfg(feature = "std")]
+fn map_pixels(image: &GrayAlphaImage, to_cell: impl Fn(u8) -> Cell) -> Grid {
+    image
+        .rows()
+        .map(|row| {
+            row.map(|pixel| {
+                let [luma, alpha] = pixel.0;
+                if alpha == 0 {
+                    Cell::Dead
+                } else {
+                    to_cell(luma)
+                }
+            })
+            .collect()
+        })
+        .collect()
+}
+
+/// Renders `grid` with `scale` x `scale` pixels per Cell, see `Theme::pixel_color`
+#[cfg(feature = "std")]
+#[allow(clippy::cast_possible_truncation)]
+pub fn to_image(grid: &[Vec<Cell>], theme: &Theme, decay: usize, scale: u32) -> RgbImage {
+    let scale = scale.max(1);
+    let col_count = grid.first().map(Vec::len).unwrap_or_default();
+    RgbImage::from_fn(
+        col_count as u32 * scale,
+        grid.len() as u32 * scale,
+        |x, y| {
+            let cell = &grid[(y / scale) as usize][(x / scale) as usize];
+            Rgb(theme.pixel_color(cell, decay, x % scale, y % scale))
+        },
+    )
+}
+
+/// Part of a grid shown on a screen of `width` x `height` pixels
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+    /// Fractional (`col`, `row`) coordinates of the center of the screen,
+    /// (`0.0`, `0.0`) being the top left corner of the grid
+    pub center: [f32; 2],
+    /// Zoom, the edge length of a pixel in Cells
+    pub cells_per_pixel: f32,
+}
+
+/// Renders what `viewport` shows of `grid`, see `Theme::cell_color`.
+/// Pixels outside of the grid take the background color of `theme`.
+#[cfg(feature = "std")]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn viewport_image(
+    grid: &[Vec<Cell>],
+    theme: &Theme,
+    decay: usize,
+    viewport: Viewport,
+) -> RgbImage {
+    let Viewport {
+        width,
+        height,
+        center: [center_col, center_row],
+        cells_per_pixel,
+    } = viewport;
+    RgbImage::from_fn(width, height, |x, y| {
+        // Sampling the center of each pixel
+        let col = (x as f32 + 0.5 - width as f32 / 2.0).mul_add(cells_per_pixel, center_col);
+        let row = (y as f32 + 0.5 - height as f32 / 2.0).mul_add(cells_per_pixel, center_row);
+        let cell = (col >= 0.0 && row >= 0.0)
+            .then(|| grid.get(row as usize)?.get(col as usize))
+            .flatten();
+        Rgb(cell.map_or(theme.background, |cell| theme.cell_color(cell, decay)))
+    })
+}
+
+/// Renders `grid` as SVG with `scale` x `scale` units per Cell,
+/// one rectangle for the background and one per Cell of any other color
+#[cfg(feature = "std")]
+pub fn to_svg(grid: &[Vec<Cell>], theme: &Theme, decay: usize, scale: u32) -> String {
+    to_annotated_svg(grid, theme, decay, scale, &[])
+}
+
+/// `to_svg` with numbered markers on the annotated Cells and their legend below the grid
+#[cfg(feature = "std")]
+pub fn to_annotated_svg(
+    grid: &[Vec<Cell>],
+    theme: &Theme,
+    decay: usize,
+    scale: u32,
+    annotations: &[crate::annotation::Annotation],
+) -> String {
+    use std::fmt::Write;
+
+    let hex = |[r, g, b]: crate::theme::Rgb| format!("#{r:02x}{g:02x}{b:02x}");
+    let scale = scale.max(1) as usize;
+    let col_count = grid.first().map(Vec::len).unwrap_or_default();
+    let (width, grid_height) = (col_count * scale, grid.len() * scale);
+    let height = grid_height + crate::annotation::legend_height(annotations);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n"
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>",
+        hex(theme.dead)
+    );
+    for (row, cells) in grid.iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            let color = theme.cell_color(cell, decay);
+            if color != theme.dead {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{scale}\" height=\"{scale}\" fill=\"{}\"/>",
+                    col * scale,
+                    row * scale,
+                    hex(color)
+                );
+            }
+        }
+    }
+    crate::annotation::write_svg(&mut svg, annotations, scale, grid_height);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        grid::{
+            bounding_box, crop, density_tiles, rotate, to_image, to_svg, trim, viewport_image,
+            BoundingBox, CellAccess, ColIdx, RowIdx, Viewport,
+        },
+        Cell, FromImage, Grid, Theme,
+    };
+    use image::{GrayAlphaImage, LumaA, Rgb};
+
+    #[test]
+    fn image_thresholding() {
+        let path = std::env::temp_dir().join("cellular_automata_image_thresholding.png");
+        GrayAlphaImage::from_fn(3, 2, |x, y| match (x, y) {
+            (0, 0) => LumaA([0, 0]),
+            (_, 0) => LumaA([0, u8::MAX]),
+            _ => LumaA([200, u8::MAX]),
+        })
+        .save(&path)
+        .unwrap();
+
+        let grid = Grid::from_image(&path, 128).unwrap();
+        assert_eq!(
+            grid,
+            vec![
+                vec![Cell::Dead, Cell::Alive, Cell::Alive],
+                vec![Cell::Dead, Cell::Dead, Cell::Dead],
+            ]
+        );
+    }
+
+    #[test]
+    fn image_levels() {
+        let path = std::env::temp_dir().join("cellular_automata_image_levels.png");
+        let pixels = [
+            LumaA([0, u8::MAX]),
+            LumaA([100, u8::MAX]),
+            LumaA([u8::MAX, u8::MAX]),
+            LumaA([0, 0]),
+        ];
+        GrayAlphaImage::from_fn(4, 1, |x, _| pixels[x as usize])
+            .save(&path)
+            .unwrap();
+
+        let grid = Grid::from_image_levels(&path, 4).unwrap();
+        assert_eq!(
+            grid,
+            vec![vec![
+                Cell::Alive,
+                Cell::Dying {
+                    ticks_till_death: 2
+                },
+                Cell::Dead,
+                Cell::Dead,
+            ]]
+        );
+    }
+
+    #[test]
+    fn rendering() {
+        let grid = vec![vec![Cell::Alive, Cell::Dead, Cell::dying_cell()]];
+        let theme = Theme::classic();
+        let image = to_image(&grid, &theme, 3, 2);
+        assert_eq!(image.dimensions(), (6, 2));
+        assert_eq!(image.get_pixel(1, 1), &Rgb(theme.alive));
+        assert_eq!(image.get_pixel(2, 0), &Rgb(theme.dead));
+        assert_eq!(
+            image.get_pixel(5, 1),
+            &Rgb(theme.cell_color(&grid[0][2], 3))
+        );
+    }
+
+    #[test]
+    fn viewport() {
+        let grid = vec![vec![Cell::Alive, Cell::Dead], vec![Cell::Dead, Cell::Alive]];
+        let theme = Theme::classic();
+        // The whole grid at 4 pixels per Cell with a margin of 4 pixels
+        let viewport = Viewport {
+            width: 16,
+            height: 16,
+            center: [1.0, 1.0],
+            cells_per_pixel: 0.25,
+        };
+        let image = viewport_image(&grid, &theme, 0, viewport);
+        assert_eq!(image.dimensions(), (16, 16));
+        assert_eq!(image.get_pixel(0, 0), &Rgb(theme.background));
+        assert_eq!(image.get_pixel(15, 8), &Rgb(theme.background));
+        assert_eq!(image.get_pixel(4, 4), &Rgb(theme.alive));
+        assert_eq!(image.get_pixel(11, 4), &Rgb(theme.dead));
+        assert_eq!(image.get_pixel(11, 11), &Rgb(theme.alive));
+
+        // Zoomed in on the bottom right Cell
+        let zoomed = Viewport {
+            center: [1.5, 1.5],
+            cells_per_pixel: 0.01,
+            ..viewport
+        };
+        let image = viewport_image(&grid, &theme, 0, zoomed);
+        assert!(image.pixels().all(|pixel| *pixel == Rgb(theme.alive)));
+    }
+
+    #[test]
+    fn svg() {
+        let grid = vec![vec![Cell::Alive, Cell::Dead], vec![Cell::Dead, Cell::Alive]];
+        let svg = to_svg(&grid, &Theme::high_contrast(), 0, 10);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("width=\"20\" height=\"20\""));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains("<rect x=\"10\" y=\"10\" width=\"10\" height=\"10\""));
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid = vec![vec![Cell::Dead; 5]; 4];
+        assert_eq!(bounding_box(&grid), None);
+        grid[1][3] = Cell::Alive;
+        grid[2][1] = Cell::Alive;
+        grid[3][2] = Cell::dying_cell();
+        let bounds = bounding_box(&grid).unwrap();
+        assert_eq!(
+            bounds,
+            BoundingBox {
+                top: 1,
+                left: 1,
+                bottom: 2,
+                right: 3
+            }
+        );
+        assert_eq!((bounds.row_count(), bounds.col_count()), (2, 3));
+
+        let trimmed = trim(&grid, 1);
+        assert_eq!((trimmed.len(), trimmed[0].len()), (4, 5));
+        assert_eq!(trimmed[1][3], Cell::Alive);
+        assert_eq!(trimmed[2][1], Cell::Alive);
+        // Only alive Cells count, the dying one is within the margin but cropped off without
+        assert_eq!(trimmed[3][2], Cell::dying_cell());
+        assert_eq!(trim(&grid, 0), crop(&grid, bounds, 0));
+        assert_eq!(trim(&grid, 0).len(), 2);
+        assert_eq!(
+            trim(&vec![vec![Cell::Dead; 5]; 4], 2),
+            vec![vec![Cell::Dead; 4]; 4]
+        );
+        let corner = BoundingBox {
+            top: 0,
+            left: 0,
+            bottom: 0,
+            right: 0,
+        };
+        assert_eq!(bounds.union(corner).row_count(), 3);
+        assert_eq!(crop(&grid, corner, 1), vec![vec![Cell::Dead; 3]; 3]);
+    }
+
+    #[test]
+    fn checked_access() {
+        let mut grid = vec![vec![Cell::Dead, Cell::Alive], vec![Cell::Dead, Cell::Dead]];
+        assert_eq!(grid.cell(RowIdx(0), ColIdx(1)), Some(&Cell::Alive));
+        assert_eq!(grid.cell(RowIdx(1), ColIdx(0)), Some(&Cell::Dead));
+        assert_eq!(grid.cell(RowIdx(2), ColIdx(0)), None);
+        assert_eq!(grid.cell(RowIdx(0), ColIdx(2)), None);
+
+        assert_eq!(
+            grid.set_cell(RowIdx(1), ColIdx(0), Cell::Alive),
+            Some(Cell::Dead)
+        );
+        assert_eq!(grid.set_cell(RowIdx(1), ColIdx(2), Cell::Alive), None);
+        assert_eq!(
+            grid,
+            vec![vec![Cell::Dead, Cell::Alive], vec![Cell::Alive, Cell::Dead]]
+        );
+    }
+
+    #[test]
+    fn density() {
+        let grid = vec![
+            vec![Cell::Alive, Cell::Dead, Cell::Alive],
+            vec![Cell::Alive, Cell::Alive, Cell::Dead],
+        ];
+        assert_eq!(density_tiles(&grid, 2), vec![vec![0.75, 0.5]]);
+        assert_eq!(density_tiles(&grid, 1)[1], vec![1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn rotation() {
+        let grid = vec![
+            vec![Cell::Alive, Cell::Dead, Cell::Dead],
+            vec![Cell::Dead, Cell::Dead, Cell::Alive],
+        ];
+        assert_eq!(
+            rotate(&grid),
+            vec![
+                vec![Cell::Dead, Cell::Alive],
+                vec![Cell::Dead, Cell::Dead],
+                vec![Cell::Alive, Cell::Dead],
+            ]
+        );
+        assert_eq!(rotate(&rotate(&rotate(&rotate(&grid)))), grid);
+    }
+}
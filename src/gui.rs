@@ -0,0 +1,68 @@
+mod annotations;
+mod bookmarks;
+mod browser;
+mod camera;
+mod compare;
+mod controls;
+mod demo;
+mod editor;
+mod gpu;
+mod input;
+mod localization;
+mod lod;
+mod minimap;
+mod presets;
+mod render;
+mod rule_editor;
+// There's no file system or synchronous clipboard access on the web
+#[cfg(not(target_arch = "wasm32"))]
+mod screenshot;
+// There's no file system on the web
+#[cfg(not(target_arch = "wasm32"))]
+mod session;
+mod ships;
+mod simulation;
+mod sound;
+mod split_view;
+mod stats;
+mod status;
+mod theme;
+// There's no file system on the web
+#[cfg(not(target_arch = "wasm32"))]
+mod timelapse;
+mod tools;
+mod tutorial;
+mod zones;
+
+pub use annotations::AnnotationsPlugin;
+pub use bookmarks::BookmarksPlugin;
+pub use browser::BrowserPlugin;
+pub use camera::CameraPlugin;
+pub use compare::ComparePlugin;
+pub use controls::ControlsPlugin;
+pub use demo::DemoPlugin;
+pub use editor::EditorPlugin;
+pub use gpu::GpuPlugin;
+pub use input::InputPlugin;
+pub use localization::LocalizationPlugin;
+pub use lod::LodPlugin;
+pub use minimap::MinimapPlugin;
+pub use presets::PresetsPlugin;
+pub use render::RenderPlugin;
+pub use rule_editor::RuleEditorPlugin;
+#[cfg(not(target_arch = "wasm32"))]
+pub use screenshot::ScreenshotPlugin;
+#[cfg(not(target_arch = "wasm32"))]
+pub use session::SessionPlugin;
+pub use ships::ShipsPlugin;
+pub use simulation::{Simulation, SimulationPlugin};
+pub use sound::SoundPlugin;
+pub use split_view::SplitViewPlugin;
+pub use stats::StatsPlugin;
+pub use status::StatusPlugin;
+pub use theme::ThemePlugin;
+#[cfg(not(target_arch = "wasm32"))]
+pub use timelapse::TimelapsePlugin;
+pub use tools::ToolsPlugin;
+pub use tutorial::TutorialPlugin;
+pub use zones::ZonesPlugin;
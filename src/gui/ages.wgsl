@@ -0,0 +1,22 @@
+// Renders the generations since each Cell was last alive into one texture of the ping-pong
+// pair in `gpu.rs`, reading the previous ones from the other
+
+@group(1) @binding(0)
+var states: texture_2d<f32>;
+@group(1) @binding(1)
+var previous: texture_2d<f32>;
+
+// The target is an sRGB texture, steps this coarse survive its encoding exactly
+const AGE_STEPS: f32 = 32.0;
+
+@fragment
+fn fragment(
+    #import bevy_sprite::mesh2d_vertex_output
+) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(states));
+    let cell = vec2<i32>(min(floor(uv * size), size - 1.0));
+    let alive = round(textureLoad(states, cell, 0).r * 255.0) == 1.0;
+    let age = round(textureLoad(previous, cell, 0).r * AGE_STEPS);
+    let next = select(min(age + 1.0, AGE_STEPS), 0.0, alive);
+    return vec4<f32>(next / AGE_STEPS, 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,175 @@
+use super::{
+    camera::MainCamera, editor::HoveredCell, localization::Locale, tools::screen_position,
+    Simulation,
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{
+    egui::{self, Color32},
+    EguiContexts,
+};
+use cellular_automata::annotation::{Annotation, MARKER_COLOR};
+
+const LABEL_COLOR: Color32 = Color32::from_rgb(MARKER_COLOR[0], MARKER_COLOR[1], MARKER_COLOR[2]);
+const LABEL_FONT_SIZE: f32 = 14.0;
+/// Radius of the dot on an annotated Cell the label floats above
+const MARKER_RADIUS: f32 = 3.0;
+
+/// Labels on the grid, saved with the session
+#[derive(Resource, Default)]
+pub struct Annotations(pub Vec<Annotation>);
+
+/// The annotation being written in the "Annotations" window,
+/// its Cell follows the last hovered one
+#[derive(Resource)]
+struct Draft {
+    row: usize,
+    col: usize,
+    text: String,
+    show_labels: bool,
+}
+
+impl Default for Draft {
+    fn default() -> Self {
+        Self {
+            row: 0,
+            col: 0,
+            text: String::new(),
+            show_labels: true,
+        }
+    }
+}
+
+/// Floats the `Annotations` over their Cells, they're added, edited and removed in the
+/// "Annotations" window
+pub struct AnnotationsPlugin;
+
+impl Plugin for AnnotationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Annotations>()
+            .init_resource::<Draft>()
+            .add_system(follow_cursor)
+            .add_system(annotations_ui.after(follow_cursor))
+            .add_system(draw_labels);
+    }
+}
+
+fn follow_cursor(hovered: Res<HoveredCell>, mut draft: ResMut<Draft>) {
+    if let Some((row, col)) = hovered.0 {
+        if (draft.row, draft.col) != (row, col) {
+            draft.row = row;
+            draft.col = col;
+        }
+    }
+}
+
+fn annotations_ui(
+    mut contexts: EguiContexts,
+    mut annotations: ResMut<Annotations>,
+    mut draft: ResMut<Draft>,
+    simulation: Res<Simulation>,
+    locale: Res<Locale>,
+) {
+    egui::Window::new(locale.text("window-annotations"))
+        .id(egui::Id::new("annotations"))
+        .default_open(false)
+        .vscroll(true)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.checkbox(
+                &mut draft.show_labels,
+                locale.text("annotations-show-labels"),
+            );
+            ui.horizontal(|ui| {
+                ui.label(locale.text("annotations-cell"));
+                let max_row = simulation.row_count.saturating_sub(1);
+                let max_col = simulation.col_count.saturating_sub(1);
+                ui.add(egui::DragValue::new(&mut draft.row).clamp_range(0..=max_row))
+                    .on_hover_text(locale.text("annotations-row"));
+                ui.add(egui::DragValue::new(&mut draft.col).clamp_range(0..=max_col))
+                    .on_hover_text(locale.text("annotations-column"));
+            });
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut draft.text)
+                    .on_hover_text(locale.text("annotations-label"));
+                let text = draft.text.trim().to_owned();
+                if ui
+                    .add_enabled(
+                        !text.is_empty(),
+                        egui::Button::new(locale.text("annotations-annotate")),
+                    )
+                    .clicked()
+                {
+                    annotations
+                        .0
+                        .push(Annotation::new(draft.row, draft.col, text));
+                    draft.text.clear();
+                }
+            });
+            let mut remove = None;
+            egui::Grid::new("annotations").striped(true).show(ui, |ui| {
+                for (index, annotation) in annotations.0.iter_mut().enumerate() {
+                    ui.label(format!("{},{}", annotation.row, annotation.col));
+                    ui.text_edit_singleline(&mut annotation.text);
+                    if ui.button(locale.text("annotations-remove")).clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(index) = remove {
+                annotations.0.remove(index);
+            }
+        });
+}
+
+/// Labels of annotations outside of the grid, e.g. after it shrank, aren't drawn
+#[allow(clippy::cast_precision_loss)]
+fn draw_labels(
+    mut contexts: EguiContexts,
+    annotations: Res<Annotations>,
+    draft: Res<Draft>,
+    simulation: Res<Simulation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    if !draft.show_labels || annotations.0.is_empty() {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let painter = contexts
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+    let visible = annotations.0.iter().filter(|annotation| {
+        annotation.row < simulation.row_count && annotation.col < simulation.col_count
+    });
+    for annotation in visible {
+        let (col, row) = (annotation.col as f32, annotation.row as f32);
+        let Some(center) = screen_position(
+            &simulation,
+            window,
+            camera,
+            camera_transform,
+            col + 0.5,
+            row + 0.5,
+        ) else {
+            continue;
+        };
+        painter.circle_filled(center, MARKER_RADIUS, LABEL_COLOR);
+        let text = painter.layout_no_wrap(
+            annotation.text.clone(),
+            egui::FontId::proportional(LABEL_FONT_SIZE),
+            LABEL_COLOR,
+        );
+        let position = center - egui::vec2(text.size().x / 2.0, text.size().y + MARKER_RADIUS);
+        painter.rect_filled(
+            egui::Rect::from_min_size(position, text.size()).expand(2.0),
+            2.0,
+            Color32::from_black_alpha(160),
+        );
+        painter.galley(position, text);
+    }
+}
@@ -0,0 +1,82 @@
+use super::{
+    localization::Locale,
+    simulation::{Paused, Rewind, Scheduler, SimulationSet},
+    Simulation,
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// Label typed for the next bookmark
+#[derive(Resource, Default)]
+struct Label(String);
+
+/// Lists the bookmarks of the `Rewind` history, jumping to one pauses there
+pub struct BookmarksPlugin;
+
+impl Plugin for BookmarksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Label>()
+            .add_system(bookmarks_ui.in_set(SimulationSet::Edit));
+    }
+}
+
+fn bookmarks_ui(
+    mut contexts: EguiContexts,
+    mut label: ResMut<Label>,
+    mut history: ResMut<Rewind>,
+    mut paused: ResMut<Paused>,
+    mut scheduler: ResMut<Scheduler>,
+    mut simulation: ResMut<Simulation>,
+    locale: Res<Locale>,
+) {
+    egui::Window::new(locale.text("window-bookmarks"))
+        .id(egui::Id::new("bookmarks"))
+        .default_open(false)
+        .vscroll(true)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut label.0)
+                    .on_hover_text(locale.text("bookmarks-label"));
+                if ui
+                    .button(locale.format(
+                        "bookmarks-add",
+                        [("generation", simulation.generation.to_string().into())],
+                    ))
+                    .clicked()
+                {
+                    let text = match label.0.trim() {
+                        "" => locale.format(
+                            "bookmarks-generation",
+                            [("generation", simulation.generation.to_string().into())],
+                        ),
+                        text => text.to_owned(),
+                    };
+                    history.bookmark(text, &simulation);
+                    label.0.clear();
+                }
+            });
+            let mut jump = None;
+            let mut remove = None;
+            egui::Grid::new("bookmarks").striped(true).show(ui, |ui| {
+                for (index, bookmark) in history.bookmarks().iter().enumerate() {
+                    ui.label(bookmark.snapshot.generation.to_string());
+                    ui.label(&bookmark.label);
+                    if ui.button(locale.text("bookmarks-jump")).clicked() {
+                        jump = Some(index);
+                    }
+                    if ui.button(locale.text("bookmarks-remove")).clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(index) = jump {
+                paused.0 = true;
+                scheduler.cancel();
+                history.jump_to_bookmark(index, &mut simulation);
+            }
+            if let Some(index) = remove {
+                history.remove_bookmark(index);
+            }
+        });
+}
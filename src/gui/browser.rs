@@ -0,0 +1,269 @@
+use super::{
+    editor::ArmedPattern,
+    input::{Action, InputMap},
+    localization::Locale,
+    simulation::SimulationSet,
+    theme::Themes,
+    Simulation,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Color32},
+    EguiContexts,
+};
+#[cfg(feature = "fetch")]
+use cellular_automata::fetch::{FetchError, Fetcher, Query};
+use cellular_automata::{
+    library::{self, Pattern},
+    localization::Localizer,
+    theme::{blend, Rgb},
+    Neighborhood, RuleSet,
+};
+use std::{fs, path::Path};
+#[cfg(feature = "fetch")]
+use std::{
+    sync::{
+        mpsc::{self, TryRecvError},
+        Mutex,
+    },
+    thread,
+};
+
+/// Directory searched for pattern files at startup, and where fetched ones are cached
+const PATTERN_DIRECTORY: &str = "patterns";
+/// Generations a thumbnail shows the trace of
+const THUMBNAIL_GENERATIONS: usize = 8;
+/// Thumbnails are scaled down to fit this size
+const THUMBNAIL_SIZE: f32 = 64.0;
+const MAX_THUMBNAIL_CELL_SIZE: f32 = 4.0;
+/// Patterns the "Scatter" button drops until changed
+const DEFAULT_SCATTER_COUNT: usize = 20;
+
+/// The embedded patterns followed by the ones found in `PATTERN_DIRECTORY`,
+/// listed in the "Patterns" window where clicking one arms it for stamping
+#[derive(Resource)]
+pub struct Library {
+    patterns: Vec<Pattern>,
+    thumbnails: Vec<Vec<Vec<f32>>>,
+    /// Rules the thumbnails were generated under
+    rules: Option<(RuleSet, Neighborhood)>,
+    /// Patterns dropped at once by the "Scatter" button
+    scatter_count: usize,
+}
+
+impl Default for Library {
+    fn default() -> Self {
+        let mut patterns = library::embedded();
+        patterns.extend(load_directory(Path::new(PATTERN_DIRECTORY)));
+        Self {
+            patterns,
+            thumbnails: Vec::new(),
+            rules: None,
+            scatter_count: DEFAULT_SCATTER_COUNT,
+        }
+    }
+}
+
+impl Library {
+    /// Replaces the pattern of the same name if there's one
+    #[cfg(feature = "fetch")]
+    fn add(&mut self, pattern: Pattern) {
+        match self
+            .patterns
+            .iter_mut()
+            .find(|known| known.name == pattern.name)
+        {
+            Some(known) => *known = pattern,
+            None => self.patterns.push(pattern),
+        }
+        self.rules = None;
+    }
+
+    /// Regenerates the thumbnails if the rules changed since they were generated
+    fn update_thumbnails(&mut self, rule_set: &RuleSet, neighborhood: Neighborhood) {
+        if self
+            .rules
+            .as_ref()
+            .is_some_and(|rules| rules.0 == *rule_set && rules.1 == neighborhood)
+        {
+            return;
+        }
+        self.thumbnails = self
+            .patterns
+            .iter()
+            .map(|pattern| pattern.thumbnail(rule_set, neighborhood, THUMBNAIL_GENERATIONS))
+            .collect();
+        self.rules = Some((rule_set.clone(), neighborhood));
+    }
+}
+
+/// Query typed into the "Patterns" window and the download started from it
+#[cfg(feature = "fetch")]
+#[derive(Resource, Default)]
+struct Download {
+    query: String,
+    pending: Option<Mutex<mpsc::Receiver<Result<Pattern, FetchError>>>>,
+}
+
+pub struct BrowserPlugin;
+
+impl Plugin for BrowserPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Library>()
+            .add_system(browser_ui.in_set(SimulationSet::Edit));
+        #[cfg(feature = "fetch")]
+        app.init_resource::<Download>();
+    }
+}
+
+/// Loads every pattern file in `directory`, skipping invalid ones
+fn load_directory(directory: &Path) -> Vec<Pattern> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut patterns: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            Pattern::load(&path)
+                .map_err(|e| warn!("Skipping {}: {e}", path.display()))
+                .ok()
+        })
+        .collect();
+    patterns.sort_by(|a, b| a.name.cmp(&b.name));
+    patterns
+}
+
+#[allow(clippy::too_many_arguments)]
+fn browser_ui(
+    mut contexts: EguiContexts,
+    mut library: ResMut<Library>,
+    mut simulation: ResMut<Simulation>,
+    themes: Res<Themes>,
+    mut armed: ResMut<ArmedPattern>,
+    locale: Res<Locale>,
+    map: Res<InputMap>,
+    #[cfg(feature = "fetch")] mut download: ResMut<Download>,
+) {
+    egui::Window::new(locale.text("window-patterns"))
+        .id(egui::Id::new("patterns"))
+        .default_open(false)
+        .vscroll(true)
+        .show(contexts.ctx_mut(), |ui| {
+            #[cfg(feature = "fetch")]
+            fetch_ui(ui, &mut download, &mut library, &locale);
+            if scatter_ui(ui, &mut library, &locale) {
+                library::scatter(
+                    &mut simulation.grid,
+                    &library.patterns,
+                    library.scatter_count,
+                    &mut rand::thread_rng(),
+                );
+            }
+            library.update_thumbnails(&simulation.rule_set, simulation.neighborhood_type);
+            if armed.0.is_some() {
+                ui.label(locale.format(
+                    "patterns-armed",
+                    [("cancel", map.describe(Action::Disarm, &locale).into())],
+                ));
+            }
+            egui::Grid::new("patterns").show(ui, |ui| {
+                for (index, (pattern, thumbnail)) in
+                    library.patterns.iter().zip(&library.thumbnails).enumerate()
+                {
+                    let response = draw_thumbnail(ui, thumbnail, &themes);
+                    let selected = armed.0.as_ref() == Some(&pattern.grid);
+                    let label = ui.selectable_label(selected, &pattern.name);
+                    if response.clicked() || label.clicked() {
+                        armed.0 = (!selected).then(|| pattern.grid.clone());
+                    }
+                    if index % 2 == 1 {
+                        ui.end_row();
+                    }
+                }
+            });
+        });
+}
+
+/// Whether randomly picked and oriented patterns of the library should be dropped
+/// onto the grid, for a soup that isn't uniform
+fn scatter_ui(ui: &mut egui::Ui, library: &mut Library, locale: &Localizer) -> bool {
+    ui.horizontal(|ui| {
+        let clicked = ui.button(locale.text("patterns-scatter")).clicked();
+        ui.add(egui::DragValue::new(&mut library.scatter_count).clamp_range(1..=500))
+            .on_hover_text(locale.text("patterns-scatter-count"));
+        clicked
+    })
+    .inner
+}
+
+/// Downloads in a thread so the app doesn't freeze, the pattern is added once it arrived
+#[cfg(feature = "fetch")]
+fn fetch_ui(ui: &mut egui::Ui, download: &mut Download, library: &mut Library, locale: &Localizer) {
+    let received = download
+        .pending
+        .as_ref()
+        .and_then(|pending| pending.lock().ok().map(|receiver| receiver.try_recv()));
+    match received {
+        Some(Ok(Ok(pattern))) => {
+            library.add(pattern);
+            download.pending = None;
+        }
+        Some(Ok(Err(e))) => {
+            warn!("Couldn't fetch {}: {e}", download.query);
+            download.pending = None;
+        }
+        Some(Err(TryRecvError::Disconnected)) => download.pending = None,
+        Some(Err(TryRecvError::Empty)) | None => {}
+    }
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut download.query)
+            .on_hover_text(locale.text("patterns-fetch-query"));
+        if download.pending.is_some() {
+            ui.spinner();
+        } else if ui.button(locale.text("patterns-fetch")).clicked()
+            && !download.query.trim().is_empty()
+        {
+            let (sender, receiver) = mpsc::channel();
+            let query = Query::parse(&download.query);
+            thread::spawn(move || {
+                // The app may have quit in the meantime
+                let _ = sender.send(Fetcher::new(PATTERN_DIRECTORY).fetch(&query));
+            });
+            download.pending = Some(Mutex::new(receiver));
+        }
+    });
+}
+
+/// Cells are colored by the fraction of generations they were alive in
+#[allow(clippy::cast_precision_loss)]
+fn draw_thumbnail(ui: &mut egui::Ui, thumbnail: &[Vec<f32>], themes: &Themes) -> egui::Response {
+    let row_count = thumbnail.len();
+    let col_count = thumbnail.first().map_or(0, Vec::len);
+    let cell_size =
+        (THUMBNAIL_SIZE / row_count.max(col_count).max(1) as f32).min(MAX_THUMBNAIL_CELL_SIZE);
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(col_count as f32, row_count as f32) * cell_size,
+        egui::Sense::click(),
+    );
+    let theme = themes.current();
+    painter.rect_filled(response.rect, 0.0, rgb(theme.dead));
+    for (row, fractions) in thumbnail.iter().enumerate() {
+        for (col, &fraction) in fractions.iter().enumerate() {
+            if fraction > 0.0 {
+                let min = response.rect.min + egui::vec2(col as f32, row as f32) * cell_size;
+                painter.rect_filled(
+                    egui::Rect::from_min_size(min, egui::Vec2::splat(cell_size)),
+                    0.0,
+                    rgb(blend(theme.dead, theme.alive, fraction)),
+                );
+            }
+        }
+    }
+    response
+}
+
+fn rgb([r, g, b]: Rgb) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
@@ -0,0 +1,170 @@
+use super::{
+    input::Action,
+    render::{cell_center, CELL_SIZE},
+    Simulation,
+};
+use bevy::{
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_egui::EguiContexts;
+
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 20.0;
+/// Zoom factor per scrolled line
+const ZOOM_STEP: f32 = 1.1;
+/// Scrolled pixels that amount to one line on touchpads
+const PIXELS_PER_LINE: f32 = 20.0;
+/// Lines per second scrolled while `Action::ZoomIn` or `Action::ZoomOut` is held
+const ZOOM_LINES_PER_SECOND: f32 = 10.0;
+/// Screen pixels per second panned with the arrow keys
+const PAN_SPEED: f32 = 600.0;
+/// Space around the pattern when fitting it into the window, relative to its size
+const FIT_PADDING: f32 = 1.2;
+
+/// Marks the camera looking at the grid
+#[derive(Component)]
+pub struct MainCamera;
+
+/// Zooming with the mouse wheel or by pinching,
+/// panning by dragging with the middle mouse button, two fingers or with the arrow keys,
+/// fitting the alive Cells into the window with `F`
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_camera)
+            .add_systems((zoom, pan, pinch, fit));
+    }
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((Camera2dBundle::default(), MainCamera));
+}
+
+fn zoom(
+    time: Res<Time>,
+    actions: Res<Input<Action>>,
+    mut contexts: EguiContexts,
+    mut wheel: EventReader<MouseWheel>,
+    mut projections: Query<&mut OrthographicProjection, With<MainCamera>>,
+) {
+    let mut lines: f32 = wheel
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        })
+        .sum();
+    if contexts.ctx_mut().is_pointer_over_area() {
+        lines = 0.0;
+    }
+    for (action, direction) in [(Action::ZoomIn, 1.0), (Action::ZoomOut, -1.0)] {
+        if actions.pressed(action) {
+            lines += direction * ZOOM_LINES_PER_SECOND * time.delta_seconds();
+        }
+    }
+    if lines.abs() < f32::EPSILON {
+        return;
+    }
+    for mut projection in &mut projections {
+        projection.scale = (projection.scale * ZOOM_STEP.powf(-lines)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
+
+fn pan(
+    time: Res<Time>,
+    actions: Res<Input<Action>>,
+    mut motion: EventReader<MouseMotion>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    // Screen space offset with y pointing up
+    let mut offset: Vec2 =
+        motion.iter().map(|event| event.delta).sum::<Vec2>() * Vec2::new(-1.0, 1.0);
+    if !actions.pressed(Action::Drag) {
+        offset = Vec2::ZERO;
+    }
+    for (action, direction) in [
+        (Action::PanLeft, Vec2::NEG_X),
+        (Action::PanRight, Vec2::X),
+        (Action::PanUp, Vec2::Y),
+        (Action::PanDown, Vec2::NEG_Y),
+    ] {
+        if actions.pressed(action) {
+            offset += direction * PAN_SPEED * time.delta_seconds();
+        }
+    }
+    for (mut transform, projection) in &mut cameras {
+        transform.translation += (offset * projection.scale).extend(0.0);
+    }
+}
+
+/// Two fingers pan by their midpoint and zoom by their distance,
+/// the point under the midpoint stays in place
+fn pinch(
+    touches: Res<Touches>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    let mut fingers = touches.iter();
+    let (Some(first), Some(second), None) = (fingers.next(), fingers.next(), fingers.next()) else {
+        return;
+    };
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    // Touch positions are in the same space as the cursor, y pointing up
+    let center = (first.position() + second.position()) / 2.0;
+    let previous_center = (first.previous_position() + second.previous_position()) / 2.0;
+    let distance = first.position().distance(second.position());
+    let previous_distance = first
+        .previous_position()
+        .distance(second.previous_position());
+    let screen_center = Vec2::new(window.width(), window.height()) / 2.0;
+    for (mut transform, mut projection) in &mut cameras {
+        let scale = if distance > f32::EPSILON {
+            (projection.scale * previous_distance / distance).clamp(MIN_ZOOM, MAX_ZOOM)
+        } else {
+            projection.scale
+        };
+        let offset =
+            (previous_center - screen_center) * projection.scale - (center - screen_center) * scale;
+        transform.translation += offset.extend(0.0);
+        projection.scale = scale;
+    }
+}
+
+/// Centers the bounding box of the alive Cells and zooms so it fills the window,
+/// the whole grid if no Cell is alive
+#[allow(clippy::cast_precision_loss)]
+fn fit(
+    actions: Res<Input<Action>>,
+    simulation: Res<Simulation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    if !actions.just_pressed(Action::Fit) {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let (top, left, bottom, right) = simulation.bounding_box().map_or(
+        (
+            0,
+            0,
+            simulation.row_count.max(1) - 1,
+            simulation.col_count.max(1) - 1,
+        ),
+        |bounds| (bounds.top, bounds.left, bounds.bottom, bounds.right),
+    );
+    let top_left = cell_center(&simulation, top, left);
+    let bottom_right = cell_center(&simulation, bottom, right);
+    let size = ((bottom_right - top_left).abs() + CELL_SIZE) * FIT_PADDING;
+    let scale = (size / Vec2::new(window.width(), window.height())).max_element();
+    for (mut transform, mut projection) in &mut cameras {
+        transform.translation = ((top_left + bottom_right) / 2.0).extend(transform.translation.z);
+        projection.scale = scale.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
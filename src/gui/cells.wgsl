@@ -0,0 +1,46 @@
+// Colors the Cells of the `CellMaterial` in `gpu.rs`, one texel of `states` per Cell
+
+struct CellStyle {
+    trail: vec4<f32>,
+    grid_line: vec4<f32>,
+    // Generations a trail takes to fade out, 0 disables trails
+    trail_length: u32,
+    // Fraction of a Cell covered by the grid lines along its right and bottom edge
+    grid_line_width: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> style: CellStyle;
+// `Cell::state_byte`s
+@group(1) @binding(1)
+var states: texture_2d<f32>;
+// Generations since each Cell was last alive, see `ages.wgsl`
+@group(1) @binding(2)
+var ages: texture_2d<f32>;
+// Color of every state byte, which includes the fading of dying Cells
+@group(1) @binding(3)
+var palette: texture_2d<f32>;
+
+const AGE_STEPS: f32 = 32.0;
+
+@fragment
+fn fragment(
+    #import bevy_sprite::mesh2d_vertex_output
+) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(states));
+    let position = uv * size;
+    let cell = vec2<i32>(min(floor(position), size - 1.0));
+    let state = i32(round(textureLoad(states, cell, 0).r * 255.0));
+    var color = textureLoad(palette, vec2<i32>(state, 0), 0);
+    if state == 0 && style.trail_length > 0u {
+        let age = u32(round(textureLoad(ages, cell, 0).r * AGE_STEPS));
+        if age > 0u && age <= style.trail_length {
+            let fade = f32(style.trail_length + 1u - age) / f32(style.trail_length + 1u);
+            color = mix(color, style.trail, fade);
+        }
+    }
+    if any(fract(position) >= vec2<f32>(1.0 - style.grid_line_width)) {
+        color = style.grid_line;
+    }
+    return color;
+}
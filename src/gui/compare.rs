@@ -0,0 +1,247 @@
+use super::{
+    localization::Locale,
+    render::{pixel_image, CELL_SIZE},
+    Simulation,
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::{
+    divergence::{self, Presence},
+    library::Pattern,
+    localization::Localizer,
+    Grid,
+};
+
+/// Above the grid and its selection outline
+const OVERLAY_Z: f32 = 3.0;
+const ONLY_A_COLOR: Color = Color::rgba(0.9, 0.15, 0.1, 0.8);
+const ONLY_B_COLOR: Color = Color::rgba(0.1, 0.45, 0.95, 0.8);
+const BOTH_COLOR: Color = Color::rgba(0.95, 0.95, 0.95, 0.8);
+
+/// One side of the comparison
+/// - `Live` => The grid of the simulation as it runs
+/// - `Fixed` => A captured grid or a loaded pattern file, named for the UI
+enum Source {
+    Live,
+    Fixed(String, Grid),
+}
+
+impl Source {
+    fn grid<'a>(&'a self, simulation: &'a Simulation) -> &'a Grid {
+        match self {
+            Self::Live => &simulation.grid,
+            Self::Fixed(_, grid) => grid,
+        }
+    }
+
+    fn name(&self, locale: &Localizer) -> String {
+        match self {
+            Self::Live => locale.text("compare-live-grid"),
+            Self::Fixed(name, _) => name.clone(),
+        }
+    }
+}
+
+/// Two grids overlaid on the simulation with their top left corners on its top left corner,
+/// Cells alive only in A, only in B or in both are highlighted
+#[derive(Resource)]
+struct Comparison {
+    a: Source,
+    b: Source,
+    shown: bool,
+    /// Pattern file typed in for loading into A or B
+    path: String,
+    /// Whether the sources changed since the overlay was drawn, other than by stepping
+    dirty: bool,
+}
+
+impl Default for Comparison {
+    fn default() -> Self {
+        Self {
+            a: Source::Live,
+            b: Source::Live,
+            shown: false,
+            path: String::new(),
+            dirty: true,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Overlay;
+
+pub struct ComparePlugin;
+
+impl Plugin for ComparePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Comparison>()
+            .add_startup_system(spawn_overlay)
+            .add_system(compare_ui)
+            .add_system(update_overlay.after(compare_ui));
+    }
+}
+
+fn spawn_overlay(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn((
+        SpriteBundle {
+            texture: images.add(Image::default()),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        Overlay,
+    ));
+}
+
+/// The buttons replacing one side of the comparison
+fn source_ui(
+    ui: &mut egui::Ui,
+    label: &str,
+    source: &mut Source,
+    path: &str,
+    simulation: &Simulation,
+    locale: &Localizer,
+) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(format!("{label}: {}", source.name(locale)));
+        let mut replaced = false;
+        if ui.button(locale.text("compare-live")).clicked() {
+            *source = Source::Live;
+            replaced = true;
+        }
+        if ui.button(locale.text("compare-capture")).clicked() {
+            let name = locale.format(
+                "compare-generation",
+                [("generation", simulation.generation.to_string().into())],
+            );
+            *source = Source::Fixed(name, simulation.grid.clone());
+            replaced = true;
+        }
+        if ui.button(locale.text("compare-load")).clicked() {
+            match Pattern::load(path) {
+                Ok(pattern) => {
+                    *source = Source::Fixed(pattern.name, pattern.grid);
+                    replaced = true;
+                }
+                Err(e) => warn!("Couldn't load {path}: {e}"),
+            }
+        }
+        replaced
+    })
+    .inner
+}
+
+fn compare_ui(
+    mut contexts: EguiContexts,
+    mut comparison: ResMut<Comparison>,
+    simulation: Res<Simulation>,
+    locale: Res<Locale>,
+) {
+    let comparison = &mut *comparison;
+    egui::Window::new(locale.text("window-compare"))
+        .id(egui::Id::new("compare"))
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(locale.text("compare-pattern-file"));
+                ui.text_edit_singleline(&mut comparison.path);
+            });
+            let a = source_ui(
+                ui,
+                "A",
+                &mut comparison.a,
+                &comparison.path,
+                &simulation,
+                &locale,
+            );
+            let b = source_ui(
+                ui,
+                "B",
+                &mut comparison.b,
+                &comparison.path,
+                &simulation,
+                &locale,
+            );
+            let shown = ui
+                .checkbox(&mut comparison.shown, locale.text("compare-show-overlay"))
+                .changed();
+            comparison.dirty |= a || b || shown;
+
+            let overlay = divergence::overlay(
+                comparison.a.grid(&simulation),
+                comparison.b.grid(&simulation),
+            );
+            let count = |presence| {
+                overlay
+                    .iter()
+                    .flatten()
+                    .filter(|&&cell| cell == presence)
+                    .count()
+            };
+            ui.label(locale.format(
+                "compare-counts",
+                [
+                    ("a", count(Presence::OnlyLeft).into()),
+                    ("b", count(Presence::OnlyRight).into()),
+                    ("both", count(Presence::Both).into()),
+                ],
+            ));
+        });
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn update_overlay(
+    mut comparison: ResMut<Comparison>,
+    simulation: Res<Simulation>,
+    mut images: ResMut<Assets<Image>>,
+    mut overlays: Query<
+        (&Handle<Image>, &mut Sprite, &mut Transform, &mut Visibility),
+        With<Overlay>,
+    >,
+) {
+    let Ok((handle, mut sprite, mut transform, mut visibility)) = overlays.get_single_mut() else {
+        return;
+    };
+    if !comparison.dirty && !simulation.is_changed() {
+        return;
+    }
+    comparison.dirty = false;
+    if !comparison.shown {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    let overlay = divergence::overlay(
+        comparison.a.grid(&simulation),
+        comparison.b.grid(&simulation),
+    );
+    let (row_count, col_count) = (overlay.len(), overlay.first().map_or(0, Vec::len));
+    if row_count == 0 || col_count == 0 {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    let data = overlay
+        .iter()
+        .flatten()
+        .flat_map(|presence| {
+            match presence {
+                Presence::Neither => Color::NONE,
+                Presence::OnlyLeft => ONLY_A_COLOR,
+                Presence::OnlyRight => ONLY_B_COLOR,
+                Presence::Both => BOTH_COLOR,
+            }
+            .as_rgba_u8()
+        })
+        .collect();
+    if let Some(image) = images.get_mut(handle) {
+        *image = pixel_image(col_count as u32, row_count as u32, data);
+    }
+    let size = Vec2::new(col_count as f32, row_count as f32) * CELL_SIZE;
+    let grid_top_left =
+        Vec2::new(-(simulation.col_count as f32), simulation.row_count as f32) * CELL_SIZE / 2.0;
+    sprite.custom_size = Some(size);
+    transform.translation = (grid_top_left + Vec2::new(size.x, -size.y) / 2.0).extend(OVERLAY_Z);
+    *visibility = Visibility::Visible;
+}
@@ -0,0 +1,86 @@
+use super::{
+    input::{Action, InputMap},
+    localization::Locale,
+    simulation::{Paused, Scheduler, SimulationSet, Speed, Stride},
+    theme::Themes,
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::localization::{Language, Localizer};
+
+/// Minimum side length of the buttons, large enough to be tapped
+const BUTTON_SIZE: f32 = 44.0;
+
+/// Buttons for everything otherwise only reachable with the keyboard,
+/// for touch screens and browsers
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(controls.in_set(SimulationSet::Edit));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn controls(
+    mut contexts: EguiContexts,
+    mut paused: ResMut<Paused>,
+    mut speed: ResMut<Speed>,
+    mut stride: ResMut<Stride>,
+    mut themes: ResMut<Themes>,
+    mut scheduler: ResMut<Scheduler>,
+    map: Res<InputMap>,
+    mut locale: ResMut<Locale>,
+) {
+    egui::TopBottomPanel::bottom("controls").show(contexts.ctx_mut(), |ui| {
+        ui.horizontal(|ui| {
+            let button = |ui: &mut egui::Ui, id: &str, action: Action| {
+                let label = locale.text(id);
+                ui.add(egui::Button::new(label).min_size(egui::Vec2::splat(BUTTON_SIZE)))
+                    .on_hover_text(map.describe(action, &locale))
+                    .clicked()
+            };
+            let play = if paused.0 {
+                "controls-play"
+            } else {
+                "controls-pause"
+            };
+            if button(ui, play, Action::Pause) {
+                paused.0 = !paused.0;
+                scheduler.cancel();
+            }
+            if button(ui, "controls-step", Action::Step) && paused.0 {
+                scheduler.request(**stride);
+            }
+            if button(ui, "controls-slower", Action::Slower) {
+                speed.scale(0.5);
+            }
+            if button(ui, "controls-faster", Action::Faster) {
+                speed.scale(2.0);
+            }
+            if button(ui, "controls-unlimited", Action::Unlimited) {
+                speed.toggle_unlimited();
+            }
+            if button(ui, "controls-shorter-stride", Action::ShorterStride) {
+                stride.halve();
+            }
+            if button(ui, "controls-longer-stride", Action::LongerStride) {
+                stride.double();
+            }
+            if button(ui, "controls-theme", Action::CycleTheme) {
+                themes.next();
+            }
+            let mut language = locale.language();
+            egui::ComboBox::from_label(locale.text("controls-language"))
+                .selected_text(language.native_name())
+                .show_ui(ui, |ui| {
+                    for option in Language::ALL {
+                        ui.selectable_value(&mut language, option, option.native_name());
+                    }
+                });
+            if language != locale.language() {
+                locale.0 = Localizer::new(language);
+            }
+        });
+    });
+}
@@ -0,0 +1,146 @@
+use super::{
+    camera::MainCamera,
+    input::Action,
+    presets::Presets,
+    render::CELL_SIZE,
+    simulation::{Paused, SimulationSet},
+    Simulation,
+};
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::{
+    demo::{Demo, Event},
+    Generation,
+};
+use std::env;
+
+/// Command line flag starting the app in the demo, e.g. on kiosk displays
+const DEMO_FLAG: &str = "--demo";
+/// Generations each preset or random rule is shown for
+const SCENE_LENGTH: u64 = 2000;
+/// Screen pixels per Cell while the demo runs
+const DEMO_CELL_PIXELS: f32 = 4.0;
+
+/// The running demo, created once the presets are available
+#[derive(Resource, Default)]
+struct DemoMode {
+    active: bool,
+    demo: Option<Demo>,
+    /// Name of the preset or rule shown
+    scene: String,
+    /// Generation the demo last looked at, so it doesn't see paused generations repeatedly
+    last: Option<Generation>,
+}
+
+/// `F11` or starting the app with `--demo` goes fullscreen with a grid filling the screen,
+/// cycling through the presets and random interesting rules and reseeding when it goes quiet
+pub struct DemoPlugin;
+
+impl Plugin for DemoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DemoMode>()
+            .add_startup_system(start_from_command_line)
+            .add_system(toggle_demo.before(run_demo))
+            .add_system(run_demo.in_set(SimulationSet::Edit))
+            .add_system(demo_ui);
+    }
+}
+
+fn set_active(mode: &mut DemoMode, window: &mut Window, active: bool) {
+    mode.active = active;
+    mode.demo = None;
+    mode.last = None;
+    window.mode = if active {
+        WindowMode::BorderlessFullscreen
+    } else {
+        WindowMode::Windowed
+    };
+}
+
+fn start_from_command_line(
+    mut mode: ResMut<DemoMode>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if let Ok(mut window) = windows.get_single_mut() {
+        if env::args().any(|arg| arg == DEMO_FLAG) {
+            set_active(&mut mode, &mut window, true);
+        }
+    }
+}
+
+fn toggle_demo(
+    actions: Res<Input<Action>>,
+    mut mode: ResMut<DemoMode>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !actions.just_pressed(Action::Demo) {
+        return;
+    }
+    if let Ok(mut window) = windows.get_single_mut() {
+        let active = !mode.active;
+        set_active(&mut mode, &mut window, active);
+    }
+}
+
+/// Resizes the grid to fill the window and ticks the demo with every rendered generation
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn run_demo(
+    mut mode: ResMut<DemoMode>,
+    presets: Res<Presets>,
+    mut paused: ResMut<Paused>,
+    mut simulation: ResMut<Simulation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    if !mode.active {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let mode = &mut *mode;
+    let demo = mode.demo.get_or_insert_with(|| {
+        paused.0 = false;
+        let presets = presets.presets().map(|(_, preset)| preset).collect();
+        Demo::new(presets, SCENE_LENGTH, rand::random())
+    });
+    let row_count = (window.height() / DEMO_CELL_PIXELS).ceil() as usize;
+    let col_count = (window.width() / DEMO_CELL_PIXELS).ceil() as usize;
+    if (simulation.row_count, simulation.col_count) != (row_count, col_count) {
+        simulation.row_count = row_count;
+        simulation.col_count = col_count;
+        demo.reseed(&mut simulation);
+        // The grid is centered around the origin
+        for (mut transform, mut projection) in &mut cameras {
+            transform.translation = Vec3::new(0.0, 0.0, transform.translation.z);
+            projection.scale = CELL_SIZE / DEMO_CELL_PIXELS;
+        }
+    }
+    if mode.last == Some(simulation.generation) {
+        return;
+    }
+    if let Some(Event::Scene(preset)) = demo.tick(&mut simulation) {
+        info!("Demo shows {} ({})", preset.name, preset.rule_set);
+        mode.scene = preset.name;
+    }
+    mode.last = Some(simulation.generation);
+}
+
+fn demo_ui(mut contexts: EguiContexts, mode: Res<DemoMode>) {
+    if !mode.active {
+        return;
+    }
+    egui::Area::new("demo")
+        .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(16.0, -16.0))
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(
+                egui::RichText::new(&mode.scene)
+                    .size(24.0)
+                    .color(egui::Color32::WHITE),
+            );
+        });
+}
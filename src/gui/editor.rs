@@ -0,0 +1,487 @@
+use super::{
+    camera::MainCamera,
+    input::Action,
+    localization::Locale,
+    render::{cell_at, cell_center, CellProjection, CELL_SIZE},
+    simulation::SimulationSet,
+    Simulation,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use arboard::Clipboard;
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::{
+    brush::{stroke, Brush},
+    figure::Figure,
+    rle, Cell, CellAccess, ColIdx, Grid, RowIdx, Symmetry,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::error::Error;
+use std::ops::RangeInclusive;
+
+/// Fingers lifted less than this many pixels away from where they touched down tap
+const TAP_SLOP: f32 = 10.0;
+
+/// The Cell currently under the mouse cursor, `None` while the cursor is over the UI
+#[derive(Resource, Default)]
+pub struct HoveredCell(pub Option<(usize, usize)>);
+
+/// Rectangle of Cells spanned by dragging with the right mouse button
+#[derive(Resource, Default)]
+pub struct Selection {
+    anchor: Option<(usize, usize)>,
+    end: Option<(usize, usize)>,
+}
+
+impl Selection {
+    /// Inclusive row and column bounds of the selection
+    pub fn bounds(&self) -> Option<(RangeInclusive<usize>, RangeInclusive<usize>)> {
+        let ((anchor_row, anchor_col), (end_row, end_col)) = self.anchor.zip(self.end)?;
+        Some((
+            anchor_row.min(end_row)..=anchor_row.max(end_row),
+            anchor_col.min(end_col)..=anchor_col.max(end_col),
+        ))
+    }
+}
+
+/// Symmetry every toggled Cell is mirrored across, around the center of the grid.
+/// `M` cycles through no symmetry and every group.
+#[derive(Resource, Default)]
+pub struct EditSymmetry(pub Option<Symmetry>);
+
+impl EditSymmetry {
+    fn next(&mut self) {
+        self.0 = match self.0 {
+            None => Some(Symmetry::C2),
+            Some(Symmetry::C2) => Some(Symmetry::C4),
+            Some(Symmetry::C4) => Some(Symmetry::D2),
+            Some(Symmetry::D2) => Some(Symmetry::D4),
+            Some(Symmetry::D4) => Some(Symmetry::D8),
+            Some(Symmetry::D8) => None,
+        };
+    }
+}
+
+/// Pattern the next left click stamps centered on the hovered Cell instead of toggling it.
+/// Holding `Shift` keeps it armed for further stamps, `Delete` disarms it.
+#[derive(Resource, Default)]
+pub struct ArmedPattern(pub Option<Grid>);
+
+/// What `Action::Draw` does unless there's an `ArmedPattern` to stamp,
+/// `B` switches to the brush and back and `R` cycles through the figures
+/// - `Toggle` => Toggles the clicked Cell
+/// - `Brush` => Paints with the `BrushTool` while held
+/// - `Figure` => Draws the figure dragged out from where it's pressed to where it's released
+#[derive(Resource, Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Tool {
+    #[default]
+    Toggle,
+    Brush,
+    Figure(Figure),
+}
+
+impl Tool {
+    pub const fn next_figure(self) -> Self {
+        match self {
+            Self::Figure(Figure::Line) => Self::Figure(Figure::Rectangle),
+            Self::Figure(Figure::Rectangle) => Self::Figure(Figure::Circle),
+            Self::Figure(Figure::Circle) => Self::Toggle,
+            Self::Toggle | Self::Brush => Self::Figure(Figure::Line),
+        }
+    }
+}
+
+/// Brush of `Tool::Brush`
+#[derive(Resource)]
+pub struct BrushTool {
+    pub brush: Brush,
+    /// State painted and drawn Cells take on, `X` swaps between living and dead
+    pub paint: Cell,
+}
+
+impl Default for BrushTool {
+    fn default() -> Self {
+        Self {
+            brush: Brush::default(),
+            paint: Cell::Alive,
+        }
+    }
+}
+
+/// Figure of `Tool::Figure` being dragged out
+#[derive(Resource, Default)]
+pub struct FigureTool {
+    pub filled: bool,
+    anchor: Option<(usize, usize)>,
+    end: Option<(usize, usize)>,
+}
+
+impl FigureTool {
+    /// Cells `figure` would cover if released now, empty while not dragging
+    pub fn pending(
+        &self,
+        figure: Figure,
+        row_count: usize,
+        col_count: usize,
+    ) -> Vec<(usize, usize)> {
+        self.anchor
+            .zip(self.end)
+            .map_or_else(Vec::new, |(anchor, end)| {
+                figure.cells(anchor, end, self.filled, row_count, col_count)
+            })
+    }
+}
+
+#[derive(Component)]
+struct SelectionOutline;
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HoveredCell>()
+            .init_resource::<Selection>()
+            .init_resource::<EditSymmetry>()
+            .init_resource::<ArmedPattern>()
+            .init_resource::<Tool>()
+            .init_resource::<BrushTool>()
+            .init_resource::<FigureTool>()
+            .add_startup_system(spawn_selection_outline)
+            .add_systems((track_cursor, cycle_edit_symmetry))
+            .add_system(tap.after(track_cursor))
+            .add_systems(
+                (
+                    paint.in_set(SimulationSet::Edit).before(toggle_cell),
+                    draw_figure.in_set(SimulationSet::Edit).before(toggle_cell),
+                    toggle_cell.in_set(SimulationSet::Edit),
+                    select,
+                )
+                    .after(tap)
+                    .after(cycle_edit_symmetry),
+            )
+            .add_system(update_selection_outline.after(select))
+            .add_system(hover_readout.after(track_cursor));
+        // There's no synchronous clipboard access on the web
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_system(clipboard.after(track_cursor).in_set(SimulationSet::Edit));
+    }
+}
+
+fn track_cursor(
+    mut contexts: EguiContexts,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    simulation: Res<Simulation>,
+    projection: Res<CellProjection>,
+    mut hovered: ResMut<HoveredCell>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    if contexts.ctx_mut().is_pointer_over_area() {
+        hovered.0 = None;
+        return;
+    }
+    hovered.0 = window
+        .cursor_position()
+        .and_then(|position| camera.viewport_to_world(camera_transform, position))
+        .and_then(|ray| cell_at(&simulation, projection.0, ray.origin.truncate()));
+}
+
+/// Lifting a finger that didn't move acts like `Action::Draw` on the Cell under it,
+/// unless another finger touched the screen meanwhile for a pinch or pan
+#[allow(clippy::too_many_arguments)]
+fn tap(
+    touches: Res<Touches>,
+    mut contexts: EguiContexts,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    simulation: Res<Simulation>,
+    projection: Res<CellProjection>,
+    mut multi_touch: Local<bool>,
+    mut hovered: ResMut<HoveredCell>,
+    mut actions: ResMut<Input<Action>>,
+) {
+    if touches.iter().nth(1).is_some() {
+        *multi_touch = true;
+    }
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    for touch in touches.iter_just_released() {
+        if *multi_touch
+            || touch.distance().length() >= TAP_SLOP
+            || contexts.ctx_mut().is_pointer_over_area()
+        {
+            continue;
+        }
+        // Touch positions are in the same space as the cursor
+        let cell = camera
+            .viewport_to_world(camera_transform, touch.position())
+            .and_then(|ray| cell_at(&simulation, projection.0, ray.origin.truncate()));
+        if cell.is_some() {
+            hovered.0 = cell;
+            actions.press(Action::Draw);
+        }
+    }
+    if touches.iter().next().is_none() {
+        *multi_touch = false;
+    }
+}
+
+fn cycle_edit_symmetry(actions: Res<Input<Action>>, mut symmetry: ResMut<EditSymmetry>) {
+    if actions.just_pressed(Action::CycleSymmetry) {
+        symmetry.next();
+    }
+}
+
+/// Sets the images of the Cells under the `EditSymmetry` to `state`
+fn set_cells(
+    simulation: &mut Simulation,
+    symmetry: &EditSymmetry,
+    cells: impl IntoIterator<Item = (usize, usize)>,
+    state: &Cell,
+) {
+    let (row_count, col_count) = (simulation.row_count, simulation.col_count);
+    for (row, col) in cells {
+        let images = symmetry.0.map_or_else(
+            || vec![(row, col)],
+            |symmetry| symmetry.images(row_count, col_count, row, col),
+        );
+        for (row, col) in images {
+            if let Some(cell) = simulation.grid.cell_mut(RowIdx(row), ColIdx(col)) {
+                *cell = state.clone();
+            }
+        }
+    }
+}
+
+/// Dabs the `BrushTool` along the path of the cursor since the last frame,
+/// so fast strokes don't leave gaps. A spray keeps spraying while held still.
+fn paint(
+    actions: Res<Input<Action>>,
+    hovered: Res<HoveredCell>,
+    symmetry: Res<EditSymmetry>,
+    armed: Res<ArmedPattern>,
+    tool: Res<Tool>,
+    brush: Res<BrushTool>,
+    mut previous: Local<Option<(usize, usize)>>,
+    mut simulation: ResMut<Simulation>,
+) {
+    let Some(cell) = hovered.0 else {
+        *previous = None;
+        return;
+    };
+    if *tool != Tool::Brush || armed.0.is_some() || !actions.pressed(Action::Draw) {
+        *previous = None;
+        return;
+    }
+    let from = previous.unwrap_or(cell);
+    *previous = Some(cell);
+    let (row_count, col_count) = (simulation.row_count, simulation.col_count);
+    let mut rng = rand::thread_rng();
+    let dabbed = stroke(from, cell)
+        .into_iter()
+        .flat_map(|(row, col)| brush.brush.dab(row, col, row_count, col_count, &mut rng))
+        .collect::<Vec<_>>();
+    set_cells(&mut simulation, &symmetry, dabbed, &brush.paint);
+}
+
+/// Pressing `Action::Draw` anchors the figure at the hovered Cell,
+/// releasing it draws the figure in the paint of the `BrushTool`
+#[allow(clippy::too_many_arguments)]
+fn draw_figure(
+    actions: Res<Input<Action>>,
+    hovered: Res<HoveredCell>,
+    symmetry: Res<EditSymmetry>,
+    armed: Res<ArmedPattern>,
+    tool: Res<Tool>,
+    brush: Res<BrushTool>,
+    mut figure: ResMut<FigureTool>,
+    mut simulation: ResMut<Simulation>,
+) {
+    let Tool::Figure(shape) = *tool else {
+        figure.anchor = None;
+        return;
+    };
+    if armed.0.is_some() {
+        figure.anchor = None;
+        return;
+    }
+    if actions.just_pressed(Action::Draw) {
+        figure.anchor = hovered.0;
+        figure.end = hovered.0;
+    } else if actions.pressed(Action::Draw) && hovered.0.is_some() {
+        figure.end = hovered.0;
+    }
+    if actions.just_released(Action::Draw) {
+        let cells = figure.pending(shape, simulation.row_count, simulation.col_count);
+        set_cells(&mut simulation, &symmetry, cells, &brush.paint);
+        figure.anchor = None;
+    }
+}
+
+/// The images of the hovered Cell under the `EditSymmetry` take on its new state,
+/// unless there's an `ArmedPattern` to stamp or another `Tool` is used
+fn toggle_cell(
+    actions: Res<Input<Action>>,
+    keys: Res<Input<KeyCode>>,
+    hovered: Res<HoveredCell>,
+    symmetry: Res<EditSymmetry>,
+    tool: Res<Tool>,
+    mut armed: ResMut<ArmedPattern>,
+    mut simulation: ResMut<Simulation>,
+) {
+    if actions.just_pressed(Action::Disarm) {
+        armed.0 = None;
+    }
+    let Some((row, col)) = hovered.0 else {
+        return;
+    };
+    if !actions.just_pressed(Action::Draw) {
+        return;
+    }
+    if let Some(pattern) = &armed.0 {
+        let height = pattern.len();
+        let width = pattern.first().map_or(0, Vec::len);
+        simulation.stamp(
+            pattern,
+            row.saturating_sub(height / 2),
+            col.saturating_sub(width / 2),
+        );
+        if !keys.any_pressed([KeyCode::LShift, KeyCode::RShift]) {
+            armed.0 = None;
+        }
+        return;
+    }
+    if *tool != Tool::Toggle {
+        return;
+    }
+    let Some(cell) = simulation.grid.cell(RowIdx(row), ColIdx(col)) else {
+        return;
+    };
+    let toggled = if cell.is_dead() {
+        Cell::Alive
+    } else {
+        Cell::Dead
+    };
+    set_cells(&mut simulation, &symmetry, [(row, col)], &toggled);
+}
+
+fn select(
+    actions: Res<Input<Action>>,
+    hovered: Res<HoveredCell>,
+    mut selection: ResMut<Selection>,
+) {
+    let Some(cell) = hovered.0 else {
+        return;
+    };
+    if actions.just_pressed(Action::Select) {
+        selection.anchor = Some(cell);
+        selection.end = Some(cell);
+    } else if actions.pressed(Action::Select) {
+        selection.end = Some(cell);
+    }
+}
+
+/// `Ctrl+V` pastes RLE from the clipboard at the hovered Cell,
+/// `Ctrl+C` copies the selection to the clipboard as RLE
+#[cfg(not(target_arch = "wasm32"))]
+fn clipboard(
+    keys: Res<Input<KeyCode>>,
+    hovered: Res<HoveredCell>,
+    selection: Res<Selection>,
+    mut simulation: ResMut<Simulation>,
+) {
+    if !keys.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        return;
+    }
+    if keys.just_pressed(KeyCode::V) {
+        let (row, col) = hovered.0.unwrap_or_default();
+        if let Err(e) = paste_rle(&mut simulation, row, col) {
+            warn!("Couldn't paste RLE: {e}");
+        }
+    } else if keys.just_pressed(KeyCode::C) {
+        if let Err(e) = copy_rle(&simulation, &selection) {
+            warn!("Couldn't copy RLE: {e}");
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn paste_rle(simulation: &mut Simulation, row: usize, col: usize) -> Result<(), Box<dyn Error>> {
+    let pattern = rle::parse(&Clipboard::new()?.get_text()?)?;
+    simulation.stamp(&pattern, row, col);
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn copy_rle(simulation: &Simulation, selection: &Selection) -> Result<(), Box<dyn Error>> {
+    let Some((rows, cols)) = selection.bounds() else {
+        return Err("Nothing is selected".into());
+    };
+    Clipboard::new()?.set_text(rle::write(&simulation.region(rows, cols)))?;
+    Ok(())
+}
+
+fn spawn_selection_outline(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0.2, 0.6, 1.0, 0.3),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        SelectionOutline,
+    ));
+}
+
+fn update_selection_outline(
+    selection: Res<Selection>,
+    simulation: Res<Simulation>,
+    mut outline: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<SelectionOutline>>,
+) {
+    let Ok((mut sprite, mut transform, mut visibility)) = outline.get_single_mut() else {
+        return;
+    };
+    let Some((rows, cols)) = selection.bounds() else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    let top_left = cell_center(&simulation, *rows.start(), *cols.start());
+    let bottom_right = cell_center(&simulation, *rows.end(), *cols.end());
+    sprite.custom_size = Some((bottom_right - top_left).abs() + CELL_SIZE);
+    transform.translation = ((top_left + bottom_right) / 2.0).extend(2.0);
+    *visibility = Visibility::Visible;
+}
+
+/// Shows the coordinates and state of the hovered Cell in the bottom left corner
+fn hover_readout(
+    mut contexts: EguiContexts,
+    hovered: Res<HoveredCell>,
+    simulation: Res<Simulation>,
+    locale: Res<Locale>,
+) {
+    let Some((row, col)) = hovered.0 else {
+        return;
+    };
+    let Some(cell) = simulation.grid.cell(RowIdx(row), ColIdx(col)) else {
+        return;
+    };
+    egui::Area::new("hover_readout")
+        .anchor(egui::Align2::LEFT_BOTTOM, [8.0, -8.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(locale.format(
+                "hover-cell",
+                [
+                    ("row", row.into()),
+                    ("col", col.into()),
+                    ("cell", cell.to_string().into()),
+                ],
+            ));
+        });
+}
@@ -0,0 +1,371 @@
+use super::{
+    input::Action,
+    lod::LevelOfDetail,
+    render::{
+        pixel_image, GridLines, RenderMode, Trails, CELL_SIZE, GRID_LINE_WIDTH, TRAIL_LENGTH,
+    },
+    theme::{color, Themes},
+    Simulation,
+};
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::clear_color::ClearColorConfig,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            AsBindGroup, Extent3d, ShaderRef, ShaderType, TextureDimension, TextureFormat,
+            TextureUsages,
+        },
+        view::RenderLayers,
+    },
+    sprite::{Material2d, Material2dPlugin, MaterialMesh2dBundle, Mesh2dHandle},
+};
+use cellular_automata::{Cell, Theme};
+
+const CELLS_SHADER: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 0x6b1f_04c2_9d3a_5e77);
+const AGES_SHADER: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 0x2e8d_71a0_c4b9_f013);
+/// Layer of the offscreen pass updating the ages, which the main camera doesn't see
+const AGES_LAYER: u8 = 1;
+
+/// Whether the Cells are colored by a fragment shader instead of on the CPU, toggled with `U`.
+/// The grid is only uploaded once per generation as one byte per Cell, so the cost of
+/// rendering doesn't depend on the number of Cells. Trails and grid lines are drawn by the
+/// shader as well, only `RenderMode::States` of individual Cells is supported.
+#[derive(Resource, Default)]
+pub struct GpuRendering(pub bool);
+
+impl GpuRendering {
+    pub fn active(&self, mode: RenderMode, lod: &LevelOfDetail) -> bool {
+        self.0 && mode == RenderMode::States && lod.0 == 1
+    }
+}
+
+/// Input of `cells.wgsl`
+#[derive(ShaderType, Debug, Clone)]
+struct CellStyle {
+    trail: Vec4,
+    grid_line: Vec4,
+    trail_length: u32,
+    grid_line_width: f32,
+}
+
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "0d4c8a51-7f2e-4b93-a6d0-3c1e9b58f247"]
+struct CellMaterial {
+    #[uniform(0)]
+    style: CellStyle,
+    #[texture(1)]
+    states: Handle<Image>,
+    #[texture(2)]
+    ages: Handle<Image>,
+    #[texture(3)]
+    palette: Handle<Image>,
+}
+
+impl Material2d for CellMaterial {
+    fn fragment_shader() -> ShaderRef {
+        CELLS_SHADER.typed().into()
+    }
+}
+
+/// Input of `ages.wgsl`
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "8a27e3f6-19b4-4c05-bd7e-52f0a6c3d918"]
+struct AgeMaterial {
+    #[texture(0)]
+    states: Handle<Image>,
+    #[texture(1)]
+    previous: Handle<Image>,
+}
+
+impl Material2d for AgeMaterial {
+    fn fragment_shader() -> ShaderRef {
+        AGES_SHADER.typed().into()
+    }
+}
+
+/// Textures and materials shared by the visible quad and the offscreen pass
+#[derive(Resource)]
+struct GpuCells {
+    /// `Cell::state_byte`s, uploaded once per generation
+    states: Handle<Image>,
+    /// Generations since every Cell was last alive, rendered into one from the other
+    ages: [Handle<Image>; 2],
+    /// Index into `ages` of the current generation
+    latest: usize,
+    palette: Handle<Image>,
+    /// Decay the palette was made for
+    decay: usize,
+    cells: Handle<CellMaterial>,
+    aging: Handle<AgeMaterial>,
+}
+
+/// Marks the quad showing the Cells
+#[derive(Component)]
+struct CellQuad;
+
+/// Marks the quad and the camera of the offscreen pass
+#[derive(Component)]
+struct AgePass;
+
+pub struct GpuPlugin;
+
+impl Plugin for GpuPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, CELLS_SHADER, "cells.wgsl", Shader::from_wgsl);
+        load_internal_asset!(app, AGES_SHADER, "ages.wgsl", Shader::from_wgsl);
+        app.add_plugin(Material2dPlugin::<CellMaterial>::default())
+            .add_plugin(Material2dPlugin::<AgeMaterial>::default())
+            .init_resource::<GpuRendering>()
+            .add_startup_system(spawn_quads)
+            .add_system(toggle_gpu_rendering)
+            .add_system(restyle.after(toggle_gpu_rendering))
+            .add_system(upload_states.after(toggle_gpu_rendering));
+    }
+}
+
+/// Image of `Cell::state_byte`s, a single channel is enough
+fn state_image(simulation: &Simulation) -> Image {
+    let data = simulation
+        .grid
+        .iter()
+        .flatten()
+        .map(Cell::state_byte)
+        .collect();
+    Image::new(
+        extent(simulation),
+        TextureDimension::D2,
+        data,
+        TextureFormat::R8Unorm,
+    )
+}
+
+/// Render target of the ages, every Cell starts out as long dead
+fn age_image(simulation: &Simulation) -> Image {
+    let mut image = Image::new_fill(
+        extent(simulation),
+        TextureDimension::D2,
+        &[u8::MAX, 0, 0, u8::MAX],
+        TextureFormat::bevy_default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn extent(simulation: &Simulation) -> Extent3d {
+    Extent3d {
+        width: simulation.col_count.max(1) as u32,
+        height: simulation.row_count.max(1) as u32,
+        depth_or_array_layers: 1,
+    }
+}
+
+/// Color of every state byte
+fn palette(theme: &Theme, decay: usize) -> Image {
+    let data = (0..=u8::MAX)
+        .flat_map(|state| {
+            let cell = Cell::from_state_number(usize::from(state));
+            color(theme.cell_color(&cell, decay)).as_rgba_u8()
+        })
+        .collect();
+    pixel_image(u32::from(u8::MAX) + 1, 1, data)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn style(theme: &Theme, trails: bool, grid_lines: bool) -> CellStyle {
+    CellStyle {
+        trail: Vec4::from_array(color(theme.trail).as_linear_rgba_f32()),
+        grid_line: Vec4::from_array(color(theme.grid_line).as_linear_rgba_f32()),
+        trail_length: if trails { TRAIL_LENGTH as u32 } else { 0 },
+        grid_line_width: if grid_lines {
+            GRID_LINE_WIDTH as f32 / CELL_SIZE
+        } else {
+            0.0
+        },
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn grid_size(simulation: &Simulation) -> Vec2 {
+    Vec2::new(simulation.col_count as f32, simulation.row_count as f32)
+}
+
+fn spawn_quads(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut cell_materials: ResMut<Assets<CellMaterial>>,
+    mut age_materials: ResMut<Assets<AgeMaterial>>,
+    simulation: Res<Simulation>,
+    themes: Res<Themes>,
+) {
+    let states = images.add(state_image(&simulation));
+    let ages = [
+        images.add(age_image(&simulation)),
+        images.add(age_image(&simulation)),
+    ];
+    let palette = images.add(palette(themes.current(), simulation.rule_set.decay));
+    let cells = cell_materials.add(CellMaterial {
+        style: style(themes.current(), false, true),
+        states: states.clone(),
+        ages: ages[0].clone(),
+        palette: palette.clone(),
+    });
+    let aging = age_materials.add(AgeMaterial {
+        states: states.clone(),
+        previous: ages[0].clone(),
+    });
+    let size = grid_size(&simulation);
+    // Between the Cells drawn on the CPU and the grid line overlay, which is hidden meanwhile
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(shape::Quad::new(size * CELL_SIZE).into()).into(),
+            material: cells.clone(),
+            transform: Transform::from_xyz(0.0, 0.0, 0.5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        CellQuad,
+    ));
+    // One pixel of the target per Cell
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(shape::Quad::new(size).into()).into(),
+            material: aging.clone(),
+            ..default()
+        },
+        RenderLayers::layer(AGES_LAYER),
+        AgePass,
+    ));
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                target: RenderTarget::Image(ages[1].clone()),
+                order: -1,
+                is_active: false,
+                ..default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::None,
+            },
+            ..default()
+        },
+        UiCameraConfig { show_ui: false },
+        RenderLayers::layer(AGES_LAYER),
+        AgePass,
+    ));
+    commands.insert_resource(GpuCells {
+        states,
+        ages,
+        latest: 0,
+        palette,
+        decay: simulation.rule_set.decay,
+        cells,
+        aging,
+    });
+}
+
+fn toggle_gpu_rendering(actions: Res<Input<Action>>, mut gpu: ResMut<GpuRendering>) {
+    if actions.just_pressed(Action::GpuRendering) {
+        gpu.0 = !gpu.0;
+    }
+}
+
+fn restyle(
+    simulation: Res<Simulation>,
+    themes: Res<Themes>,
+    trails: Res<Trails>,
+    grid_lines: Res<GridLines>,
+    mut gpu_cells: ResMut<GpuCells>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<CellMaterial>>,
+) {
+    let decay = simulation.rule_set.decay;
+    let restyled = themes.is_changed() || trails.is_changed() || grid_lines.is_changed();
+    if !restyled && gpu_cells.decay == decay {
+        return;
+    }
+    gpu_cells.decay = decay;
+    if let Some(image) = images.get_mut(&gpu_cells.palette) {
+        *image = palette(themes.current(), decay);
+    }
+    if let Some(material) = materials.get_mut(&gpu_cells.cells) {
+        material.style = style(themes.current(), trails.enabled, grid_lines.0);
+    }
+}
+
+/// Uploads the grid after every generation and renders the next ages into the other
+/// texture of the pair, which the visible quad shows from then on
+#[allow(clippy::too_many_arguments)]
+fn upload_states(
+    simulation: Res<Simulation>,
+    gpu: Res<GpuRendering>,
+    mode: Res<RenderMode>,
+    lod: Res<LevelOfDetail>,
+    mut gpu_cells: ResMut<GpuCells>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut cell_materials: ResMut<Assets<CellMaterial>>,
+    mut age_materials: ResMut<Assets<AgeMaterial>>,
+    mut quads: Query<(&mut Visibility, &Mesh2dHandle), With<CellQuad>>,
+    age_quads: Query<&Mesh2dHandle, With<AgePass>>,
+    mut age_cameras: Query<&mut Camera, With<AgePass>>,
+) {
+    let active = gpu.active(*mode, &lod);
+    let Ok(mut camera) = age_cameras.get_single_mut() else {
+        return;
+    };
+    camera.is_active = false;
+    let Ok((mut visibility, quad)) = quads.get_single_mut() else {
+        return;
+    };
+    *visibility = if active {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    // Ages keep counting frames with a new generation like `Trail` does on the CPU
+    if !active || !(simulation.is_changed() || gpu.is_changed() || mode.is_changed()) {
+        return;
+    }
+
+    let resized = images
+        .get(&gpu_cells.states)
+        .is_none_or(|image| image.texture_descriptor.size != extent(&simulation));
+    if resized {
+        for handle in &gpu_cells.ages {
+            if let Some(image) = images.get_mut(handle) {
+                *image = age_image(&simulation);
+            }
+        }
+        let size = grid_size(&simulation);
+        if let Some(mesh) = meshes.get_mut(&quad.0) {
+            *mesh = shape::Quad::new(size * CELL_SIZE).into();
+        }
+        for age_quad in &age_quads {
+            if let Some(mesh) = meshes.get_mut(&age_quad.0) {
+                *mesh = shape::Quad::new(size).into();
+            }
+        }
+    }
+    if let Some(image) = images.get_mut(&gpu_cells.states) {
+        *image = state_image(&simulation);
+    }
+
+    let (previous, next) = (gpu_cells.latest, 1 - gpu_cells.latest);
+    camera.target = RenderTarget::Image(gpu_cells.ages[next].clone());
+    camera.is_active = true;
+    if let Some(material) = age_materials.get_mut(&gpu_cells.aging) {
+        material.previous = gpu_cells.ages[previous].clone();
+    }
+    if let Some(material) = cell_materials.get_mut(&gpu_cells.cells) {
+        material.ages = gpu_cells.ages[next].clone();
+    }
+    gpu_cells.latest = next;
+}
@@ -0,0 +1,337 @@
+use super::localization::Locale;
+use bevy::{input::InputSystem, prelude::*, utils::HashMap};
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::localization::Localizer;
+use serde::Deserialize;
+use std::{collections::HashMap as StdHashMap, fs, io};
+
+/// Read at startup, every action listed replaces its default bindings, e.g.
+/// ```toml
+/// pause = [{ key = "P" }, { gamepad = "Start" }]
+/// draw = [{ mouse = "Left" }]
+/// zoom_in = [{ key = "PageUp" }, { gamepad = "RightTrigger2" }]
+/// ```
+const INPUT_FILE: &str = "input.toml";
+
+/// Everything the app does on a key press or mouse or gamepad button,
+/// systems read them from `Input<Action>` instead of the raw input.
+/// `Ctrl` and `Shift` combinations aren't remappable.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Action {
+    Pause,
+    Step,
+    Rewind,
+    Faster,
+    Slower,
+    Unlimited,
+    LongerStride,
+    ShorterStride,
+    AutoGrow,
+    /// Toggling Cells and stamping patterns
+    Draw,
+    Select,
+    CycleSymmetry,
+    Disarm,
+    /// Switching between toggling Cells and painting with the brush
+    Brush,
+    BrushLarger,
+    BrushSmaller,
+    /// Cycling through drawing lines, rectangles and circles and toggling Cells
+    CycleFigure,
+    /// Switching the brush and figures between painting living and dead Cells
+    SwapPaint,
+    /// Panning while held and moving the mouse
+    Drag,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    ZoomIn,
+    ZoomOut,
+    Fit,
+    CycleTheme,
+    CycleRenderMode,
+    CycleProjection,
+    GridLines,
+    Trails,
+    CrossFade,
+    Ships,
+    GpuRendering,
+    /// Saving the viewport as PNG, or copying it to the clipboard with `Ctrl`
+    Screenshot,
+    /// Entering and leaving the fullscreen demo cycling through rules by itself
+    Demo,
+    /// Silencing and unsilencing the sound effects
+    Mute,
+}
+
+impl Action {
+    pub const ALL: [Self; 37] = [
+        Self::Pause,
+        Self::Step,
+        Self::Rewind,
+        Self::Faster,
+        Self::Slower,
+        Self::Unlimited,
+        Self::LongerStride,
+        Self::ShorterStride,
+        Self::AutoGrow,
+        Self::Draw,
+        Self::Select,
+        Self::CycleSymmetry,
+        Self::Disarm,
+        Self::Brush,
+        Self::BrushLarger,
+        Self::BrushSmaller,
+        Self::CycleFigure,
+        Self::SwapPaint,
+        Self::Drag,
+        Self::PanLeft,
+        Self::PanRight,
+        Self::PanUp,
+        Self::PanDown,
+        Self::ZoomIn,
+        Self::ZoomOut,
+        Self::Fit,
+        Self::CycleTheme,
+        Self::CycleRenderMode,
+        Self::CycleProjection,
+        Self::GridLines,
+        Self::Trails,
+        Self::CrossFade,
+        Self::Ships,
+        Self::GpuRendering,
+        Self::Screenshot,
+        Self::Demo,
+        Self::Mute,
+    ];
+
+    /// Name in `INPUT_FILE`
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Step => "step",
+            Self::Rewind => "rewind",
+            Self::Faster => "faster",
+            Self::Slower => "slower",
+            Self::Unlimited => "unlimited",
+            Self::LongerStride => "longer_stride",
+            Self::ShorterStride => "shorter_stride",
+            Self::AutoGrow => "auto_grow",
+            Self::Draw => "draw",
+            Self::Select => "select",
+            Self::CycleSymmetry => "cycle_symmetry",
+            Self::Disarm => "disarm",
+            Self::Brush => "brush",
+            Self::BrushLarger => "brush_larger",
+            Self::BrushSmaller => "brush_smaller",
+            Self::CycleFigure => "cycle_figure",
+            Self::SwapPaint => "swap_paint",
+            Self::Drag => "drag",
+            Self::PanLeft => "pan_left",
+            Self::PanRight => "pan_right",
+            Self::PanUp => "pan_up",
+            Self::PanDown => "pan_down",
+            Self::ZoomIn => "zoom_in",
+            Self::ZoomOut => "zoom_out",
+            Self::Fit => "fit",
+            Self::CycleTheme => "cycle_theme",
+            Self::CycleRenderMode => "cycle_render_mode",
+            Self::CycleProjection => "cycle_projection",
+            Self::GridLines => "grid_lines",
+            Self::Trails => "trails",
+            Self::CrossFade => "cross_fade",
+            Self::Ships => "ships",
+            Self::GpuRendering => "gpu_rendering",
+            Self::Screenshot => "screenshot",
+            Self::Demo => "demo",
+            Self::Mute => "mute",
+        }
+    }
+
+    /// The gamepad bindings are laid out for demos from the couch
+    fn default_bindings(self) -> Vec<Binding> {
+        use Binding::{Gamepad, Key, Mouse};
+        use GamepadButtonType as Button;
+        match self {
+            Self::Pause => vec![Key(KeyCode::Space), Gamepad(Button::South)],
+            Self::Step => vec![Key(KeyCode::N), Gamepad(Button::East)],
+            Self::Rewind => vec![Key(KeyCode::Back), Gamepad(Button::West)],
+            Self::Faster => vec![
+                Key(KeyCode::Equals),
+                Key(KeyCode::NumpadAdd),
+                Gamepad(Button::RightTrigger),
+            ],
+            Self::Slower => vec![
+                Key(KeyCode::Minus),
+                Key(KeyCode::NumpadSubtract),
+                Gamepad(Button::LeftTrigger),
+            ],
+            Self::Unlimited => vec![Key(KeyCode::Key0)],
+            Self::LongerStride => vec![Key(KeyCode::BracketRight)],
+            Self::ShorterStride => vec![Key(KeyCode::BracketLeft)],
+            Self::AutoGrow => vec![Key(KeyCode::E)],
+            Self::Draw => vec![Mouse(MouseButton::Left)],
+            Self::Select => vec![Mouse(MouseButton::Right)],
+            Self::CycleSymmetry => vec![Key(KeyCode::M)],
+            Self::Disarm => vec![Key(KeyCode::Delete)],
+            Self::Brush => vec![Key(KeyCode::B)],
+            Self::BrushLarger => vec![Key(KeyCode::Period)],
+            Self::BrushSmaller => vec![Key(KeyCode::Comma)],
+            Self::CycleFigure => vec![Key(KeyCode::R)],
+            Self::SwapPaint => vec![Key(KeyCode::X)],
+            Self::Drag => vec![Mouse(MouseButton::Middle)],
+            Self::PanLeft => vec![Key(KeyCode::Left), Gamepad(Button::DPadLeft)],
+            Self::PanRight => vec![Key(KeyCode::Right), Gamepad(Button::DPadRight)],
+            Self::PanUp => vec![Key(KeyCode::Up), Gamepad(Button::DPadUp)],
+            Self::PanDown => vec![Key(KeyCode::Down), Gamepad(Button::DPadDown)],
+            Self::ZoomIn => vec![Key(KeyCode::PageUp), Gamepad(Button::RightTrigger2)],
+            Self::ZoomOut => vec![Key(KeyCode::PageDown), Gamepad(Button::LeftTrigger2)],
+            Self::Fit => vec![Key(KeyCode::F), Gamepad(Button::North)],
+            Self::CycleTheme => vec![Key(KeyCode::Y), Gamepad(Button::Select)],
+            Self::CycleRenderMode => vec![Key(KeyCode::H)],
+            Self::CycleProjection => vec![Key(KeyCode::P)],
+            Self::GridLines => vec![Key(KeyCode::G)],
+            Self::Trails => vec![Key(KeyCode::T)],
+            Self::CrossFade => vec![Key(KeyCode::I)],
+            Self::Ships => vec![Key(KeyCode::S)],
+            Self::GpuRendering => vec![Key(KeyCode::U)],
+            Self::Screenshot => vec![Key(KeyCode::F12)],
+            Self::Demo => vec![Key(KeyCode::F11)],
+            Self::Mute => vec![Key(KeyCode::K), Key(KeyCode::Mute)],
+        }
+    }
+}
+
+/// An input triggering an `Action`, gamepad buttons work on any connected gamepad
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl Binding {
+    /// The binding in the language of the UI, keys go by their names
+    pub fn describe(&self, locale: &Localizer) -> String {
+        match self {
+            Self::Key(key) => format!("{key:?}"),
+            Self::Mouse(button) => {
+                locale.format("binding-mouse", [("button", format!("{button:?}").into())])
+            }
+            Self::Gamepad(button) => locale.format(
+                "binding-gamepad",
+                [("button", format!("{button:?}").into())],
+            ),
+        }
+    }
+}
+
+/// Bindings of every `Action`, the defaults overridden by `INPUT_FILE`
+#[derive(Resource)]
+pub struct InputMap(HashMap<Action, Vec<Binding>>);
+
+impl Default for InputMap {
+    fn default() -> Self {
+        let mut bindings = Action::ALL
+            .into_iter()
+            .map(|action| (action, action.default_bindings()))
+            .collect::<HashMap<_, _>>();
+        match fs::read_to_string(INPUT_FILE) {
+            Ok(text) => match toml::from_str::<StdHashMap<String, Vec<Binding>>>(&text) {
+                Ok(overrides) => {
+                    for (name, overridden) in overrides {
+                        match Action::ALL.into_iter().find(|action| action.name() == name) {
+                            Some(action) => {
+                                bindings.insert(action, overridden);
+                            }
+                            None => warn!("Skipping unknown action {name} in {INPUT_FILE}"),
+                        }
+                    }
+                }
+                Err(e) => warn!("Skipping {INPUT_FILE}: {e}"),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Couldn't read {INPUT_FILE}: {e}"),
+        }
+        Self(bindings)
+    }
+}
+
+impl InputMap {
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The bindings of `action` for hints in the UI
+    pub fn describe(&self, action: Action, locale: &Localizer) -> String {
+        let bindings = self.bindings(action);
+        if bindings.is_empty() {
+            return locale.text("bindings-unbound");
+        }
+        bindings
+            .iter()
+            .map(|binding| binding.describe(locale))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub struct InputPlugin;
+
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<Input<Action>>()
+            .add_system(
+                update_actions
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem),
+            )
+            .add_system(bindings_ui);
+    }
+}
+
+/// Presses every action with a pressed binding and releases the others
+fn update_actions(
+    map: Res<InputMap>,
+    keys: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut actions: ResMut<Input<Action>>,
+) {
+    actions.clear();
+    for (&action, bindings) in &map.0 {
+        let pressed = bindings.iter().any(|binding| match *binding {
+            Binding::Key(key) => keys.pressed(key),
+            Binding::Mouse(button) => mouse.pressed(button),
+            Binding::Gamepad(button_type) => gamepads
+                .iter()
+                .any(|gamepad| gamepad_buttons.pressed(GamepadButton::new(gamepad, button_type))),
+        });
+        if pressed {
+            actions.press(action);
+        } else {
+            actions.release(action);
+        }
+    }
+}
+
+fn bindings_ui(mut contexts: EguiContexts, map: Res<InputMap>, locale: Res<Locale>) {
+    egui::Window::new(locale.text("window-bindings"))
+        .id(egui::Id::new("bindings"))
+        .default_open(false)
+        .vscroll(true)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(locale.format("bindings-rebind", [("file", INPUT_FILE.into())]));
+            egui::Grid::new("bindings").striped(true).show(ui, |ui| {
+                for action in Action::ALL {
+                    ui.label(action.name());
+                    ui.label(map.describe(action, &locale));
+                    ui.end_row();
+                }
+            });
+        });
+}
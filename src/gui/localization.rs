@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::localization::{Language, Localizer};
+use std::{fs, path::Path};
+
+/// Directory searched for fonts at startup, which egui falls back to for glyphs its built in
+/// fonts lack, like the Japanese ones
+const FONT_DIRECTORY: &str = "fonts";
+
+/// The language of the UI, detected from the environment and picked in the controls panel
+#[derive(Resource, Deref, DerefMut)]
+pub struct Locale(pub Localizer);
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self(Localizer::new(Language::detect()))
+    }
+}
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Locale>().add_system(load_fonts);
+    }
+}
+
+/// Adds the fonts in `FONT_DIRECTORY` as fallbacks once the egui context exists
+fn load_fonts(mut contexts: EguiContexts, mut loaded: Local<bool>) {
+    if *loaded {
+        return;
+    }
+    *loaded = true;
+    let Ok(entries) = fs::read_dir(Path::new(FONT_DIRECTORY)) else {
+        return;
+    };
+    let mut fonts = egui::FontDefinitions::default();
+    let mut paths: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "ttf" || extension == "otf")
+        })
+        .collect();
+    paths.sort();
+    for path in paths {
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Skipping {}: {e}", path.display());
+                continue;
+            }
+        };
+        let name = path.display().to_string();
+        fonts
+            .font_data
+            .insert(name.clone(), egui::FontData::from_owned(bytes));
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            fonts.families.entry(family).or_default().push(name.clone());
+        }
+    }
+    contexts.ctx_mut().set_fonts(fonts);
+}
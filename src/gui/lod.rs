@@ -0,0 +1,118 @@
+use super::{
+    camera::MainCamera,
+    render::{pixel_image, CellTexture, CELL_SIZE},
+    theme::Themes,
+    Simulation,
+};
+use bevy::prelude::*;
+use cellular_automata::{grid::density_tiles, theme::blend};
+
+/// Cells per density tile along each axis, `1` renders individual Cells
+///
+/// Chosen from the zoom level so that a tile never gets smaller than a pixel.
+#[derive(Resource, PartialEq, Eq)]
+pub struct LevelOfDetail(pub usize);
+
+impl Default for LevelOfDetail {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Marks the sprite displaying the downsampled grid
+#[derive(Component)]
+struct DensityTiles;
+
+pub struct LodPlugin;
+
+impl Plugin for LodPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelOfDetail>()
+            .add_startup_system(spawn_density_tiles)
+            .add_system(choose_lod)
+            .add_systems((switch_lod, update_density_tiles).after(choose_lod));
+    }
+}
+
+fn spawn_density_tiles(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn((
+        SpriteBundle {
+            texture: images.add(Image::default()),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        DensityTiles,
+    ));
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn choose_lod(
+    projections: Query<&OrthographicProjection, With<MainCamera>>,
+    mut lod: ResMut<LevelOfDetail>,
+) {
+    let Ok(projection) = projections.get_single() else {
+        return;
+    };
+    let cells_per_pixel = projection.scale / CELL_SIZE;
+    let block_size = if cells_per_pixel <= 1.0 {
+        1
+    } else {
+        (cells_per_pixel.ceil() as usize).next_power_of_two()
+    };
+    lod.set_if_neq(LevelOfDetail(block_size));
+}
+
+fn switch_lod(
+    lod: Res<LevelOfDetail>,
+    mut tiles: Query<&mut Visibility, With<DensityTiles>>,
+    mut cells: Query<&mut Visibility, (With<CellTexture>, Without<DensityTiles>)>,
+) {
+    if !lod.is_changed() {
+        return;
+    }
+    let (tiles_visibility, cells_visibility) = if lod.0 > 1 {
+        (Visibility::Visible, Visibility::Hidden)
+    } else {
+        (Visibility::Hidden, Visibility::Visible)
+    };
+    for mut visibility in &mut tiles {
+        *visibility = tiles_visibility;
+    }
+    for mut visibility in &mut cells {
+        *visibility = cells_visibility;
+    }
+}
+
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn update_density_tiles(
+    simulation: Res<Simulation>,
+    lod: Res<LevelOfDetail>,
+    themes: Res<Themes>,
+    mut images: ResMut<Assets<Image>>,
+    mut tiles: Query<(&Handle<Image>, &mut Sprite), With<DensityTiles>>,
+) {
+    if lod.0 <= 1 || !(simulation.is_changed() || lod.is_changed() || themes.is_changed()) {
+        return;
+    }
+    let Ok((handle, mut sprite)) = tiles.get_single_mut() else {
+        return;
+    };
+    let Some(image) = images.get_mut(handle) else {
+        return;
+    };
+
+    let theme = themes.current();
+    let tiles = density_tiles(&simulation.grid, lod.0);
+    let width = tiles.first().map(Vec::len).unwrap_or_default();
+    let data = tiles
+        .iter()
+        .flatten()
+        .flat_map(|&density| {
+            let [r, g, b] = blend(theme.dead, theme.alive, density);
+            [r, g, b, u8::MAX]
+        })
+        .collect();
+    *image = pixel_image(width as u32, tiles.len() as u32, data);
+    sprite.custom_size =
+        Some(Vec2::new(simulation.col_count as f32, simulation.row_count as f32) * CELL_SIZE);
+}
@@ -0,0 +1,115 @@
+use super::{
+    camera::MainCamera,
+    localization::Locale,
+    render::{grid_position, world_position},
+    Simulation,
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{
+    egui::{self, Color32, Pos2, Rect, Sense, Stroke},
+    EguiContexts,
+};
+use cellular_automata::grid::density_tiles;
+
+/// Edge length of the minimap in screen pixels
+const MINIMAP_SIZE: f32 = 160.0;
+/// Maximum number of blocks along each axis the grid is downsampled to
+const MINIMAP_RESOLUTION: usize = 80;
+const VIEWPORT_COLOR: Color32 = Color32::YELLOW;
+/// Darkest shade of blocks containing any living Cells, so sparse blocks remain visible
+const MIN_TILE_GRAY: u8 = 64;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(minimap);
+    }
+}
+
+/// Shows the whole grid with the visible part outlined while zoomed in,
+/// clicking or dragging on it moves the camera there
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn minimap(
+    mut contexts: EguiContexts,
+    simulation: Res<Simulation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+    locale: Res<Locale>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((mut transform, projection)) = cameras.get_single_mut() else {
+        return;
+    };
+
+    let half_extents = Vec2::new(window.width(), window.height()) / 2.0 * projection.scale;
+    let center = transform.translation.truncate();
+    let top_left = grid_position(
+        &simulation,
+        center + Vec2::new(-half_extents.x, half_extents.y),
+    );
+    let bottom_right = grid_position(
+        &simulation,
+        center + Vec2::new(half_extents.x, -half_extents.y),
+    );
+    let (row_count, col_count) = (simulation.row_count, simulation.col_count);
+    let whole_grid_visible = top_left.x <= 0.0
+        && top_left.y <= 0.0
+        && bottom_right.x >= col_count as f32
+        && bottom_right.y >= row_count as f32;
+    if whole_grid_visible || row_count == 0 || col_count == 0 {
+        return;
+    }
+
+    let block_size = row_count.max(col_count).div_ceil(MINIMAP_RESOLUTION);
+    let scale = MINIMAP_SIZE / row_count.max(col_count) as f32;
+    let size = egui::vec2(col_count as f32, row_count as f32) * scale;
+    egui::Window::new(locale.text("window-minimap"))
+        .id(egui::Id::new("minimap"))
+        .anchor(egui::Align2::RIGHT_TOP, [-8.0, 8.0])
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let (response, painter) = ui.allocate_painter(size, Sense::click_and_drag());
+            let origin = response.rect.min;
+            painter.rect_filled(response.rect, 0.0, Color32::BLACK);
+
+            let tiles = density_tiles(&simulation.grid, block_size);
+            for (block_row, tile_row) in tiles.iter().enumerate() {
+                for (block_col, &density) in tile_row.iter().enumerate() {
+                    if density <= 0.0 {
+                        continue;
+                    }
+                    let min = origin
+                        + egui::vec2(block_col as f32, block_row as f32)
+                            * block_size as f32
+                            * scale;
+                    painter.rect_filled(
+                        Rect::from_min_size(min, egui::Vec2::splat(block_size as f32 * scale)),
+                        0.0,
+                        Color32::from_gray(
+                            ((density * f32::from(u8::MAX)) as u8).max(MIN_TILE_GRAY),
+                        ),
+                    );
+                }
+            }
+
+            let to_minimap = |position: Vec2| origin + egui::vec2(position.x, position.y) * scale;
+            painter.rect_stroke(
+                Rect::from_two_pos(to_minimap(top_left), to_minimap(bottom_right)),
+                0.0,
+                Stroke::new(1.0, VIEWPORT_COLOR),
+            );
+
+            if let Some(Pos2 { x, y }) = response.interact_pointer_pos() {
+                let target = Vec2::new(x - origin.x, y - origin.y) / scale;
+                let target = world_position(&simulation, target);
+                transform.translation = target.extend(transform.translation.z);
+            }
+        });
+}
@@ -0,0 +1,62 @@
+use super::{localization::Locale, simulation::SimulationSet, theme::Themes, Simulation};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Color32},
+    EguiContexts,
+};
+use cellular_automata::plugin::Registry;
+
+const SWATCH_SIZE: f32 = 12.0;
+
+/// Automaton families available in the app, their presets are listed in the "Presets" window
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Presets(pub Registry);
+
+pub struct PresetsPlugin;
+
+impl Plugin for PresetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Presets>()
+            .add_system(presets_ui.in_set(SimulationSet::Edit));
+    }
+}
+
+/// Applying a preset switches to the palette of its family
+fn presets_ui(
+    mut contexts: EguiContexts,
+    presets: Res<Presets>,
+    mut simulation: ResMut<Simulation>,
+    mut themes: ResMut<Themes>,
+    locale: Res<Locale>,
+) {
+    egui::Window::new(locale.text("window-presets"))
+        .id(egui::Id::new("presets"))
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            for plugin in presets.plugins() {
+                ui.collapsing(plugin.name(), |ui| {
+                    let palette = plugin.palette();
+                    let decay = plugin.rule_set().decay;
+                    ui.horizontal(|ui| {
+                        for state in plugin.states() {
+                            let [r, g, b] = palette.cell_color(&state, decay);
+                            let (rect, response) = ui.allocate_exact_size(
+                                egui::Vec2::splat(SWATCH_SIZE),
+                                egui::Sense::hover(),
+                            );
+                            ui.painter()
+                                .rect_filled(rect, 0.0, Color32::from_rgb(r, g, b));
+                            response.on_hover_text(state.to_string());
+                        }
+                    });
+                    for preset in plugin.presets() {
+                        let label = format!("{} ({})", preset.name, preset.rule_set);
+                        if ui.button(label).clicked() {
+                            preset.apply(&mut simulation);
+                            themes.select(plugin.palette());
+                        }
+                    }
+                });
+            }
+        });
+}
@@ -0,0 +1,585 @@
+use super::{
+    gpu::GpuRendering,
+    input::Action,
+    lod::LevelOfDetail,
+    simulation::Speed,
+    theme::{color, Themes},
+    Simulation,
+};
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{AddressMode, Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+use cellular_automata::{
+    projection::Projection, theme::blend, Activity, Automaton, Cell, Theme, Trail,
+};
+use itertools::iproduct;
+
+/// Edge length of a single Cell in world units
+pub const CELL_SIZE: f32 = 8.0;
+/// Fraction of heat a Cell keeps per generation in the heatmap
+const ACTIVITY_DECAY: f32 = 0.95;
+/// Generations it takes the trail of a dead Cell to fade out
+pub const TRAIL_LENGTH: usize = 12;
+/// Width of the grid lines in pixels of a tile covering one Cell
+pub const GRID_LINE_WIDTH: u32 = 1;
+/// Pixels along each edge of a Cell in the `CellTexture` while the theme has pattern fills
+const FILL_PIXELS: usize = 4;
+/// Pixels along the width of a Cell in the `CellTexture` while the Cells are projected
+const PROJECTED_PIXELS: f32 = 4.0;
+/// Longest side of the `CellTexture` while the Cells are projected, fewer pixels per Cell
+/// are used for larger grids
+const MAX_PROJECTED_SIDE: f32 = 4096.0;
+
+/// What the Cells display, `H` cycles through the modes
+/// - `States` => The color of each Cell's state
+/// - `Heatmap` => How often each Cell changed recently
+/// - `Changes` => Highlights the Cells that changed during the last generation
+#[derive(Resource, Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RenderMode {
+    #[default]
+    States,
+    Heatmap,
+    Changes,
+}
+
+impl RenderMode {
+    const fn next(self) -> Self {
+        match self {
+            Self::States => Self::Heatmap,
+            Self::Heatmap => Self::Changes,
+            Self::Changes => Self::States,
+        }
+    }
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct CellActivity(Activity);
+
+/// Fading trails behind moving patterns in `RenderMode::States`, toggled with `T`
+#[derive(Resource)]
+pub struct Trails {
+    pub enabled: bool,
+    trail: Trail,
+}
+
+/// Whether the Cells are separated by grid lines, toggled with `G`
+#[derive(Resource)]
+pub struct GridLines(pub bool);
+
+impl Default for GridLines {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Whether the Cells cross-fade from one rendered generation to the next over a tick,
+/// so slowly ticking simulations look smooth, toggled with `I`. Only the pixels of the
+/// `CellTexture` are blended, the simulation still steps all at once.
+#[derive(Resource)]
+pub struct CrossFade {
+    pub enabled: bool,
+    /// Pixels shown when the last generation was rendered
+    from: Vec<u8>,
+    /// Pixels of the last rendered generation
+    to: Vec<u8>,
+    /// Seconds since the last generation was rendered, infinite once the fade is over
+    elapsed: f32,
+    generation: u64,
+}
+
+impl Default for CrossFade {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            from: Vec::new(),
+            to: Vec::new(),
+            elapsed: f32::INFINITY,
+            generation: 0,
+        }
+    }
+}
+
+impl CrossFade {
+    /// Starts fading from the shown `pixels` once they're overwritten by a new generation
+    fn start(&mut self, pixels: &[u8], generation: u64) {
+        self.from = pixels.to_vec();
+        self.elapsed = 0.0;
+        self.generation = generation;
+    }
+
+    /// Ends the fade, e.g. because the Cells changed without stepping
+    fn finish(&mut self, generation: u64) {
+        self.elapsed = f32::INFINITY;
+        self.generation = generation;
+    }
+}
+
+/// How the Cells are laid out on screen, `P` cycles through the projections. Only the
+/// Cells rendered on the CPU are projected, without pattern fills or grid lines, the
+/// overlays stay on the square grid.
+#[derive(Resource, Default)]
+pub struct CellProjection(pub Projection);
+
+/// Marks the sprite whose texture holds one pixel per Cell
+#[derive(Component)]
+pub struct CellTexture;
+
+/// Marks the sprite tiling grid lines over the Cells
+#[derive(Component)]
+struct GridLineOverlay;
+
+pub struct RenderPlugin;
+
+impl Plugin for RenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RenderMode>()
+            .init_resource::<GridLines>()
+            .init_resource::<CrossFade>()
+            .init_resource::<CellProjection>()
+            .insert_resource(CellActivity(Activity::new(ACTIVITY_DECAY)))
+            .insert_resource(Trails {
+                enabled: false,
+                trail: Trail::new(TRAIL_LENGTH),
+            })
+            .add_startup_system(spawn_cells)
+            .add_systems((
+                cycle_render_mode,
+                toggle_trails,
+                toggle_grid_lines,
+                toggle_cross_fade,
+                cycle_projection,
+            ))
+            .add_system(
+                show_grid_lines
+                    .after(toggle_grid_lines)
+                    .after(cycle_projection),
+            )
+            .add_system(restyle_grid_lines)
+            .add_system(resize_grid_lines)
+            .add_system(observe_activity)
+            .add_system(
+                update_cells
+                    .after(cycle_render_mode)
+                    .after(toggle_trails)
+                    .after(cycle_projection)
+                    .after(observe_activity),
+            )
+            .add_system(fade_cells.after(update_cells).after(toggle_cross_fade));
+    }
+}
+
+/// World position of the center of the Cell at (`row`, `col`),
+/// the grid is centered around the origin with row 0 at the top
+#[allow(clippy::cast_precision_loss)]
+pub fn cell_center(automaton: &Automaton, row: usize, col: usize) -> Vec2 {
+    Vec2::new(
+        (col as f32 - automaton.col_count as f32 / 2.0 + 0.5) * CELL_SIZE,
+        (automaton.row_count as f32 / 2.0 - row as f32 - 0.5) * CELL_SIZE,
+    )
+}
+
+/// Fractional (`col`, `row`) coordinates of a world `position`,
+/// (`0.0`, `0.0`) being the top left corner of the grid
+#[allow(clippy::cast_precision_loss)]
+pub fn grid_position(automaton: &Automaton, position: Vec2) -> Vec2 {
+    Vec2::new(
+        position.x / CELL_SIZE + automaton.col_count as f32 / 2.0,
+        automaton.row_count as f32 / 2.0 - position.y / CELL_SIZE,
+    )
+}
+
+/// Inverse of `grid_position`
+#[allow(clippy::cast_precision_loss)]
+pub fn world_position(automaton: &Automaton, grid_position: Vec2) -> Vec2 {
+    Vec2::new(
+        (grid_position.x - automaton.col_count as f32 / 2.0) * CELL_SIZE,
+        (automaton.row_count as f32 / 2.0 - grid_position.y) * CELL_SIZE,
+    )
+}
+
+/// Inverse of `cell_center`, `None` if `position` lies outside of the grid.
+/// Finds the Cell by its shape in the `projection` the grid is shown in.
+pub fn cell_at(
+    automaton: &Automaton,
+    projection: Projection,
+    position: Vec2,
+) -> Option<(usize, usize)> {
+    let (rows, cols) = (automaton.row_count, automaton.col_count);
+    let (min, max) = projection.extent(rows, cols);
+    // The projected grid is centered around the origin just like the square one
+    let center = (Vec2::from(min) + Vec2::from(max)) / 2.0;
+    let Vec2 { x, y } = center + Vec2::new(position.x, -position.y) / CELL_SIZE;
+    projection.cell_at(x, y, rows, cols)
+}
+
+pub fn cell_color(theme: &Theme, cell: &Cell, decay: usize) -> Color {
+    color(theme.cell_color(cell, decay))
+}
+
+/// Tints dead Cells by the opacity of their trail
+pub fn trail_color(theme: &Theme, cell: &Cell, decay: usize, fade: f32) -> Color {
+    match cell {
+        Cell::Dead => color(blend(theme.dead, theme.trail, fade)),
+        cell => cell_color(theme, cell, decay),
+    }
+}
+
+/// Births flash green and deaths red, unchanged Cells are dimmed
+pub fn change_color(theme: &Theme, cell: &Cell, decay: usize, changed: bool) -> Color {
+    match (changed, cell) {
+        (true, Cell::Alive) => Color::rgb(0.2, 1.0, 0.3),
+        (true, _) => Color::rgb(0.9, 0.15, 0.1),
+        (false, cell) => cell_color(theme, cell, decay) * 0.35,
+    }
+}
+
+/// Black through red and yellow to white with rising `intensity`
+pub fn heat_color(intensity: f32) -> Color {
+    let intensity = intensity.clamp(0.0, 1.0) * 3.0;
+    Color::rgb(
+        intensity.min(1.0),
+        (intensity - 1.0).clamp(0.0, 1.0),
+        (intensity - 2.0).clamp(0.0, 1.0),
+    )
+}
+
+/// Creates an image of `width` x `height` pixels, sampled without smoothing
+pub fn pixel_image(width: u32, height: u32, data: Vec<u8>) -> Image {
+    let mut image = Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = ImageSampler::nearest();
+    image
+}
+
+/// A tile covering one Cell with a grid line along its right and bottom edge
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn grid_line_tile(theme: &Theme) -> Image {
+    let size = CELL_SIZE as u32;
+    let data = iproduct!(0..size, 0..size)
+        .flat_map(|(y, x)| {
+            if x >= size - GRID_LINE_WIDTH || y >= size - GRID_LINE_WIDTH {
+                color(theme.grid_line).as_rgba_u8()
+            } else {
+                [0; 4]
+            }
+        })
+        .collect();
+    let mut image = pixel_image(size, size, data);
+    if let ImageSampler::Descriptor(descriptor) = &mut image.sampler_descriptor {
+        descriptor.address_mode_u = AddressMode::Repeat;
+        descriptor.address_mode_v = AddressMode::Repeat;
+    }
+    image
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn spawn_cells(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    simulation: Res<Simulation>,
+    themes: Res<Themes>,
+) {
+    let (rows, cols) = (simulation.row_count, simulation.col_count);
+    let size = Vec2::new(cols as f32, rows as f32) * CELL_SIZE;
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(size),
+                ..default()
+            },
+            texture: images.add(Image::default()),
+            ..default()
+        },
+        CellTexture,
+    ));
+    // The tile repeats once per Cell as the rect exceeds the texture
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(size),
+                rect: Some(Rect::from_corners(Vec2::ZERO, size)),
+                ..default()
+            },
+            texture: images.add(grid_line_tile(themes.current())),
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+            ..default()
+        },
+        GridLineOverlay,
+    ));
+}
+
+fn cycle_render_mode(actions: Res<Input<Action>>, mut mode: ResMut<RenderMode>) {
+    if actions.just_pressed(Action::CycleRenderMode) {
+        *mode = mode.next();
+    }
+}
+
+fn toggle_grid_lines(actions: Res<Input<Action>>, mut grid_lines: ResMut<GridLines>) {
+    if actions.just_pressed(Action::GridLines) {
+        grid_lines.0 = !grid_lines.0;
+    }
+}
+
+/// Grid lines are only drawn while individual Cells are rendered on the CPU,
+/// the shader of `GpuRendering` draws its own
+fn show_grid_lines(
+    grid_lines: Res<GridLines>,
+    lod: Res<LevelOfDetail>,
+    gpu: Res<GpuRendering>,
+    mode: Res<RenderMode>,
+    projection: Res<CellProjection>,
+    mut overlays: Query<&mut Visibility, With<GridLineOverlay>>,
+) {
+    let changed = grid_lines.is_changed()
+        || lod.is_changed()
+        || gpu.is_changed()
+        || mode.is_changed()
+        || projection.is_changed();
+    if !changed {
+        return;
+    }
+    let square = projection.0 == Projection::Square;
+    for mut visibility in &mut overlays {
+        *visibility = if grid_lines.0 && square && lod.0 == 1 && !gpu.active(*mode, &lod) {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn restyle_grid_lines(
+    themes: Res<Themes>,
+    mut images: ResMut<Assets<Image>>,
+    overlays: Query<&Handle<Image>, With<GridLineOverlay>>,
+) {
+    if !themes.is_changed() {
+        return;
+    }
+    for handle in &overlays {
+        if let Some(image) = images.get_mut(handle) {
+            *image = grid_line_tile(themes.current());
+        }
+    }
+}
+
+/// Keeps the overlay covering the grid when its dimensions change
+#[allow(clippy::cast_precision_loss)]
+fn resize_grid_lines(
+    simulation: Res<Simulation>,
+    mut overlays: Query<&mut Sprite, With<GridLineOverlay>>,
+) {
+    if !simulation.is_changed() {
+        return;
+    }
+    let size = Vec2::new(simulation.col_count as f32, simulation.row_count as f32) * CELL_SIZE;
+    for mut sprite in &mut overlays {
+        if sprite.custom_size != Some(size) {
+            sprite.custom_size = Some(size);
+            sprite.rect = Some(Rect::from_corners(Vec2::ZERO, size));
+        }
+    }
+}
+
+fn toggle_trails(actions: Res<Input<Action>>, mut trails: ResMut<Trails>) {
+    if actions.just_pressed(Action::Trails) {
+        trails.enabled = !trails.enabled;
+    }
+}
+
+fn toggle_cross_fade(actions: Res<Input<Action>>, mut cross_fade: ResMut<CrossFade>) {
+    if actions.just_pressed(Action::CrossFade) {
+        cross_fade.enabled = !cross_fade.enabled;
+    }
+}
+
+fn cycle_projection(actions: Res<Input<Action>>, mut projection: ResMut<CellProjection>) {
+    if actions.just_pressed(Action::CycleProjection) {
+        projection.0 = projection.0.next();
+        info!("Projection: {}", projection.0);
+    }
+}
+
+fn observe_activity(
+    simulation: Res<Simulation>,
+    mut activity: ResMut<CellActivity>,
+    mut trails: ResMut<Trails>,
+) {
+    if simulation.is_changed() {
+        activity.observe(&simulation.grid);
+        trails.trail.observe(&simulation.grid);
+    }
+}
+
+/// Writes the color of every Cell into the pixels of the `CellTexture`, with `FILL_PIXELS`
+/// pixels per edge showing the `Theme::fill` of the states if the theme has pattern fills.
+/// Pixels outside of every projected Cell are transparent.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::too_many_arguments
+)]
+fn update_cells(
+    simulation: Res<Simulation>,
+    mode: Res<RenderMode>,
+    activity: Res<CellActivity>,
+    trails: Res<Trails>,
+    lod: Res<LevelOfDetail>,
+    themes: Res<Themes>,
+    gpu: Res<GpuRendering>,
+    projection: Res<CellProjection>,
+    mut cross_fade: ResMut<CrossFade>,
+    mut images: ResMut<Assets<Image>>,
+    mut textures: Query<(&Handle<Image>, &mut Sprite), With<CellTexture>>,
+) {
+    let changed = simulation.is_changed()
+        || mode.is_changed()
+        || trails.is_changed()
+        || lod.is_changed()
+        || themes.is_changed()
+        || gpu.is_changed()
+        || projection.is_changed();
+    // Individual Cells are hidden while density tiles are shown or covered by the shader
+    if !changed || lod.0 > 1 || gpu.active(*mode, &lod) {
+        return;
+    }
+    let Ok((handle, mut sprite)) = textures.get_single_mut() else {
+        return;
+    };
+    let Some(image) = images.get_mut(handle) else {
+        return;
+    };
+
+    let (rows, cols) = (simulation.row_count, simulation.col_count);
+    let (theme, decay) = (themes.current(), simulation.rule_set.decay);
+    let projection = projection.0;
+    let square = projection == Projection::Square;
+    let scale = if square && *mode == RenderMode::States && theme.pattern_fills {
+        FILL_PIXELS
+    } else {
+        1
+    };
+    let (min, max) = projection.extent(rows, cols);
+    let (min, size) = (Vec2::from(min), Vec2::from(max) - Vec2::from(min));
+    // Pixels per Cell
+    let density = if square {
+        scale as f32
+    } else {
+        PROJECTED_PIXELS.min(MAX_PROJECTED_SIDE / size.max_element())
+    };
+    let (width, height) = if square {
+        (cols * scale, rows * scale)
+    } else {
+        let pixels = (size * density).ceil().max(Vec2::ONE);
+        (pixels.x as usize, pixels.y as usize)
+    };
+    let resized = image.size() != Vec2::new(width as f32, height as f32);
+    if resized {
+        *image = pixel_image(width as u32, height as u32, vec![0; width * height * 4]);
+    }
+    if sprite.custom_size != Some(size * CELL_SIZE) {
+        sprite.custom_size = Some(size * CELL_SIZE);
+    }
+    // Only stepping fades, edits, rewinding and restyling show up at once
+    let stepped = !resized && simulation.generation > cross_fade.generation;
+    if cross_fade.enabled && stepped {
+        cross_fade.start(&image.data, simulation.generation);
+    }
+    for ((y, x), pixel) in iproduct!(0..height, 0..width).zip(image.data.chunks_exact_mut(4)) {
+        let (row, col) = if square {
+            (y / scale, x / scale)
+        } else {
+            let position = min + (Vec2::new(x as f32, y as f32) + 0.5) / density;
+            let Some(cell) = projection.cell_at(position.x, position.y, rows, cols) else {
+                pixel.copy_from_slice(&[0; 4]);
+                continue;
+            };
+            cell
+        };
+        let cell = &simulation.grid[row][col];
+        if !theme
+            .fill(cell, decay)
+            .covers((x % scale) as u32, (y % scale) as u32)
+        {
+            pixel.copy_from_slice(&cell_color(theme, &Cell::Dead, decay).as_rgba_u8());
+            continue;
+        }
+        let color = match *mode {
+            RenderMode::States if trails.enabled => {
+                trail_color(theme, cell, decay, trails.trail.fade(row, col))
+            }
+            RenderMode::States => cell_color(theme, cell, decay),
+            RenderMode::Heatmap => heat_color(activity.intensity(row, col)),
+            RenderMode::Changes => change_color(theme, cell, decay, activity.changed(row, col)),
+        };
+        pixel.copy_from_slice(&color.as_rgba_u8());
+    }
+    if cross_fade.enabled && stepped {
+        cross_fade.to.clone_from(&image.data);
+    } else {
+        cross_fade.finish(simulation.generation);
+    }
+}
+
+/// Blends the `CellTexture` from the previous generation to the last one over the period of
+/// a tick, `Speed::Unlimited` ticks too fast to fade
+#[allow(clippy::too_many_arguments)]
+fn fade_cells(
+    time: Res<Time>,
+    speed: Res<Speed>,
+    mode: Res<RenderMode>,
+    lod: Res<LevelOfDetail>,
+    gpu: Res<GpuRendering>,
+    mut cross_fade: ResMut<CrossFade>,
+    mut images: ResMut<Assets<Image>>,
+    textures: Query<&Handle<Image>, With<CellTexture>>,
+) {
+    if cross_fade.elapsed.is_infinite() || lod.0 > 1 || gpu.active(*mode, &lod) {
+        return;
+    }
+    let Ok(handle) = textures.get_single() else {
+        return;
+    };
+    let Some(image) = images.get_mut(handle) else {
+        return;
+    };
+    if image.data.len() != cross_fade.to.len() || cross_fade.from.len() != cross_fade.to.len() {
+        cross_fade.elapsed = f32::INFINITY;
+        return;
+    }
+    let period = match *speed {
+        Speed::GenerationsPerSecond(rate) => rate.recip(),
+        Speed::Unlimited => 0.0,
+    };
+    cross_fade.elapsed += time.delta_seconds();
+    // Switching the fade off or to unlimited speed jumps to the last generation
+    let t = if cross_fade.enabled && cross_fade.elapsed < period {
+        cross_fade.elapsed / period
+    } else {
+        cross_fade.elapsed = f32::INFINITY;
+        1.0
+    };
+    let CrossFade { from, to, .. } = &*cross_fade;
+    let pixels = image
+        .data
+        .chunks_exact_mut(4)
+        .zip(from.chunks_exact(4).zip(to.chunks_exact(4)));
+    for (pixel, (from, to)) in pixels {
+        let rgb = blend([from[0], from[1], from[2]], [to[0], to[1], to[2]], t);
+        pixel[..3].copy_from_slice(&rgb);
+        pixel[3] = to[3];
+    }
+}
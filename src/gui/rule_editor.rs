@@ -0,0 +1,140 @@
+use super::{localization::Locale, simulation::SimulationSet, theme::Themes, Simulation};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Color32},
+    EguiContexts,
+};
+use cellular_automata::{Automaton, RuleSet, Theme};
+use itertools::iproduct;
+
+const PREVIEW_SIZE: usize = 32;
+const PREVIEW_CELL_SIZE: f32 = 4.0;
+const PREVIEW_SECONDS_PER_GENERATION: f32 = 0.1;
+const MAX_DECAY: usize = 16;
+
+/// Candidate rule being edited and the preview automaton running it
+#[derive(Resource)]
+struct RuleEditor {
+    birth: [bool; 9],
+    survival: [bool; 9],
+    decay: usize,
+    preview: Automaton,
+    timer: Timer,
+}
+
+impl Default for RuleEditor {
+    fn default() -> Self {
+        let mut editor = Self {
+            birth: [false; 9],
+            survival: [false; 9],
+            decay: 0,
+            preview: Automaton::default(),
+            timer: Timer::from_seconds(PREVIEW_SECONDS_PER_GENERATION, TimerMode::Repeating),
+        };
+        editor.birth[3] = true;
+        editor.survival[2] = true;
+        editor.survival[3] = true;
+        editor.reseed();
+        editor
+    }
+}
+
+impl RuleEditor {
+    fn rule_set(&self) -> RuleSet {
+        let counts = |toggles: &[bool; 9]| {
+            toggles
+                .iter()
+                .enumerate()
+                .filter_map(|(count, &enabled)| enabled.then_some(count))
+                .collect()
+        };
+        RuleSet::life_like(counts(&self.birth), counts(&self.survival), self.decay)
+    }
+
+    fn reseed(&mut self) {
+        self.preview = Automaton::builder()
+            .row_count(PREVIEW_SIZE)
+            .col_count(PREVIEW_SIZE)
+            .rule_set(self.rule_set())
+            .build();
+    }
+}
+
+pub struct RuleEditorPlugin;
+
+impl Plugin for RuleEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RuleEditor>()
+            .add_system(rule_editor_ui.in_set(SimulationSet::Edit))
+            .add_system(step_preview);
+    }
+}
+
+fn step_preview(time: Res<Time>, mut editor: ResMut<RuleEditor>) {
+    if editor.timer.tick(time.delta()).just_finished() {
+        editor.preview.step();
+    }
+}
+
+fn rule_editor_ui(
+    mut contexts: EguiContexts,
+    mut editor: ResMut<RuleEditor>,
+    mut simulation: ResMut<Simulation>,
+    themes: Res<Themes>,
+    locale: Res<Locale>,
+) {
+    let editor = &mut *editor;
+    egui::Window::new(locale.text("window-rules"))
+        .id(egui::Id::new("rules"))
+        .show(contexts.ctx_mut(), |ui| {
+            let mut changed = false;
+            for (id, toggles) in [
+                ("rules-birth", &mut editor.birth),
+                ("rules-survival", &mut editor.survival),
+            ] {
+                ui.horizontal(|ui| {
+                    ui.label(locale.text(id));
+                    for (count, enabled) in toggles.iter_mut().enumerate() {
+                        changed |= ui.checkbox(enabled, count.to_string()).changed();
+                    }
+                });
+            }
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut editor.decay, 0..=MAX_DECAY)
+                        .text(locale.text("rules-decay")),
+                )
+                .changed();
+            if changed {
+                editor.preview.rule_set = editor.rule_set();
+            }
+
+            preview(ui, &editor.preview, themes.current());
+
+            ui.horizontal(|ui| {
+                if ui.button(locale.text("rules-reseed")).clicked() {
+                    editor.reseed();
+                }
+                if ui.button(locale.text("rules-apply")).clicked() {
+                    simulation.rule_set = editor.rule_set();
+                }
+            });
+        });
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn preview(ui: &mut egui::Ui, automaton: &Automaton, theme: &Theme) {
+    let (response, painter) = ui.allocate_painter(
+        egui::Vec2::splat(PREVIEW_SIZE as f32 * PREVIEW_CELL_SIZE),
+        egui::Sense::hover(),
+    );
+    for (row, col) in iproduct!(0..automaton.row_count, 0..automaton.col_count) {
+        let [r, g, b] = theme.cell_color(&automaton.grid[row][col], automaton.rule_set.decay);
+        let min = response.rect.min + egui::vec2(col as f32, row as f32) * PREVIEW_CELL_SIZE;
+        painter.rect_filled(
+            egui::Rect::from_min_size(min, egui::Vec2::splat(PREVIEW_CELL_SIZE)),
+            0.0,
+            Color32::from_rgb(r, g, b),
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use super::{
+    camera::MainCamera,
+    input::Action,
+    render::{grid_position, CELL_SIZE},
+    theme::Themes,
+    Simulation,
+};
+use arboard::{Clipboard, ImageData};
+use bevy::{prelude::*, window::PrimaryWindow};
+use cellular_automata::grid::{viewport_image, Viewport};
+use image::{DynamicImage, RgbImage};
+use std::{
+    error::Error,
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Where screenshots are saved, named after the time they were taken
+const SCREENSHOT_DIRECTORY: &str = "screenshots";
+
+/// `F12` saves what the window shows of the grid as PNG, `Ctrl+F12` copies it to the clipboard.
+/// The image is rendered at the resolution of the window with the current theme and zoom.
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(screenshot);
+    }
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn screenshot(
+    actions: Res<Input<Action>>,
+    keys: Res<Input<KeyCode>>,
+    simulation: Res<Simulation>,
+    themes: Res<Themes>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    if !actions.just_pressed(Action::Screenshot) {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((transform, projection)) = cameras.get_single() else {
+        return;
+    };
+    let center = grid_position(&simulation, transform.translation.truncate());
+    // The projection scale is in world units per logical pixel
+    let viewport = Viewport {
+        width: window.physical_width(),
+        height: window.physical_height(),
+        center: center.into(),
+        cells_per_pixel: projection.scale / CELL_SIZE / window.scale_factor() as f32,
+    };
+    let image = viewport_image(
+        &simulation.grid,
+        themes.current(),
+        simulation.rule_set.decay,
+        viewport,
+    );
+    if keys.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        match copy(image) {
+            Ok(()) => info!("Copied the screenshot to the clipboard"),
+            Err(e) => warn!("Couldn't copy the screenshot: {e}"),
+        }
+    } else {
+        match save(&image) {
+            Ok(path) => info!("Saved the screenshot as {}", path.display()),
+            Err(e) => warn!("Couldn't save the screenshot: {e}"),
+        }
+    }
+}
+
+fn save(image: &RgbImage) -> Result<PathBuf, Box<dyn Error>> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    fs::create_dir_all(SCREENSHOT_DIRECTORY)?;
+    let path = PathBuf::from(SCREENSHOT_DIRECTORY).join(format!("screenshot-{timestamp}.png"));
+    image.save(&path)?;
+    Ok(path)
+}
+
+fn copy(image: RgbImage) -> Result<(), Box<dyn Error>> {
+    let (width, height) = image.dimensions();
+    let rgba = DynamicImage::ImageRgb8(image).into_rgba8();
+    Clipboard::new()?.set_image(ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: rgba.into_raw().into(),
+    })?;
+    Ok(())
+}
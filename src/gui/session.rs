@@ -0,0 +1,162 @@
+use super::{
+    annotations::Annotations,
+    camera::MainCamera,
+    simulation::{Paused, Rewind, Speed, Stride},
+    theme::Themes,
+    Simulation,
+};
+use bevy::{app::AppExit, prelude::*};
+use cellular_automata::session::{Session, View};
+use std::{path::Path, time::Duration};
+
+/// Saved on exit and every `AUTOSAVE_INTERVAL`, restored at startup if it exists
+const SESSION_FILE: &str = "session.json.gz";
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Resource, Deref, DerefMut)]
+struct Autosave(Timer);
+
+/// Keeps the grid, rules, rewind history, annotations, camera, theme and speed across launches,
+/// delete `SESSION_FILE` to start over
+pub struct SessionPlugin;
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Autosave(Timer::new(
+            AUTOSAVE_INTERVAL,
+            TimerMode::Repeating,
+        )))
+        // After the camera was spawned
+        .add_startup_system(restore.in_base_set(StartupSet::PostStartup))
+        .add_system(autosave)
+        .add_system(save_on_exit.in_base_set(CoreSet::Last));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn restore(
+    mut simulation: ResMut<Simulation>,
+    mut rewind: ResMut<Rewind>,
+    mut annotations: ResMut<Annotations>,
+    mut themes: ResMut<Themes>,
+    mut speed: ResMut<Speed>,
+    mut stride: ResMut<Stride>,
+    mut paused: ResMut<Paused>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    if !Path::new(SESSION_FILE).exists() {
+        return;
+    }
+    let session = match Session::load(SESSION_FILE) {
+        Ok(session) => session,
+        Err(e) => {
+            warn!("Starting a new session, {SESSION_FILE} is unusable: {e}");
+            return;
+        }
+    };
+    session.restore(&mut simulation);
+    rewind.0 = session.history;
+    annotations.0 = session.annotations;
+    let view = session.view;
+    if !themes.select_named(&view.theme) {
+        warn!("Theme {} of the session isn't available", view.theme);
+    }
+    *speed = view
+        .speed
+        .map_or(Speed::Unlimited, Speed::GenerationsPerSecond);
+    stride.0 = view.stride.max(1);
+    paused.0 = view.paused;
+    for (mut transform, mut projection) in &mut cameras {
+        transform.translation = Vec2::from(view.camera).extend(transform.translation.z);
+        projection.scale = view.zoom;
+    }
+    info!(
+        "Restored the session at generation {}",
+        simulation.generation
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn save(
+    simulation: &Simulation,
+    rewind: &Rewind,
+    annotations: &Annotations,
+    themes: &Themes,
+    speed: Speed,
+    stride: &Stride,
+    paused: &Paused,
+    camera: Option<(&Transform, &OrthographicProjection)>,
+) {
+    let (camera, zoom) = camera.map_or(([0.0; 2], 1.0), |(transform, projection)| {
+        (transform.translation.truncate().into(), projection.scale)
+    });
+    let view = View {
+        camera,
+        zoom,
+        theme: themes.current().name.clone(),
+        speed: match speed {
+            Speed::GenerationsPerSecond(rate) => Some(rate),
+            Speed::Unlimited => None,
+        },
+        stride: stride.0,
+        paused: paused.0,
+    };
+    let mut session = Session::new(simulation, rewind.0.clone(), view);
+    session.annotations = annotations.0.clone();
+    if let Err(e) = session.save(SESSION_FILE) {
+        warn!("Couldn't save the session: {e}");
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn autosave(
+    time: Res<Time>,
+    mut autosave: ResMut<Autosave>,
+    simulation: Res<Simulation>,
+    rewind: Res<Rewind>,
+    annotations: Res<Annotations>,
+    themes: Res<Themes>,
+    speed: Res<Speed>,
+    stride: Res<Stride>,
+    paused: Res<Paused>,
+    cameras: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    if autosave.tick(time.delta()).just_finished() {
+        save(
+            &simulation,
+            &rewind,
+            &annotations,
+            &themes,
+            *speed,
+            &stride,
+            &paused,
+            cameras.get_single().ok(),
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn save_on_exit(
+    exits: EventReader<AppExit>,
+    simulation: Res<Simulation>,
+    rewind: Res<Rewind>,
+    annotations: Res<Annotations>,
+    themes: Res<Themes>,
+    speed: Res<Speed>,
+    stride: Res<Stride>,
+    paused: Res<Paused>,
+    cameras: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    if !exits.is_empty() {
+        save(
+            &simulation,
+            &rewind,
+            &annotations,
+            &themes,
+            *speed,
+            &stride,
+            &paused,
+            cameras.get_single().ok(),
+        );
+    }
+}
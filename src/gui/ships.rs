@@ -0,0 +1,88 @@
+use super::{
+    input::Action,
+    render::{cell_center, CELL_SIZE},
+    Simulation,
+};
+use bevy::prelude::*;
+use cellular_automata::analysis;
+
+/// Whether detected spaceships are highlighted and labeled with their velocity,
+/// toggled with `S`
+#[derive(Resource, Default)]
+struct ShipOverlay(bool);
+
+/// Marks the highlights and labels, which are replaced whenever the grid changes
+#[derive(Component)]
+struct ShipLabel;
+
+pub struct ShipsPlugin;
+
+impl Plugin for ShipsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShipOverlay>()
+            .add_system(toggle_ship_overlay)
+            .add_system(label_ships.after(toggle_ship_overlay));
+    }
+}
+
+fn toggle_ship_overlay(actions: Res<Input<Action>>, mut overlay: ResMut<ShipOverlay>) {
+    if actions.just_pressed(Action::Ships) {
+        overlay.0 = !overlay.0;
+    }
+}
+
+fn label_ships(
+    mut commands: Commands,
+    simulation: Res<Simulation>,
+    overlay: Res<ShipOverlay>,
+    labels: Query<Entity, With<ShipLabel>>,
+) {
+    if !overlay.is_changed() && !simulation.is_changed() {
+        return;
+    }
+    for label in &labels {
+        commands.entity(label).despawn();
+    }
+    if !overlay.0 {
+        return;
+    }
+    for ship in analysis::find_spaceships(&simulation, analysis::SHIP_PERIOD) {
+        let top_left = cell_center(&simulation, ship.row, ship.col);
+        let bottom_right = cell_center(
+            &simulation,
+            ship.row + ship.row_count - 1,
+            ship.col + ship.col_count - 1,
+        );
+        let center = (top_left + bottom_right) / 2.0;
+        let size = (bottom_right - top_left).abs() + CELL_SIZE;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(1.0, 0.5, 0.1, 0.3),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(center.extend(2.0)),
+                ..default()
+            },
+            ShipLabel,
+        ));
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    ship.velocity(),
+                    TextStyle {
+                        font_size: 2.0 * CELL_SIZE,
+                        color: Color::ORANGE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(
+                    (center + Vec2::Y * (size.y / 2.0 + CELL_SIZE)).extend(3.0),
+                ),
+                ..default()
+            },
+            ShipLabel,
+        ));
+    }
+}
@@ -0,0 +1,322 @@
+use super::{camera::MainCamera, input::Action, render::CELL_SIZE, zones::RuleZones};
+use bevy::{prelude::*, utils::Instant};
+use cellular_automata::{history::History, localization::Localizer, Automaton};
+use std::time::Duration;
+
+const ROW_COUNT: usize = 64;
+const COL_COUNT: usize = 64;
+const DEFAULT_GENERATIONS_PER_SECOND: f32 = 10.0;
+const MIN_GENERATIONS_PER_SECOND: f32 = 0.25;
+const MAX_GENERATIONS_PER_SECOND: f32 = 1000.0;
+/// Time spent stepping per frame, keeps the app responsive.
+/// `Speed::Unlimited` uses all of it, other due generations are carried over to the next frame.
+const FRAME_BUDGET: Duration = Duration::from_millis(12);
+/// Due generations beyond this are dropped instead of catching up after a long stall
+const MAX_PENDING: u64 = 1 << 16;
+const MAX_STRIDE: usize = 1 << 12;
+/// Dead rows or columns added at once when the pattern comes this close to an edge
+const GROWTH_MARGIN: usize = 16;
+/// The grid stops growing once a side reaches this many Cells
+const MAX_GROWN_SIZE: usize = 2048;
+/// Rendered generations `Backspace` can rewind through
+const HISTORY_CAPACITY: usize = 10_000;
+
+/// The automaton driven by the app
+#[derive(Resource, Deref, DerefMut)]
+pub struct Simulation(pub Automaton);
+
+/// Whether stepping is suspended, toggled with `Space`
+#[derive(Resource, Default)]
+pub struct Paused(pub bool);
+
+/// How fast the simulation advances, independent of the frame rate
+/// - `GenerationsPerSecond` => Steps on a fixed timestep through the `Scheduler`,
+/// several times per frame if the frame rate is lower than the tick rate
+/// - `Unlimited` => Steps as often as fits into a frame
+///
+/// `=` doubles and `-` halves the tick rate, `0` toggles `Unlimited`
+#[derive(Resource, Debug, PartialEq, Clone, Copy)]
+pub enum Speed {
+    GenerationsPerSecond(f32),
+    Unlimited,
+}
+
+/// Generations computed per step, only the last one of them gets rendered.
+/// `]` doubles and `[` halves the stride.
+#[derive(Resource, Deref, DerefMut)]
+pub struct Stride(pub usize);
+
+/// Whether the grid expands when the pattern approaches an edge
+/// instead of clipping its growth, toggled with `E`
+#[derive(Resource, Default)]
+pub struct AutoGrow(pub bool);
+
+/// Compressed snapshots of the rendered generations, `Backspace` pauses and steps back
+#[derive(Resource, Deref, DerefMut)]
+pub struct Rewind(pub History);
+
+/// Phases of a frame, so edits land on a generation boundary,
+/// on the generation that was rendered when they were made
+/// - `Edit` => Every system changing the grid other than by stepping
+/// - `Step` => Computes the due generations after all edits
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum SimulationSet {
+    Edit,
+    Step,
+}
+
+/// Deterministic fixed-step scheduling of generations. Elapsed time turns into due generations
+/// at the tick rate, which are computed within `FRAME_BUDGET` and carried over if it runs out.
+/// Neither the frame rate, pausing nor the speed change which generations are computed, only
+/// when they are, and the `Automaton::generation` counts them exactly.
+#[derive(Resource, Default)]
+pub struct Scheduler {
+    /// Time not yet turned into generations
+    elapsed: Duration,
+    /// Generations due but not computed yet
+    pending: u64,
+}
+
+impl Scheduler {
+    /// Schedules another `generations`, e.g. a single stride while paused
+    pub fn request(&mut self, generations: usize) {
+        self.pending = (self.pending + generations as u64).min(MAX_PENDING);
+    }
+
+    /// Drops the due generations
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+
+    pub const fn pending(&self) -> u64 {
+        self.pending
+    }
+}
+
+impl Default for Stride {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl Stride {
+    pub fn double(&mut self) {
+        self.0 = (self.0 * 2).min(MAX_STRIDE);
+    }
+
+    pub fn halve(&mut self) {
+        self.0 = (self.0 / 2).max(1);
+    }
+}
+
+impl Speed {
+    pub fn toggle_unlimited(&mut self) {
+        *self = if *self == Self::Unlimited {
+            Self::default()
+        } else {
+            Self::Unlimited
+        };
+    }
+
+    /// Multiplies the tick rate by `factor`, `Unlimited` stays unlimited
+    pub fn scale(&mut self, factor: f32) {
+        if let Self::GenerationsPerSecond(rate) = *self {
+            *self = Self::GenerationsPerSecond(
+                (rate * factor).clamp(MIN_GENERATIONS_PER_SECOND, MAX_GENERATIONS_PER_SECOND),
+            );
+        }
+    }
+
+    /// The speed for the status bar, in the language of the UI
+    pub fn describe(&self, locale: &Localizer) -> String {
+        match self {
+            Self::GenerationsPerSecond(rate) => {
+                locale.format("speed-rate", [("rate", rate.to_string().into())])
+            }
+            Self::Unlimited => locale.text("speed-unlimited"),
+        }
+    }
+}
+
+impl Default for Speed {
+    fn default() -> Self {
+        Self::GenerationsPerSecond(DEFAULT_GENERATIONS_PER_SECOND)
+    }
+}
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Simulation(
+            Automaton::builder()
+                .row_count(ROW_COUNT)
+                .col_count(COL_COUNT)
+                .build(),
+        ))
+        .init_resource::<Paused>()
+        .init_resource::<Speed>()
+        .init_resource::<Stride>()
+        .init_resource::<AutoGrow>()
+        .insert_resource(Rewind(History::new(HISTORY_CAPACITY)))
+        .init_resource::<Scheduler>()
+        .configure_set(SimulationSet::Edit.before(SimulationSet::Step))
+        .add_systems((toggle_pause, change_speed, change_stride, toggle_auto_grow))
+        .add_system(rewind.after(toggle_pause).in_set(SimulationSet::Edit))
+        .add_system(request_step.after(toggle_pause).before(SimulationSet::Step))
+        .add_system(
+            schedule
+                .after(toggle_pause)
+                .after(change_speed)
+                .after(change_stride)
+                .before(SimulationSet::Step),
+        )
+        .add_system(step.in_set(SimulationSet::Step))
+        .add_system(grow.after(SimulationSet::Step).after(toggle_auto_grow))
+        .add_system(record_history.after(grow));
+    }
+}
+
+/// Pausing stops at the rendered generation
+fn toggle_pause(
+    actions: Res<Input<Action>>,
+    mut paused: ResMut<Paused>,
+    mut scheduler: ResMut<Scheduler>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        paused.0 = !paused.0;
+        scheduler.cancel();
+    }
+}
+
+/// Advances by the stride while paused
+fn request_step(
+    actions: Res<Input<Action>>,
+    paused: Res<Paused>,
+    stride: Res<Stride>,
+    mut scheduler: ResMut<Scheduler>,
+) {
+    if actions.just_pressed(Action::Step) && paused.0 {
+        scheduler.request(**stride);
+    }
+}
+
+fn change_speed(actions: Res<Input<Action>>, mut speed: ResMut<Speed>) {
+    if actions.just_pressed(Action::Unlimited) {
+        speed.toggle_unlimited();
+    } else if actions.just_pressed(Action::Faster) {
+        speed.scale(2.0);
+    } else if actions.just_pressed(Action::Slower) {
+        speed.scale(0.5);
+    }
+}
+
+fn change_stride(actions: Res<Input<Action>>, mut stride: ResMut<Stride>) {
+    if actions.just_pressed(Action::LongerStride) {
+        stride.double();
+    } else if actions.just_pressed(Action::ShorterStride) {
+        stride.halve();
+    }
+}
+
+fn toggle_auto_grow(actions: Res<Input<Action>>, mut auto_grow: ResMut<AutoGrow>) {
+    if actions.just_pressed(Action::AutoGrow) {
+        auto_grow.0 = !auto_grow.0;
+    }
+}
+
+/// Expands the grid around the pattern and moves the camera along,
+/// so the Cells stay in place on screen although the grid is centered around the origin
+#[allow(clippy::cast_precision_loss)]
+fn grow(
+    auto_grow: Res<AutoGrow>,
+    mut simulation: ResMut<Simulation>,
+    mut cameras: Query<&mut Transform, With<MainCamera>>,
+) {
+    let too_large = simulation.row_count.max(simulation.col_count) >= MAX_GROWN_SIZE;
+    if !auto_grow.0 || !simulation.is_changed() || too_large {
+        return;
+    }
+    // Only mark the simulation as changed if the grid actually grew
+    let growth = simulation.bypass_change_detection().grow(GROWTH_MARGIN);
+    if growth.is_empty() {
+        return;
+    }
+    simulation.set_changed();
+    let shift = Vec2::new(
+        growth.left as f32 - growth.right as f32,
+        growth.bottom as f32 - growth.top as f32,
+    ) * CELL_SIZE
+        / 2.0;
+    for mut transform in &mut cameras {
+        transform.translation += shift.extend(0.0);
+    }
+}
+
+fn record_history(simulation: Res<Simulation>, mut history: ResMut<Rewind>) {
+    if simulation.is_changed() {
+        history.record(&simulation);
+    }
+}
+
+fn rewind(
+    actions: Res<Input<Action>>,
+    mut paused: ResMut<Paused>,
+    mut scheduler: ResMut<Scheduler>,
+    mut history: ResMut<Rewind>,
+    mut simulation: ResMut<Simulation>,
+) {
+    if actions.just_pressed(Action::Rewind) {
+        paused.0 = true;
+        scheduler.cancel();
+        history.rewind(&mut simulation);
+    }
+}
+
+/// Turns the time of the frame into due strides at the tick rate
+fn schedule(
+    time: Res<Time>,
+    speed: Res<Speed>,
+    stride: Res<Stride>,
+    paused: Res<Paused>,
+    mut scheduler: ResMut<Scheduler>,
+) {
+    let Speed::GenerationsPerSecond(rate) = *speed else {
+        return;
+    };
+    if paused.0 {
+        return;
+    }
+    let period = Duration::from_secs_f32(rate.recip());
+    scheduler.elapsed += time.delta();
+    while scheduler.elapsed >= period {
+        scheduler.elapsed -= period;
+        scheduler.request(**stride);
+    }
+}
+
+/// The only system stepping the simulation, one stride at a time,
+/// every Cell with the rules of its zone if there are `RuleZones`
+fn step(
+    speed: Res<Speed>,
+    stride: Res<Stride>,
+    paused: Res<Paused>,
+    zones: Option<Res<RuleZones>>,
+    mut scheduler: ResMut<Scheduler>,
+    mut simulation: ResMut<Simulation>,
+) {
+    let unlimited = *speed == Speed::Unlimited && !paused.0;
+    let start = Instant::now();
+    while (unlimited || scheduler.pending > 0) && start.elapsed() < FRAME_BUDGET {
+        let generations = if unlimited {
+            **stride
+        } else {
+            usize::try_from(scheduler.pending).map_or(**stride, |pending| pending.min(**stride))
+        };
+        match &zones {
+            Some(zones) => zones.0.advance(&mut simulation, generations),
+            None => simulation.advance(generations),
+        }
+        scheduler.pending = scheduler.pending.saturating_sub(generations as u64);
+    }
+}
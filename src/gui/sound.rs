@@ -0,0 +1,201 @@
+use super::{input::Action, localization::Locale, simulation::SimulationSet, Simulation};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::{
+    sonification::{self, Note},
+    stop::{StopCondition, Stopper},
+    Generation,
+};
+use std::time::Duration;
+
+/// Sample rate the effects are synthesized at
+const SAMPLE_RATE: u32 = 44_100;
+/// Generations tick this often at most, faster simulations would buzz
+const TICK_INTERVAL: Duration = Duration::from_millis(80);
+/// Ticks are quieter than the chimes
+const TICK_VOLUME: f32 = 0.25;
+/// Repeating grids with at most this period count as stabilized, still lifes and blinkers
+const SETTLED_PERIOD: u64 = 2;
+
+/// Volume of the effects, changed in the "Sound" window
+/// - `muted` => Toggled with `K`, silences every effect
+/// - `ticks` => Whether generations tick, the chimes play regardless
+#[derive(Resource)]
+pub struct SoundSettings {
+    pub volume: f32,
+    pub muted: bool,
+    pub ticks: bool,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            volume: 0.5,
+            muted: false,
+            ticks: true,
+        }
+    }
+}
+
+/// The effects synthesized at startup
+#[derive(Resource)]
+struct Effects {
+    tick: Handle<AudioSource>,
+    /// Rising, for a grid that settled into a still life or oscillator
+    settled: Handle<AudioSource>,
+    /// Falling, for a grid that died out
+    extinct: Handle<AudioSource>,
+}
+
+/// Watches the generations for the events the effects are tied to
+#[derive(Resource)]
+struct Listener {
+    stopper: Stopper,
+    last: Option<Generation>,
+    /// Whether the chime already played for the grid being settled or extinct
+    chimed: bool,
+    last_tick: Option<Duration>,
+}
+
+impl Default for Listener {
+    fn default() -> Self {
+        Self {
+            stopper: settled_stopper(),
+            last: None,
+            chimed: false,
+            last_tick: None,
+        }
+    }
+}
+
+/// Ticks with every generation and chimes once the grid stabilizes or dies out
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoundSettings>()
+            .init_resource::<Listener>()
+            .add_startup_system(synthesize_effects)
+            .add_system(toggle_mute)
+            .add_system(sound_ui)
+            .add_system(play_effects.after(SimulationSet::Step));
+    }
+}
+
+fn settled_stopper() -> Stopper {
+    Stopper::new(vec![
+        StopCondition::Extinction,
+        StopCondition::Period(SETTLED_PERIOD),
+    ])
+}
+
+/// Sine tones of `keys` held for `millis` and decaying with `half_life_millis`
+fn effect(
+    sources: &mut Assets<AudioSource>,
+    keys: &[u8],
+    millis: u64,
+    half_life_millis: u64,
+) -> Handle<AudioSource> {
+    let notes: Vec<_> = keys
+        .iter()
+        .map(|&key| Note { key, velocity: 100 })
+        .collect();
+    let mut samples = sonification::synthesize(&notes, Duration::from_millis(millis), SAMPLE_RATE);
+    sonification::decay(
+        &mut samples,
+        Duration::from_millis(half_life_millis),
+        SAMPLE_RATE,
+    );
+    sources.add(AudioSource {
+        bytes: sonification::wav_bytes(&samples, SAMPLE_RATE).into(),
+    })
+}
+
+fn synthesize_effects(mut commands: Commands, mut sources: ResMut<Assets<AudioSource>>) {
+    commands.insert_resource(Effects {
+        tick: effect(&mut sources, &[96], 25, 5),
+        // Major triads, C6 going up and A4 going down
+        settled: effect(&mut sources, &[84, 88, 91], 700, 150),
+        extinct: effect(&mut sources, &[69, 72, 76], 900, 200),
+    });
+}
+
+fn toggle_mute(actions: Res<Input<Action>>, mut settings: ResMut<SoundSettings>) {
+    if actions.just_pressed(Action::Mute) {
+        settings.muted = !settings.muted;
+    }
+}
+
+fn sound_ui(mut contexts: EguiContexts, mut settings: ResMut<SoundSettings>, locale: Res<Locale>) {
+    egui::Window::new(locale.text("window-sound"))
+        .id(egui::Id::new("sound"))
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.checkbox(&mut settings.muted, locale.text("sound-muted"));
+            ui.add_enabled_ui(!settings.muted, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut settings.volume, 0.0..=1.0)
+                        .text(locale.text("sound-volume")),
+                );
+                ui.checkbox(&mut settings.ticks, locale.text("sound-ticks"));
+            });
+        });
+}
+
+/// Generations computed within the same frame tick once. Edits, rewinds and new grids
+/// start over, so a grid chimes again once it settles anew.
+fn play_effects(
+    settings: Res<SoundSettings>,
+    effects: Option<Res<Effects>>,
+    audio: Res<Audio>,
+    simulation: Res<Simulation>,
+    time: Res<Time>,
+    mut listener: ResMut<Listener>,
+) {
+    if !simulation.is_changed() {
+        return;
+    }
+    let Some(effects) = effects else {
+        return;
+    };
+    let advanced = listener
+        .last
+        .is_some_and(|last| simulation.generation > last);
+    listener.last = Some(simulation.generation);
+    if !advanced {
+        listener.stopper = settled_stopper();
+        listener.chimed = false;
+        return;
+    }
+    let play = |effect: &Handle<AudioSource>, volume: f32| {
+        if !settings.muted {
+            audio.play_with_settings(
+                effect.clone(),
+                PlaybackSettings::ONCE.with_volume(settings.volume * volume),
+            );
+        }
+    };
+    let stopped = listener.stopper.check(&simulation);
+    match stopped {
+        Some(stopped) if !listener.chimed => {
+            listener.chimed = true;
+            if stopped.condition == StopCondition::Extinction {
+                play(&effects.extinct, 1.0);
+            } else {
+                play(&effects.settled, 1.0);
+            }
+        }
+        Some(_) => {}
+        None => {
+            listener.chimed = false;
+            let now = time.elapsed();
+            let due = listener
+                .last_tick
+                .is_none_or(|last_tick| now.saturating_sub(last_tick) >= TICK_INTERVAL);
+            if settings.ticks && due {
+                listener.last_tick = Some(now);
+                play(&effects.tick, TICK_VOLUME);
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use super::{
+    localization::Locale,
+    presets::Presets,
+    render::{cell_color, pixel_image, CELL_SIZE},
+    theme::{color, Themes},
+    Simulation,
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::{divergence, Automaton, Neighborhood};
+
+/// Space between neighboring panes in world units
+const PANE_GAP: f32 = 4.0 * CELL_SIZE;
+const LABEL_FONT_SIZE: f32 = 3.0 * CELL_SIZE;
+/// Cells differing from the main simulation while showing the difference
+const DIFFERENCE_COLOR: Color = Color::rgb(0.9, 0.15, 0.1);
+
+/// An automaton shown next to the main simulation, stepped in lockstep with it
+struct Pane {
+    automaton: Automaton,
+    sprite: Entity,
+    label: Entity,
+}
+
+/// Automata compared side by side with the main simulation, placed to the right of it.
+/// They start from the grid of the main simulation and follow its edits and generations.
+#[derive(Resource, Default)]
+pub struct SplitView {
+    panes: Vec<Pane>,
+    /// Whether the panes show the XOR difference to the main simulation instead of their grids
+    show_difference: bool,
+    /// Preset and neighborhood of the next pane to be added
+    rule: usize,
+    neighborhood: Neighborhood,
+}
+
+pub struct SplitViewPlugin;
+
+impl Plugin for SplitViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SplitView>()
+            .add_system(split_view_ui)
+            .add_system(step_panes.after(split_view_ui))
+            .add_system(update_panes.after(step_panes));
+    }
+}
+
+fn pane_label(automaton: &Automaton) -> String {
+    format!("{} {:?}", automaton.rule_set, automaton.neighborhood_type)
+}
+
+/// Copies the grid and generation of the main simulation into `automaton`
+fn sync(automaton: &mut Automaton, simulation: &Automaton) {
+    automaton.row_count = simulation.row_count;
+    automaton.col_count = simulation.col_count;
+    automaton.generation = simulation.generation;
+    automaton.grid.clone_from(&simulation.grid);
+}
+
+fn split_view_ui(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut images: ResMut<Assets<Image>>,
+    mut split_view: ResMut<SplitView>,
+    simulation: Res<Simulation>,
+    presets: Res<Presets>,
+    locale: Res<Locale>,
+) {
+    let split_view = &mut *split_view;
+    // Rules available for comparison
+    let presets = presets
+        .presets()
+        .map(|(_, preset)| preset)
+        .collect::<Vec<_>>();
+    egui::Window::new(locale.text("window-split-view"))
+        .id(egui::Id::new("split-view"))
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            split_view.panes.retain(|pane| {
+                let keep = ui
+                    .horizontal(|ui| {
+                        ui.label(
+                            locale.format(
+                                "split-view-pane",
+                                [
+                                    ("pane", pane_label(&pane.automaton).into()),
+                                    (
+                                        "differing",
+                                        divergence::differing(
+                                            &simulation.grid,
+                                            &pane.automaton.grid,
+                                        )
+                                        .into(),
+                                    ),
+                                ],
+                            ),
+                        );
+                        !ui.button(locale.text("split-view-remove")).clicked()
+                    })
+                    .inner;
+                if !keep {
+                    commands.entity(pane.sprite).despawn();
+                    commands.entity(pane.label).despawn();
+                }
+                keep
+            });
+
+            ui.checkbox(
+                &mut split_view.show_difference,
+                locale.text("split-view-show-difference"),
+            );
+
+            ui.separator();
+            egui::ComboBox::from_label(locale.text("split-view-rules"))
+                .selected_text(presets[split_view.rule].name.as_str())
+                .show_ui(ui, |ui| {
+                    for (index, preset) in presets.iter().enumerate() {
+                        ui.selectable_value(&mut split_view.rule, index, preset.name.as_str());
+                    }
+                });
+            ui.horizontal(|ui| {
+                for neighborhood in [Neighborhood::Moore, Neighborhood::VonNeumann] {
+                    ui.radio_value(
+                        &mut split_view.neighborhood,
+                        neighborhood,
+                        format!("{neighborhood:?}"),
+                    );
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button(locale.text("split-view-add")).clicked() {
+                    let mut automaton = Automaton::builder()
+                        .neighborhood_type(split_view.neighborhood)
+                        .rule_set(presets[split_view.rule].rule_set.clone())
+                        .build();
+                    sync(&mut automaton, &simulation);
+                    let sprite = commands
+                        .spawn(SpriteBundle {
+                            texture: images.add(Image::default()),
+                            ..default()
+                        })
+                        .id();
+                    let label = commands
+                        .spawn(Text2dBundle {
+                            text: Text::from_section(
+                                pane_label(&automaton),
+                                TextStyle {
+                                    font_size: LABEL_FONT_SIZE,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                            ),
+                            ..default()
+                        })
+                        .id();
+                    split_view.panes.push(Pane {
+                        automaton,
+                        sprite,
+                        label,
+                    });
+                }
+                if ui.button(locale.text("split-view-resync")).clicked() {
+                    for pane in &mut split_view.panes {
+                        sync(&mut pane.automaton, &simulation);
+                    }
+                }
+            });
+        });
+}
+
+/// Advances the panes to the generation of the main simulation,
+/// edits of the main grid are copied over
+#[allow(clippy::cast_possible_truncation)]
+fn step_panes(simulation: Res<Simulation>, mut split_view: ResMut<SplitView>) {
+    if !simulation.is_changed() {
+        return;
+    }
+    for pane in &mut split_view.panes {
+        let automaton = &mut pane.automaton;
+        let resized = (automaton.row_count, automaton.col_count)
+            != (simulation.row_count, simulation.col_count);
+        if resized || automaton.generation >= simulation.generation {
+            sync(automaton, &simulation);
+        } else {
+            automaton.advance((simulation.generation - automaton.generation) as usize);
+        }
+    }
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn update_panes(
+    simulation: Res<Simulation>,
+    split_view: Res<SplitView>,
+    themes: Res<Themes>,
+    mut images: ResMut<Assets<Image>>,
+    mut sprites: Query<(&Handle<Image>, &mut Sprite, &mut Transform), Without<Text>>,
+    mut labels: Query<&mut Transform, With<Text>>,
+) {
+    if !simulation.is_changed() && !split_view.is_changed() && !themes.is_changed() {
+        return;
+    }
+    let size = Vec2::new(simulation.col_count as f32, simulation.row_count as f32) * CELL_SIZE;
+    for (index, pane) in split_view.panes.iter().enumerate() {
+        let center = Vec2::new((index + 1) as f32 * (size.x + PANE_GAP), 0.0);
+        if let Ok(mut transform) = labels.get_mut(pane.label) {
+            transform.translation = (center + Vec2::new(0.0, size.y / 2.0 + LABEL_FONT_SIZE))
+                .extend(transform.translation.z);
+        }
+        let Ok((handle, mut sprite, mut transform)) = sprites.get_mut(pane.sprite) else {
+            continue;
+        };
+        transform.translation = center.extend(transform.translation.z);
+        sprite.custom_size = Some(size);
+        let Some(image) = images.get_mut(handle) else {
+            continue;
+        };
+
+        let automaton = &pane.automaton;
+        let data = if split_view.show_difference {
+            divergence::difference(&simulation.grid, &automaton.grid)
+                .iter()
+                .flatten()
+                .flat_map(|&differs| {
+                    let color = if differs {
+                        DIFFERENCE_COLOR
+                    } else {
+                        color(themes.current().dead)
+                    };
+                    color.as_rgba_u8()
+                })
+                .collect()
+        } else {
+            automaton
+                .grid
+                .iter()
+                .flatten()
+                .flat_map(|cell| {
+                    cell_color(themes.current(), cell, automaton.rule_set.decay).as_rgba_u8()
+                })
+                .collect()
+        };
+        *image = pixel_image(automaton.col_count as u32, automaton.row_count as u32, data);
+    }
+}
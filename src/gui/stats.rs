@@ -0,0 +1,143 @@
+use super::{localization::Locale, Simulation};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{
+        self,
+        plot::{Legend, Line, Plot, PlotPoints, Points},
+    },
+    EguiContexts,
+};
+use cellular_automata::{
+    mean_field::{self, FixedPoint},
+    Neighborhood, RuleSet, Sample, Stats,
+};
+
+/// Generations kept for the population chart
+const HISTORY_LENGTH: usize = 500;
+/// Densities the mean-field map is plotted at
+const MEAN_FIELD_SAMPLES: usize = 101;
+const FIXED_POINT_RADIUS: f32 = 3.0;
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct PopulationHistory(pub Stats);
+
+impl Default for PopulationHistory {
+    fn default() -> Self {
+        Self(Stats::with_capacity(HISTORY_LENGTH))
+    }
+}
+
+pub struct StatsPlugin;
+
+/// The mean-field map of the rules of the simulation, recomputed when they change
+#[derive(Resource, Default)]
+struct MeanField {
+    rules: Option<(RuleSet, Neighborhood)>,
+    curve: Vec<[f64; 2]>,
+    fixed_points: Vec<FixedPoint>,
+}
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PopulationHistory>()
+            .init_resource::<MeanField>()
+            .add_system(record)
+            .add_system(update_mean_field)
+            .add_system(population_chart.after(record).after(update_mean_field));
+    }
+}
+
+/// Samples the simulation once per rendered generation
+fn record(simulation: Res<Simulation>, mut history: ResMut<PopulationHistory>) {
+    let generation = history.latest().map(|sample| sample.generation);
+    if generation != Some(simulation.generation) {
+        history.record(&simulation);
+    }
+}
+
+fn update_mean_field(simulation: Res<Simulation>, mut mean_field: ResMut<MeanField>) {
+    let rules = (simulation.rule_set.clone(), simulation.neighborhood_type);
+    if mean_field.rules.as_ref() != Some(&rules) {
+        mean_field.curve = mean_field::curve(&rules.0, rules.1, MEAN_FIELD_SAMPLES);
+        mean_field.fixed_points = mean_field::fixed_points(&rules.0, rules.1);
+        mean_field.rules = Some(rules);
+    }
+}
+
+/// The population over time, and below the mean-field map of the rules with its fixed points
+/// and the current density of the grid
+#[allow(clippy::cast_precision_loss)]
+fn population_chart(
+    mut contexts: EguiContexts,
+    history: Res<PopulationHistory>,
+    mean_field: Res<MeanField>,
+    simulation: Res<Simulation>,
+    locale: Res<Locale>,
+) {
+    let line = |name: &str, value: fn(&Sample) -> usize| {
+        let points = history
+            .samples()
+            .map(|sample| [sample.generation.0 as f64, value(sample) as f64]);
+        Line::new(points.collect::<PlotPoints>()).name(name)
+    };
+    egui::Window::new(locale.text("window-population"))
+        .id(egui::Id::new("population"))
+        .show(contexts.ctx_mut(), |ui| {
+            Plot::new("population_history")
+                .height(120.0)
+                .legend(Legend::default())
+                .show(ui, |plot_ui| {
+                    plot_ui.line(line(&locale.text("stats-population"), |sample| {
+                        sample.population
+                    }));
+                    plot_ui.line(line(&locale.text("stats-births"), |sample| sample.births));
+                    plot_ui.line(line(&locale.text("stats-deaths"), |sample| sample.deaths));
+                });
+            egui::CollapsingHeader::new(locale.text("stats-mean-field")).show(ui, |ui| {
+                let cells = (simulation.row_count * simulation.col_count).max(1) as f64;
+                let density = simulation.population() as f64 / cells;
+                let fixed_points = mean_field
+                    .fixed_points
+                    .iter()
+                    .map(|fixed_point| [fixed_point.density, fixed_point.density])
+                    .collect::<PlotPoints>();
+                Plot::new("mean_field")
+                    .height(160.0)
+                    .data_aspect(1.0)
+                    .include_x(0.0)
+                    .include_x(1.0)
+                    .include_y(0.0)
+                    .include_y(1.0)
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(
+                            Line::new(mean_field.curve.iter().copied().collect::<PlotPoints>())
+                                .name(locale.text("stats-next-density")),
+                        );
+                        plot_ui.line(
+                            Line::new(PlotPoints::new(vec![[0.0, 0.0], [1.0, 1.0]]))
+                                .name(locale.text("stats-unchanged")),
+                        );
+                        plot_ui.points(
+                            Points::new(fixed_points)
+                                .radius(FIXED_POINT_RADIUS)
+                                .name(locale.text("stats-fixed-points")),
+                        );
+                        plot_ui.points(
+                            Points::new(vec![[density, density]])
+                                .radius(FIXED_POINT_RADIUS)
+                                .name(locale.text("stats-current-density")),
+                        );
+                    });
+                for fixed_point in &mean_field.fixed_points {
+                    ui.label(locale.format(
+                        "stats-fixed-point",
+                        [
+                            ("density", format!("{:.3}", fixed_point.density).into()),
+                            ("stable", fixed_point.stable.to_string().into()),
+                        ],
+                    ));
+                }
+            });
+        });
+}
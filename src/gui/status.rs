@@ -0,0 +1,68 @@
+use super::{
+    editor::EditSymmetry,
+    localization::Locale,
+    simulation::{Paused, Scheduler, Speed, Stride},
+    Simulation,
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::tui;
+
+pub struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(status);
+    }
+}
+
+/// Shows the state of the simulation in the window title and a status bar
+#[allow(clippy::too_many_arguments)]
+fn status(
+    mut contexts: EguiContexts,
+    simulation: Res<Simulation>,
+    speed: Res<Speed>,
+    stride: Res<Stride>,
+    paused: Res<Paused>,
+    scheduler: Res<Scheduler>,
+    symmetry: Res<EditSymmetry>,
+    locale: Res<Locale>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let mut status = format!(
+        "{} | {}",
+        tui::localized_status_line(&simulation, &locale),
+        speed.describe(&locale)
+    );
+    if **stride > 1 {
+        status.push_str(&format!(" x{}", **stride));
+    }
+    if scheduler.pending() > 0 {
+        let behind = locale.format(
+            "status-behind",
+            [("generations", scheduler.pending().into())],
+        );
+        status.push_str(&format!(" | {behind}"));
+    }
+    if let Some(symmetry) = symmetry.0 {
+        let mirroring = locale.format(
+            "status-mirroring",
+            [("symmetry", symmetry.to_string().into())],
+        );
+        status.push_str(&format!(" | {mirroring}"));
+    }
+    if paused.0 {
+        status.push_str(&format!(" | {}", locale.text("status-paused")));
+    }
+
+    if let Ok(mut window) = windows.get_single_mut() {
+        let title = format!("{} - {status}", locale.text("status-title"));
+        // Only assigning on change keeps the window from being updated every frame
+        if window.title != title {
+            window.title = title;
+        }
+    }
+    egui::TopBottomPanel::top("status").show(contexts.ctx_mut(), |ui| {
+        ui.label(status);
+    });
+}
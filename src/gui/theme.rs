@@ -0,0 +1,104 @@
+use super::input::Action;
+use bevy::prelude::*;
+use cellular_automata::{theme::Rgb, Theme};
+use std::{fs, path::Path};
+
+/// Directory searched for TOML themes at startup
+const THEME_DIRECTORY: &str = "themes";
+
+/// The built in themes followed by the ones found in `THEME_DIRECTORY`,
+/// `Y` switches to the next one
+#[derive(Resource)]
+pub struct Themes {
+    available: Vec<Theme>,
+    current: usize,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        let mut available = Theme::built_in();
+        available.extend(load_directory(Path::new(THEME_DIRECTORY)));
+        Self {
+            available,
+            current: 0,
+        }
+    }
+}
+
+impl Themes {
+    pub fn current(&self) -> &Theme {
+        &self.available[self.current]
+    }
+
+    pub fn next(&mut self) {
+        self.current = (self.current + 1) % self.available.len();
+    }
+
+    /// Switches to `theme`, adding it if there's none of the same name yet
+    pub fn select(&mut self, theme: Theme) {
+        self.current = self
+            .available
+            .iter()
+            .position(|available| available.name == theme.name)
+            .unwrap_or_else(|| {
+                self.available.push(theme);
+                self.available.len() - 1
+            });
+    }
+
+    /// Switches to the available theme called `name`, `false` if there's none
+    pub fn select_named(&mut self, name: &str) -> bool {
+        let position = self.available.iter().position(|theme| theme.name == name);
+        if let Some(position) = position {
+            self.current = position;
+        }
+        position.is_some()
+    }
+}
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Themes>()
+            .add_system(cycle_theme)
+            .add_system(apply_background.after(cycle_theme));
+    }
+}
+
+pub fn color([r, g, b]: Rgb) -> Color {
+    Color::rgb_u8(r, g, b)
+}
+
+/// Loads every `.toml` file in `directory`, skipping invalid ones
+fn load_directory(directory: &Path) -> Vec<Theme> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "toml")
+        })
+        .filter_map(|path| {
+            Theme::load(&path)
+                .map_err(|e| warn!("Skipping {}: {e}", path.display()))
+                .ok()
+        })
+        .collect()
+}
+
+fn cycle_theme(actions: Res<Input<Action>>, mut themes: ResMut<Themes>) {
+    if actions.just_pressed(Action::CycleTheme) {
+        themes.next();
+        info!("Theme: {}", themes.current().name);
+    }
+}
+
+fn apply_background(themes: Res<Themes>, mut clear_color: ResMut<ClearColor>) {
+    if themes.is_changed() {
+        clear_color.0 = color(themes.current().background);
+    }
+}
@@ -0,0 +1,156 @@
+use super::{localization::Locale, simulation::SimulationSet, theme::Themes, Simulation};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use cellular_automata::{
+    timelapse::{Cadence, Timelapse},
+    Generation, Grid,
+};
+use std::{
+    sync::mpsc::{self, Sender},
+    thread,
+};
+
+/// A generation due for recording, with the decay of the rule set it was computed with
+type Frame = (Generation, Grid, usize);
+
+/// Settings of the "Timelapse" window and the recording started from it
+#[derive(Resource)]
+struct Recorder {
+    /// `.gif` for an animated GIF, otherwise a directory of numbered PNG frames
+    path: String,
+    every: u64,
+    scale: u32,
+    delay_ms: u32,
+    recording: Option<Recording>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self {
+            path: "timelapse.gif".to_owned(),
+            every: 10,
+            scale: 2,
+            delay_ms: 50,
+            recording: None,
+        }
+    }
+}
+
+/// Frames are encoded in a thread so the simulation keeps running at full speed,
+/// the recording is finished once the sender is dropped
+struct Recording {
+    cadence: Cadence,
+    sender: Sender<Frame>,
+    frame_count: usize,
+}
+
+/// Records every K-th rendered generation while the simulation runs.
+/// Generations computed within the same frame can't be told apart, so with a stride or an
+/// unlimited speed the first rendered one at or after each multiple of K is recorded.
+pub struct TimelapsePlugin;
+
+impl Plugin for TimelapsePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Recorder>()
+            .add_system(timelapse_ui)
+            .add_system(record.after(SimulationSet::Step));
+    }
+}
+
+fn start(recorder: &Recorder, themes: &Themes) -> Option<Recording> {
+    let timelapse = Timelapse::create(
+        &recorder.path,
+        recorder.every,
+        themes.current().clone(),
+        recorder.scale,
+        recorder.delay_ms,
+    );
+    let mut timelapse = match timelapse {
+        Ok(timelapse) => timelapse,
+        Err(e) => {
+            warn!("Couldn't create {}: {e}", recorder.path);
+            return None;
+        }
+    };
+    let (sender, receiver) = mpsc::channel::<Frame>();
+    let path = recorder.path.clone();
+    thread::spawn(move || {
+        for (generation, grid, decay) in receiver {
+            if let Err(e) = timelapse.write_frame(generation, &grid, decay) {
+                warn!("Couldn't record generation {generation} into {path}: {e}");
+            }
+        }
+        info!("Recorded {} frames into {path}", timelapse.frame_count());
+    });
+    Some(Recording {
+        cadence: Cadence::new(recorder.every),
+        sender,
+        frame_count: 0,
+    })
+}
+
+fn timelapse_ui(
+    mut contexts: EguiContexts,
+    mut recorder: ResMut<Recorder>,
+    themes: Res<Themes>,
+    locale: Res<Locale>,
+) {
+    egui::Window::new(locale.text("window-timelapse"))
+        .id(egui::Id::new("timelapse"))
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let recording = recorder.recording.is_some();
+            ui.add_enabled_ui(!recording, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(locale.text("timelapse-path"));
+                    ui.text_edit_singleline(&mut recorder.path)
+                        .on_hover_text(locale.text("timelapse-path-hint"));
+                });
+                ui.add(
+                    egui::DragValue::new(&mut recorder.every)
+                        .clamp_range(1..=1_000_000)
+                        .prefix(format!("{} ", locale.text("timelapse-every"))),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut recorder.scale)
+                        .clamp_range(1..=16)
+                        .suffix(format!(" {}", locale.text("timelapse-scale"))),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut recorder.delay_ms)
+                        .clamp_range(10..=5000)
+                        .suffix(format!(" {}", locale.text("timelapse-delay"))),
+                );
+            });
+            if let Some(recording) = &recorder.recording {
+                ui.label(locale.format(
+                    "timelapse-recording",
+                    [("frames", recording.frame_count.into())],
+                ));
+                if ui.button(locale.text("timelapse-stop")).clicked() {
+                    recorder.recording = None;
+                }
+            } else if ui.button(locale.text("timelapse-start")).clicked() {
+                recorder.recording = start(&recorder, &themes);
+            }
+        });
+}
+
+fn record(mut recorder: ResMut<Recorder>, simulation: Res<Simulation>) {
+    let Some(recording) = &mut recorder.recording else {
+        return;
+    };
+    if !recording.cadence.tick(simulation.generation) {
+        return;
+    }
+    let frame = (
+        simulation.generation,
+        simulation.grid.clone(),
+        simulation.rule_set.decay,
+    );
+    if recording.sender.send(frame).is_ok() {
+        recording.frame_count += 1;
+    } else {
+        recorder.recording = None;
+    }
+}
@@ -0,0 +1,234 @@
+use super::{
+    camera::MainCamera,
+    editor::{BrushTool, FigureTool, HoveredCell, Tool},
+    input::{Action, InputMap},
+    localization::Locale,
+    render::world_position,
+    Simulation,
+};
+use bevy::{prelude::*, utils::HashSet, window::PrimaryWindow};
+use bevy_egui::{
+    egui::{self, Color32, Stroke},
+    EguiContexts,
+};
+use cellular_automata::{brush::Shape, figure::Figure, Cell};
+
+const MAX_RADIUS: usize = 32;
+/// Density a spray starts out with
+const SPRAY_DENSITY: f64 = 0.2;
+const PREVIEW_COLOR: Color32 = Color32::YELLOW;
+/// Translucent, so the Cells below a pending figure remain visible
+const PENDING_COLOR: Color32 = Color32::from_rgba_premultiplied(96, 96, 0, 96);
+
+/// Settings and previews of the editing tools
+pub struct ToolsPlugin;
+
+impl Plugin for ToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems((tool_keys, tools_ui, brush_preview, figure_preview));
+    }
+}
+
+fn tool_keys(actions: Res<Input<Action>>, mut tool: ResMut<Tool>, mut brush: ResMut<BrushTool>) {
+    if actions.just_pressed(Action::Brush) {
+        *tool = if *tool == Tool::Brush {
+            Tool::Toggle
+        } else {
+            Tool::Brush
+        };
+    }
+    if actions.just_pressed(Action::CycleFigure) {
+        *tool = tool.next_figure();
+    }
+    if actions.just_pressed(Action::BrushLarger) {
+        brush.brush.radius = (brush.brush.radius + 1).min(MAX_RADIUS);
+    }
+    if actions.just_pressed(Action::BrushSmaller) {
+        brush.brush.radius = brush.brush.radius.saturating_sub(1);
+    }
+    if actions.just_pressed(Action::SwapPaint) {
+        brush.paint = if brush.paint.is_dead() {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        };
+    }
+}
+
+fn tools_ui(
+    mut contexts: EguiContexts,
+    map: Res<InputMap>,
+    mut tool: ResMut<Tool>,
+    mut brush: ResMut<BrushTool>,
+    mut figure: ResMut<FigureTool>,
+    locale: Res<Locale>,
+) {
+    egui::Window::new(locale.text("window-tools"))
+        .id(egui::Id::new("tools"))
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut *tool, Tool::Toggle, locale.text("tools-toggle"));
+                ui.radio_value(&mut *tool, Tool::Brush, locale.text("tools-brush"))
+                    .on_hover_text(map.describe(Action::Brush, &locale));
+            });
+            ui.horizontal(|ui| {
+                for (id, shape) in [
+                    ("tools-line", Figure::Line),
+                    ("tools-rectangle", Figure::Rectangle),
+                    ("tools-circle", Figure::Circle),
+                ] {
+                    ui.radio_value(&mut *tool, Tool::Figure(shape), locale.text(id))
+                        .on_hover_text(map.describe(Action::CycleFigure, &locale));
+                }
+            });
+            ui.checkbox(&mut figure.filled, locale.text("tools-filled"));
+            ui.horizontal(|ui| {
+                ui.label(locale.text("tools-paint"));
+                let paint = &mut brush.paint;
+                ui.radio_value(paint, Cell::Alive, locale.text("tools-alive"));
+                ui.radio_value(paint, Cell::Dead, locale.text("tools-dead"));
+            })
+            .response
+            .on_hover_text(map.describe(Action::SwapPaint, &locale));
+            ui.separator();
+            ui.label(locale.text("tools-brush"));
+            ui.horizontal(|ui| {
+                let shape = &mut brush.brush.shape;
+                for (id, choice) in [
+                    ("tools-circle", Shape::Circle),
+                    ("tools-square", Shape::Square),
+                    ("tools-line", Shape::Line),
+                ] {
+                    if ui.radio(*shape == choice, locale.text(id)).clicked() {
+                        *shape = choice;
+                    }
+                }
+                let spraying = matches!(shape, Shape::Spray { .. });
+                if ui.radio(spraying, locale.text("tools-spray")).clicked() && !spraying {
+                    *shape = Shape::Spray {
+                        density: SPRAY_DENSITY,
+                    };
+                }
+            });
+            ui.add(
+                egui::Slider::new(&mut brush.brush.radius, 0..=MAX_RADIUS)
+                    .text(locale.text("tools-radius")),
+            )
+            .on_hover_text(format!(
+                "{} / {}",
+                map.describe(Action::BrushLarger, &locale),
+                map.describe(Action::BrushSmaller, &locale)
+            ));
+            if let Shape::Spray { density } = &mut brush.brush.shape {
+                ui.add(egui::Slider::new(density, 0.01..=1.0).text(locale.text("tools-density")));
+            }
+        });
+}
+
+/// Maps fractional (`col`, `row`) grid coordinates to egui's screen coordinates
+pub fn screen_position(
+    simulation: &Simulation,
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    col: f32,
+    row: f32,
+) -> Option<egui::Pos2> {
+    let world = world_position(simulation, Vec2::new(col, row));
+    // Viewport coordinates start at the bottom left, egui's at the top left
+    camera
+        .world_to_viewport(camera_transform, world.extend(0.0))
+        .map(|position| egui::pos2(position.x, window.height() - position.y))
+}
+
+/// Outlines the Cells the brush covers around the hovered Cell
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_wrap)]
+fn brush_preview(
+    mut contexts: EguiContexts,
+    tool: Res<Tool>,
+    brush: Res<BrushTool>,
+    hovered: Res<HoveredCell>,
+    simulation: Res<Simulation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    let (Tool::Brush, Some((row, col))) = (*tool, hovered.0) else {
+        return;
+    };
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let to_screen =
+        |col, row| screen_position(&simulation, window, camera, camera_transform, col, row);
+    let footprint = brush.brush.footprint().into_iter().collect::<HashSet<_>>();
+    let painter = contexts
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+    let stroke = Stroke::new(1.5, PREVIEW_COLOR);
+    for &(row_offset, col_offset) in &footprint {
+        let top = (row as isize + row_offset) as f32;
+        let left = (col as isize + col_offset) as f32;
+        // Edges between a covered and an uncovered Cell
+        let edges = [
+            ((-1, 0), (left, top), (left + 1.0, top)),
+            ((1, 0), (left, top + 1.0), (left + 1.0, top + 1.0)),
+            ((0, -1), (left, top), (left, top + 1.0)),
+            ((0, 1), (left + 1.0, top), (left + 1.0, top + 1.0)),
+        ];
+        for ((row_step, col_step), start, end) in edges {
+            if footprint.contains(&(row_offset + row_step, col_offset + col_step)) {
+                continue;
+            }
+            if let Some((start, end)) = to_screen(start.0, start.1).zip(to_screen(end.0, end.1)) {
+                painter.line_segment([start, end], stroke);
+            }
+        }
+    }
+}
+
+/// Shades the Cells of the figure being dragged out
+#[allow(clippy::cast_precision_loss)]
+fn figure_preview(
+    mut contexts: EguiContexts,
+    tool: Res<Tool>,
+    figure: Res<FigureTool>,
+    simulation: Res<Simulation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    let Tool::Figure(shape) = *tool else {
+        return;
+    };
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let painter = contexts
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+    for (row, col) in figure.pending(shape, simulation.row_count, simulation.col_count) {
+        let (top, left) = (row as f32, col as f32);
+        let corners = screen_position(&simulation, window, camera, camera_transform, left, top)
+            .zip(screen_position(
+                &simulation,
+                window,
+                camera,
+                camera_transform,
+                left + 1.0,
+                top + 1.0,
+            ));
+        if let Some((top_left, bottom_right)) = corners {
+            painter.rect_filled(
+                egui::Rect::from_two_pos(top_left, bottom_right),
+                0.0,
+                PENDING_COLOR,
+            );
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use super::{localization::Locale, Simulation};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Color32},
+    EguiContexts,
+};
+use cellular_automata::tutorial::{Goal, Tutorial};
+use std::{fs, path::Path};
+
+/// Directory searched for RON tutorials at startup
+const TUTORIAL_DIRECTORY: &str = "tutorials";
+const HIGHLIGHT_COLOR: Color32 = Color32::from_rgb(255, 190, 0);
+const HIGHLIGHT_WIDTH: f32 = 3.0;
+/// Space between a highlighted window and its outline
+const HIGHLIGHT_MARGIN: f32 = 4.0;
+
+/// The built in tutorial followed by the ones found in `TUTORIAL_DIRECTORY`
+#[derive(Resource)]
+struct Tutorials {
+    available: Vec<Tutorial>,
+    /// Index of the tutorial being followed and of its current step
+    current: Option<(usize, usize)>,
+}
+
+impl Default for Tutorials {
+    fn default() -> Self {
+        let mut available = vec![Tutorial::getting_started()];
+        available.extend(load_directory(Path::new(TUTORIAL_DIRECTORY)));
+        Self {
+            available,
+            current: None,
+        }
+    }
+}
+
+/// Lists the tutorials in the "Tutorial" window, which then shows the current step and
+/// advances once its goal is reached. The window a step is about is outlined.
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Tutorials>()
+            .add_system(advance)
+            .add_system(tutorial_ui.after(advance))
+            .add_system(highlight.after(tutorial_ui));
+    }
+}
+
+/// Skips files that aren't valid tutorials
+fn load_directory(directory: &Path) -> Vec<Tutorial> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut tutorials: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+        .filter_map(|path| {
+            Tutorial::load(&path)
+                .map_err(|e| warn!("Skipping {}: {e}", path.display()))
+                .ok()
+        })
+        .collect();
+    tutorials.sort_by(|a, b| a.name.cmp(&b.name));
+    tutorials
+}
+
+/// Only looks at the grid when it or the step changed, finding patterns isn't cheap
+fn advance(mut tutorials: ResMut<Tutorials>, simulation: Res<Simulation>) {
+    if !simulation.is_changed() && !tutorials.is_changed() {
+        return;
+    }
+    let Some((tutorial, step)) = tutorials.current else {
+        return;
+    };
+    let next = tutorials.available[tutorial].advance(step, &simulation);
+    if next != step {
+        tutorials.current = Some((tutorial, next));
+    }
+}
+
+fn tutorial_ui(mut contexts: EguiContexts, mut tutorials: ResMut<Tutorials>, locale: Res<Locale>) {
+    egui::Window::new(locale.text("window-tutorial"))
+        .id(egui::Id::new("tutorial"))
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let Some((index, step)) = tutorials.current else {
+                let mut start = None;
+                for (index, tutorial) in tutorials.available.iter().enumerate() {
+                    if ui.button(&tutorial.name).clicked() {
+                        start = Some(index);
+                    }
+                }
+                if let Some(index) = start {
+                    tutorials.current = Some((index, 0));
+                }
+                return;
+            };
+            let tutorial = &tutorials.available[index];
+            let mut next = None;
+            if let Some(current) = tutorial.steps.get(step) {
+                ui.heading(format!(
+                    "{} ({}/{})",
+                    tutorial.name,
+                    step + 1,
+                    tutorial.steps.len()
+                ));
+                ui.label(&current.text);
+                ui.horizontal(|ui| {
+                    if current.goal == Goal::Continue {
+                        if ui.button(locale.text("tutorial-next")).clicked() {
+                            next = Some(Some((index, step + 1)));
+                        }
+                    } else if ui.button(locale.text("tutorial-skip")).clicked() {
+                        next = Some(Some((index, step + 1)));
+                    }
+                    if ui.button(locale.text("tutorial-quit")).clicked() {
+                        next = Some(None);
+                    }
+                });
+            } else {
+                ui.heading(&tutorial.name);
+                ui.label(locale.text("tutorial-done"));
+                if ui.button(locale.text("tutorial-close")).clicked() {
+                    next = Some(None);
+                }
+            }
+            if let Some(next) = next {
+                tutorials.current = next;
+            }
+        });
+}
+
+/// Outlines the window the current step is about where egui last laid it out
+fn highlight(mut contexts: EguiContexts, tutorials: Res<Tutorials>) {
+    let Some(id) = tutorials
+        .current
+        .and_then(|(index, step)| tutorials.available[index].steps.get(step))
+        .and_then(|step| step.highlight.as_ref())
+    else {
+        return;
+    };
+    let ctx = contexts.ctx_mut();
+    let rect = ctx.memory(|memory| {
+        memory
+            .areas
+            .get(egui::Id::new(id))
+            .map(egui::area::State::rect)
+    });
+    if let Some(rect) = rect {
+        ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("tutorial_highlight"),
+        ))
+        .rect_stroke(
+            rect.expand(HIGHLIGHT_MARGIN),
+            4.0,
+            egui::Stroke::new(HIGHLIGHT_WIDTH, HIGHLIGHT_COLOR),
+        );
+    }
+}
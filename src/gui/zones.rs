@@ -0,0 +1,186 @@
+use super::{camera::MainCamera, localization::Locale, tools::screen_position, Simulation};
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{
+    egui::{self, Color32},
+    EguiContexts,
+};
+use cellular_automata::{
+    grid::BoundingBox,
+    zone::{Border, Zone, Zones},
+    RuleSet,
+};
+
+const OUTLINE_COLOR: Color32 = Color32::from_rgb(80, 200, 255);
+const OUTLINE_WIDTH: f32 = 2.0;
+const LABEL_FONT_SIZE: f32 = 12.0;
+
+/// Zones of the grid following rules of their own, the simulation steps with them
+#[derive(Resource, Default)]
+pub struct RuleZones(pub Zones);
+
+/// The zone being set up in the "Zones" window
+#[derive(Resource)]
+struct Draft {
+    bounds: BoundingBox,
+    rules: String,
+    show_outlines: bool,
+}
+
+impl Default for Draft {
+    fn default() -> Self {
+        Self {
+            bounds: BoundingBox {
+                top: 0,
+                left: 0,
+                bottom: 15,
+                right: 15,
+            },
+            rules: "B36/S23".into(),
+            show_outlines: true,
+        }
+    }
+}
+
+/// Outlines the `RuleZones` on the grid, they're added and removed in the "Zones" window
+pub struct ZonesPlugin;
+
+impl Plugin for ZonesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RuleZones>()
+            .init_resource::<Draft>()
+            .add_system(zones_ui)
+            .add_system(draw_outlines);
+    }
+}
+
+fn zones_ui(
+    mut contexts: EguiContexts,
+    mut zones: ResMut<RuleZones>,
+    mut draft: ResMut<Draft>,
+    simulation: Res<Simulation>,
+    locale: Res<Locale>,
+) {
+    egui::Window::new(locale.text("window-zones"))
+        .id(egui::Id::new("zones"))
+        .default_open(false)
+        .vscroll(true)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.checkbox(&mut draft.show_outlines, locale.text("zones-show-outlines"));
+            let mut border = zones.0.border;
+            egui::ComboBox::from_label(locale.text("zones-border"))
+                .selected_text(border.to_string())
+                .show_ui(ui, |ui| {
+                    for option in Border::ALL {
+                        ui.selectable_value(&mut border, option, option.to_string());
+                    }
+                });
+            // Only touch the resource if the border actually changed
+            if border != zones.0.border {
+                zones.0.border = border;
+            }
+            let max_row = simulation.row_count.saturating_sub(1);
+            let max_col = simulation.col_count.saturating_sub(1);
+            let bounds = &mut draft.bounds;
+            ui.horizontal(|ui| {
+                ui.label(locale.text("zones-top-left"));
+                ui.add(egui::DragValue::new(&mut bounds.top).clamp_range(0..=max_row));
+                ui.add(egui::DragValue::new(&mut bounds.left).clamp_range(0..=max_col));
+            });
+            ui.horizontal(|ui| {
+                ui.label(locale.text("zones-bottom-right"));
+                ui.add(egui::DragValue::new(&mut bounds.bottom).clamp_range(bounds.top..=max_row));
+                ui.add(egui::DragValue::new(&mut bounds.right).clamp_range(bounds.left..=max_col));
+            });
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut draft.rules)
+                    .on_hover_text(locale.text("zones-rules"));
+                let rule_set = draft.rules.parse::<RuleSet>();
+                if ui
+                    .add_enabled(
+                        rule_set.is_ok(),
+                        egui::Button::new(locale.text("zones-add")),
+                    )
+                    .clicked()
+                {
+                    if let Ok(rule_set) = rule_set {
+                        zones.0.zones.push(Zone::new(draft.bounds, rule_set));
+                    }
+                }
+            });
+            ui.label(locale.text("zones-order"));
+            let mut remove = None;
+            egui::Grid::new("zones").striped(true).show(ui, |ui| {
+                for (index, zone) in zones.0.zones.iter().enumerate() {
+                    let bounds = zone.bounds;
+                    ui.label(locale.format(
+                        "zones-bounds",
+                        [
+                            ("top", bounds.top.into()),
+                            ("left", bounds.left.into()),
+                            ("bottom", bounds.bottom.into()),
+                            ("right", bounds.right.into()),
+                        ],
+                    ));
+                    ui.label(zone.rule_set.to_string());
+                    if ui.button(locale.text("zones-remove")).clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(index) = remove {
+                zones.0.zones.remove(index);
+            }
+        });
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn draw_outlines(
+    mut contexts: EguiContexts,
+    zones: Res<RuleZones>,
+    draft: Res<Draft>,
+    simulation: Res<Simulation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    if !draft.show_outlines || zones.0.is_empty() {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let painter = contexts
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+    let position = |col: usize, row: usize| {
+        screen_position(
+            &simulation,
+            window,
+            camera,
+            camera_transform,
+            col as f32,
+            row as f32,
+        )
+    };
+    for zone in &zones.0.zones {
+        let bounds = zone.bounds;
+        let (Some(top_left), Some(bottom_right)) = (
+            position(bounds.left, bounds.top),
+            position(bounds.right + 1, bounds.bottom + 1),
+        ) else {
+            continue;
+        };
+        let rect = egui::Rect::from_two_pos(top_left, bottom_right);
+        painter.rect_stroke(rect, 0.0, egui::Stroke::new(OUTLINE_WIDTH, OUTLINE_COLOR));
+        painter.text(
+            rect.left_top() + egui::vec2(OUTLINE_WIDTH, OUTLINE_WIDTH),
+            egui::Align2::LEFT_TOP,
+            zone.rule_set.to_string(),
+            egui::FontId::proportional(LABEL_FONT_SIZE),
+            OUTLINE_COLOR,
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Run-length compressed snapshots of past generations for rewinding
+
+use crate::{Automaton, Cell, Generation, Grid};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// State of an `Automaton` with its grid stored as runs of equal `Cell::state_byte`s.
+///
+/// Each run takes a state byte and its length as LEB128, so a sparse grid takes a few bytes
+/// per alive Cell instead of 16 bytes per Cell.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub generation: Generation,
+    pub row_count: usize,
+    pub col_count: usize,
+    births: usize,
+    deaths: usize,
+    runs: Vec<u8>,
+}
+
+impl Snapshot {
+    pub fn new(automaton: &Automaton) -> Self {
+        let mut runs = Vec::new();
+        let mut cells = automaton.grid.iter().flatten().map(Cell::state_byte);
+        if let Some(mut state) = cells.next() {
+            let mut length = 1_usize;
+            for next in cells {
+                if next == state {
+                    length += 1;
+                } else {
+                    push_run(&mut runs, state, length);
+                    (state, length) = (next, 1);
+                }
+            }
+            push_run(&mut runs, state, length);
+        }
+        Self {
+            generation: automaton.generation,
+            row_count: automaton.grid.len(),
+            col_count: automaton.grid.first().map_or(0, Vec::len),
+            births: automaton.births,
+            deaths: automaton.deaths,
+            runs,
+        }
+    }
+
+    /// Bytes taken by the compressed Cells
+    pub const fn compressed_size(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Decompresses the Cells
+    pub fn grid(&self) -> Grid {
+        let mut cells = Vec::with_capacity(self.row_count * self.col_count);
+        let mut bytes = self.runs.iter().copied();
+        while let Some(state) = bytes.next() {
+            let mut length = 0;
+            for (shift, byte) in (0..usize::BITS).step_by(7).zip(bytes.by_ref()) {
+                length |= usize::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            let cell = Cell::from_state_number(usize::from(state));
+            cells.extend(std::iter::repeat_n(cell, length));
+        }
+        if self.col_count == 0 {
+            return vec![Vec::new(); self.row_count];
+        }
+        cells.chunks(self.col_count).map(<[Cell]>::to_vec).collect()
+    }
+
+    /// Puts `automaton` back into the snapshotted generation, its rules are kept
+    pub fn restore(&self, automaton: &mut Automaton) {
+        automaton.grid = self.grid();
+        automaton.row_count = self.row_count;
+        automaton.col_count = self.col_count;
+        automaton.generation = self.generation;
+        automaton.births = self.births;
+        automaton.deaths = self.deaths;
+    }
+}
+
+/// Appends a run as its state followed by its length in LEB128
+fn push_run(runs: &mut Vec<u8>, state: u8, mut length: usize) {
+    runs.push(state);
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = (length & 0x7f) as u8;
+        length >>= 7;
+        if length == 0 {
+            runs.push(byte);
+            break;
+        }
+        runs.push(byte | 0x80);
+    }
+}
+
+/// A generation labeled to jump back to, e.g. "gun fires"
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub label: String,
+    pub snapshot: Snapshot,
+}
+
+/// The last `capacity` recorded generations, oldest first,
+/// and bookmarks that are kept regardless of the capacity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct History {
+    snapshots: VecDeque<Snapshot>,
+    capacity: usize,
+    /// Sorted by generation
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::new(),
+            capacity: capacity.max(1),
+            bookmarks: Vec::new(),
+        }
+    }
+
+    /// Snapshots the current generation. Snapshots of this or later generations,
+    /// left over from rewinding, are dropped, as is the oldest one once the history is full.
+    pub fn record(&mut self, automaton: &Automaton) {
+        while self
+            .snapshots
+            .back()
+            .is_some_and(|last| last.generation >= automaton.generation)
+        {
+            self.snapshots.pop_back();
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(Snapshot::new(automaton));
+    }
+
+    /// Restores the latest recorded generation before the current one of `automaton`,
+    /// `false` if there's none
+    pub fn rewind(&mut self, automaton: &mut Automaton) -> bool {
+        while let Some(snapshot) = self.snapshots.back() {
+            if snapshot.generation < automaton.generation {
+                snapshot.restore(automaton);
+                return true;
+            }
+            self.snapshots.pop_back();
+        }
+        false
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Bytes taken by the compressed Cells of all snapshots
+    pub fn compressed_size(&self) -> usize {
+        self.snapshots.iter().map(Snapshot::compressed_size).sum()
+    }
+
+    /// Labels the current generation of `automaton`
+    pub fn bookmark(&mut self, label: impl Into<String>, automaton: &Automaton) {
+        let index = self
+            .bookmarks
+            .partition_point(|bookmark| bookmark.snapshot.generation <= automaton.generation);
+        let bookmark = Bookmark {
+            label: label.into(),
+            snapshot: Snapshot::new(automaton),
+        };
+        self.bookmarks.insert(index, bookmark);
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn remove_bookmark(&mut self, index: usize) -> Option<Bookmark> {
+        (index < self.bookmarks.len()).then(|| self.bookmarks.remove(index))
+    }
+
+    /// Restores the bookmarked generation at `index`, `false` if there's none.
+    /// Recorded generations after it are dropped once the next one is recorded,
+    /// the ones before it can still be rewound to.
+    pub fn jump_to_bookmark(&self, index: usize, automaton: &mut Automaton) -> bool {
+        let Some(bookmark) = self.bookmarks.get(index) else {
+            return false;
+        };
+        bookmark.snapshot.restore(automaton);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        history::{History, Snapshot},
+        rle, Automaton, Cell, Generation,
+    };
+
+    #[test]
+    fn rewind() {
+        let mut automaton = Automaton::builder()
+            .row_count(64)
+            .col_count(64)
+            .grid(vec![vec![Cell::Dead; 64]; 64])
+            .build();
+        automaton.stamp(&rle::parse("bo$2bo$3o!").unwrap(), 1, 1);
+        let start = automaton.clone();
+        let mut history = History::new(100);
+        for _ in 0..150 {
+            history.record(&automaton);
+            automaton.step();
+        }
+        assert_eq!(history.len(), 100);
+        // A few runs per row the glider occupies instead of 4096 Cells per generation
+        assert!(history.compressed_size() < 100 * 64);
+
+        let end = automaton.clone();
+        assert!(history.rewind(&mut automaton));
+        assert_eq!(automaton.generation, Generation(149));
+        assert!(history.rewind(&mut automaton));
+        assert!(history.rewind(&mut automaton));
+        automaton.advance(3);
+        assert_eq!(automaton.grid, end.grid);
+
+        while history.rewind(&mut automaton) {}
+        assert_eq!(automaton.generation, Generation(50));
+        assert!(history.is_empty());
+
+        let snapshot = Snapshot::new(&start);
+        assert_eq!(snapshot.grid(), start.grid);
+        let empty = Automaton::builder().grid(Vec::new()).build();
+        assert_eq!(Snapshot::new(&empty).grid(), empty.grid);
+    }
+
+    #[test]
+    fn bookmarks() {
+        let mut automaton = Automaton::builder()
+            .row_count(32)
+            .col_count(32)
+            .grid(vec![vec![Cell::Dead; 32]; 32])
+            .build();
+        automaton.stamp(&rle::parse("bo$2bo$3o!").unwrap(), 1, 1);
+        let mut history = History::new(4);
+        for generation in 0..20 {
+            if generation == 12 {
+                history.bookmark("later", &automaton);
+            } else if generation == 5 {
+                history.bookmark("glider", &automaton);
+            }
+            history.record(&automaton);
+            automaton.step();
+        }
+        let labels = history.bookmarks().iter().map(|bookmark| &bookmark.label);
+        assert_eq!(labels.collect::<Vec<_>>(), ["glider", "later"]);
+
+        // Long gone from the recorded generations
+        let end = automaton.clone();
+        assert!(history.jump_to_bookmark(0, &mut automaton));
+        assert_eq!(automaton.generation, Generation(5));
+        automaton.advance(15);
+        assert_eq!(automaton.grid, end.grid);
+        assert!(!history.jump_to_bookmark(2, &mut automaton));
+
+        assert_eq!(history.remove_bookmark(1).unwrap().label, "later");
+        assert!(history.remove_bookmark(1).is_none());
+        assert_eq!(history.bookmarks().len(), 1);
+    }
+}
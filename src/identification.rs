@@ -0,0 +1,234 @@
+//! Infers the Life-like or Generations rules most likely to have produced a sequence of grids,
+//! e.g. of a recording whose rules were lost.
+//!
+//! Every observed transition of a dead or alive Cell votes for or against its neighbor count
+//! giving birth or letting it survive.
+
+use crate::{rules::MAX_NEIGHBORS, transition, Cell, Grid, NeighborCounts, Neighborhood, RuleSet};
+use std::{error, fmt};
+
+/// Errors that can occur while identifying rules
+/// - `TooFewGrids` => There are fewer than two grids, so no transitions to observe
+/// - `Size` => The grid at this index isn't as large as the first one, e.g. it was cropped
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IdentificationError {
+    TooFewGrids,
+    Size(usize),
+}
+
+impl fmt::Display for IdentificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewGrids => write!(f, "At least two consecutive grids are needed"),
+            Self::Size(index) => write!(
+                f,
+                "Grid {index} differs in size from the first one, grids have to be uncropped"
+            ),
+        }
+    }
+}
+
+impl error::Error for IdentificationError {}
+
+/// The rules explaining the most observed transitions
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Identification {
+    pub rule_set: RuleSet,
+    pub neighborhood: Neighborhood,
+    /// Observed transitions of Cells the rules predict
+    pub matching: usize,
+    /// Observed transitions of Cells, one per Cell and pair of consecutive grids
+    pub observed: usize,
+    /// Neighbor counts no dead Cell had, whether they give birth is unknown
+    pub unobserved_birth: Vec<usize>,
+    /// Neighbor counts no alive Cell had, whether they let it survive is unknown
+    pub unobserved_survival: Vec<usize>,
+}
+
+impl Identification {
+    /// Whether the rules predict every observed transition
+    pub const fn is_consistent(&self) -> bool {
+        self.matching == self.observed
+    }
+
+    /// Share of the observed transitions the rules predict
+    #[allow(clippy::cast_precision_loss)]
+    pub fn accuracy(&self) -> f64 {
+        if self.observed == 0 {
+            1.0
+        } else {
+            self.matching as f64 / self.observed as f64
+        }
+    }
+}
+
+/// How often each neighbor count was observed to give birth or let survive, and not to
+#[derive(Default)]
+struct Votes {
+    yes: [usize; MAX_NEIGHBORS + 1],
+    no: [usize; MAX_NEIGHBORS + 1],
+}
+
+impl Votes {
+    fn cast(&mut self, count: usize, yes: bool) {
+        let votes = if yes { &mut self.yes } else { &mut self.no };
+        votes[count.min(MAX_NEIGHBORS)] += 1;
+    }
+
+    /// Counts with more votes for than against
+    fn elected(&self) -> Vec<usize> {
+        (0..=MAX_NEIGHBORS)
+            .filter(|&count| self.yes[count] > self.no[count])
+            .collect()
+    }
+
+    fn unobserved(&self) -> Vec<usize> {
+        (0..=MAX_NEIGHBORS)
+            .filter(|&count| self.yes[count] == 0 && self.no[count] == 0)
+            .collect()
+    }
+}
+
+fn check(grids: &[Grid]) -> Result<(), IdentificationError> {
+    if grids.len() < 2 {
+        return Err(IdentificationError::TooFewGrids);
+    }
+    let size = |grid: &Grid| (grid.len(), grid.first().map_or(0, Vec::len));
+    grids
+        .iter()
+        .position(|grid| size(grid) != size(&grids[0]))
+        .map_or(Ok(()), |index| Err(IdentificationError::Size(index)))
+}
+
+/// Every Cell of every pair of consecutive `grids` with its neighbors and next state
+fn transitions(
+    grids: &[Grid],
+    neighborhood: Neighborhood,
+) -> impl Iterator<Item = (&Cell, NeighborCounts, &Cell)> {
+    grids.windows(2).flat_map(move |pair| {
+        let (grid, next) = (&pair[0], &pair[1]);
+        grid.iter().enumerate().flat_map(move |(row, cells)| {
+            cells.iter().enumerate().map(move |(col, cell)| {
+                let neighbors = transition::neighbors(grid, neighborhood, row, col).collect();
+                (cell, neighbors, &next[row][col])
+            })
+        })
+    })
+}
+
+/// The Life-like rules, or Generations rules if Cells are dying, explaining the most
+/// transitions between consecutive `grids` with `neighborhood`.
+///
+/// Generations rules count only alive neighbors and let Cells decay for as long as the longest
+/// observed dying.
+pub fn identify(
+    grids: &[Grid],
+    neighborhood: Neighborhood,
+) -> Result<Identification, IdentificationError> {
+    check(grids)?;
+    let (mut birth, mut survival) = (Votes::default(), Votes::default());
+    for (cell, neighbors, next) in transitions(grids, neighborhood) {
+        match cell {
+            Cell::Dead => birth.cast(neighbors.alive(), *next == Cell::Alive),
+            Cell::Alive => survival.cast(neighbors.alive(), *next == Cell::Alive),
+            Cell::Dying { .. } => {}
+        }
+    }
+    let decay = grids
+        .iter()
+        .flatten()
+        .flatten()
+        .filter_map(|cell| match cell {
+            Cell::Dying { ticks_till_death } => Some(*ticks_till_death),
+            Cell::Dead | Cell::Alive => None,
+        })
+        .max()
+        .unwrap_or_default();
+    let rule_set = RuleSet::life_like(birth.elected(), survival.elected(), decay);
+    let (mut matching, mut observed) = (0, 0);
+    for (cell, neighbors, next) in transitions(grids, neighborhood) {
+        observed += 1;
+        if rule_set.next_state(cell, &neighbors) == *next {
+            matching += 1;
+        }
+    }
+    Ok(Identification {
+        rule_set,
+        neighborhood,
+        matching,
+        observed,
+        unobserved_birth: birth.unobserved(),
+        unobserved_survival: survival.unobserved(),
+    })
+}
+
+/// The identification of `identify` with the neighborhood explaining more transitions,
+/// `Neighborhood::Moore` if both explain as many
+pub fn identify_any(grids: &[Grid]) -> Result<Identification, IdentificationError> {
+    let moore = identify(grids, Neighborhood::Moore)?;
+    let von_neumann = identify(grids, Neighborhood::VonNeumann)?;
+    Ok(if von_neumann.matching > moore.matching {
+        von_neumann
+    } else {
+        moore
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        identification::{self, IdentificationError},
+        seeding, Automaton, Cell, Neighborhood, RuleSet,
+    };
+
+    #[test]
+    fn recovers_rules() {
+        let record = |rules: &str, neighborhood, generations| {
+            let mut automaton = Automaton::builder()
+                .row_count(24)
+                .col_count(24)
+                .grid(seeding::soup(3, 24, 24, 0.4))
+                .rule_set(rules.parse::<RuleSet>().unwrap())
+                .neighborhood_type(neighborhood)
+                .build();
+            let mut grids = vec![automaton.grid.clone()];
+            grids.extend(automaton.generations().take(generations));
+            grids
+        };
+
+        let grids = record("B36/S23", Neighborhood::Moore, 12);
+        let identified = identification::identify_any(&grids).unwrap();
+        assert_eq!(identified.neighborhood, Neighborhood::Moore);
+        assert!(identified.is_consistent());
+        // Counts a soup never shows can't be told apart, but all shown ones agree
+        let expected: RuleSet = "B36/S23".parse().unwrap();
+        for count in 0..=8 {
+            if !identified.unobserved_birth.contains(&count) {
+                assert_eq!(
+                    identified.rule_set.birth().contains(&count),
+                    expected.birth().contains(&count)
+                );
+            }
+        }
+
+        let grids = record("B2/S/C3", Neighborhood::Moore, 6);
+        let identified = identification::identify(&grids, Neighborhood::Moore).unwrap();
+        assert_eq!(identified.rule_set.decay, 1);
+        assert!(identified.is_consistent());
+
+        let grids = record("B1/S012", Neighborhood::VonNeumann, 6);
+        let identified = identification::identify_any(&grids).unwrap();
+        assert_eq!(identified.neighborhood, Neighborhood::VonNeumann);
+        assert!(identified.accuracy() > 0.99);
+
+        assert_eq!(
+            identification::identify(&grids[..1], Neighborhood::Moore),
+            Err(IdentificationError::TooFewGrids)
+        );
+        let cropped = vec![grids[0].clone(), vec![vec![Cell::Dead; 3]; 3]];
+        assert_eq!(
+            identification::identify_any(&cropped),
+            Err(IdentificationError::Size(1))
+        );
+    }
+}
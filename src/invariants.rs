@@ -0,0 +1,89 @@
+use crate::{grid, Automaton, Cell};
+
+/// `automaton` with its grid rotated clockwise by 90 degrees
+pub fn rotated(automaton: &Automaton) -> Automaton {
+    Automaton {
+        row_count: automaton.col_count,
+        col_count: automaton.row_count,
+        grid: grid::rotate(&automaton.grid),
+        ..automaton.clone()
+    }
+}
+
+/// Stepping the rotated grid equals rotating the stepped grid,
+/// every `Neighborhood` is symmetric under rotation so this holds for all rules
+pub fn step_commutes_with_rotation(automaton: &Automaton) -> bool {
+    let mut rotated_first = rotated(automaton);
+    rotated_first.step();
+    let mut stepped = automaton.clone();
+    stepped.step();
+    rotated_first.grid == grid::rotate(&stepped.grid)
+}
+
+/// An empty grid stays empty, unless Cells are born without any living neighbors (`B0`)
+pub fn empty_stays_empty(automaton: &Automaton) -> bool {
+    let mut empty = Automaton {
+        grid: vec![vec![Cell::Dead; automaton.col_count]; automaton.row_count],
+        ..automaton.clone()
+    };
+    empty.step();
+    automaton.rule_set.birth().contains(&0) || empty.population() == 0
+}
+
+/// The population never exceeds the size of the grid
+/// and changes by exactly the births minus the deaths of a generation
+pub fn population_bounds(automaton: &Automaton) -> bool {
+    let mut stepped = automaton.clone();
+    stepped.step();
+    let population = stepped.population();
+    population <= stepped.row_count * stepped.col_count
+        && population + stepped.deaths == automaton.population() + stepped.births
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{invariants, Automaton, Cell, Neighborhood, RuleSet};
+    use proptest::{collection::vec, prelude::*, sample::subsequence};
+
+    fn automata() -> impl Strategy<Value = Automaton> {
+        let grids = (1..16_usize, 1..16_usize).prop_flat_map(|(row_count, col_count)| {
+            vec(
+                vec(
+                    any::<bool>().prop_map(|alive| if alive { Cell::Alive } else { Cell::Dead }),
+                    col_count,
+                ),
+                row_count,
+            )
+        });
+        let counts = || subsequence((0..=8).collect::<Vec<usize>>(), 0..=9);
+        let rule_sets = (counts(), counts(), 0..4_usize)
+            .prop_map(|(birth, survival, decay)| RuleSet::life_like(birth, survival, decay));
+        let neighborhoods = prop_oneof![Just(Neighborhood::Moore), Just(Neighborhood::VonNeumann)];
+        (grids, rule_sets, neighborhoods).prop_map(|(grid, rule_set, neighborhood)| {
+            Automaton::builder()
+                .row_count(grid.len())
+                .col_count(grid[0].len())
+                .grid(grid)
+                .rule_set(rule_set)
+                .neighborhood_type(neighborhood)
+                .build()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn rotation(automaton in automata()) {
+            prop_assert!(invariants::step_commutes_with_rotation(&automaton));
+        }
+
+        #[test]
+        fn empty(automaton in automata()) {
+            prop_assert!(invariants::empty_stays_empty(&automaton));
+        }
+
+        #[test]
+        fn population(automaton in automata()) {
+            prop_assert!(invariants::population_bounds(&automaton));
+        }
+    }
+}
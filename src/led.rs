@@ -0,0 +1,154 @@
+use crate::{theme::Rgb, Automaton, Theme};
+use rpi_led_matrix::{LedCanvas, LedColor, LedMatrix, LedMatrixOptions};
+use std::{error, fmt};
+
+/// Errors that can occur while setting up a `LedDisplay`
+/// - `Brightness` => The brightness isn't within `1..=100`
+/// - `Init` => The matrix couldn't be initialized, e.g. without access to the GPIO pins
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LedError {
+    Brightness(u8),
+    Init(&'static str),
+}
+
+impl fmt::Display for LedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Brightness(brightness) => {
+                write!(f, "Brightness {brightness} isn't between 1 and 100")
+            }
+            Self::Init(e) => write!(f, "Couldn't initialize the LED matrix: {e}"),
+        }
+    }
+}
+
+impl error::Error for LedError {}
+
+/// Panel layout, brightness and palette of a `LedDisplay`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LedConfig {
+    /// Rows of a single panel
+    pub rows: u32,
+    /// Columns of a single panel
+    pub cols: u32,
+    /// Panels daisy chained horizontally
+    pub chain_length: u32,
+    /// Percentage from 1 to 100
+    pub brightness: u8,
+    /// Wiring of the panel, e.g. "regular" or "adafruit-hat"
+    pub hardware_mapping: String,
+    pub theme: Theme,
+}
+
+impl Default for LedConfig {
+    fn default() -> Self {
+        Self {
+            rows: 32,
+            cols: 32,
+            chain_length: 1,
+            brightness: 50,
+            hardware_mapping: "regular".into(),
+            theme: Theme::high_contrast(),
+        }
+    }
+}
+
+impl LedConfig {
+    /// Width and height in pixels of all chained panels together
+    pub const fn size(&self) -> (u32, u32) {
+        (self.cols * self.chain_length, self.rows)
+    }
+}
+
+/// RGB LED matrix attached to a Raspberry Pi, e.g. a Unicorn HAT HD or an Adafruit RGB Matrix HAT,
+/// showing one Cell per pixel. Grids larger than the matrix are clipped.
+pub struct LedDisplay {
+    matrix: LedMatrix,
+    /// Drawn off screen and swapped in by `show` to avoid tearing
+    canvas: Option<LedCanvas>,
+    config: LedConfig,
+}
+
+impl LedDisplay {
+    /// Takes over the matrix, which usually needs root privileges
+    pub fn new(config: LedConfig) -> Result<Self, LedError> {
+        let mut options = LedMatrixOptions::new();
+        options.set_rows(config.rows);
+        options.set_cols(config.cols);
+        options.set_chain_length(config.chain_length);
+        options.set_hardware_mapping(&config.hardware_mapping);
+        options
+            .set_brightness(config.brightness)
+            .map_err(|_| LedError::Brightness(config.brightness))?;
+        let matrix = LedMatrix::new(Some(options), None).map_err(LedError::Init)?;
+        let canvas = Some(matrix.offscreen_canvas());
+        Ok(Self {
+            matrix,
+            canvas,
+            config,
+        })
+    }
+
+    pub const fn config(&self) -> &LedConfig {
+        &self.config
+    }
+
+    /// Shows the current generation of `automaton`
+    pub fn show(&mut self, automaton: &Automaton) {
+        let Some(mut canvas) = self.canvas.take() else {
+            return;
+        };
+        for (x, y, [red, green, blue]) in pixels(automaton, &self.config.theme, self.config.size())
+        {
+            canvas.set(x, y, &LedColor { red, green, blue });
+        }
+        self.canvas = Some(self.matrix.swap(canvas));
+    }
+}
+
+/// Pixel coordinates and colors of the Cells of `automaton` that fit into `width` x `height`
+pub fn pixels<'a>(
+    automaton: &'a Automaton,
+    theme: &'a Theme,
+    (width, height): (u32, u32),
+) -> impl Iterator<Item = (i32, i32, Rgb)> + 'a {
+    let decay = automaton.rule_set.decay;
+    let coordinate = |index: usize, limit: u32| {
+        u32::try_from(index)
+            .ok()
+            .filter(|&index| index < limit)
+            .and_then(|index| i32::try_from(index).ok())
+    };
+    automaton
+        .grid
+        .iter()
+        .enumerate()
+        .map_while(move |(row, cells)| Some((coordinate(row, height)?, cells)))
+        .flat_map(move |(y, cells)| {
+            cells.iter().enumerate().map_while(move |(col, cell)| {
+                Some((coordinate(col, width)?, y, theme.cell_color(cell, decay)))
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{led::pixels, Automaton, Cell, Theme};
+
+    #[test]
+    fn clipped_pixels() {
+        let automaton = Automaton::builder()
+            .row_count(3)
+            .col_count(4)
+            .grid(vec![
+                vec![Cell::Dead, Cell::Alive, Cell::Dead, Cell::Alive];
+                3
+            ])
+            .build();
+        let theme = Theme::high_contrast();
+        let pixels = pixels(&automaton, &theme, (3, 2)).collect::<Vec<_>>();
+        assert_eq!(pixels.len(), 6);
+        assert_eq!(pixels[1], (1, 0, theme.alive));
+        assert_eq!(pixels[5], (2, 1, theme.dead));
+    }
+}
@@ -0,0 +1,160 @@
+//! Without the default `std` feature only the `no_std` stepping core is built,
+//! see `transition`.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+    // clippy::cargo
+)]
+#![allow(
+    clippy::must_use_candidate,
+    clippy::missing_errors_doc,
+    clippy::missing_panics_doc
+)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod activity;
+#[cfg(feature = "std")]
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod annotation;
+#[cfg(feature = "ndarray")]
+pub mod array;
+#[cfg(feature = "std")]
+pub mod automaton;
+#[cfg(feature = "std")]
+pub mod brush;
+pub mod cell;
+#[cfg(feature = "std")]
+pub mod collision;
+#[cfg(feature = "std")]
+pub mod demo;
+#[cfg(feature = "std")]
+pub mod distributed;
+#[cfg(feature = "std")]
+pub mod divergence;
+#[cfg(feature = "std")]
+pub mod enumeration;
+#[cfg(feature = "std")]
+pub mod experiment;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+#[cfg(feature = "std")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod figure;
+#[cfg(feature = "std")]
+pub mod format;
+#[cfg(all(test, feature = "std"))]
+mod golden;
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod identification;
+#[cfg(feature = "std")]
+pub mod invariants;
+#[cfg(feature = "led-matrix")]
+pub mod led;
+#[cfg(feature = "std")]
+pub mod library;
+#[cfg(feature = "std")]
+pub mod localization;
+#[cfg(feature = "mapped-grid")]
+pub mod mapped;
+#[cfg(feature = "std")]
+pub mod mean_field;
+#[cfg(feature = "std")]
+pub mod mesh;
+#[cfg(feature = "std")]
+pub mod metrics;
+pub mod neighborhood;
+#[cfg(feature = "engines")]
+pub mod oracle;
+pub mod packed;
+#[cfg(feature = "std")]
+pub mod plugin;
+#[cfg(feature = "std")]
+pub mod predecessor;
+#[cfg(feature = "std")]
+pub mod projection;
+#[cfg(feature = "std")]
+pub mod remote;
+#[cfg(feature = "std")]
+pub mod reversibility;
+#[cfg(feature = "std")]
+pub mod rle;
+pub mod rule_table;
+pub mod rules;
+#[cfg(feature = "std")]
+pub mod schedule;
+#[cfg(feature = "scripting")]
+pub mod script;
+#[cfg(feature = "std")]
+pub mod seeding;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "std")]
+pub mod session;
+#[cfg(feature = "shared-memory")]
+pub mod shared_memory;
+#[cfg(feature = "std")]
+pub mod sonification;
+#[cfg(feature = "std")]
+pub mod state_history;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod stop;
+#[cfg(feature = "std")]
+pub mod table;
+#[cfg(feature = "std")]
+pub mod theme;
+#[cfg(feature = "std")]
+pub mod tiled;
+#[cfg(feature = "std")]
+pub mod timelapse;
+#[cfg(feature = "std")]
+pub mod trail;
+pub mod transition;
+#[cfg(feature = "std")]
+pub mod tui;
+#[cfg(feature = "std")]
+pub mod tutorial;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+pub mod zobrist;
+#[cfg(feature = "std")]
+pub mod zone;
+
+#[cfg(feature = "std")]
+pub use activity::Activity;
+#[cfg(feature = "std")]
+pub use automaton::{Automaton, Change, Generation, Generations};
+pub use cell::Cell;
+#[cfg(feature = "std")]
+pub use divergence::Divergence;
+#[cfg(feature = "std")]
+pub use grid::FromImage;
+pub use grid::{CellAccess, ColIdx, Grid, RowIdx};
+pub use neighborhood::{NeighborCounts, Neighborhood};
+pub use packed::PackedGrid;
+pub use rule_table::RuleTable;
+pub use rules::{Action, Counting, Priority, RuleSet, Rules};
+#[cfg(feature = "std")]
+pub use seeding::{Seeding, Symmetry};
+#[cfg(feature = "std")]
+pub use stats::{Sample, Stats};
+#[cfg(feature = "std")]
+pub use theme::Theme;
+#[cfg(feature = "std")]
+pub use trail::Trail;
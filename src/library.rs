@@ -0,0 +1,233 @@
+//! Patterns shipped with the crate and loading of user collections, see the pattern browser
+
+use crate::{
+    format::{Format, FormatError},
+    grid, Automaton, Cell, Grid, Neighborhood, RuleSet,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{error, fmt, fs, io, path::Path};
+
+/// Name and RLE of the embedded patterns, all of them in Conway's Game of Life
+const EMBEDDED: &[(&str, &str)] = &[
+    ("Glider", "bo$2bo$3o!"),
+    ("Lightweight spaceship", "bo2bo$o4b$o3bo$4o!"),
+    ("Blinker", "3o!"),
+    ("Toad", "b3o$3o!"),
+    ("Beacon", "2o2b$2o2b$2b2o$2b2o!"),
+    (
+        "Pulsar",
+        "2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$o4bobo4bo$\
+         o4bobo4bo2$2b3o3b3o!",
+    ),
+    ("Pentadecathlon", "2bo4bo2b$2ob4ob2o$2bo4bo!"),
+    ("R-pentomino", "b2o$2o$bo!"),
+    ("Acorn", "bo5b$3bo3b$2o2b3o!"),
+    ("Diehard", "6bob$2o6b$bo3b3o!"),
+    (
+        "Gosper glider gun",
+        "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$\
+         10bo5bo7bo$11bo3bo$12b2o!",
+    ),
+];
+
+/// Errors that can occur while loading a pattern file
+/// - `Io` => The file couldn't be read
+/// - `Format` => The file isn't a valid pattern
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Format(FormatError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't read pattern: {e}"),
+            Self::Format(e) => write!(f, "Invalid pattern: {e}"),
+        }
+    }
+}
+
+impl error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<FormatError> for LoadError {
+    fn from(value: FormatError) -> Self {
+        Self::Format(value)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pattern {
+    pub name: String,
+    pub grid: Grid,
+}
+
+impl Pattern {
+    /// Loads a pattern file in any `Format`, named after the file.
+    /// The format is picked by the extension, or detected if it's unknown.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let format = match Format::from_path(path) {
+            Some(format) => format,
+            None => Format::detect(&text)?,
+        };
+        Ok(Self {
+            name: path
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned()),
+            grid: format.parse(&text)?,
+        })
+    }
+
+    /// Fraction of the first `generations` generations under `rule_set` and `neighborhood`
+    /// each Cell was alive in.
+    /// The pattern is surrounded by `generations` dead Cells, so anything moving at most
+    /// one Cell per generation stays in view.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn thumbnail(
+        &self,
+        rule_set: &RuleSet,
+        neighborhood: Neighborhood,
+        generations: usize,
+    ) -> Vec<Vec<f32>> {
+        let row_count = self.grid.len() + 2 * generations;
+        let col_count = self.grid.first().map_or(0, Vec::len) + 2 * generations;
+        let mut automaton = Automaton::builder()
+            .row_count(row_count)
+            .col_count(col_count)
+            .grid(vec![vec![Cell::Dead; col_count]; row_count])
+            .rule_set(rule_set.clone())
+            .neighborhood_type(neighborhood)
+            .build();
+        automaton.stamp(&self.grid, generations, generations);
+        let mut alive = vec![vec![0_usize; col_count]; row_count];
+        for _ in 0..generations.max(1) {
+            for (counts, cells) in alive.iter_mut().zip(&automaton.grid) {
+                for (count, cell) in counts.iter_mut().zip(cells) {
+                    *count += usize::from(*cell == Cell::Alive);
+                }
+            }
+            automaton.step();
+        }
+        alive
+            .into_iter()
+            .map(|counts| {
+                counts
+                    .into_iter()
+                    .map(|count| count as f32 / generations.max(1) as f32)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The patterns shipped with the crate
+pub fn embedded() -> Vec<Pattern> {
+    EMBEDDED
+        .iter()
+        .map(|&(name, rle)| Pattern {
+            name: name.into(),
+            grid: crate::rle::parse(rle).expect("Embedded patterns are valid RLE"),
+        })
+        .collect()
+}
+
+/// Drops `count` patterns picked at random from `patterns` onto `grid`, each rotated by a
+/// random multiple of 90 degrees and possibly mirrored.
+///
+/// They're placed where they fit entirely if the grid is big enough. Only their living Cells
+/// are copied, so overlapping patterns merge instead of cutting holes into each other.
+pub fn scatter(grid: &mut [Vec<Cell>], patterns: &[Pattern], count: usize, rng: &mut impl Rng) {
+    let (row_count, col_count) = (grid.len(), grid.first().map_or(0, Vec::len));
+    for _ in 0..count {
+        let Some(pattern) = patterns.choose(rng) else {
+            return;
+        };
+        let mut oriented = pattern.grid.clone();
+        for _ in 0..rng.gen_range(0..4) {
+            oriented = grid::rotate(&oriented);
+        }
+        if rng.gen_bool(0.5) {
+            for row in &mut oriented {
+                row.reverse();
+            }
+        }
+        let (height, width) = (oriented.len(), oriented.first().map_or(0, Vec::len));
+        let top = rng.gen_range(0..=row_count.saturating_sub(height));
+        let left = rng.gen_range(0..=col_count.saturating_sub(width));
+        for (pattern_row, grid_row) in oriented.iter().zip(grid.iter_mut().skip(top)) {
+            for (cell, target) in pattern_row.iter().zip(grid_row.iter_mut().skip(left)) {
+                if *cell != Cell::Dead {
+                    *target = cell.clone();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{library, Cell, Neighborhood, RuleSet};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::fs;
+
+    #[test]
+    fn embedded_and_loaded() {
+        let patterns = library::embedded();
+        assert!(patterns.iter().all(|pattern| !pattern.grid.is_empty()));
+        let pulsar = patterns.iter().find(|pattern| pattern.name == "Pulsar");
+        assert_eq!(pulsar.unwrap().grid.len(), 13);
+        let life = RuleSet::default();
+        let blinker = patterns.iter().find(|pattern| pattern.name == "Blinker");
+        let thumbnail = blinker.unwrap().thumbnail(&life, Neighborhood::Moore, 4);
+        assert_eq!((thumbnail.len(), thumbnail[0].len()), (9, 11));
+        assert!((thumbnail[4][5] - 1.0).abs() < f32::EPSILON);
+        assert!((thumbnail[4][4] - 0.5).abs() < f32::EPSILON);
+        assert!((thumbnail[3][5] - 0.5).abs() < f32::EPSILON);
+        assert!(thumbnail[0][0] < f32::EPSILON);
+
+        let glider = patterns.iter().find(|pattern| pattern.name == "Glider");
+        let thumbnail = glider.unwrap().thumbnail(&life, Neighborhood::Moore, 8);
+        // Five Cells alive in every generation
+        let total = thumbnail.iter().flatten().sum::<f32>();
+        assert!((total - 5.0).abs() < 1e-4);
+
+        let path = std::env::temp_dir().join("cellular_automata_library.cells");
+        fs::write(&path, "!Name: Block\nOO\nOO\n").unwrap();
+        let block = library::Pattern::load(&path).unwrap();
+        assert_eq!(block.name, "cellular_automata_library");
+        assert_eq!(block.grid.len(), 2);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn scatter() {
+        let patterns = library::embedded();
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut grid = vec![vec![Cell::Dead; 64]; 64];
+        library::scatter(&mut grid, &patterns, 0, &mut rng);
+        assert!(grid.iter().flatten().all(|cell| *cell == Cell::Dead));
+
+        let blinker = patterns.iter().filter(|pattern| pattern.name == "Blinker");
+        let blinker = blinker.cloned().collect::<Vec<_>>();
+        library::scatter(&mut grid, &blinker, 1, &mut rng);
+        let alive = grid.iter().flatten().filter(|cell| **cell == Cell::Alive);
+        assert_eq!(alive.count(), 3);
+
+        library::scatter(&mut grid, &patterns, 20, &mut rng);
+        let alive = grid.iter().flatten().filter(|cell| **cell == Cell::Alive);
+        assert!(alive.count() > 3);
+        // Patterns bigger than the grid are clipped
+        let mut tiny = vec![vec![Cell::Dead; 4]; 4];
+        library::scatter(&mut tiny, &patterns, 20, &mut rng);
+        assert_eq!(tiny.len(), 4);
+        assert!(tiny.iter().all(|row| row.len() == 4));
+    }
+}
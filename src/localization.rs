@@ -0,0 +1,186 @@
+//! Translations of user-facing strings with [Fluent](https://projectfluent.org).
+//!
+//! The messages of every language live in `src/localization/<code>.ftl` and are embedded into
+//! the binary.
+//! Messages missing from a translation fall back to English.
+
+use clap::ValueEnum;
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use std::env;
+use unic_langid::LanguageIdentifier;
+
+/// Languages the user-facing strings are translated to
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Japanese,
+}
+
+impl Language {
+    pub const ALL: [Self; 3] = [Self::English, Self::German, Self::Japanese];
+
+    /// ISO 639-1 code
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::Japanese => "ja",
+        }
+    }
+
+    /// Name of the language in itself, for picking it
+    pub const fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+            Self::Japanese => "日本語",
+        }
+    }
+
+    const fn messages(self) -> &'static str {
+        match self {
+            Self::English => include_str!("localization/en.ftl"),
+            Self::German => include_str!("localization/de.ftl"),
+            Self::Japanese => include_str!("localization/ja.ftl"),
+        }
+    }
+
+    /// The language of a POSIX locale like `de_DE.UTF-8`, or of a tag like `ja-JP`
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale
+            .split(['_', '-', '.', '@'])
+            .next()?
+            .to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    /// The language of the first of `LC_ALL`, `LC_MESSAGES` and `LANG` that is set,
+    /// English if it isn't translated
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|variable| env::var(variable).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
+
+/// Looks up messages in one language
+pub struct Localizer {
+    language: Language,
+    /// The language followed by English as the fallback
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    pub fn new(language: Language) -> Self {
+        let mut languages = vec![language];
+        if language != Language::English {
+            languages.push(Language::English);
+        }
+        let bundles = languages
+            .into_iter()
+            .map(|language| {
+                let id = language
+                    .code()
+                    .parse::<LanguageIdentifier>()
+                    .expect("Language codes are valid identifiers");
+                let resource = FluentResource::try_new(language.messages().to_owned())
+                    .unwrap_or_else(|(resource, _)| resource);
+                let mut bundle = FluentBundle::new_concurrent(vec![id]);
+                // Isolation marks around arguments show up as boxes in egui and terminals
+                bundle.set_use_isolating(false);
+                // Only fails on messages defined twice, the first definition wins
+                let _ = bundle.add_resource(resource);
+                bundle
+            })
+            .collect();
+        Self { language, bundles }
+    }
+
+    pub const fn language(&self) -> Language {
+        self.language
+    }
+
+    /// The message `id`, or `id` itself if no language defines it
+    pub fn text(&self, id: &str) -> String {
+        self.format(id, [])
+    }
+
+    /// The message `id` with its `{ $name }` placeables replaced by `args`
+    pub fn format<'a>(
+        &self,
+        id: &str,
+        args: impl IntoIterator<Item = (&'a str, FluentValue<'a>)>,
+    ) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(name, value);
+        }
+        self.bundles
+            .iter()
+            .find_map(|bundle| {
+                let pattern = bundle.get_message(id)?.value()?;
+                let mut errors = Vec::new();
+                Some(
+                    bundle
+                        .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                        .into_owned(),
+                )
+            })
+            .unwrap_or_else(|| id.to_owned())
+    }
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::localization::{Language, Localizer};
+
+    #[test]
+    fn translations_and_fallback() {
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), Some(Language::German));
+        assert_eq!(Language::from_locale("ja-JP"), Some(Language::Japanese));
+        assert_eq!(Language::from_locale("C"), None);
+
+        let english = Localizer::default();
+        let german = Localizer::new(Language::German);
+        assert_eq!(english.text("controls-step"), "Step");
+        assert_eq!(german.text("controls-step"), "Schritt");
+        assert_eq!(german.text("no-such-message"), "no-such-message");
+        assert_eq!(
+            german.format("status-behind", [("generations", 12.into())]),
+            "12 im Rückstand"
+        );
+        assert_eq!(
+            german.format(
+                "stats-fixed-point",
+                [("density", "0.375".into()), ("stable", "false".into())]
+            ),
+            "Fixpunkt 0.375 (instabil)"
+        );
+
+        // Every translation defines the messages English does
+        let ids = |language: Language| {
+            language
+                .messages()
+                .lines()
+                .filter_map(|line| line.split_once(" = "))
+                .map(|(id, _)| id.trim())
+                .collect::<Vec<_>>()
+        };
+        for language in Language::ALL {
+            assert_eq!(ids(language), ids(Language::English), "{language:?}");
+        }
+    }
+}
@@ -0,0 +1,177 @@
+# Window titles
+window-bindings = Tastenbelegung
+window-population = Population
+window-presets = Vorlagen
+window-bookmarks = Lesezeichen
+window-tools = Werkzeuge
+window-split-view = Geteilte Ansicht
+window-patterns = Muster
+window-minimap = Übersichtskarte
+window-compare = Vergleich
+window-rules = Regeln
+window-timelapse = Zeitraffer
+window-tutorial = Einführung
+window-sound = Ton
+window-annotations = Anmerkungen
+window-zones = Zonen
+
+# Buttons of the controls panel
+controls-play = Start
+controls-pause = Pause
+controls-step = Schritt
+controls-slower = Langsamer
+controls-faster = Schneller
+controls-unlimited = Unbegrenzt
+controls-shorter-stride = Schrittweite /2
+controls-longer-stride = Schrittweite x2
+controls-theme = Farbschema
+controls-language = Sprache
+
+# Tutorial window
+tutorial-next = Weiter
+tutorial-skip = Überspringen
+tutorial-quit = Beenden
+tutorial-done = Geschafft, gut gemacht!
+tutorial-close = Schließen
+
+# Bindings window
+bindings-rebind = Neu belegen in { $file } im Arbeitsverzeichnis
+bindings-unbound = Nicht belegt
+binding-mouse = Maus { $button }
+binding-gamepad = Gamepad { $button }
+
+# Population window
+stats-population = Population
+stats-births = Geburten
+stats-deaths = Tode
+stats-mean-field = Molekularfeld
+stats-next-density = Nächste Dichte
+stats-unchanged = Unverändert
+stats-fixed-points = Fixpunkte
+stats-current-density = Aktuelle Dichte
+stats-fixed-point = Fixpunkt { $density } ({ $stable ->
+        [true] stabil
+       *[false] instabil
+    })
+
+# Bookmarks window
+bookmarks-label = Bezeichnung, z. B. „Kanone feuert“
+bookmarks-add = Generation { $generation } merken
+bookmarks-generation = Generation { $generation }
+bookmarks-jump = Springen
+bookmarks-remove = Entfernen
+
+# Tools window
+tools-toggle = Umschalten
+tools-brush = Pinsel
+tools-line = Linie
+tools-rectangle = Rechteck
+tools-circle = Kreis
+tools-square = Quadrat
+tools-spray = Sprühen
+tools-filled = Gefüllte Figuren
+tools-paint = Farbe
+tools-alive = Lebendig
+tools-dead = Tot
+tools-radius = Radius
+tools-density = Dichte
+
+# Split view window
+split-view-pane = { $pane } | Abweichend: { $differing }
+split-view-remove = Entfernen
+split-view-show-difference = Unterschied zeigen
+split-view-rules = Regeln
+split-view-add = Hinzufügen
+split-view-resync = Neu abgleichen
+
+# Patterns window
+patterns-armed = Klicken zum Stempeln, Umschalt halten zum Weiterstempeln, { $cancel } zum Abbrechen
+patterns-scatter = Verstreuen
+patterns-scatter-count = Anzahl zufälliger Muster
+patterns-fetch = Abrufen
+patterns-fetch-query = Name eines Musters im LifeWiki oder ein apgcode
+
+# Compare window
+compare-pattern-file = Musterdatei
+compare-live = Live
+compare-live-grid = Laufendes Gitter
+compare-capture = Festhalten
+compare-generation = Generation { $generation }
+compare-load = Laden
+compare-show-overlay = Überlagerung zeigen
+compare-counts = Nur A: { $a } | Nur B: { $b } | Beide: { $both }
+
+# Rules window
+rules-birth = Geburt
+rules-survival = Überleben
+rules-decay = Zerfall
+rules-reseed = Vorschau neu säen
+rules-apply = Anwenden
+
+# Timelapse window
+timelapse-path = Pfad
+timelapse-path-hint = Endet auf .gif für ein animiertes GIF, sonst ein Verzeichnis von PNG-Bildern
+timelapse-every = Alle
+timelapse-scale = px pro Zelle
+timelapse-delay = ms pro GIF-Bild
+timelapse-recording = Aufnahme läuft, bisher { $frames } Bilder
+timelapse-start = Starten
+timelapse-stop = Stoppen
+
+# Sound window
+sound-muted = Stumm
+sound-volume = Lautstärke
+sound-ticks = Bei jeder Generation ticken
+
+# Annotations window
+annotations-show-labels = Beschriftungen zeigen
+annotations-cell = Zelle
+annotations-row = Zeile, folgt der Zelle unter dem Zeiger
+annotations-column = Spalte, folgt der Zelle unter dem Zeiger
+annotations-label = Beschriftung, z. B. „Fresser“
+annotations-annotate = Beschriften
+annotations-remove = Entfernen
+
+# Zones window
+zones-show-outlines = Umrisse zeigen
+zones-border = Rand
+zones-top-left = Oben links
+zones-bottom-right = Unten rechts
+zones-rules = Regeln der Zone in B/S-Notation
+zones-add = Zone hinzufügen
+zones-order = Spätere Zonen überdecken frühere, der Rest des Gitters behält seine Regeln
+zones-bounds = { $top },{ $left } bis { $bottom },{ $right }
+zones-remove = Entfernen
+
+# Readout of the hovered Cell
+hover-cell = Zeile { $row }, Spalte { $col }: { $cell }
+
+# Status bar and terminal
+status-line = Generation: { $generation } | Population: { $population } | Regeln: { $rules }
+status-behind = { $generations } im Rückstand
+status-mirroring = Spiegelung { $symmetry }
+status-paused = Pausiert
+status-population = Population: { $population } (+{ $births } -{ $deaths })
+status-title = Zelluläre Automaten
+speed-rate = { $rate } Gen./s
+speed-unlimited = Unbegrenzt
+
+# Terminal
+experiment-runs = { $runs } Läufe auf { $threads } Threads
+fetch-cached = Zwischengespeichert unter { $path }
+serve-websocket = Streame auf { $address }
+serve-http = HTTP-API auf { $address }
+serve-chat = Nehme Befehle aus #{ $channel } entgegen
+warning-theme = { $error }, stattdessen wird das klassische Farbschema verwendet
+warning-record = Generation { $generation } konnte nicht aufgenommen werden: { $error }
+warning-chat = Chat von #{ $channel } getrennt: { $error }
+error-read = { $path } konnte nicht gelesen werden: { $error }
+error-detect = Das Format von { $path } konnte nicht erkannt werden: { $error }, gib es mit --from an
+error-parse = { $path } konnte nicht als { $format } gelesen werden: { $error }
+error-preset = Unbekannte Vorlage „{ $name }“, verfügbar sind: { $available }
+error-create = { $path } konnte nicht erstellt werden: { $error }
+error-write = { $path } konnte nicht geschrieben werden: { $error }
+error-parquet = Parquet-Ausgabe benötigt das Feature parquet
+error-render = { $path } konnte nicht gerendert werden: { $error }
+error-listen = Auf { $address } konnte nicht gelauscht werden: { $error }
+error-output = Die Ausgabe konnte nicht geschrieben werden: { $error }
@@ -0,0 +1,177 @@
+# Window titles
+window-bindings = Bindings
+window-population = Population
+window-presets = Presets
+window-bookmarks = Bookmarks
+window-tools = Tools
+window-split-view = Split view
+window-patterns = Patterns
+window-minimap = Minimap
+window-compare = Compare
+window-rules = Rules
+window-timelapse = Timelapse
+window-tutorial = Tutorial
+window-sound = Sound
+window-annotations = Annotations
+window-zones = Zones
+
+# Buttons of the controls panel
+controls-play = Play
+controls-pause = Pause
+controls-step = Step
+controls-slower = Slower
+controls-faster = Faster
+controls-unlimited = Unlimited
+controls-shorter-stride = Stride /2
+controls-longer-stride = Stride x2
+controls-theme = Theme
+controls-language = Language
+
+# Tutorial window
+tutorial-next = Next
+tutorial-skip = Skip
+tutorial-quit = Quit
+tutorial-done = Done, well played!
+tutorial-close = Close
+
+# Bindings window
+bindings-rebind = Rebind in { $file } in the working directory
+bindings-unbound = Unbound
+binding-mouse = Mouse { $button }
+binding-gamepad = Gamepad { $button }
+
+# Population window
+stats-population = Population
+stats-births = Births
+stats-deaths = Deaths
+stats-mean-field = Mean field
+stats-next-density = Next density
+stats-unchanged = Unchanged
+stats-fixed-points = Fixed points
+stats-current-density = Current density
+stats-fixed-point = Fixed point { $density } ({ $stable ->
+        [true] stable
+       *[false] unstable
+    })
+
+# Bookmarks window
+bookmarks-label = Label, e.g. "gun fires"
+bookmarks-add = Bookmark generation { $generation }
+bookmarks-generation = Generation { $generation }
+bookmarks-jump = Jump
+bookmarks-remove = Remove
+
+# Tools window
+tools-toggle = Toggle
+tools-brush = Brush
+tools-line = Line
+tools-rectangle = Rectangle
+tools-circle = Circle
+tools-square = Square
+tools-spray = Spray
+tools-filled = Filled figures
+tools-paint = Paint
+tools-alive = Alive
+tools-dead = Dead
+tools-radius = Radius
+tools-density = Density
+
+# Split view window
+split-view-pane = { $pane } | Differing: { $differing }
+split-view-remove = Remove
+split-view-show-difference = Show difference
+split-view-rules = Rules
+split-view-add = Add
+split-view-resync = Resync
+
+# Patterns window
+patterns-armed = Click to stamp, hold Shift to keep stamping, { $cancel } to cancel
+patterns-scatter = Scatter
+patterns-scatter-count = Number of random patterns
+patterns-fetch = Fetch
+patterns-fetch-query = Name of a pattern on LifeWiki, or an apgcode
+
+# Compare window
+compare-pattern-file = Pattern file
+compare-live = Live
+compare-live-grid = Live grid
+compare-capture = Capture
+compare-generation = Generation { $generation }
+compare-load = Load
+compare-show-overlay = Show overlay
+compare-counts = Only A: { $a } | Only B: { $b } | Both: { $both }
+
+# Rules window
+rules-birth = Birth
+rules-survival = Survival
+rules-decay = Decay
+rules-reseed = Reseed preview
+rules-apply = Apply
+
+# Timelapse window
+timelapse-path = Path
+timelapse-path-hint = Ending in .gif for an animated GIF, otherwise a directory of PNG frames
+timelapse-every = Every
+timelapse-scale = px per Cell
+timelapse-delay = ms per GIF frame
+timelapse-recording = Recording, { $frames } frames so far
+timelapse-start = Start
+timelapse-stop = Stop
+
+# Sound window
+sound-muted = Muted
+sound-volume = Volume
+sound-ticks = Tick with every generation
+
+# Annotations window
+annotations-show-labels = Show labels
+annotations-cell = Cell
+annotations-row = Row, follows the hovered Cell
+annotations-column = Column, follows the hovered Cell
+annotations-label = Label, e.g. "eater"
+annotations-annotate = Annotate
+annotations-remove = Remove
+
+# Zones window
+zones-show-outlines = Show outlines
+zones-border = Border
+zones-top-left = Top left
+zones-bottom-right = Bottom right
+zones-rules = Rules of the zone in B/S notation
+zones-add = Add zone
+zones-order = Later zones cover earlier ones, the rest of the grid keeps its rules
+zones-bounds = { $top },{ $left } to { $bottom },{ $right }
+zones-remove = Remove
+
+# Readout of the hovered Cell
+hover-cell = Row { $row }, Col { $col }: { $cell }
+
+# Status bar and terminal
+status-line = Generation: { $generation } | Population: { $population } | Rules: { $rules }
+status-behind = { $generations } behind
+status-mirroring = Mirroring { $symmetry }
+status-paused = Paused
+status-population = Population: { $population } (+{ $births } -{ $deaths })
+status-title = Cellular Automata
+speed-rate = { $rate } gen/s
+speed-unlimited = Unlimited
+
+# Terminal
+experiment-runs = { $runs } runs on { $threads } threads
+fetch-cached = Cached at { $path }
+serve-websocket = Streaming on { $address }
+serve-http = HTTP API on { $address }
+serve-chat = Taking commands from #{ $channel }
+warning-theme = { $error }, falling back to the classic theme
+warning-record = Couldn't record generation { $generation }: { $error }
+warning-chat = Chat of #{ $channel } disconnected: { $error }
+error-read = Couldn't read { $path }: { $error }
+error-detect = Couldn't detect the format of { $path }: { $error }, pass --from
+error-parse = Couldn't parse { $path } as { $format }: { $error }
+error-preset = Unknown preset "{ $name }", available are: { $available }
+error-create = Couldn't create { $path }: { $error }
+error-write = Couldn't write { $path }: { $error }
+error-parquet = Parquet output needs the parquet feature
+error-render = Couldn't render { $path }: { $error }
+error-listen = Couldn't listen on { $address }: { $error }
+error-output = Couldn't write the output: { $error }
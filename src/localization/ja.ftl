@@ -0,0 +1,177 @@
+# Window titles
+window-bindings = キー割り当て
+window-population = 個体数
+window-presets = プリセット
+window-bookmarks = ブックマーク
+window-tools = ツール
+window-split-view = 分割表示
+window-patterns = パターン
+window-minimap = ミニマップ
+window-compare = 比較
+window-rules = ルール
+window-timelapse = タイムラプス
+window-tutorial = チュートリアル
+window-sound = サウンド
+window-annotations = 注釈
+window-zones = ゾーン
+
+# Buttons of the controls panel
+controls-play = 再生
+controls-pause = 一時停止
+controls-step = ステップ
+controls-slower = 遅く
+controls-faster = 速く
+controls-unlimited = 無制限
+controls-shorter-stride = ストライド /2
+controls-longer-stride = ストライド x2
+controls-theme = テーマ
+controls-language = 言語
+
+# Tutorial window
+tutorial-next = 次へ
+tutorial-skip = スキップ
+tutorial-quit = 終了
+tutorial-done = 完了、お見事！
+tutorial-close = 閉じる
+
+# Bindings window
+bindings-rebind = 作業ディレクトリの { $file } で割り当てを変更できます
+bindings-unbound = 未割り当て
+binding-mouse = マウス { $button }
+binding-gamepad = ゲームパッド { $button }
+
+# Population window
+stats-population = 個体数
+stats-births = 誕生
+stats-deaths = 死亡
+stats-mean-field = 平均場
+stats-next-density = 次の密度
+stats-unchanged = 不変
+stats-fixed-points = 不動点
+stats-current-density = 現在の密度
+stats-fixed-point = 不動点 { $density } ({ $stable ->
+        [true] 安定
+       *[false] 不安定
+    })
+
+# Bookmarks window
+bookmarks-label = ラベル (例: 「銃が発射」)
+bookmarks-add = 第 { $generation } 世代をブックマーク
+bookmarks-generation = 第 { $generation } 世代
+bookmarks-jump = ジャンプ
+bookmarks-remove = 削除
+
+# Tools window
+tools-toggle = 切り替え
+tools-brush = ブラシ
+tools-line = 線
+tools-rectangle = 長方形
+tools-circle = 円
+tools-square = 正方形
+tools-spray = スプレー
+tools-filled = 図形を塗りつぶす
+tools-paint = 塗り
+tools-alive = 生
+tools-dead = 死
+tools-radius = 半径
+tools-density = 密度
+
+# Split view window
+split-view-pane = { $pane } | 相違: { $differing }
+split-view-remove = 削除
+split-view-show-difference = 差分を表示
+split-view-rules = ルール
+split-view-add = 追加
+split-view-resync = 再同期
+
+# Patterns window
+patterns-armed = クリックでスタンプ、Shift を押したままで連続スタンプ、{ $cancel } でキャンセル
+patterns-scatter = ばらまく
+patterns-scatter-count = ランダムなパターンの数
+patterns-fetch = 取得
+patterns-fetch-query = LifeWiki のパターン名、または apgcode
+
+# Compare window
+compare-pattern-file = パターンファイル
+compare-live = ライブ
+compare-live-grid = ライブのグリッド
+compare-capture = キャプチャ
+compare-generation = 第 { $generation } 世代
+compare-load = 読み込み
+compare-show-overlay = オーバーレイを表示
+compare-counts = A のみ: { $a } | B のみ: { $b } | 両方: { $both }
+
+# Rules window
+rules-birth = 誕生
+rules-survival = 生存
+rules-decay = 減衰
+rules-reseed = プレビューを再シード
+rules-apply = 適用
+
+# Timelapse window
+timelapse-path = パス
+timelapse-path-hint = .gif で終わればアニメーション GIF、それ以外は PNG フレームのディレクトリ
+timelapse-every = 間隔
+timelapse-scale = px/セル
+timelapse-delay = ms/GIF フレーム
+timelapse-recording = 録画中、これまでに { $frames } フレーム
+timelapse-start = 開始
+timelapse-stop = 停止
+
+# Sound window
+sound-muted = ミュート
+sound-volume = 音量
+sound-ticks = 世代ごとにティック音を鳴らす
+
+# Annotations window
+annotations-show-labels = ラベルを表示
+annotations-cell = セル
+annotations-row = 行 (ホバー中のセルに追従)
+annotations-column = 列 (ホバー中のセルに追従)
+annotations-label = ラベル (例: 「イーター」)
+annotations-annotate = 注釈を付ける
+annotations-remove = 削除
+
+# Zones window
+zones-show-outlines = 輪郭を表示
+zones-border = 境界
+zones-top-left = 左上
+zones-bottom-right = 右下
+zones-rules = B/S 記法によるゾーンのルール
+zones-add = ゾーンを追加
+zones-order = 後のゾーンが前のゾーンを覆い、残りのグリッドは元のルールのままです
+zones-bounds = { $top },{ $left } から { $bottom },{ $right }
+zones-remove = 削除
+
+# Readout of the hovered Cell
+hover-cell = 行 { $row }、列 { $col }: { $cell }
+
+# Status bar and terminal
+status-line = 世代: { $generation } | 個体数: { $population } | ルール: { $rules }
+status-behind = { $generations } 世代遅延
+status-mirroring = 対称: { $symmetry }
+status-paused = 一時停止中
+status-population = 個体数: { $population } (+{ $births } -{ $deaths })
+status-title = セルオートマトン
+speed-rate = { $rate } 世代/秒
+speed-unlimited = 無制限
+
+# Terminal
+experiment-runs = { $runs } 件の実行を { $threads } スレッドで
+fetch-cached = { $path } にキャッシュしました
+serve-websocket = { $address } で配信中
+serve-http = HTTP API: { $address }
+serve-chat = #{ $channel } からコマンドを受け付けています
+warning-theme = { $error }。クラシックテーマを使用します
+warning-record = 第 { $generation } 世代を記録できませんでした: { $error }
+warning-chat = #{ $channel } のチャットが切断されました: { $error }
+error-read = { $path } を読み込めませんでした: { $error }
+error-detect = { $path } の形式を判別できませんでした: { $error }。--from で指定してください
+error-parse = { $path } を { $format } として解析できませんでした: { $error }
+error-preset = 不明なプリセット「{ $name }」。利用可能: { $available }
+error-create = { $path } を作成できませんでした: { $error }
+error-write = { $path } に書き込めませんでした: { $error }
+error-parquet = Parquet 出力には parquet フィーチャーが必要です
+error-render = { $path } をレンダリングできませんでした: { $error }
+error-listen = { $address } で待ち受けできませんでした: { $error }
+error-output = 出力を書き込めませんでした: { $error }
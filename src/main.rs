@@ -1,12 +1,64 @@
+#![warn(
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+    // clippy::cargo
+)]
+// Bevy systems take their parameters by value
+#![allow(clippy::needless_pass_by_value)]
+
+mod gui;
+
 use bevy::{prelude::*, window::close_on_esc};
-fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .add_system(close_on_esc)
-        .add_system(print_hi)
-        .run();
-}
+use bevy_egui::EguiPlugin;
 
-fn print_hi() {
-    println!("Hi");
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "Cellular Automata".into(),
+            // Only has an effect on the web, where the canvas fills the page
+            fit_canvas_to_parent: true,
+            ..default()
+        }),
+        ..default()
+    }))
+    .add_plugin(EguiPlugin)
+    .add_plugin(gui::LocalizationPlugin)
+    .add_plugin(gui::InputPlugin)
+    .add_plugin(gui::SimulationPlugin)
+    .add_plugin(gui::ThemePlugin)
+    .add_plugin(gui::CameraPlugin)
+    .add_plugin(gui::RenderPlugin)
+    .add_plugin(gui::GpuPlugin)
+    .add_plugin(gui::LodPlugin)
+    .add_plugin(gui::EditorPlugin)
+    .add_plugin(gui::ToolsPlugin)
+    .add_plugin(gui::RuleEditorPlugin)
+    .add_plugin(gui::PresetsPlugin)
+    .add_plugin(gui::BrowserPlugin)
+    .add_plugin(gui::BookmarksPlugin)
+    .add_plugin(gui::AnnotationsPlugin)
+    .add_plugin(gui::ZonesPlugin)
+    .add_plugin(gui::StatsPlugin)
+    .add_plugin(gui::ShipsPlugin)
+    .add_plugin(gui::MinimapPlugin)
+    .add_plugin(gui::SplitViewPlugin)
+    .add_plugin(gui::ComparePlugin)
+    .add_plugin(gui::StatusPlugin)
+    .add_plugin(gui::ControlsPlugin)
+    .add_plugin(gui::DemoPlugin)
+    .add_plugin(gui::TutorialPlugin)
+    .add_plugin(gui::SoundPlugin)
+    .add_system(close_on_esc);
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_plugin(gui::SessionPlugin)
+        .add_plugin(gui::ScreenshotPlugin)
+        .add_plugin(gui::TimelapsePlugin);
+    app.run();
 }
@@ -0,0 +1,291 @@
+//! Grids backed by a memory mapped file, for universes bigger than the available RAM.
+//! The kernel pages tiles in and out as needed, which is slow but keeps the simulation going.
+//!
+//! The file is scratch space: every tile of `TILE_SIZE` x `TILE_SIZE` `Cell::state_byte`s
+//! takes one page per generation buffer, both buffers of a tile lie next to each other.
+//! Only tiles next to a tile that changed in the last step are stepped, so quiet regions
+//! are never paged in again, and the active tiles are prefetched before each step.
+
+use crate::{Cell, ColIdx, Generation, Grid, Neighborhood, RowIdx, RuleTable};
+use memmap2::MmapMut;
+use std::{error, fmt, fs::OpenOptions, io, ops::RangeInclusive, path::Path};
+
+/// Rows and columns of a tile
+pub const TILE_SIZE: usize = 64;
+const TILE_LEN: usize = TILE_SIZE * TILE_SIZE;
+
+/// Errors that can occur while creating a mapped grid
+/// - `Io` => The file couldn't be created or mapped
+/// - `TooLarge` => The grid doesn't fit into the address space
+#[derive(Debug)]
+pub enum MappedGridError {
+    Io(io::Error),
+    TooLarge,
+}
+
+impl fmt::Display for MappedGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't map the grid: {e}"),
+            Self::TooLarge => write!(f, "The grid doesn't fit into the address space"),
+        }
+    }
+}
+
+impl error::Error for MappedGridError {}
+
+impl From<io::Error> for MappedGridError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Grid of `Cell`s in a memory mapped file, the edges don't wrap around like in `transition`
+pub struct MappedGrid {
+    mmap: MmapMut,
+    row_count: usize,
+    col_count: usize,
+    tile_cols: usize,
+    /// Which of its two buffers holds the current generation of every tile
+    sides: Vec<bool>,
+    /// Tiles that changed in the last step or were edited since
+    changed: Vec<bool>,
+    generation: Generation,
+}
+
+impl MappedGrid {
+    /// Creates or truncates the file at `path` for a grid of `row_count` x `col_count`
+    /// `Cell::Dead` Cells. The file is sparse, so only the tiles that were touched take up disk.
+    pub fn create(
+        path: impl AsRef<Path>,
+        row_count: usize,
+        col_count: usize,
+    ) -> Result<Self, MappedGridError> {
+        let tile_rows = row_count.div_ceil(TILE_SIZE);
+        let tile_cols = col_count.div_ceil(TILE_SIZE);
+        let tiles = tile_rows
+            .checked_mul(tile_cols)
+            .ok_or(MappedGridError::TooLarge)?;
+        let len = tiles
+            .checked_mul(2 * TILE_LEN)
+            .and_then(|len| u64::try_from(len).ok())
+            .ok_or(MappedGridError::TooLarge)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(len)?;
+        // Safety: the file was just sized and is only ever accessed through this mapping
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        Ok(Self {
+            mmap,
+            row_count,
+            col_count,
+            tile_cols,
+            sides: vec![false; tiles],
+            // Rules may give birth to dead Cells, so everything is stepped once
+            changed: vec![true; tiles],
+            generation: Generation::default(),
+        })
+    }
+
+    pub const fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub const fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    pub const fn generation(&self) -> Generation {
+        self.generation
+    }
+
+    /// The Cell at (`row`, `col`), `None` outside of the grid
+    pub fn get(&self, RowIdx(row): RowIdx, ColIdx(col): ColIdx) -> Option<Cell> {
+        (row < self.row_count && col < self.col_count).then(|| self.cell(row, col))
+    }
+
+    /// Replaces the Cell at (`row`, `col`), returning the previous one.
+    /// Outside of the grid nothing is changed and `None` is returned.
+    pub fn set(&mut self, RowIdx(row): RowIdx, ColIdx(col): ColIdx, cell: &Cell) -> Option<Cell> {
+        let previous = self.get(RowIdx(row), ColIdx(col))?;
+        let tile = self.tile(row, col);
+        let offset = offset(tile, self.sides[tile], row, col);
+        self.mmap[offset] = cell.state_byte();
+        self.changed[tile] = true;
+        Some(previous)
+    }
+
+    /// Copies `pattern` into the grid with its top left corner at (`row`, `col`),
+    /// parts outside of the grid are clipped
+    pub fn stamp(&mut self, pattern: &[Vec<Cell>], row: usize, col: usize) {
+        for (pattern_row, cells) in pattern.iter().enumerate() {
+            for (pattern_col, cell) in cells.iter().enumerate() {
+                self.set(RowIdx(row + pattern_row), ColIdx(col + pattern_col), cell);
+            }
+        }
+    }
+
+    /// Copies the Cells within the given bounds out of the grid
+    pub fn region(&self, rows: RangeInclusive<usize>, cols: RangeInclusive<usize>) -> Grid {
+        let last_row = (*rows.end()).min(self.row_count.saturating_sub(1));
+        let last_col = (*cols.end()).min(self.col_count.saturating_sub(1));
+        (*rows.start()..=last_row)
+            .filter(|_| self.col_count > 0)
+            .map(|row| {
+                (*cols.start()..=last_col)
+                    .map(|col| self.cell(row, col))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Tiles that will be stepped next, the ones next to a changed tile
+    pub fn active_tiles(&self) -> usize {
+        self.stepped_tiles().len()
+    }
+
+    /// Advances the grid by one generation according to the rules compiled into `table`
+    pub fn step(&mut self, neighborhood: Neighborhood, table: &RuleTable) {
+        let stepped = self.stepped_tiles();
+        self.prefetch(&stepped);
+        let mut changed = vec![false; self.sides.len()];
+        for &tile in &stepped {
+            let next_side = !self.sides[tile];
+            let first_row = tile / self.tile_cols * TILE_SIZE;
+            let first_col = tile % self.tile_cols * TILE_SIZE;
+            for row in first_row..(first_row + TILE_SIZE).min(self.row_count) {
+                for col in first_col..(first_col + TILE_SIZE).min(self.col_count) {
+                    let current = self.cell(row, col);
+                    let neighbors = self.neighbors(neighborhood, row, col);
+                    let next = table.next_state(&current, &neighbors);
+                    changed[tile] |= next != current;
+                    let offset = offset(tile, next_side, row, col);
+                    self.mmap[offset] = next.state_byte();
+                }
+            }
+        }
+        // Tiles read their neighbors' current generation until every tile is stepped
+        for &tile in &stepped {
+            self.sides[tile] ^= changed[tile];
+        }
+        self.changed = changed;
+        self.generation.0 += 1;
+    }
+
+    /// Writes the dirty pages back to the file
+    pub fn flush(&self) -> Result<(), MappedGridError> {
+        Ok(self.mmap.flush()?)
+    }
+
+    fn stepped_tiles(&self) -> Vec<usize> {
+        let tile_rows = self.sides.len().checked_div(self.tile_cols).unwrap_or(0);
+        (0..self.sides.len())
+            .filter(|&tile| {
+                let (tile_row, tile_col) = (tile / self.tile_cols, tile % self.tile_cols);
+                (tile_row.saturating_sub(1)..=(tile_row + 1).min(tile_rows - 1)).any(|row| {
+                    (tile_col.saturating_sub(1)..=(tile_col + 1).min(self.tile_cols - 1))
+                        .any(|col| self.changed[row * self.tile_cols + col])
+                })
+            })
+            .collect()
+    }
+
+    /// Asks the kernel to page in both buffers of `tiles`, merging consecutive tiles
+    fn prefetch(&self, tiles: &[usize]) {
+        #[cfg(unix)]
+        for run in tiles.chunk_by(|previous, next| previous + 1 == *next) {
+            // Only a hint, stepping works without it
+            let _ = self.mmap.advise_range(
+                memmap2::Advice::WillNeed,
+                run[0] * 2 * TILE_LEN,
+                run.len() * 2 * TILE_LEN,
+            );
+        }
+        #[cfg(not(unix))]
+        let _ = tiles;
+    }
+
+    fn neighbors(&self, neighborhood: Neighborhood, row: usize, col: usize) -> Vec<Cell> {
+        (row.saturating_sub(1)..=(row + 1).min(self.row_count - 1))
+            .flat_map(|irow| {
+                (col.saturating_sub(1)..=(col + 1).min(self.col_count - 1))
+                    .map(move |icol| (irow, icol))
+            })
+            .filter(|&(irow, icol)| match neighborhood {
+                Neighborhood::Moore => irow != row || icol != col,
+                Neighborhood::VonNeumann => (irow == row) != (icol == col),
+            })
+            .map(|(irow, icol)| self.cell(irow, icol))
+            .collect()
+    }
+
+    /// Current Cell at (`row`, `col`), which has to lie within the grid
+    fn cell(&self, row: usize, col: usize) -> Cell {
+        let tile = self.tile(row, col);
+        let byte = self.mmap[offset(tile, self.sides[tile], row, col)];
+        Cell::from_state_number(usize::from(byte))
+    }
+
+    const fn tile(&self, row: usize, col: usize) -> usize {
+        row / TILE_SIZE * self.tile_cols + col / TILE_SIZE
+    }
+}
+
+/// Position of the Cell at (`row`, `col`) of `tile` within the buffer `side` of the tile
+fn offset(tile: usize, side: bool, row: usize, col: usize) -> usize {
+    let buffer = tile * 2 + usize::from(side);
+    buffer * TILE_LEN + row % TILE_SIZE * TILE_SIZE + col % TILE_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mapped::{MappedGrid, TILE_SIZE},
+        rle, Automaton, Cell, ColIdx, RowIdx, RuleSet, RuleTable,
+    };
+    use std::fs;
+
+    #[test]
+    fn matches_automaton() {
+        let path = std::env::temp_dir().join("cellular_automata_mapped_grid");
+        let (row_count, col_count) = (5 * TILE_SIZE, 5 * TILE_SIZE);
+        let mut automaton = Automaton::builder()
+            .row_count(row_count)
+            .col_count(col_count)
+            .grid(vec![vec![Cell::Dead; col_count]; row_count])
+            .build();
+        let mut mapped = MappedGrid::create(&path, row_count, col_count).unwrap();
+        // A glider crossing the corner of four tiles and a blinker at the edge of the grid
+        let glider = rle::parse("bo$2bo$3o!").unwrap();
+        let blinker = rle::parse("3o!").unwrap();
+        for (pattern, row, col) in [(&glider, 58, 58), (&blinker, 300, 300)] {
+            automaton.stamp(pattern, row, col);
+            mapped.stamp(pattern, row, col);
+        }
+        assert_eq!(mapped.active_tiles(), 25);
+
+        let table = RuleTable::new(&RuleSet::default());
+        for _ in 0..40 {
+            automaton.step();
+            mapped.step(automaton.neighborhood_type, &table);
+        }
+        assert_eq!(mapped.region(0..=row_count, 0..=col_count), automaton.grid);
+        assert_eq!(mapped.generation(), automaton.generation);
+        // Only the tiles around the glider, which has moved on to the second tile, and the blinker
+        assert_eq!(mapped.active_tiles(), 13);
+
+        assert_eq!(
+            mapped.set(RowIdx(319), ColIdx(319), &Cell::Alive),
+            Some(Cell::Dead)
+        );
+        assert_eq!(mapped.get(RowIdx(319), ColIdx(319)), Some(Cell::Alive));
+        assert_eq!(mapped.get(RowIdx(320), ColIdx(0)), None);
+        assert!(mapped.region(320..=330, 0..=10).is_empty());
+        mapped.flush().unwrap();
+        fs::remove_file(path).unwrap();
+    }
+}
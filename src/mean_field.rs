@@ -0,0 +1,143 @@
+//! Mean-field approximation of the density of alive Cells.
+//!
+//! Every Cell is assumed to be alive independently with the same probability,
+//! so the density of the next generation is a function of the current one.
+//! Its fixed points are the densities a soup would settle at if the Cells really were
+//! uncorrelated, a quick sanity check when exploring rules.
+
+use crate::{transition, Neighborhood, RuleSet};
+
+/// Densities `fixed_points` checks for crossings of the diagonal in between
+const SEARCH_SAMPLES: usize = 1000;
+/// Bisection steps refining each crossing
+const BISECTIONS: usize = 48;
+/// Step of the difference quotient approximating the slope at a fixed point
+const SLOPE_STEP: f64 = 1e-6;
+
+/// A density the mean-field map keeps unchanged
+/// - `stable` => Nearby densities approach it, the map is flatter than the diagonal there
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FixedPoint {
+    pub density: f64,
+    pub stable: bool,
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |product, i| product * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Probability that a Cell with `neighbors` neighbors, each alive with probability `density`,
+/// has an alive neighbor count among `counts`
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn probability(counts: &[usize], neighbors: usize, density: f64) -> f64 {
+    counts
+        .iter()
+        .filter(|&&count| count <= neighbors)
+        .map(|&count| {
+            binomial(neighbors, count)
+                * density.powi(count as i32)
+                * (1.0 - density).powi((neighbors - count) as i32)
+        })
+        .sum()
+}
+
+/// Expected density of alive Cells in the next generation if every Cell is alive with
+/// probability `density`.
+///
+/// Dying Cells of Generations rules are assumed to have settled, each of the `decay` dying
+/// states holding the Cells that died during one generation.
+#[allow(clippy::cast_precision_loss)]
+pub fn next_density(rule_set: &RuleSet, neighborhood: Neighborhood, density: f64) -> f64 {
+    let neighbors = transition::neighbor_positions(neighborhood, 1, 1).count();
+    let survival = probability(&rule_set.survival(), neighbors, density);
+    let birth = probability(&rule_set.birth(), neighbors, density);
+    let dying = rule_set.decay as f64 * density * (1.0 - survival);
+    let dead = (1.0 - density - dying).max(0.0);
+    density.mul_add(survival, dead * birth).clamp(0.0, 1.0)
+}
+
+/// `samples` evenly spaced densities from 0 to 1 with their `next_density`
+#[allow(clippy::cast_precision_loss)]
+pub fn curve(rule_set: &RuleSet, neighborhood: Neighborhood, samples: usize) -> Vec<[f64; 2]> {
+    let steps = samples.saturating_sub(1).max(1) as f64;
+    (0..samples)
+        .map(|index| {
+            let density = index as f64 / steps;
+            [density, next_density(rule_set, neighborhood, density)]
+        })
+        .collect()
+}
+
+/// Densities `next_density` keeps unchanged, in ascending order
+#[allow(clippy::cast_precision_loss)]
+pub fn fixed_points(rule_set: &RuleSet, neighborhood: Neighborhood) -> Vec<FixedPoint> {
+    let excess = |density: f64| next_density(rule_set, neighborhood, density) - density;
+    let density = |index: usize| index as f64 / SEARCH_SAMPLES as f64;
+    let mut densities = Vec::new();
+    for index in 0..=SEARCH_SAMPLES {
+        let (low, high) = (density(index), density(index + 1).min(1.0));
+        if excess(low) == 0.0 {
+            densities.push(low);
+        } else if high > low && excess(low).signum() != excess(high).signum() && excess(high) != 0.0
+        {
+            let (mut low, mut high) = (low, high);
+            for _ in 0..BISECTIONS {
+                let middle = f64::midpoint(low, high);
+                if excess(middle).signum() == excess(low).signum() {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            densities.push(f64::midpoint(low, high));
+        }
+    }
+    densities
+        .into_iter()
+        .map(|density| {
+            let (low, high) = (
+                (density - SLOPE_STEP).max(0.0),
+                (density + SLOPE_STEP).min(1.0),
+            );
+            let slope = (next_density(rule_set, neighborhood, high)
+                - next_density(rule_set, neighborhood, low))
+                / (high - low);
+            FixedPoint {
+                density,
+                stable: slope.abs() < 1.0,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mean_field, Neighborhood, RuleSet};
+
+    #[test]
+    fn life_fixed_points() {
+        let life: RuleSet = "B3/S23".parse().unwrap();
+        let next = |density| mean_field::next_density(&life, Neighborhood::Moore, density);
+        assert!(next(0.0).abs() < f64::EPSILON);
+        assert!(next(1.0).abs() < f64::EPSILON);
+        // (1-p) * 56p³(1-p)⁵ + p * (28p²(1-p)⁶ + 56p³(1-p)⁵) at p = 1/2
+        assert!((next(0.5) - 140.0 / 512.0).abs() < 1e-12);
+
+        let fixed_points = mean_field::fixed_points(&life, Neighborhood::Moore);
+        assert_eq!(fixed_points.len(), 3);
+        assert!(fixed_points[0].density.abs() < f64::EPSILON);
+        assert!(fixed_points[0].stable);
+        // Life's well known mean-field fixed points near 0.192 and 0.370
+        assert!((fixed_points[1].density - 0.1924).abs() < 1e-3);
+        assert!(!fixed_points[1].stable);
+        assert!((fixed_points[2].density - 0.3701).abs() < 1e-3);
+        assert!(fixed_points[2].stable);
+
+        let curve = mean_field::curve(&life, Neighborhood::VonNeumann, 5);
+        assert_eq!(curve.len(), 5);
+        assert!((curve[2][0] - 0.5).abs() < f64::EPSILON);
+        // 3 of 4 neighbors are alive far more often than 3 of 8
+        assert!(curve[2][1] > next(0.5));
+    }
+}
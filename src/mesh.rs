@@ -0,0 +1,249 @@
+//! Surfaces of voxel volumes as few large quads, exported as Wavefront OBJ to render them in
+//! other tools or to 3D print them.
+//!
+//! The generations of a grid stacked on top of each other, time pointing up, are such a volume.
+//! Used by `no_bevy_2d render --output stack.obj`.
+
+use crate::{Cell, Grid};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+/// Filled and empty voxels of a `width` x `height` x `depth` box, `y` pointing up
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Volume {
+    size: [usize; 3],
+    voxels: Vec<bool>,
+}
+
+impl Volume {
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        Self {
+            size: [width, height, depth],
+            voxels: vec![false; width * height * depth],
+        }
+    }
+
+    /// The alive Cells of `grids` as layers, the first grid at the bottom.
+    /// Columns run along `x` and rows along `z`.
+    pub fn stack(grids: &[Grid]) -> Self {
+        let rows = grids.first().map_or(0, Vec::len);
+        let cols = grids
+            .first()
+            .and_then(|grid| grid.first())
+            .map_or(0, Vec::len);
+        let mut volume = Self::new(cols, grids.len(), rows);
+        for (generation, grid) in grids.iter().enumerate() {
+            for (row, cells) in grid.iter().enumerate().take(rows) {
+                for (col, cell) in cells.iter().enumerate().take(cols) {
+                    volume.set([col, generation, row], *cell == Cell::Alive);
+                }
+            }
+        }
+        volume
+    }
+
+    /// Width, height and depth
+    pub const fn size(&self) -> [usize; 3] {
+        self.size
+    }
+
+    fn index(&self, [x, y, z]: [usize; 3]) -> Option<usize> {
+        let [width, height, depth] = self.size;
+        (x < width && y < height && z < depth).then_some((z * height + y) * width + x)
+    }
+
+    /// Whether the voxel at `position` is filled, `false` outside of the volume
+    pub fn get(&self, position: [usize; 3]) -> bool {
+        self.index(position).is_some_and(|index| self.voxels[index])
+    }
+
+    /// Positions outside of the volume are ignored
+    pub fn set(&mut self, position: [usize; 3], filled: bool) {
+        if let Some(index) = self.index(position) {
+            self.voxels[index] = filled;
+        }
+    }
+}
+
+/// A rectangle on the surface, its corners counterclockwise seen from outside
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Quad {
+    pub corners: [[usize; 3]; 4],
+    /// Points out of the volume along one axis
+    pub normal: [i8; 3],
+}
+
+/// The surface of a `Volume`
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Mesh {
+    pub quads: Vec<Quad>,
+}
+
+/// Covers every face between a filled and an empty voxel, merging neighboring faces of the same
+/// orientation into rectangles.
+///
+/// The rectangles are grown greedily: each one as far as possible along one axis,
+/// then along the other.
+pub fn greedy_mesh(volume: &Volume) -> Mesh {
+    let size = volume.size();
+    let mut quads = Vec::new();
+    for axis in 0..3 {
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let position = |layer: usize, i: usize, j: usize| {
+            let mut position = [0; 3];
+            position[axis] = layer;
+            position[u] = i;
+            position[v] = j;
+            position
+        };
+        // Orientation of the face in front of each voxel of a layer, if any
+        let mut mask = vec![0_i8; size[u] * size[v]];
+        for layer in 0..=size[axis] {
+            for (index, face) in mask.iter_mut().enumerate() {
+                let (i, j) = (index % size[u], index / size[u]);
+                let behind = layer > 0 && volume.get(position(layer - 1, i, j));
+                let ahead = volume.get(position(layer, i, j));
+                *face = match (behind, ahead) {
+                    (true, false) => 1,
+                    (false, true) => -1,
+                    _ => 0,
+                };
+            }
+            for index in 0..mask.len() {
+                let face = mask[index];
+                if face == 0 {
+                    continue;
+                }
+                let (i, j) = (index % size[u], index / size[u]);
+                let width = (i..size[u])
+                    .take_while(|&i| mask[j * size[u] + i] == face)
+                    .count();
+                let height = (j..size[v])
+                    .take_while(|&j| (i..i + width).all(|i| mask[j * size[u] + i] == face))
+                    .count();
+                for j in j..j + height {
+                    mask[j * size[u] + i..j * size[u] + i + width].fill(0);
+                }
+                let mut corners = [
+                    position(layer, i, j),
+                    position(layer, i + width, j),
+                    position(layer, i + width, j + height),
+                    position(layer, i, j + height),
+                ];
+                if face < 0 {
+                    corners.reverse();
+                }
+                let mut normal = [0; 3];
+                normal[axis] = face;
+                quads.push(Quad { corners, normal });
+            }
+        }
+    }
+    Mesh { quads }
+}
+
+impl Mesh {
+    /// Writes the quads as Wavefront OBJ with `scale` units per voxel, corners shared by
+    /// several quads are written once
+    #[allow(clippy::cast_precision_loss)]
+    pub fn write_obj(&self, mut writer: impl Write, scale: f32) -> io::Result<()> {
+        writeln!(writer, "# {} quads", self.quads.len())?;
+        let mut vertices = HashMap::new();
+        for quad in &self.quads {
+            for corner in quad.corners {
+                if !vertices.contains_key(&corner) {
+                    vertices.insert(corner, vertices.len() + 1);
+                    let [x, y, z] = corner.map(|coordinate| coordinate as f32 * scale);
+                    writeln!(writer, "v {x} {y} {z}")?;
+                }
+            }
+        }
+        let axes = [
+            [1, 0, 0],
+            [-1, 0, 0],
+            [0, 1, 0],
+            [0, -1, 0],
+            [0, 0, 1],
+            [0, 0, -1],
+        ];
+        for [x, y, z] in axes {
+            writeln!(writer, "vn {x} {y} {z}")?;
+        }
+        for quad in &self.quads {
+            let normal = axes
+                .iter()
+                .position(|&axis| axis == quad.normal)
+                .unwrap_or_default()
+                + 1;
+            let [a, b, c, d] = quad.corners.map(|corner| vertices[&corner]);
+            writeln!(
+                writer,
+                "f {a}//{normal} {b}//{normal} {c}//{normal} {d}//{normal}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mesh::{self, Volume},
+        rle, Automaton, Cell,
+    };
+
+    #[test]
+    fn greedy_boxes() {
+        let mut volume = Volume::new(3, 3, 3);
+        volume.set([1, 1, 1], true);
+        let mesh = mesh::greedy_mesh(&volume);
+        assert_eq!(mesh.quads.len(), 6);
+        for quad in &mesh.quads {
+            // Counterclockwise corners make the normal by the right hand rule
+            let [a, b, c, _] = quad
+                .corners
+                .map(|corner| corner.map(|x| i64::try_from(x).unwrap()));
+            let (ab, ac) = (
+                [0, 1, 2].map(|i| b[i] - a[i]),
+                [0, 1, 2].map(|i| c[i] - a[i]),
+            );
+            let cross = [
+                ab[1] * ac[2] - ab[2] * ac[1],
+                ab[2] * ac[0] - ab[0] * ac[2],
+                ab[0] * ac[1] - ab[1] * ac[0],
+            ];
+            assert_eq!(cross, quad.normal.map(i64::from));
+        }
+
+        // A block stays a box through the generations, a blinker turns every generation
+        let mut automaton = Automaton::builder()
+            .row_count(6)
+            .col_count(10)
+            .grid(vec![vec![Cell::Dead; 10]; 6])
+            .build();
+        automaton.stamp(&rle::parse("2o$2o!").unwrap(), 2, 1);
+        automaton.stamp(&rle::parse("3o!").unwrap(), 2, 5);
+        let mut grids = vec![automaton.grid.clone()];
+        grids.extend(automaton.generations().take(3));
+        let volume = Volume::stack(&grids);
+        assert_eq!(volume.size(), [10, 4, 6]);
+        assert!(volume.get([1, 3, 2]) && volume.get([6, 1, 1]) && !volume.get([5, 1, 2]));
+        let mesh = mesh::greedy_mesh(&volume);
+        let block = mesh
+            .quads
+            .iter()
+            .filter(|quad| quad.corners.iter().all(|corner| corner[0] <= 3))
+            .count();
+        assert_eq!(block, 6);
+
+        let mut obj = Vec::new();
+        mesh.write_obj(&mut obj, 2.0).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+        let count = |prefix| obj.lines().filter(|line| line.starts_with(prefix)).count();
+        assert_eq!(count("f "), mesh.quads.len());
+        assert_eq!(count("vn "), 6);
+        assert!(obj.contains("v 6 8 8\n"));
+    }
+}
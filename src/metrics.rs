@@ -0,0 +1,129 @@
+//! Spatial entropy and complexity measures for classifying rules, see `Automaton::metrics`
+
+use crate::{Automaton, Cell};
+use flate2::{write::GzEncoder, Compression};
+use std::{collections::HashMap, hash::Hash, io::Write};
+
+/// Side length of the blocks of `Metrics::block_entropy`
+pub const BLOCK_SIZE: usize = 2;
+
+/// Measures of a single generation
+/// - `entropy` => See `entropy`
+/// - `block_entropy` => See `block_entropy`, with `BLOCK_SIZE` x `BLOCK_SIZE` blocks
+/// - `compression_ratio` => See `compression_ratio`
+/// - `activity` => Fraction of the Cells that were born or died during the last step
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub struct Metrics {
+    pub entropy: f64,
+    pub block_entropy: f64,
+    pub compression_ratio: f64,
+    pub activity: f64,
+}
+
+impl From<&Automaton> for Metrics {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Automaton) -> Self {
+        let cell_count = value.grid.iter().map(Vec::len).sum::<usize>().max(1);
+        Self {
+            entropy: entropy(&value.grid),
+            block_entropy: block_entropy(&value.grid, BLOCK_SIZE),
+            compression_ratio: compression_ratio(&value.grid),
+            activity: (value.births + value.deaths) as f64 / cell_count as f64,
+        }
+    }
+}
+
+/// Shannon entropy of the distribution of Cell states in bits,
+/// `0.0` for a uniform grid and `1.0` for a two state grid that is half alive
+pub fn entropy(grid: &[Vec<Cell>]) -> f64 {
+    shannon(grid.iter().flatten())
+}
+
+/// Shannon entropy in bits of the alive patterns within all overlapping `size` x `size` blocks,
+/// at most `size * size` bits.
+///
+/// Unlike `entropy` it tells structure from noise: a striped grid and a random soup are both
+/// half alive, but only the soup has many patterns. `size` is clamped to `1..=8`.
+pub fn block_entropy(grid: &[Vec<Cell>], size: usize) -> f64 {
+    let size = size.clamp(1, 8);
+    let col_count = grid.first().map_or(0, Vec::len);
+    if col_count < size {
+        return 0.0;
+    }
+    let blocks = grid.windows(size).flat_map(|rows| {
+        (0..=col_count - size).map(move |col| {
+            rows.iter()
+                .flat_map(|row| &row[col..col + size])
+                .fold(0_u64, |pattern, cell| {
+                    pattern << 1 | u64::from(*cell == Cell::Alive)
+                })
+        })
+    });
+    shannon(blocks)
+}
+
+/// Size of the gzipped state bytes relative to their uncompressed size,
+/// an estimate of the Kolmogorov complexity. Regular grids compress far below `1.0`,
+/// random soups stay closer to it.
+#[allow(clippy::cast_precision_loss)]
+pub fn compression_ratio(grid: &[Vec<Cell>]) -> f64 {
+    let bytes = grid
+        .iter()
+        .flatten()
+        .map(Cell::state_byte)
+        .collect::<Vec<_>>();
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let compressed = encoder
+        .write_all(&bytes)
+        .and_then(|()| encoder.finish())
+        .map_or(bytes.len(), |compressed| compressed.len());
+    compressed as f64 / bytes.len() as f64
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn shannon<T: Eq + Hash>(values: impl IntoIterator<Item = T>) -> f64 {
+    let mut counts = HashMap::<T, usize>::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    let total = counts.values().sum::<usize>() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let probability = count as f64 / total;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{metrics, Cell};
+
+    #[test]
+    fn structure_and_noise() {
+        let stripes = (0..33)
+            .map(|row| {
+                vec![
+                    if row % 2 == 0 {
+                        Cell::Alive
+                    } else {
+                        Cell::Dead
+                    };
+                    32
+                ]
+            })
+            .collect::<Vec<_>>();
+        let soup = crate::seeding::soup(7, 32, 32, 0.5);
+        assert!((metrics::entropy(&stripes[..32]) - 1.0).abs() < f64::EPSILON);
+        assert!(metrics::entropy(&soup) > 0.95);
+        // Every 2 x 2 block of the stripes is one of two equally frequent patterns
+        assert!((metrics::block_entropy(&stripes, 2) - 1.0).abs() < f64::EPSILON);
+        assert!(metrics::block_entropy(&soup, 2) > 3.5);
+        assert!(metrics::compression_ratio(&stripes) < metrics::compression_ratio(&soup));
+        assert!(metrics::entropy(&[vec![Cell::Dead; 4]]).abs() < f64::EPSILON);
+    }
+}
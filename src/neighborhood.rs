@@ -0,0 +1,78 @@
+use crate::Cell;
+use alloc::collections::BTreeMap;
+
+/// Represents the Neighborhood checking type
+/// - `Moore` => Checks all neighbors including the diagonal neighbors
+/// - `VonNeumann` => Checks all neighbors excluding the diagonal neighbors
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(clap::ValueEnum, serde::Serialize, serde::Deserialize)
+)]
+pub enum Neighborhood {
+    #[default]
+    Moore,
+    VonNeumann,
+}
+
+/// Number of neighbors of a Cell in each state
+#[derive(Default, Debug, PartialEq, Eq, Clone)]
+pub struct NeighborCounts {
+    counts: BTreeMap<Cell, usize>,
+}
+
+impl NeighborCounts {
+    /// Neighbors in exactly the state `state`
+    pub fn of(&self, state: &Cell) -> usize {
+        self.counts.get(state).copied().unwrap_or_default()
+    }
+
+    /// Neighbors that are `Cell::Alive`, `Cell::Dying` ones excluded
+    pub fn alive(&self) -> usize {
+        self.of(&Cell::Alive)
+    }
+
+    /// Neighbors that are `Cell::Dying`, regardless of their remaining ticks
+    pub fn dying(&self) -> usize {
+        self.counts
+            .iter()
+            .filter(|(state, _)| state.is_dying())
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+impl<'a> FromIterator<&'a Cell> for NeighborCounts {
+    fn from_iter<I: IntoIterator<Item = &'a Cell>>(neighbors: I) -> Self {
+        let mut counts = BTreeMap::new();
+        for neighbor in neighbors {
+            *counts.entry(neighbor.clone()).or_default() += 1;
+        }
+        Self { counts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, NeighborCounts};
+
+    #[test]
+    fn counts_per_state() {
+        let dying = |ticks_till_death| Cell::Dying { ticks_till_death };
+        let neighbors = [
+            Cell::Alive,
+            Cell::Dead,
+            dying(1),
+            dying(2),
+            dying(2),
+            Cell::Alive,
+        ]
+        .iter()
+        .collect::<NeighborCounts>();
+        assert_eq!(neighbors.of(&Cell::Alive), 2);
+        assert_eq!(neighbors.of(&dying(2)), 2);
+        assert_eq!(neighbors.of(&dying(3)), 0);
+        assert_eq!(neighbors.dying(), 3);
+        assert_eq!(neighbors.alive(), 2);
+    }
+}
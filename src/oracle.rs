@@ -0,0 +1,263 @@
+//! Differential testing of the stepping engines.
+//!
+//! Soups are stepped by `Automaton::step` and by every engine in `ENGINES`,
+//! reporting the first generation they disagree on. Run with `cargo test --features engines`, plus the features of the optional engines.
+
+#[cfg(feature = "mapped-grid")]
+use crate::mapped::MappedGrid;
+pub use crate::seeding::soup;
+#[cfg(any(feature = "mapped-grid", feature = "ndarray"))]
+use crate::RuleTable;
+#[cfg(feature = "ndarray")]
+use crate::{array, PackedGrid};
+use crate::{
+    distributed,
+    grid::BoundingBox,
+    tiled::TilePool,
+    zone::{Border, Zone, Zones},
+    Automaton, Cell, Neighborhood, RuleSet,
+};
+use itertools::iproduct;
+#[cfg(feature = "mapped-grid")]
+use std::{
+    env, fs, process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{error, fmt};
+
+/// Advances an `Automaton` by one generation
+pub type Engine = fn(&mut Automaton);
+
+/// Engines checked against the naive `Automaton::step`,
+/// optimized engines are registered here to be covered by the oracle
+pub const ENGINES: &[(&str, Engine)] = &[
+    ("reference", reference_step),
+    ("tiled", tiled_step),
+    ("zoned", zoned_step),
+    ("distributed", distributed_step),
+    #[cfg(feature = "mapped-grid")]
+    ("mapped", mapped_step),
+    #[cfg(feature = "ndarray")]
+    ("ndarray", ndarray_step),
+];
+
+/// A grid an engine computed differently than `Automaton::step`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Mismatch {
+    pub engine: &'static str,
+    /// Seed of the soup, reproduces the failure with `check`
+    pub seed: u64,
+    pub generation: usize,
+    pub rule_set: String,
+    pub neighborhood: Neighborhood,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Engine {:?} diverged in generation {} of soup {} ({}, {:?})",
+            self.engine, self.generation, self.seed, self.rule_set, self.neighborhood
+        )
+    }
+}
+
+impl error::Error for Mismatch {}
+
+/// Life-like stepping written independently of `Automaton::step`, straight from the
+/// birth and survival counts of the rules
+fn reference_step(automaton: &mut Automaton) {
+    let (birth, survival) = (automaton.rule_set.birth(), automaton.rule_set.survival());
+    let (row_count, col_count) = (automaton.row_count, automaton.col_count);
+    let grid = &automaton.grid;
+    let mut next = grid.clone();
+    for (row, col) in iproduct!(0..row_count, 0..col_count) {
+        let mut alive_neighbors = 0;
+        for (row_offset, col_offset) in iproduct!(-1_isize..=1, -1_isize..=1) {
+            let diagonal = row_offset != 0 && col_offset != 0;
+            if (row_offset, col_offset) == (0, 0)
+                || (diagonal && automaton.neighborhood_type == Neighborhood::VonNeumann)
+            {
+                continue;
+            }
+            let neighbor = row
+                .checked_add_signed(row_offset)
+                .zip(col.checked_add_signed(col_offset))
+                .filter(|&(row, col)| row < row_count && col < col_count);
+            if let Some((row, col)) = neighbor {
+                alive_neighbors += usize::from(grid[row][col].is_alive());
+            }
+        }
+        let counts = if grid[row][col].is_alive() {
+            &survival
+        } else {
+            &birth
+        };
+        next[row][col] = if counts.contains(&alive_neighbors) {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        };
+    }
+    automaton.grid = next;
+    automaton.generation += 1;
+}
+
+/// One generation on a `TilePool` with tiles small enough that soups span several of them
+fn tiled_step(automaton: &mut Automaton) {
+    let mut pool = TilePool::new(automaton, 5, 3);
+    pool.advance(1);
+    automaton.grid = pool.grid();
+    automaton.generation += 1;
+}
+
+/// One generation with the grid split into two zones with open borders, both following
+/// the rules of the automaton
+fn zoned_step(automaton: &mut Automaton) {
+    let half = |left, right| {
+        let bounds = BoundingBox {
+            top: 0,
+            left,
+            bottom: automaton.row_count.saturating_sub(1),
+            right,
+        };
+        Zone::new(bounds, automaton.rule_set.clone())
+    };
+    let middle = automaton.col_count / 2;
+    let zones = Zones {
+        border: Border::Open,
+        zones: vec![
+            half(0, middle.saturating_sub(1)),
+            half(middle, automaton.col_count.saturating_sub(1)),
+        ],
+    };
+    zones.step(automaton);
+}
+
+/// One generation of the grid split into three `distributed::Strip`s, each stepped with the
+/// edges of its neighbors as halos like on a worker
+fn distributed_step(automaton: &mut Automaton) {
+    let mut strips = distributed::split(automaton, 3);
+    let edges = strips
+        .iter()
+        .map(|strip| (strip.cells.first().cloned(), strip.cells.last().cloned()))
+        .collect::<Vec<_>>();
+    for (index, strip) in strips.iter_mut().enumerate() {
+        let above = index
+            .checked_sub(1)
+            .and_then(|above| edges[above].1.as_deref());
+        let below = edges.get(index + 1).and_then(|below| below.0.as_deref());
+        strip.step(above, below);
+    }
+    automaton.grid = strips.into_iter().flat_map(|strip| strip.cells).collect();
+    automaton.generation += 1;
+}
+
+/// One generation on a `MappedGrid` in a temporary file
+#[cfg(feature = "mapped-grid")]
+fn mapped_step(automaton: &mut Automaton) {
+    // Oracles may run on several threads at once
+    static FILES: AtomicUsize = AtomicUsize::new(0);
+    let path = env::temp_dir().join(format!(
+        "cellular_automata_oracle_{}_{}",
+        process::id(),
+        FILES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let (row_count, col_count) = (automaton.row_count, automaton.col_count);
+    let mut grid = MappedGrid::create(&path, row_count, col_count)
+        .expect("The temporary directory is writable");
+    grid.stamp(&automaton.grid, 0, 0);
+    grid.step(
+        automaton.neighborhood_type,
+        &RuleTable::new(&automaton.rule_set),
+    );
+    automaton.grid = grid.region(
+        0..=row_count.saturating_sub(1),
+        0..=col_count.saturating_sub(1),
+    );
+    drop(grid);
+    let _ = fs::remove_file(path);
+    automaton.generation += 1;
+}
+
+/// One generation of the packed grid as an `ndarray` array
+#[cfg(feature = "ndarray")]
+fn ndarray_step(automaton: &mut Automaton) {
+    let next = array::next_array(
+        automaton.packed().view(),
+        automaton.neighborhood_type,
+        &RuleTable::new(&automaton.rule_set),
+    );
+    automaton.grid = PackedGrid::from(next).to_grid();
+    automaton.generation += 1;
+}
+
+/// Runs the soup of `seed` for `generations` through `Automaton::step` and every engine
+/// in `ENGINES`, comparing their grids after each generation.
+/// Only `rule_set`s without decay are supported.
+pub fn check(
+    seed: u64,
+    size: usize,
+    generations: usize,
+    rule_set: &RuleSet,
+    neighborhood: Neighborhood,
+) -> Result<(), Mismatch> {
+    let naive = Automaton::builder()
+        .row_count(size)
+        .col_count(size)
+        .grid(soup(seed, size, size, 0.5))
+        .rule_set(rule_set.clone())
+        .neighborhood_type(neighborhood)
+        .build();
+    let mut engines = ENGINES
+        .iter()
+        .map(|&(name, engine)| (name, engine, naive.clone()))
+        .collect::<Vec<_>>();
+    let mut naive = naive;
+    for generation in 1..=generations {
+        naive.step();
+        for &mut (name, engine, ref mut automaton) in &mut engines {
+            engine(automaton);
+            if automaton.grid != naive.grid {
+                return Err(Mismatch {
+                    engine: name,
+                    seed,
+                    generation,
+                    rule_set: rule_set.to_string(),
+                    neighborhood,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{oracle, Neighborhood, RuleSet};
+
+    const SOUPS: u64 = 16;
+    const SIZE: usize = 24;
+    const GENERATIONS: usize = 32;
+
+    #[test]
+    fn engines_match_naive() {
+        let rule_sets = [
+            RuleSet::default(),
+            RuleSet::life_like(vec![3, 6], vec![2, 3], 0),
+            RuleSet::life_like(vec![3, 6, 7, 8], vec![3, 4, 6, 7, 8], 0),
+            RuleSet::life_like(vec![2], vec![], 0),
+        ];
+        for rule_set in &rule_sets {
+            for neighborhood in [Neighborhood::Moore, Neighborhood::VonNeumann] {
+                for seed in 0..SOUPS {
+                    if let Err(mismatch) =
+                        oracle::check(seed, SIZE, GENERATIONS, rule_set, neighborhood)
+                    {
+                        panic!("{mismatch}");
+                    }
+                }
+            }
+        }
+    }
+}
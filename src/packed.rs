@@ -0,0 +1,144 @@
+//! Compact snapshots of grids with one byte per Cell. Like `transition` it only needs `alloc`.
+//!
+//! The grid an `Automaton` steps stays a `Grid`, packing is for the copies kept around besides it,
+//! e.g. the generations `analysis::find_period` remembers.
+
+use crate::{Cell, ColIdx, Grid, RowIdx};
+use alloc::vec::Vec;
+
+/// A `Grid` stored as one `Cell::state_byte` per Cell, row by row.
+///
+/// `Vec<Vec<Cell>>` spends 16 bytes per Cell, this 1 byte, which adds up when keeping
+/// many generations around. The `Cell` API is kept at the edges,
+/// only `Cell::Dying` Cells with more than 254 ticks till death don't survive packing.
+#[derive(Default, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct PackedGrid {
+    col_count: usize,
+    bytes: Vec<u8>,
+}
+
+impl PackedGrid {
+    /// A grid of `Cell::Dead` Cells
+    pub fn new(row_count: usize, col_count: usize) -> Self {
+        Self {
+            col_count,
+            bytes: alloc::vec![Cell::Dead.state_byte(); row_count * col_count],
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.bytes
+            .len()
+            .checked_div(self.col_count)
+            .unwrap_or_default()
+    }
+
+    pub const fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    /// The Cell at (`row`, `col`), `None` outside of the grid
+    pub fn get(&self, RowIdx(row): RowIdx, ColIdx(col): ColIdx) -> Option<Cell> {
+        let byte = self.bytes.get(self.index(row, col)?)?;
+        Some(Cell::from_state_number(usize::from(*byte)))
+    }
+
+    /// Replaces the Cell at (`row`, `col`), returning the previous one.
+    /// Outside of the grid nothing is changed and `None` is returned.
+    pub fn set(&mut self, RowIdx(row): RowIdx, ColIdx(col): ColIdx, cell: &Cell) -> Option<Cell> {
+        let index = self.index(row, col)?;
+        let byte = self.bytes.get_mut(index)?;
+        let previous = Cell::from_state_number(usize::from(*byte));
+        *byte = cell.state_byte();
+        Some(previous)
+    }
+
+    /// `Cell::state_byte`s row by row
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Grid of `col_count` columns out of `Cell::state_byte`s row by row,
+    /// `None` unless the number of bytes is a multiple of `col_count`
+    pub fn from_bytes(col_count: usize, bytes: Vec<u8>) -> Option<Self> {
+        bytes
+            .len()
+            .is_multiple_of(col_count)
+            .then_some(Self { col_count, bytes })
+    }
+
+    /// Unpacks every Cell
+    pub fn to_grid(&self) -> Grid {
+        if self.col_count == 0 {
+            return Grid::new();
+        }
+        self.bytes
+            .chunks(self.col_count)
+            .map(|row| {
+                row.iter()
+                    .map(|&byte| Cell::from_state_number(usize::from(byte)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn index(&self, row: usize, col: usize) -> Option<usize> {
+        (col < self.col_count).then_some(row * self.col_count + col)
+    }
+}
+
+impl From<&[Vec<Cell>]> for PackedGrid {
+    fn from(grid: &[Vec<Cell>]) -> Self {
+        Self {
+            col_count: grid.first().map_or(0, Vec::len),
+            bytes: grid.iter().flatten().map(Cell::state_byte).collect(),
+        }
+    }
+}
+
+impl From<&Grid> for PackedGrid {
+    fn from(grid: &Grid) -> Self {
+        grid.as_slice().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{packed::PackedGrid, Cell, ColIdx, RowIdx};
+    use alloc::vec;
+
+    #[test]
+    fn round_trip() {
+        let dying = |ticks_till_death| Cell::Dying { ticks_till_death };
+        let grid = vec![
+            vec![Cell::Dead, Cell::Alive, dying(1)],
+            vec![dying(254), Cell::Dead, Cell::Alive],
+        ];
+        let mut packed = PackedGrid::from(&grid);
+        assert_eq!((packed.row_count(), packed.col_count()), (2, 3));
+        assert_eq!(packed.as_bytes().len(), 6);
+        assert_eq!(packed.to_grid(), grid);
+        assert_eq!(packed.get(RowIdx(1), ColIdx(0)), Some(dying(254)));
+        assert_eq!(packed.get(RowIdx(0), ColIdx(3)), None);
+
+        assert_eq!(
+            packed.set(RowIdx(0), ColIdx(0), &dying(1000)),
+            Some(Cell::Dead)
+        );
+        assert_eq!(packed.get(RowIdx(0), ColIdx(0)), Some(dying(254)));
+        assert_eq!(packed.set(RowIdx(2), ColIdx(0), &Cell::Alive), None);
+        assert_eq!(
+            PackedGrid::new(2, 2).to_grid(),
+            vec![vec![Cell::Dead; 2]; 2]
+        );
+        assert_eq!(PackedGrid::new(0, 0).row_count(), 0);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{Automaton, Cell, Neighborhood, RuleSet, Theme};
+
+/// A family of automata, registered in a `Registry` to show up in the CLI `--preset` list
+/// and the GUI.
+///
+/// Only `name` and `rule_set` have to be implemented,
+/// new methods are only ever added with a default implementation.
+pub trait AutomatonPlugin: Send + Sync {
+    /// Unique name of the family
+    fn name(&self) -> &str;
+
+    /// Transition of the family, expressed in the rule system
+    fn rule_set(&self) -> RuleSet;
+
+    /// States a Cell of the family can be in
+    fn states(&self) -> Vec<Cell> {
+        let decay = self.rule_set().decay;
+        let dying = (1..=decay)
+            .rev()
+            .map(|ticks_till_death| Cell::Dying { ticks_till_death });
+        [Cell::Dead, Cell::Alive].into_iter().chain(dying).collect()
+    }
+
+    /// Colors the family is rendered with by default
+    fn palette(&self) -> Theme {
+        Theme::classic()
+    }
+
+    /// Named configurations of the family, by default only its `rule_set`
+    fn presets(&self) -> Vec<Preset> {
+        vec![Preset::new(self.name(), self.rule_set())]
+    }
+}
+
+/// A named rule set and neighborhood
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Preset {
+    pub name: String,
+    pub rule_set: RuleSet,
+    pub neighborhood: Neighborhood,
+}
+
+impl Preset {
+    pub fn new(name: impl Into<String>, rule_set: RuleSet) -> Self {
+        Self {
+            name: name.into(),
+            rule_set,
+            neighborhood: Neighborhood::default(),
+        }
+    }
+
+    /// Switches `automaton` to the rules and neighborhood of the preset, keeping its grid
+    pub fn apply(&self, automaton: &mut Automaton) {
+        automaton.rule_set = self.rule_set.clone();
+        automaton.neighborhood_type = self.neighborhood;
+    }
+}
+
+/// Life-like automata, Cells are either dead or alive
+pub struct LifeLike;
+
+impl AutomatonPlugin for LifeLike {
+    fn name(&self) -> &'static str {
+        "Life-like"
+    }
+
+    fn rule_set(&self) -> RuleSet {
+        RuleSet::default()
+    }
+
+    fn presets(&self) -> Vec<Preset> {
+        vec![
+            Preset::new("Conway", RuleSet::default()),
+            Preset::new("HighLife", RuleSet::life_like(vec![3, 6], vec![2, 3], 0)),
+            Preset::new(
+                "Day & Night",
+                RuleSet::life_like(vec![3, 6, 7, 8], vec![3, 4, 6, 7, 8], 0),
+            ),
+            Preset::new("Seeds", RuleSet::life_like(vec![2], vec![], 0)),
+        ]
+    }
+}
+
+/// Life-like automata whose Cells spend some generations dying
+pub struct Generations;
+
+impl AutomatonPlugin for Generations {
+    fn name(&self) -> &'static str {
+        "Generations"
+    }
+
+    fn rule_set(&self) -> RuleSet {
+        RuleSet::life_like(vec![2], vec![], 1)
+    }
+
+    fn palette(&self) -> Theme {
+        Theme::ocean()
+    }
+
+    fn presets(&self) -> Vec<Preset> {
+        vec![
+            Preset::new("Brian's Brain", self.rule_set()),
+            Preset::new("Star Wars", RuleSet::life_like(vec![2], vec![3, 4, 5], 2)),
+        ]
+    }
+}
+
+/// The available automaton families, starting with the built in ones
+pub struct Registry {
+    plugins: Vec<Box<dyn AutomatonPlugin>>,
+    /// Keeps the libraries of loaded plugins alive, dropped after the plugins
+    #[cfg(feature = "dynamic-plugins")]
+    libraries: Vec<libloading::Library>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Self {
+            plugins: Vec::new(),
+            #[cfg(feature = "dynamic-plugins")]
+            libraries: Vec::new(),
+        };
+        registry.register(LifeLike);
+        registry.register(Generations);
+        registry
+    }
+}
+
+impl Registry {
+    pub fn register(&mut self, plugin: impl AutomatonPlugin + 'static) {
+        self.plugins.push(Box::new(plugin));
+    }
+
+    pub fn plugins(&self) -> impl Iterator<Item = &dyn AutomatonPlugin> {
+        self.plugins.iter().map(AsRef::as_ref)
+    }
+
+    /// Presets of every family together with the family
+    pub fn presets(&self) -> impl Iterator<Item = (&dyn AutomatonPlugin, Preset)> {
+        self.plugins().flat_map(|plugin| {
+            plugin
+                .presets()
+                .into_iter()
+                .map(move |preset| (plugin, preset))
+        })
+    }
+
+    /// Looks up a preset by its case insensitive name
+    pub fn preset(&self, name: &str) -> Option<(&dyn AutomatonPlugin, Preset)> {
+        self.presets()
+            .find(|(_, preset)| preset.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Loads a plugin from a dynamic library exporting it with `declare_plugin!`
+    ///
+    /// # Safety
+    /// The library has to be built with the same compiler and version of this crate,
+    /// as plugins are passed as Rust trait objects.
+    #[cfg(feature = "dynamic-plugins")]
+    pub unsafe fn load(
+        &mut self,
+        path: impl AsRef<std::ffi::OsStr>,
+    ) -> Result<(), libloading::Error> {
+        let library = libloading::Library::new(path)?;
+        let constructor = library.get::<fn() -> Box<dyn AutomatonPlugin>>(b"automaton_plugin")?;
+        self.plugins.push(constructor());
+        self.libraries.push(library);
+        Ok(())
+    }
+}
+
+/// Exports a plugin from a dynamic library for `Registry::load`,
+/// `$constructor` creates the plugin
+#[cfg(feature = "dynamic-plugins")]
+#[macro_export]
+macro_rules! declare_plugin {
+    ($constructor:expr) => {
+        #[no_mangle]
+        pub fn automaton_plugin() -> Box<dyn $crate::plugin::AutomatonPlugin> {
+            Box::new($constructor)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        plugin::{AutomatonPlugin, Preset, Registry},
+        Action, Cell, RuleSet, Rules,
+    };
+
+    /// Cells come alive and stay alive next to any living Cell
+    struct Flood;
+
+    impl AutomatonPlugin for Flood {
+        fn name(&self) -> &'static str {
+            "Flood"
+        }
+
+        fn rule_set(&self) -> RuleSet {
+            RuleSet {
+                alive: vec![(Rules::Range(0..=8), Action::Live)],
+                dead: vec![(Rules::Range(1..=8), Action::Live)],
+                ..RuleSet::default()
+            }
+        }
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = Registry::default();
+        let (family, preset) = registry.preset("brian's brain").unwrap();
+        assert_eq!(family.name(), "Generations");
+        assert_eq!(preset.rule_set.to_string(), "B2/S/C3");
+        assert_eq!(
+            family.states(),
+            vec![
+                Cell::Dead,
+                Cell::Alive,
+                Cell::Dying {
+                    ticks_till_death: 1
+                }
+            ]
+        );
+        assert!(registry.preset("Flood").is_none());
+
+        registry.register(Flood);
+        let (family, preset) = registry.preset("Flood").unwrap();
+        assert_eq!(preset, Preset::new("Flood", Flood.rule_set()));
+        assert_eq!(family.states(), vec![Cell::Dead, Cell::Alive]);
+        assert_eq!(registry.plugins().count(), 3);
+    }
+}
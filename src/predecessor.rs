@@ -0,0 +1,234 @@
+//! Bounded backtracking search for predecessors of small patterns,
+//! used by `no_bevy_2d analyze predecessor` to hunt for Gardens of Eden
+
+use crate::{Cell, Grid, NeighborCounts, Neighborhood, RuleSet};
+use clap::ValueEnum;
+use std::iter;
+
+/// Assumptions about the Cells of a predecessor around the target
+/// - `Free` => A ring of one Cell around the target may be in any state. As nothing further out
+///   affects the target, `Search::None` proves it's an orphan: no pattern containing it has a
+///   predecessor, so every such pattern is a Garden of Eden.
+/// - `Dead` => The predecessor fits into the box of the target, every Cell around it is dead
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum Boundary {
+    #[default]
+    Free,
+    Dead,
+}
+
+/// Outcome of `find_predecessor`
+/// - `Found` => A predecessor, including the ring around the target for `Boundary::Free`
+/// - `None` => No predecessor exists under the `Boundary` assumptions
+/// - `GaveUp` => The search assigned `max_nodes` Cells without settling the question
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Search {
+    Found(Grid),
+    None,
+    GaveUp,
+}
+
+/// Searches for a grid that becomes `target` after one step of `rule_set`.
+///
+/// Only `Cell::Alive` and `Cell::Dead` are considered, other states of the target count as dead.
+/// Cells are assigned in reading order and a branch is abandoned as soon as some Cell of the
+/// target can't reach its state anymore, whatever the unassigned neighbors turn out to be.
+/// The search is exponential in the width of the target, so keep it small.
+pub fn find_predecessor(
+    target: &[Vec<Cell>],
+    neighborhood: Neighborhood,
+    rule_set: &RuleSet,
+    boundary: Boundary,
+    max_nodes: u64,
+) -> Search {
+    if target.first().is_none_or(Vec::is_empty) {
+        return Search::Found(target.to_vec());
+    }
+    let row_count = target.len() + 2;
+    let col_count = target[0].len() + 2;
+    let mut search = Backtracking {
+        target: target
+            .iter()
+            .map(|row| row.iter().map(|cell| *cell == Cell::Alive).collect())
+            .collect(),
+        neighborhood,
+        // Whether a Cell is alive next generation, by its own state and its alive neighbors
+        alive_next: [Cell::Dead, Cell::Alive].map(|current| {
+            (0..=8)
+                .map(|count| {
+                    let neighbors = iter::repeat_n(&Cell::Alive, count).collect::<NeighborCounts>();
+                    rule_set.next_state(&current, &neighbors) == Cell::Alive
+                })
+                .collect()
+        }),
+        col_count,
+        cells: vec![None; row_count * col_count],
+        boundary,
+        nodes: 0,
+        max_nodes,
+    };
+    match search.assign(0) {
+        Some(true) => {
+            let grid = search
+                .cells
+                .chunks(col_count)
+                .map(|row| {
+                    row.iter()
+                        .map(|&alive| {
+                            if alive == Some(true) {
+                                Cell::Alive
+                            } else {
+                                Cell::Dead
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Grid>();
+            Search::Found(match boundary {
+                Boundary::Free => grid,
+                Boundary::Dead => grid[1..row_count - 1]
+                    .iter()
+                    .map(|row| row[1..col_count - 1].to_vec())
+                    .collect(),
+            })
+        }
+        Some(false) => Search::None,
+        None => Search::GaveUp,
+    }
+}
+
+/// State of `find_predecessor`, the predecessor has a ring of one Cell around the target
+struct Backtracking {
+    target: Vec<Vec<bool>>,
+    neighborhood: Neighborhood,
+    alive_next: [Vec<bool>; 2],
+    col_count: usize,
+    /// Predecessor in reading order, `None` while unassigned
+    cells: Vec<Option<bool>>,
+    boundary: Boundary,
+    nodes: u64,
+    max_nodes: u64,
+}
+
+impl Backtracking {
+    /// Whether the Cells from `index` on can be assigned consistently, `None` if out of nodes
+    fn assign(&mut self, index: usize) -> Option<bool> {
+        if index == self.cells.len() {
+            return Some(true);
+        }
+        let (row, col) = (index / self.col_count, index % self.col_count);
+        let on_ring = row == 0
+            || col == 0
+            || row == self.cells.len() / self.col_count - 1
+            || col == self.col_count - 1;
+        let candidates: &[bool] = if on_ring && self.boundary == Boundary::Dead {
+            &[false]
+        } else {
+            &[false, true]
+        };
+        for &alive in candidates {
+            self.nodes += 1;
+            if self.nodes > self.max_nodes {
+                return None;
+            }
+            self.cells[index] = Some(alive);
+            if self.consistent(row, col) && self.assign(index + 1)? {
+                return Some(true);
+            }
+        }
+        self.cells[index] = None;
+        Some(false)
+    }
+
+    /// Whether every Cell of the target next to the predecessor Cell at (`row`, `col`)
+    /// can still reach its state
+    fn consistent(&self, row: usize, col: usize) -> bool {
+        // Target Cell (r, c) is centered on predecessor Cell (r + 1, c + 1)
+        let rows = row.saturating_sub(2)..=row.min(self.target.len() - 1);
+        rows.flat_map(|target_row| {
+            let cols = col.saturating_sub(2)..=col.min(self.col_count - 3);
+            cols.map(move |target_col| (target_row, target_col))
+        })
+        .all(|(target_row, target_col)| self.reachable(target_row, target_col))
+    }
+
+    fn reachable(&self, target_row: usize, target_col: usize) -> bool {
+        let cell = |row: usize, col: usize| self.cells[row * self.col_count + col];
+        let (row, col) = (target_row + 1, target_col + 1);
+        let (mut alive, mut unknown) = (0, 0);
+        for neighbor_row in row - 1..=row + 1 {
+            for neighbor_col in col - 1..=col + 1 {
+                let neighbor = match self.neighborhood {
+                    Neighborhood::Moore => neighbor_row != row || neighbor_col != col,
+                    Neighborhood::VonNeumann => (neighbor_row == row) != (neighbor_col == col),
+                };
+                match cell(neighbor_row, neighbor_col) {
+                    _ if !neighbor => {}
+                    Some(true) => alive += 1,
+                    Some(false) => {}
+                    None => unknown += 1,
+                }
+            }
+        }
+        let centers = cell(row, col).map_or_else(|| vec![false, true], |alive| vec![alive]);
+        let wanted = self.target[target_row][target_col];
+        centers.into_iter().any(|center| {
+            self.alive_next[usize::from(center)][alive..=alive + unknown].contains(&wanted)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        predecessor::{find_predecessor, Boundary, Search},
+        rle, transition, Neighborhood, RuleSet,
+    };
+
+    #[test]
+    fn predecessors() {
+        let life = RuleSet::default();
+        let blinker = rle::parse("5b$5b$b3ob$5b$5b!").unwrap();
+        let Search::Found(predecessor) = find_predecessor(
+            &blinker,
+            Neighborhood::Moore,
+            &life,
+            Boundary::Dead,
+            1 << 20,
+        ) else {
+            panic!("The blinker is its own grandparent");
+        };
+        assert_eq!(
+            transition::next_grid(&predecessor, Neighborhood::Moore, &life),
+            blinker
+        );
+
+        assert_eq!(
+            find_predecessor(&blinker, Neighborhood::Moore, &life, Boundary::Dead, 4),
+            Search::GaveUp
+        );
+
+        // A single Cell with nothing around it dies or stays dead
+        let lonely = rle::parse("o!").unwrap();
+        assert_eq!(
+            find_predecessor(&lonely, Neighborhood::Moore, &life, Boundary::Dead, 1 << 20),
+            Search::None
+        );
+        assert!(matches!(
+            find_predecessor(&lonely, Neighborhood::Moore, &life, Boundary::Free, 1 << 20),
+            Search::Found(_)
+        ));
+        // Without birth or survival counts any alive Cell is an orphan
+        let sterile = "B/S".parse::<RuleSet>().unwrap();
+        assert_eq!(
+            find_predecessor(
+                &lonely,
+                Neighborhood::Moore,
+                &sterile,
+                Boundary::Free,
+                1 << 20
+            ),
+            Search::None
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Alternative layouts of the grid on screen, e.g. for screenshots or to show a grid as hexagons.
+//!
+//! Doesn't influence the simulation in any way, the neighbors of a Cell stay the same.
+//! Positions are in a plane measured in Cells with `y` pointing down, like rows.
+
+use std::fmt;
+
+/// Horizontal shift of each row in Cells in `Projection::Skewed`
+const SKEW: f32 = 0.5;
+
+/// How the Cells of the grid are laid out
+/// - `Square` => The usual grid of squares
+/// - `Isometric` => Diamond tiles twice as wide as high, the grid turned by 45 degrees
+/// - `Skewed` => Each row shifted by half a Cell further than the one above, a parallelogram
+/// - `PointyHex` => Hexagons with a pointy top, every odd row shifted by half a Cell
+/// - `FlatHex` => Hexagons with a flat top, every odd column shifted by half a Cell
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Projection {
+    #[default]
+    Square,
+    Isometric,
+    Skewed,
+    PointyHex,
+    FlatHex,
+}
+
+impl fmt::Display for Projection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Square => write!(f, "square"),
+            Self::Isometric => write!(f, "isometric"),
+            Self::Skewed => write!(f, "skewed"),
+            Self::PointyHex => write!(f, "pointy hex"),
+            Self::FlatHex => write!(f, "flat hex"),
+        }
+    }
+}
+
+/// Distance of the center of a hexagon one Cell across to its corners
+fn hex_size() -> f32 {
+    3.0_f32.sqrt().recip()
+}
+
+/// Axial hexagon coordinates of the hexagon containing fractional axial coordinates
+#[allow(clippy::cast_possible_truncation)]
+fn round_hex(q: f32, r: f32) -> (i64, i64) {
+    let s = -q - r;
+    let (mut rounded_q, mut rounded_r, rounded_s) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = (
+        (rounded_q - q).abs(),
+        (rounded_r - r).abs(),
+        (rounded_s - s).abs(),
+    );
+    if dq > dr && dq > ds {
+        rounded_q = -rounded_r - rounded_s;
+    } else if dr > ds {
+        rounded_r = -rounded_q - rounded_s;
+    }
+    (rounded_q as i64, rounded_r as i64)
+}
+
+impl Projection {
+    pub const ALL: [Self; 5] = [
+        Self::Square,
+        Self::Isometric,
+        Self::Skewed,
+        Self::PointyHex,
+        Self::FlatHex,
+    ];
+
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Square => Self::Isometric,
+            Self::Isometric => Self::Skewed,
+            Self::Skewed => Self::PointyHex,
+            Self::PointyHex => Self::FlatHex,
+            Self::FlatHex => Self::Square,
+        }
+    }
+
+    /// Position of the center of the Cell at (`row`, `col`)
+    #[allow(clippy::cast_precision_loss)]
+    pub fn center(self, row: usize, col: usize) -> [f32; 2] {
+        let (x, y) = (col as f32 + 0.5, row as f32 + 0.5);
+        let size = hex_size();
+        match self {
+            Self::Square => [x, y],
+            Self::Isometric => [x - y, f32::midpoint(x, y)],
+            Self::Skewed => [SKEW.mul_add(y, x), y],
+            Self::PointyHex => [
+                0.5f32.mul_add((row % 2) as f32, x),
+                (1.5 * row as f32).mul_add(size, size),
+            ],
+            Self::FlatHex => [
+                (1.5 * col as f32).mul_add(size, size),
+                0.5f32.mul_add((col % 2) as f32, y),
+            ],
+        }
+    }
+
+    /// Top left and bottom right corner of the smallest rectangle around a grid
+    /// of `row_count` x `col_count` Cells
+    #[allow(clippy::cast_precision_loss)]
+    pub fn extent(self, row_count: usize, col_count: usize) -> ([f32; 2], [f32; 2]) {
+        let (cols, rows) = (col_count as f32, row_count as f32);
+        let size = hex_size();
+        match self {
+            Self::Square => ([0.0; 2], [cols, rows]),
+            Self::Isometric => ([-rows, 0.0], [cols, f32::midpoint(cols, rows)]),
+            Self::Skewed => ([0.0; 2], [SKEW.mul_add(rows, cols), rows]),
+            Self::PointyHex => ([0.0; 2], [cols + 0.5, 1.5f32.mul_add(rows, 0.5) * size]),
+            Self::FlatHex => ([0.0; 2], [1.5f32.mul_add(cols, 0.5) * size, rows + 0.5]),
+        }
+    }
+
+    /// (`row`, `col`) of the Cell covering position (`x`, `y`), `None` outside of a grid of
+    /// `row_count` x `col_count` Cells
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn cell_at(
+        self,
+        x: f32,
+        y: f32,
+        row_count: usize,
+        col_count: usize,
+    ) -> Option<(usize, usize)> {
+        let size = hex_size();
+        let (row, col) = match self {
+            Self::Square => (y.floor() as i64, x.floor() as i64),
+            Self::Isometric => ((y - x / 2.0).floor() as i64, (y + x / 2.0).floor() as i64),
+            Self::Skewed => (y.floor() as i64, SKEW.mul_add(-y, x).floor() as i64),
+            Self::PointyHex => {
+                let (x, y) = ((x - 0.5) / size, (y - size) / size);
+                let (q, r) = round_hex(3.0_f32.sqrt().mul_add(x, -y) / 3.0, 2.0 / 3.0 * y);
+                (r, q + (r - (r & 1)) / 2)
+            }
+            Self::FlatHex => {
+                let (x, y) = ((x - size) / size, (y - 0.5) / size);
+                let (q, r) = round_hex(2.0 / 3.0 * x, 3.0_f32.sqrt().mul_add(y, -x) / 3.0);
+                (r + (q - (q & 1)) / 2, q)
+            }
+        };
+        let row = usize::try_from(row).ok().filter(|&row| row < row_count)?;
+        let col = usize::try_from(col).ok().filter(|&col| col < col_count)?;
+        Some((row, col))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::projection::Projection;
+
+    #[test]
+    fn centers_round_trip() {
+        for projection in Projection::ALL {
+            let (min, max) = projection.extent(5, 7);
+            for (row, col) in itertools::iproduct!(0..5, 0..7) {
+                let [x, y] = projection.center(row, col);
+                assert!((min[0]..=max[0]).contains(&x) && (min[1]..=max[1]).contains(&y));
+                assert_eq!(
+                    projection.cell_at(x, y, 5, 7),
+                    Some((row, col)),
+                    "{projection}"
+                );
+            }
+            assert_eq!(projection.cell_at(min[0] - 1.0, min[1] - 1.0, 5, 7), None);
+        }
+
+        // The corner left out between two pointy hexagons of the first row belongs to none
+        let size = 3.0_f32.sqrt().recip();
+        assert_eq!(Projection::PointyHex.cell_at(1.0, 0.1 * size, 5, 7), None);
+        assert_eq!(Projection::Isometric.cell_at(0.0, 0.25, 5, 7), Some((0, 0)));
+        assert_eq!(Projection::Skewed.cell_at(0.75, 1.5, 5, 7), Some((1, 0)));
+        assert_eq!(Projection::FlatHex.next(), Projection::Square);
+    }
+}
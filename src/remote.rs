@@ -0,0 +1,287 @@
+//! Binary protocol between a headless simulation and its remote clients.
+//! Integers are little endian, Cells are sent as `Cell::state_byte`.
+//!
+//! Server to client, see `Frame`:
+//! - `0`, generation: u64, rows: u32, cols: u32, rows * cols Cells => `Frame::Full`
+//! - `1`, generation: u64, count: u32, count * (row: u32, col: u32, Cell) => `Frame::Diff`
+//!
+//! Client to server, see `Request`:
+//! - `0` => `Request::Pause`
+//! - `1` => `Request::Resume`
+//! - `2`, generations: u32 => `Request::Step`
+//! - `3`, row: u32, col: u32, Cell => `Request::SetCell`
+
+use crate::{Cell, Generation, Grid};
+use std::{error, fmt};
+
+/// Errors that can occur while decoding a message
+/// - `Truncated` => The message ended early
+/// - `UnknownTag` => The first byte doesn't name a message
+/// - `TrailingBytes` => There are bytes left after the message
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ProtocolError {
+    Truncated,
+    UnknownTag(u8),
+    TrailingBytes,
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "Message ended early"),
+            Self::UnknownTag(tag) => write!(f, "Unknown message tag {tag}"),
+            Self::TrailingBytes => write!(f, "Unexpected bytes after the message"),
+        }
+    }
+}
+
+impl error::Error for ProtocolError {}
+
+/// A Cell that changed, or is to be changed
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Change {
+    pub row: u32,
+    pub col: u32,
+    pub cell: Cell,
+}
+
+/// Message from the server
+/// - `Full` => The whole grid, sent to new clients and whenever the grid is replaced
+/// - `Diff` => The Cells that changed since the previous frame
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Frame {
+    Full {
+        generation: Generation,
+        grid: Grid,
+    },
+    Diff {
+        generation: Generation,
+        changes: Vec<Change>,
+    },
+}
+
+/// Message from a client
+/// - `Pause` => Stops stepping automatically
+/// - `Resume` => Steps automatically again
+/// - `Step` => Steps `generations` times, e.g. while paused
+/// - `SetCell` => Edits a single Cell
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Request {
+    Pause,
+    Resume,
+    Step { generations: u32 },
+    SetCell(Change),
+}
+
+/// Cells that differ between `before` and `after`, which have the same dimensions
+#[allow(clippy::cast_possible_truncation)]
+pub fn diff(before: &[Vec<Cell>], after: &[Vec<Cell>]) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (row, (before, after)) in before.iter().zip(after).enumerate() {
+        for (col, (before, after)) in before.iter().zip(after).enumerate() {
+            if before != after {
+                changes.push(Change {
+                    row: row as u32,
+                    col: col as u32,
+                    cell: after.clone(),
+                });
+            }
+        }
+    }
+    changes
+}
+
+impl Frame {
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Self::Full { generation, grid } => {
+                let cols = grid.first().map_or(0, Vec::len);
+                bytes.push(0);
+                bytes.extend(generation.0.to_le_bytes());
+                bytes.extend((grid.len() as u32).to_le_bytes());
+                bytes.extend((cols as u32).to_le_bytes());
+                bytes.extend(grid.iter().flatten().map(Cell::state_byte));
+            }
+            Self::Diff {
+                generation,
+                changes,
+            } => {
+                bytes.push(1);
+                bytes.extend(generation.0.to_le_bytes());
+                bytes.extend((changes.len() as u32).to_le_bytes());
+                for change in changes {
+                    change.encode(&mut bytes);
+                }
+            }
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        let mut reader = Reader(bytes);
+        let frame = match reader.u8()? {
+            0 => {
+                let generation = Generation(reader.u64()?);
+                let (rows, cols) = (reader.u32()?, reader.u32()?);
+                let grid = (0..rows)
+                    .map(|_| (0..cols).map(|_| reader.cell()).collect())
+                    .collect::<Result<_, _>>()?;
+                Self::Full { generation, grid }
+            }
+            1 => {
+                let generation = Generation(reader.u64()?);
+                let count = reader.u32()?;
+                let changes = (0..count)
+                    .map(|_| Change::decode(&mut reader))
+                    .collect::<Result<_, _>>()?;
+                Self::Diff {
+                    generation,
+                    changes,
+                }
+            }
+            tag => return Err(ProtocolError::UnknownTag(tag)),
+        };
+        reader.finish(frame)
+    }
+}
+
+impl Request {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            Self::Pause => bytes.push(0),
+            Self::Resume => bytes.push(1),
+            Self::Step { generations } => {
+                bytes.push(2);
+                bytes.extend(generations.to_le_bytes());
+            }
+            Self::SetCell(change) => {
+                bytes.push(3);
+                change.encode(&mut bytes);
+            }
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        let mut reader = Reader(bytes);
+        let request = match reader.u8()? {
+            0 => Self::Pause,
+            1 => Self::Resume,
+            2 => Self::Step {
+                generations: reader.u32()?,
+            },
+            3 => Self::SetCell(Change::decode(&mut reader)?),
+            tag => return Err(ProtocolError::UnknownTag(tag)),
+        };
+        reader.finish(request)
+    }
+}
+
+impl Change {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend(self.row.to_le_bytes());
+        bytes.extend(self.col.to_le_bytes());
+        bytes.push(self.cell.state_byte());
+    }
+
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, ProtocolError> {
+        Ok(Self {
+            row: reader.u32()?,
+            col: reader.u32()?,
+            cell: reader.cell()?,
+        })
+    }
+}
+
+/// Consumes a message from the front
+pub(crate) struct Reader<'a>(pub(crate) &'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], ProtocolError> {
+        self.slice(N)?
+            .try_into()
+            .map_err(|_| ProtocolError::Truncated)
+    }
+
+    pub(crate) fn slice(&mut self, len: usize) -> Result<&'a [u8], ProtocolError> {
+        let bytes = self.0.get(..len).ok_or(ProtocolError::Truncated)?;
+        self.0 = &self.0[len..];
+        Ok(bytes)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, ProtocolError> {
+        Ok(u8::from_le_bytes(self.bytes()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, ProtocolError> {
+        Ok(u32::from_le_bytes(self.bytes()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, ProtocolError> {
+        Ok(u64::from_le_bytes(self.bytes()?))
+    }
+
+    pub(crate) fn cell(&mut self) -> Result<Cell, ProtocolError> {
+        Ok(Cell::from_state_number(self.u8()?.into()))
+    }
+
+    /// `message` if all bytes were consumed
+    pub(crate) fn finish<T>(self, message: T) -> Result<T, ProtocolError> {
+        if self.0.is_empty() {
+            Ok(message)
+        } else {
+            Err(ProtocolError::TrailingBytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        remote::{diff, Change, Frame, ProtocolError, Request},
+        Automaton, Cell, Generation,
+    };
+
+    #[test]
+    fn roundtrip() {
+        let mut automaton = Automaton::default();
+        let full = Frame::Full {
+            generation: automaton.generation,
+            grid: automaton.grid.clone(),
+        };
+        assert_eq!(Frame::decode(&full.encode()), Ok(full));
+
+        let before = automaton.grid.clone();
+        automaton.step();
+        let changes = diff(&before, &automaton.grid);
+        assert_eq!(
+            changes.len(),
+            automaton.births + automaton.deaths,
+            "Conway's Game of Life has no dying Cells"
+        );
+        let frame = Frame::Diff {
+            generation: Generation(1),
+            changes,
+        };
+        assert_eq!(Frame::decode(&frame.encode()), Ok(frame));
+
+        for request in [
+            Request::Pause,
+            Request::Resume,
+            Request::Step { generations: 7 },
+            Request::SetCell(Change {
+                row: 2,
+                col: 3,
+                cell: Cell::dying_cell(),
+            }),
+        ] {
+            assert_eq!(Request::decode(&request.encode()), Ok(request));
+        }
+        assert_eq!(Request::decode(&[2, 1]), Err(ProtocolError::Truncated));
+        assert_eq!(Request::decode(&[9]), Err(ProtocolError::UnknownTag(9)));
+        assert_eq!(Request::decode(&[0, 0]), Err(ProtocolError::TrailingBytes));
+    }
+}
@@ -0,0 +1,257 @@
+//! Whether rules are reversible on small toroidal grids,
+//! i.e. injective: no two grids share a successor.
+//!
+//! Tiny grids are checked exhaustively, larger ones by a randomized search that can only ever
+//! disprove reversibility. Used by `no_bevy_2d analyze reversibility`.
+
+use crate::{transition, Cell, Grid, Neighborhood, RuleSet, RuleTable};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{collections::HashMap, error, fmt};
+
+/// Largest number of grids an exhaustive check accepts,
+/// it keeps one bit per grid to remember which ones were successors
+pub const MAX_GRIDS: u64 = 1 << 22;
+
+/// Errors that can occur while checking reversibility
+/// - `TooLarge` => An exhaustive check would have to step more than `MAX_GRIDS` grids
+/// - `Empty` => The grid has no Cells
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReversibilityError {
+    TooLarge,
+    Empty,
+}
+
+impl fmt::Display for ReversibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge => write!(
+                f,
+                "More than {MAX_GRIDS} grids to check exhaustively, sample them instead"
+            ),
+            Self::Empty => write!(f, "The grid has no Cells"),
+        }
+    }
+}
+
+impl error::Error for ReversibilityError {}
+
+/// How grids are picked
+/// - `Exhaustive` => Every grid, proves reversibility if no two share a successor
+/// - `Random` => `samples` random grids seeded by `seed`, each also compared with a copy
+///   differing in a single Cell, where collisions of most rules are found quickly
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Method {
+    Exhaustive,
+    Random { samples: usize, seed: u64 },
+}
+
+/// Two different grids with the same successor
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Counterexample {
+    pub first: Grid,
+    pub second: Grid,
+    pub successor: Grid,
+}
+
+/// Result of a `Check`
+/// - `Reversible` => All of these grids have different successors
+/// - `Irreversible` => The rules aren't injective
+/// - `Inconclusive` => None of these sampled grids shared a successor, which proves nothing
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Verdict {
+    Reversible(u64),
+    Irreversible(Counterexample),
+    Inconclusive(usize),
+}
+
+/// Reversibility of `rule_set` on a `row_count` x `col_count` torus
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Check {
+    pub row_count: usize,
+    pub col_count: usize,
+    pub rule_set: RuleSet,
+    pub neighborhood: Neighborhood,
+}
+
+impl Check {
+    /// Number of states a Cell can be in, `Cell::Dying` ones included
+    const fn state_count(&self) -> usize {
+        self.rule_set.decay + 2
+    }
+
+    /// Successor of `grid` with the edges wrapping around
+    fn successor(&self, grid: &[Vec<Cell>], table: &RuleTable) -> Grid {
+        let offsets = transition::neighbor_positions(self.neighborhood, 1, 1).collect::<Vec<_>>();
+        let (row_count, col_count) = (self.row_count, self.col_count);
+        (0..row_count)
+            .map(|row| {
+                (0..col_count)
+                    .map(|col| {
+                        let neighbors = offsets.iter().map(|&(drow, dcol)| {
+                            &grid[(row + row_count + drow - 1) % row_count]
+                                [(col + col_count + dcol - 1) % col_count]
+                        });
+                        table.next_state(&grid[row][col], neighbors)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Grid number `index`, counting through the states of the Cells in row-major order
+    fn grid(&self, mut index: u64) -> Grid {
+        let states = self.state_count() as u64;
+        (0..self.row_count)
+            .map(|_| {
+                (0..self.col_count)
+                    .map(|_| {
+                        let state = index % states;
+                        index /= states;
+                        Cell::from_state_number(usize::try_from(state).unwrap_or_default())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Inverse of `Check::grid`
+    fn number(&self, grid: &[Vec<Cell>]) -> u64 {
+        let states = self.state_count() as u64;
+        grid.iter().flatten().rev().fold(0, |number, cell| {
+            number * states + cell.state_number() as u64
+        })
+    }
+
+    fn random_grid(&self, rng: &mut impl Rng) -> Grid {
+        (0..self.row_count)
+            .map(|_| {
+                (0..self.col_count)
+                    .map(|_| Cell::from_state_number(rng.gen_range(0..self.state_count())))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn run(&self, method: Method) -> Result<Verdict, ReversibilityError> {
+        let cell_count = self.row_count * self.col_count;
+        if cell_count == 0 {
+            return Err(ReversibilityError::Empty);
+        }
+        let table = RuleTable::new(&self.rule_set);
+        match method {
+            Method::Exhaustive => {
+                let grids = u32::try_from(cell_count)
+                    .ok()
+                    .and_then(|cells| (self.state_count() as u64).checked_pow(cells))
+                    .filter(|&grids| grids <= MAX_GRIDS)
+                    .ok_or(ReversibilityError::TooLarge)?;
+                let mut successors = vec![0_u64; grids.div_ceil(64) as usize];
+                for index in 0..grids {
+                    let grid = self.grid(index);
+                    let successor = self.successor(&grid, &table);
+                    let number = self.number(&successor);
+                    let (word, bit) = ((number / 64) as usize, 1 << (number % 64));
+                    if successors[word] & bit != 0 {
+                        // Only which grids were successors is kept, their preimage is searched again
+                        let first = (0..index)
+                            .map(|other| self.grid(other))
+                            .find(|other| self.successor(other, &table) == successor)
+                            .unwrap_or_default();
+                        return Ok(Verdict::Irreversible(Counterexample {
+                            first,
+                            second: grid,
+                            successor,
+                        }));
+                    }
+                    successors[word] |= bit;
+                }
+                Ok(Verdict::Reversible(grids))
+            }
+            Method::Random { samples, seed } => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut preimages = HashMap::new();
+                for _ in 0..samples {
+                    let grid = self.random_grid(&mut rng);
+                    let mut neighbor = grid.clone();
+                    let (row, col) = (
+                        rng.gen_range(0..self.row_count),
+                        rng.gen_range(0..self.col_count),
+                    );
+                    let state = neighbor[row][col].state_number();
+                    let other = (state + rng.gen_range(1..self.state_count())) % self.state_count();
+                    neighbor[row][col] = Cell::from_state_number(other);
+                    for grid in [grid, neighbor] {
+                        let successor = self.successor(&grid, &table);
+                        match preimages.get(&successor) {
+                            Some(first) if *first != grid => {
+                                return Ok(Verdict::Irreversible(Counterexample {
+                                    first: Grid::clone(first),
+                                    second: grid,
+                                    successor,
+                                }));
+                            }
+                            Some(_) => {}
+                            None => {
+                                preimages.insert(successor, grid);
+                            }
+                        }
+                    }
+                }
+                Ok(Verdict::Inconclusive(samples))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        reversibility::{Check, Method, ReversibilityError, Verdict},
+        Neighborhood,
+    };
+
+    #[test]
+    fn injective_rules() {
+        let check = |rules: &str, row_count, col_count| Check {
+            row_count,
+            col_count,
+            rule_set: rules.parse().unwrap(),
+            neighborhood: Neighborhood::Moore,
+        };
+        let random = Method::Random {
+            samples: 64,
+            seed: 1,
+        };
+
+        // Nothing is born and everything survives, every grid is its own successor
+        let identity = check("B/S012345678", 3, 4);
+        assert_eq!(
+            identity.run(Method::Exhaustive),
+            Ok(Verdict::Reversible(1 << 12))
+        );
+        assert_eq!(identity.run(random), Ok(Verdict::Inconclusive(64)));
+
+        for method in [Method::Exhaustive, random] {
+            let Ok(Verdict::Irreversible(counterexample)) = check("B3/S23", 4, 4).run(method)
+            else {
+                panic!("Life isn't reversible");
+            };
+            assert_ne!(counterexample.first, counterexample.second);
+        }
+        // Dying Cells always decay, so the one state they came from is lost as well
+        assert!(matches!(
+            check("B2/S/C3", 2, 3).run(Method::Exhaustive),
+            Ok(Verdict::Irreversible(_))
+        ));
+
+        assert_eq!(
+            check("B3/S23", 6, 6).run(Method::Exhaustive),
+            Err(ReversibilityError::TooLarge)
+        );
+        assert_eq!(
+            check("B3/S23", 0, 6).run(random),
+            Err(ReversibilityError::Empty)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{Cell, Grid};
+use std::{error, fmt, num::ParseIntError};
+
+/// Maximum line length of written RLE, as recommended by the format
+const LINE_LENGTH: usize = 70;
+/// Largest number of Cells a parsed pattern may have,
+/// so malformed or malicious input can't exhaust the memory
+pub const MAX_CELLS: usize = 1 << 24;
+
+/// Errors that can occur while parsing RLE text
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    /// The `x = .., y = ..` header line is malformed
+    InvalidHeader(String),
+    /// A run count couldn't be parsed
+    InvalidCount(ParseIntError),
+    /// A tag other than `b`, `o`, `$` or `!` was encountered
+    UnexpectedTag(char),
+    /// The pattern doesn't fit into the dimensions declared in the header
+    OutOfBounds,
+    /// The pattern has more than `MAX_CELLS` Cells
+    TooLarge,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader(header) => write!(f, "Invalid RLE header: {header}"),
+            Self::InvalidCount(e) => write!(f, "Invalid run count: {e}"),
+            Self::UnexpectedTag(tag) => write!(f, "Unexpected RLE tag: {tag:?}"),
+            Self::OutOfBounds => write!(f, "Pattern exceeds the size declared in the header"),
+            Self::TooLarge => write!(f, "Pattern has more than {MAX_CELLS} Cells"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl From<ParseIntError> for ParseError {
+    fn from(value: ParseIntError) -> Self {
+        Self::InvalidCount(value)
+    }
+}
+
+/// Parses a pattern in the run length encoded format used by `LifeWiki`
+///
+/// - `#` lines are treated as comments
+/// - The `x = .., y = ..` header is optional,
+///   without it the size is inferred from the pattern itself
+/// - `b`/`.` => `Cell::Dead`, `o`/`A` => `Cell::Alive`
+pub fn parse(text: &str) -> Result<Grid, ParseError> {
+    let mut size = None;
+    let mut body = String::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if size.is_none() && body.is_empty() && line.starts_with('x') {
+            size = Some(parse_header(line)?);
+        } else {
+            body.push_str(line);
+        }
+    }
+
+    if size.is_some_and(|(col_count, row_count)| cell_count(col_count, row_count).is_none()) {
+        return Err(ParseError::TooLarge);
+    }
+
+    let mut rows: Grid = vec![Vec::new()];
+    let mut count = String::new();
+    // Cells and rows pushed so far, bounds the memory used by huge run counts
+    let mut pushed = 0_usize;
+    for tag in body.chars().filter(|c| !c.is_whitespace()) {
+        if tag.is_ascii_digit() {
+            count.push(tag);
+            continue;
+        }
+        let run: usize = if count.is_empty() { 1 } else { count.parse()? };
+        count.clear();
+        pushed = pushed
+            .checked_add(run)
+            .filter(|&pushed| pushed <= MAX_CELLS)
+            .ok_or(ParseError::TooLarge)?;
+        match tag {
+            'b' | '.' => rows
+                .last_mut()
+                .unwrap()
+                .extend((0..run).map(|_| Cell::Dead)),
+            'o' | 'A' => rows
+                .last_mut()
+                .unwrap()
+                .extend((0..run).map(|_| Cell::Alive)),
+            '$' => rows.extend((0..run).map(|_| Vec::new())),
+            '!' => break,
+            tag => return Err(ParseError::UnexpectedTag(tag)),
+        }
+    }
+
+    let (col_count, row_count) = size.unwrap_or_else(|| {
+        let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+        (width, rows.len())
+    });
+    if rows.len() > row_count || rows.iter().any(|row| row.len() > col_count) {
+        return Err(ParseError::OutOfBounds);
+    }
+    if cell_count(col_count, row_count).is_none() {
+        return Err(ParseError::TooLarge);
+    }
+    rows.resize_with(row_count, Vec::new);
+    for row in &mut rows {
+        row.resize(col_count, Cell::Dead);
+    }
+    Ok(rows)
+}
+
+/// Number of Cells of a pattern, `None` if it exceeds `MAX_CELLS`
+fn cell_count(col_count: usize, row_count: usize) -> Option<usize> {
+    col_count
+        .checked_mul(row_count)
+        .filter(|&cells| cells <= MAX_CELLS)
+}
+
+/// Parses `x = m, y = n[, rule = ..]` into (`m`, `n`)
+fn parse_header(line: &str) -> Result<(usize, usize), ParseError> {
+    let invalid = || ParseError::InvalidHeader(line.to_owned());
+    let mut width = None;
+    let mut height = None;
+    for entry in line.split(',') {
+        let (key, value) = entry.split_once('=').ok_or_else(invalid)?;
+        match key.trim() {
+            "x" => width = Some(value.trim().parse()?),
+            "y" => height = Some(value.trim().parse()?),
+            _ => {}
+        }
+    }
+    width.zip(height).ok_or_else(invalid)
+}
+
+/// Encodes the grid as RLE, including the `x = .., y = ..` header
+///
+/// Only `Cell::Alive` is written as alive, every other state is written as dead.
+pub fn write(grid: &[Vec<Cell>]) -> String {
+    let row_count = grid.len();
+    let col_count = grid.first().map(Vec::len).unwrap_or_default();
+
+    let mut runs = Vec::new();
+    for row in grid {
+        for cell in row {
+            push_run(&mut runs, if *cell == Cell::Alive { 'o' } else { 'b' });
+        }
+        if matches!(runs.last(), Some((_, 'b'))) {
+            runs.pop();
+        }
+        push_run(&mut runs, '$');
+    }
+    if matches!(runs.last(), Some((_, '$'))) {
+        runs.pop();
+    }
+    runs.push((1, '!'));
+
+    let mut rle = format!("x = {col_count}, y = {row_count}\n");
+    let mut line_length = 0;
+    for (count, tag) in runs {
+        let token = if count == 1 {
+            tag.to_string()
+        } else {
+            format!("{count}{tag}")
+        };
+        if line_length + token.len() > LINE_LENGTH {
+            rle.push('\n');
+            line_length = 0;
+        }
+        line_length += token.len();
+        rle.push_str(&token);
+    }
+    rle.push('\n');
+    rle
+}
+
+/// Extends the last run if it has the same tag, otherwise starts a new one
+fn push_run(runs: &mut Vec<(usize, char)>, tag: char) {
+    match runs.last_mut() {
+        Some((count, last)) if *last == tag => *count += 1,
+        _ => runs.push((1, tag)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rle, Cell};
+
+    #[test]
+    fn glider_roundtrip() {
+        let text = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+        let grid = rle::parse(text).unwrap();
+        assert_eq!(
+            grid,
+            vec![
+                vec![Cell::Dead, Cell::Alive, Cell::Dead],
+                vec![Cell::Dead, Cell::Dead, Cell::Alive],
+                vec![Cell::Alive, Cell::Alive, Cell::Alive],
+            ]
+        );
+        assert_eq!(rle::write(&grid), "x = 3, y = 3\nbo$2bo$3o!\n");
+        assert_eq!(rle::parse(&rle::write(&grid)).unwrap(), grid);
+    }
+
+    #[test]
+    fn headerless_and_invalid() {
+        assert_eq!(rle::parse("2o$o!").unwrap().len(), 2);
+        assert_eq!(
+            rle::parse("x = 1, y = 1\n3o!"),
+            Err(rle::ParseError::OutOfBounds)
+        );
+        assert_eq!(rle::parse("2z!"), Err(rle::ParseError::UnexpectedTag('z')));
+    }
+
+    #[test]
+    fn size_limit() {
+        let too_large = Err(rle::ParseError::TooLarge);
+        assert_eq!(rle::parse("x = 100000, y = 100000\no!"), too_large);
+        assert_eq!(rle::parse("99999999999o!"), too_large);
+        assert_eq!(rle::parse("5000o99999$o!"), too_large);
+    }
+}
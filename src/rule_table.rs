@@ -0,0 +1,203 @@
+//! `RuleSet`s compiled into lookup tables, so the rules aren't evaluated for every Cell.
+//! Like `transition` it only needs `alloc`.
+
+use crate::{rules::MAX_NEIGHBORS, Cell, NeighborCounts, RuleSet, Rules};
+use alloc::vec::Vec;
+use core::iter;
+
+/// Entries per state, indexed by the alive, dying and total neighbor counts
+const COUNTS: usize = (MAX_NEIGHBORS + 1) * (MAX_NEIGHBORS + 1) * (MAX_NEIGHBORS + 1);
+
+/// Next states of `Cell::Dead` and `Cell::Alive` Cells by how many of their neighbors are alive,
+/// dying and how many they have at all, which differs at the edges of the grid.
+///
+/// `Cell::Dying` Cells approach `Cell::Dead` regardless of their neighbors.
+///
+/// Rules counting neighbors in one particular `Cell::Dying` state can't be tabulated,
+/// such rule sets are evaluated for every Cell instead.
+#[derive(Debug, Clone)]
+pub struct RuleTable {
+    rule_set: RuleSet,
+    table: Option<Vec<Cell>>,
+}
+
+impl RuleTable {
+    pub fn new(rule_set: &RuleSet) -> Self {
+        let tabulable = rule_set
+            .alive
+            .iter()
+            .chain(&rule_set.dead)
+            .all(|(rules, _)| !counts_dying_state(rules));
+        let table = tabulable.then(|| {
+            [Cell::Dead, Cell::Alive]
+                .iter()
+                .flat_map(|current| {
+                    (0..COUNTS).map(move |index| {
+                        let (alive, dying, total) = counts(index);
+                        if alive + dying > total {
+                            return Cell::Dead;
+                        }
+                        let neighbors = iter::repeat_n(&Cell::Alive, alive)
+                            .chain(iter::repeat_n(
+                                &Cell::Dying {
+                                    ticks_till_death: 1,
+                                },
+                                dying,
+                            ))
+                            .chain(iter::repeat_n(&Cell::Dead, total - alive - dying))
+                            .collect::<NeighborCounts>();
+                        rule_set.next_state(current, &neighbors)
+                    })
+                })
+                .collect()
+        });
+        Self {
+            rule_set: rule_set.clone(),
+            table,
+        }
+    }
+
+    /// The rule set the table was compiled from
+    pub const fn rule_set(&self) -> &RuleSet {
+        &self.rule_set
+    }
+
+    /// Whether the rule set is looked up rather than evaluated
+    pub const fn is_tabulated(&self) -> bool {
+        self.table.is_some()
+    }
+
+    /// State of a Cell in state `current` with the given `neighbors` in the next generation,
+    /// the same as `RuleSet::next_state`
+    pub fn next_state<'a>(
+        &self,
+        current: &Cell,
+        neighbors: impl IntoIterator<Item = &'a Cell>,
+    ) -> Cell {
+        let Some(table) = &self.table else {
+            return self
+                .rule_set
+                .next_state(current, &neighbors.into_iter().collect());
+        };
+        let offset = match current {
+            Cell::Dead => 0,
+            Cell::Alive => COUNTS,
+            Cell::Dying { .. } => {
+                return self
+                    .rule_set
+                    .next_state(current, &NeighborCounts::default())
+            }
+        };
+        let (mut alive, mut dying, mut total) = (0, 0, 0);
+        for neighbor in neighbors {
+            match neighbor {
+                Cell::Alive => alive += 1,
+                Cell::Dying { .. } => dying += 1,
+                Cell::Dead => {}
+            }
+            total += 1;
+        }
+        table[offset + index(alive, dying, total)].clone()
+    }
+}
+
+/// Tables are equal if they step every Cell the same way, however their rules are written,
+/// e.g. `RuleSet::default` and `B3/S23`. Rule sets that can't be tabulated are compared as written.
+impl PartialEq for RuleTable {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.table, &other.table) {
+            (Some(table), Some(other)) => table == other,
+            _ => self.rule_set == other.rule_set,
+        }
+    }
+}
+
+impl Eq for RuleTable {}
+
+const fn index(alive: usize, dying: usize, total: usize) -> usize {
+    (alive * (MAX_NEIGHBORS + 1) + dying) * (MAX_NEIGHBORS + 1) + total
+}
+
+/// Inverse of `index`
+const fn counts(index: usize) -> (usize, usize, usize) {
+    (
+        index / ((MAX_NEIGHBORS + 1) * (MAX_NEIGHBORS + 1)),
+        index / (MAX_NEIGHBORS + 1) % (MAX_NEIGHBORS + 1),
+        index % (MAX_NEIGHBORS + 1),
+    )
+}
+
+/// Whether `rules` count the neighbors in one particular `Cell::Dying` state
+fn counts_dying_state(rules: &Rules) -> bool {
+    match rules {
+        Rules::Range(_) | Rules::Singles(_) => false,
+        Rules::Count { state, counts } => state.is_dying() || counts_dying_state(counts),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rule_table::RuleTable, Action, Cell, Counting, RuleSet, Rules};
+    use alloc::{boxed::Box, vec, vec::Vec};
+    use itertools::iproduct;
+
+    #[test]
+    fn matches_rule_set() {
+        let dying = |ticks_till_death| Cell::Dying { ticks_till_death };
+        let brians_brain = RuleSet {
+            counting: Counting::AliveAndDying,
+            ..RuleSet::life_like(vec![2], vec![], 1)
+        };
+        let dead_neighbors = RuleSet {
+            dead: vec![(
+                Rules::Count {
+                    state: Cell::Dead,
+                    counts: Box::new(Rules::Singles(vec![5])),
+                },
+                Action::Live,
+            )],
+            ..RuleSet::default()
+        };
+        let one_tick = RuleSet {
+            dead: vec![(
+                Rules::Count {
+                    state: dying(1),
+                    counts: Box::new(Rules::Range(1..=8)),
+                },
+                Action::Live,
+            )],
+            ..RuleSet::default()
+        };
+        let states = [Cell::Dead, Cell::Alive, dying(1), dying(2)];
+        for rule_set in [
+            RuleSet::default(),
+            RuleSet::life_like(vec![3, 6], vec![2, 3], 3),
+            brians_brain,
+            dead_neighbors,
+            one_tick.clone(),
+        ] {
+            let table = RuleTable::new(&rule_set);
+            assert_eq!(table.is_tabulated(), rule_set != one_tick);
+            // Every center with every combination of three neighbors, as at a corner
+            for (current, first, second, third) in iproduct!(&states, &states, &states, &states) {
+                let neighbors: Vec<&Cell> = vec![first, second, third];
+                assert_eq!(
+                    table.next_state(current, neighbors.iter().copied()),
+                    rule_set.next_state(current, &neighbors.iter().copied().collect()),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn equal_if_stepping_alike() {
+        let conway = RuleTable::new(&RuleSet::default());
+        assert_eq!(conway, RuleTable::new(&"B3/S23".parse().unwrap()));
+        assert_ne!(conway, RuleTable::new(&"B36/S23".parse().unwrap()));
+        let counting_dying = RuleSet {
+            counting: Counting::AliveAndDying,
+            ..RuleSet::default()
+        };
+        assert_ne!(conway, RuleTable::new(&counting_dying));
+    }
+}
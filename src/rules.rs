@@ -0,0 +1,665 @@
+use crate::{Cell, NeighborCounts};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, ops::RangeInclusive, str::FromStr};
+use itertools::Itertools;
+
+/// Largest possible number of neighbors of a Cell
+pub const MAX_NEIGHBORS: usize = 8;
+
+/// Neighborhood of `count` `Cell::Alive` and otherwise `Cell::Dead` neighbors
+fn alive_neighbors(count: usize) -> NeighborCounts {
+    neighbors_in(&Cell::Alive, count)
+}
+
+/// Neighborhood of `count` neighbors in `state` and otherwise `Cell::Dead` ones
+fn neighbors_in(state: &Cell, count: usize) -> NeighborCounts {
+    (0..count).map(|_| state).collect()
+}
+
+/// `RuleSets` for the Automata
+///
+/// It is combined
+/// Defaults to the Rules of Conway's Game of Life
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSet {
+    /// Rules for an `Cell::Alive`
+    pub alive: Vec<(Rules, Action)>,
+    /// Rules for an `Cell::Dead`
+    pub dead: Vec<(Rules, Action)>,
+    /// Generations an `Cell::Alive` spends as `Cell::Dying` after it died,
+    /// `0` lets it die immediately.
+    /// Only describes the rules for notation and coloring,
+    /// the dying itself is done by `Action::StartDying`
+    pub decay: usize,
+    /// Which rule applies when several match
+    pub priority: Priority,
+    /// Which neighbors `Rules::Range` and `Rules::Singles` count as alive
+    pub counting: Counting,
+}
+
+/// Contribution of each state to the alive neighbors of a Cell
+/// - `AliveOnly` => Only `Cell::Alive` neighbors count, like in Brian's Brain
+/// - `AliveAndDying` => `Cell::Dying` neighbors count as alive as well
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Counting {
+    #[default]
+    AliveOnly,
+    AliveAndDying,
+}
+
+impl Counting {
+    /// Number of `neighbors` counting as alive
+    pub fn alive_neighbors(self, neighbors: &NeighborCounts) -> usize {
+        match self {
+            Self::AliveOnly => neighbors.alive(),
+            Self::AliveAndDying => neighbors.alive() + neighbors.dying(),
+        }
+    }
+}
+
+/// How a `RuleSet` resolves several of its rules matching the same neighbor count
+/// - `FirstMatch` => The earliest matching rule applies
+/// - `LastMatch` => The latest matching rule applies
+/// - `Strict` => Rules mustn't overlap with different actions,
+///   `RuleSet::validate` rejects such rule sets
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Priority {
+    #[default]
+    FirstMatch,
+    LastMatch,
+    Strict,
+}
+
+/// Problems found by `RuleSet::conflicts`, rules are identified by their index
+/// within the rules for `state`
+/// - `Overlap` => Two rules match the same neighbor `counts` with different actions
+/// - `Unreachable` => A rule never applies as rules of higher priority match all of its counts
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Conflict {
+    Overlap {
+        state: Cell,
+        first: usize,
+        second: usize,
+        counts: Vec<usize>,
+    },
+    Unreachable {
+        state: Cell,
+        rule: usize,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap {
+                state,
+                first,
+                second,
+                counts,
+            } => write!(
+                f,
+                "Rules {first} and {second} for {state:?} Cells both match {counts:?} neighbors"
+            ),
+            Self::Unreachable { state, rule } => {
+                write!(f, "Rule {rule} for {state:?} Cells never applies")
+            }
+        }
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            alive: vec![
+                (Rules::Range(0..=1), Action::Die),
+                (Rules::Range(2..=3), Action::Live),
+                (Rules::Range(4..=9), Action::Die),
+            ],
+            dead: vec![(Rules::Singles(vec![3]), Action::Live)],
+            decay: 0,
+            priority: Priority::FirstMatch,
+            counting: Counting::AliveOnly,
+        }
+    }
+}
+
+impl RuleSet {
+    /// Rules of a Life-like automaton
+    /// - A dead Cell with a neighbor count in `birth` comes alive
+    /// - A living Cell with a neighbor count in `survival` stays alive, every other one dies,
+    ///   spending `decay` generations as `Cell::Dying` first
+    pub fn life_like(birth: Vec<usize>, survival: Vec<usize>, decay: usize) -> Self {
+        let die = if decay > 0 {
+            Action::StartDying { ticks: decay }
+        } else {
+            Action::Die
+        };
+        Self {
+            alive: vec![
+                (Rules::Singles(survival), Action::Live),
+                (Rules::Range(0..=8), die),
+            ],
+            dead: vec![(Rules::Singles(birth), Action::Live)],
+            decay,
+            priority: Priority::FirstMatch,
+            counting: Counting::AliveOnly,
+        }
+    }
+
+    /// Rules applying to a Cell in state `cell`, ordered by descending priority
+    pub fn rules_for<'a>(
+        &'a self,
+        cell: &Cell,
+    ) -> Box<dyn Iterator<Item = &'a (Rules, Action)> + 'a> {
+        let rules = if cell.is_dead() {
+            &self.dead
+        } else {
+            &self.alive
+        };
+        match self.priority {
+            Priority::FirstMatch | Priority::Strict => Box::new(rules.iter()),
+            Priority::LastMatch => Box::new(rules.iter().rev()),
+        }
+    }
+
+    /// Overlapping and unreachable rules, regardless of whether the `priority` allows them.
+    /// Each rule is tried with every count of neighbors in the state it counts, see `Rules::counted`.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for (state, rules) in [(Cell::Dead, &self.dead), (Cell::Alive, &self.alive)] {
+            for ((first, (first_rule, first_action)), (second, (second_rule, second_action))) in
+                rules.iter().enumerate().tuple_combinations()
+            {
+                let counts = (0..=MAX_NEIGHBORS)
+                    .filter(|&count| {
+                        [first_rule.counted(), second_rule.counted()]
+                            .iter()
+                            .any(|counted| {
+                                let neighbors = neighbors_in(counted, count);
+                                first_rule.matches(&neighbors, self.counting)
+                                    && second_rule.matches(&neighbors, self.counting)
+                            })
+                    })
+                    .collect::<Vec<_>>();
+                if first_action != second_action && !counts.is_empty() {
+                    conflicts.push(Conflict::Overlap {
+                        state: state.clone(),
+                        first,
+                        second,
+                        counts,
+                    });
+                }
+            }
+
+            let mut prioritized = self.rules_for(&state).enumerate().collect::<Vec<_>>();
+            if self.priority == Priority::LastMatch {
+                for (index, _) in &mut prioritized {
+                    *index = rules.len() - 1 - *index;
+                }
+            }
+            for (position, &(rule, (rules, _))) in prioritized.iter().enumerate() {
+                let reachable = (0..=MAX_NEIGHBORS)
+                    .map(|count| neighbors_in(rules.counted(), count))
+                    .any(|neighbors| {
+                        rules.matches(&neighbors, self.counting)
+                            && !prioritized[..position]
+                                .iter()
+                                .any(|(_, (higher, _))| higher.matches(&neighbors, self.counting))
+                    });
+                if !reachable {
+                    conflicts.push(Conflict::Unreachable {
+                        state: state.clone(),
+                        rule,
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Rejects rule sets whose `conflicts` aren't resolved by the `priority`,
+    /// which are overlaps with `Priority::Strict`
+    pub fn validate(&self) -> Result<(), Vec<Conflict>> {
+        let conflicts = self
+            .conflicts()
+            .into_iter()
+            .filter(|conflict| {
+                self.priority == Priority::Strict && matches!(conflict, Conflict::Overlap { .. })
+            })
+            .collect::<Vec<_>>();
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(conflicts)
+        }
+    }
+
+    /// State of a Cell in state `current` in the next generation.
+    /// `Cell::Dying` Cells ignore the rules and approach `Cell::Dead` by one tick,
+    /// other Cells take the action of the matching rule of highest priority
+    /// and stay unchanged if no rule matches.
+    pub fn next_state(&self, current: &Cell, neighbors: &NeighborCounts) -> Cell {
+        match current {
+            Cell::Dying {
+                ticks_till_death: 0 | 1,
+            } => Cell::Dead,
+            &Cell::Dying { ticks_till_death } => Cell::Dying {
+                ticks_till_death: ticks_till_death - 1,
+            },
+            Cell::Dead | Cell::Alive => self
+                .rules_for(current)
+                .find(|(rule, _)| rule.matches(neighbors, self.counting))
+                .map_or_else(|| current.clone(), |(_, action)| action.apply(current)),
+        }
+    }
+
+    /// Neighbor counts for which a dead Cell comes alive
+    pub fn birth(&self) -> Vec<usize> {
+        self.live_counts(&Cell::Dead)
+    }
+
+    /// Neighbor counts for which a living Cell stays alive
+    pub fn survival(&self) -> Vec<usize> {
+        self.live_counts(&Cell::Alive)
+    }
+
+    /// Neighbor counts for which a Cell in state `cell` is alive in the next generation
+    fn live_counts(&self, cell: &Cell) -> Vec<usize> {
+        (0..=MAX_NEIGHBORS)
+            .filter(|&count| self.next_state(cell, &alive_neighbors(count)) == Cell::Alive)
+            .collect()
+    }
+}
+
+/// B/S notation, e.g. `B3/S23` for Conway's Game of Life.
+/// A `decay` is written as the total number of states, e.g. `B2/S/C3` for Brian's Brain.
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits =
+            |counts: Vec<usize>| counts.iter().map(ToString::to_string).collect::<String>();
+        write!(f, "B{}/S{}", digits(self.birth()), digits(self.survival()))?;
+        if self.decay > 0 {
+            write!(f, "/C{}", self.decay + 2)?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors that can occur while parsing B/S notation
+/// - `Part` => A part isn't `B`, `S` or `C` followed by digits
+/// - `Count` => A neighbor count is larger than 8
+/// - `States` => The number of states after `C` is below 2
+/// - `Missing` => There's no `B` or no `S` part
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NotationError {
+    Part(String),
+    Count(char),
+    States(String),
+    Missing,
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Part(part) => write!(f, "Invalid part {part:?} of B/S notation"),
+            Self::Count(count) => write!(f, "Neighbor count {count} is larger than 8"),
+            Self::States(states) => write!(f, "Invalid number of states {states:?}"),
+            Self::Missing => write!(f, "B/S notation needs both a B and an S part"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotationError {}
+
+/// Parses the B/S notation written by `Display`, case insensitively
+impl FromStr for RuleSet {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let counts = |digits: &str| {
+            digits
+                .chars()
+                .map(|digit| match digit.to_digit(10) {
+                    Some(count) if count as usize <= MAX_NEIGHBORS => Ok(count as usize),
+                    _ => Err(NotationError::Count(digit)),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let (mut birth, mut survival, mut decay) = (None, None, 0);
+        for part in s.trim().split('/') {
+            let mut chars = part.chars();
+            let kind = chars.next().map(|kind| kind.to_ascii_uppercase());
+            let value = chars.as_str();
+            if !value.chars().all(|digit| digit.is_ascii_digit()) {
+                return Err(NotationError::Part(part.to_owned()));
+            }
+            match kind {
+                Some('B') => birth = Some(counts(value)?),
+                Some('S') => survival = Some(counts(value)?),
+                Some('C') => {
+                    decay = value
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|states| states.checked_sub(2))
+                        .ok_or_else(|| NotationError::States(value.to_owned()))?;
+                }
+                _ => return Err(NotationError::Part(part.to_owned())),
+            }
+        }
+        let (Some(birth), Some(survival)) = (birth, survival) else {
+            return Err(NotationError::Missing);
+        };
+        Ok(Self::life_like(birth, survival, decay))
+    }
+}
+
+/// Subset of `RuleSet`, matched against the number of alive neighbors
+/// (see `Counting`) unless counting a specific `state`
+///
+/// - `Range` Determines an Inclusive range in which a rule Applies
+/// - `Singles` Determines multiple values in which a rule Applies
+/// - `Count` Applies when the number of neighbors in exactly `state` is matched by `counts`,
+///   e.g. "1 or 2 neighbors are `Cell::Alive`" ignoring `Cell::Dying` ones
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rules {
+    Range(RangeInclusive<usize>),
+    Singles(Vec<usize>),
+    Count { state: Cell, counts: Box<Self> },
+}
+
+impl Rules {
+    /// State of the neighbors whose number is matched, `Cell::Alive` unless counting
+    /// a specific `state`. Which of them count as alive depends on the `Counting`.
+    pub fn counted(&self) -> &Cell {
+        match self {
+            Self::Range(_) | Self::Singles(_) => &Cell::Alive,
+            Self::Count { state, counts } => match **counts {
+                Self::Count { .. } => counts.counted(),
+                _ => state,
+            },
+        }
+    }
+
+    pub fn matches(&self, neighbors: &NeighborCounts, counting: Counting) -> bool {
+        self.matches_count(counting.alive_neighbors(neighbors), neighbors)
+    }
+
+    fn matches_count(&self, count: usize, neighbors: &NeighborCounts) -> bool {
+        match self {
+            Self::Range(r) => r.contains(&count),
+            Self::Singles(s) => s.contains(&count),
+            Self::Count { state, counts } => counts.matches_count(neighbors.of(state), neighbors),
+        }
+    }
+}
+
+/// The action to perform when Operating on a Cell
+///
+/// - `Live` => transforms the Cell to `Cell::Alive`
+/// - `Die`  => transforms the Cell to `Cell::Dead`, exactly like Conway's Game of Life
+/// - `StartDying` => transforms the Cell to `Cell::Dying` with `ticks` generations left,
+///   `0` ticks die immediately
+/// - `Set` => transforms the Cell to the given state
+/// - `Keep` => leaves the Cell as it is
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    #[default]
+    Live,
+    Die,
+    StartDying {
+        ticks: usize,
+    },
+    Set(Cell),
+    Keep,
+}
+
+impl Action {
+    /// State of `cell` after performing the action on it
+    pub fn apply(&self, cell: &Cell) -> Cell {
+        match self {
+            Self::Live => Cell::Alive,
+            Self::Die | Self::StartDying { ticks: 0 } => Cell::Dead,
+            &Self::StartDying { ticks } => Cell::Dying {
+                ticks_till_death: ticks,
+            },
+            Self::Set(state) => state.clone(),
+            Self::Keep => cell.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rules::{alive_neighbors, Conflict, Counting, NotationError, Priority},
+        Action, Cell, NeighborCounts, RuleSet, Rules,
+    };
+
+    #[test]
+    fn notation() {
+        assert_eq!(RuleSet::default().to_string(), "B3/S23");
+        assert_eq!(
+            RuleSet::life_like(vec![3, 6], vec![2, 3], 0).to_string(),
+            "B36/S23"
+        );
+        assert_eq!(
+            RuleSet::life_like(vec![2], vec![], 1).to_string(),
+            "B2/S/C3"
+        );
+    }
+
+    #[test]
+    fn parse_notation() {
+        for notation in ["B3/S23", "B36/S23", "B2/S/C3", "B/S012345678"] {
+            assert_eq!(notation.parse::<RuleSet>().unwrap().to_string(), notation);
+        }
+        assert_eq!(
+            "b3/s23".parse(),
+            Ok(RuleSet::life_like(vec![3], vec![2, 3], 0))
+        );
+        assert_eq!("B3/S29".parse::<RuleSet>(), Err(NotationError::Count('9')));
+        assert_eq!(
+            "B3/S2x".parse::<RuleSet>(),
+            Err(NotationError::Part("S2x".into()))
+        );
+        assert_eq!(
+            "B3/S23/C1".parse::<RuleSet>(),
+            Err(NotationError::States("1".into()))
+        );
+        assert_eq!("B3".parse::<RuleSet>(), Err(NotationError::Missing));
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut rule_set = RuleSet {
+            dead: vec![
+                (Rules::Range(2..=4), Action::Live),
+                (Rules::Singles(vec![4, 5]), Action::Die),
+                (Rules::Singles(vec![3]), Action::Die),
+            ],
+            ..RuleSet::default()
+        };
+        let overlap = |first, second, counts| Conflict::Overlap {
+            state: Cell::Dead,
+            first,
+            second,
+            counts,
+        };
+        let unreachable = |rule| Conflict::Unreachable {
+            state: Cell::Dead,
+            rule,
+        };
+        assert_eq!(
+            rule_set.conflicts(),
+            vec![
+                overlap(0, 1, vec![4]),
+                overlap(0, 2, vec![3]),
+                unreachable(2)
+            ]
+        );
+        assert_eq!(rule_set.validate(), Ok(()));
+        assert_eq!(rule_set.birth(), vec![2, 3, 4]);
+
+        rule_set.priority = Priority::LastMatch;
+        assert_eq!(rule_set.birth(), vec![2]);
+        assert_eq!(rule_set.conflicts().len(), 2);
+
+        rule_set.priority = Priority::Strict;
+        assert_eq!(rule_set.validate().unwrap_err().len(), 2);
+        assert_eq!(RuleSet::default().conflicts(), vec![]);
+    }
+
+    #[test]
+    fn next_state() {
+        let conway = RuleSet::default();
+        assert_eq!(
+            conway.next_state(&Cell::Dead, &alive_neighbors(3)),
+            Cell::Alive
+        );
+        assert_eq!(
+            conway.next_state(&Cell::Dead, &alive_neighbors(2)),
+            Cell::Dead
+        );
+        assert_eq!(
+            conway.next_state(&Cell::Alive, &alive_neighbors(2)),
+            Cell::Alive
+        );
+        assert_eq!(
+            conway.next_state(&Cell::Alive, &alive_neighbors(4)),
+            Cell::Dead
+        );
+
+        let dying = |ticks_till_death| Cell::Dying { ticks_till_death };
+        assert_eq!(conway.next_state(&dying(2), &alive_neighbors(3)), dying(1));
+        assert_eq!(
+            conway.next_state(&dying(1), &alive_neighbors(3)),
+            Cell::Dead
+        );
+
+        let brians_brain = RuleSet::life_like(vec![2], vec![], 1);
+        assert_eq!(
+            brians_brain.next_state(&Cell::Alive, &alive_neighbors(2)),
+            dying(1)
+        );
+
+        // Without a matching rule the Cell is left as it is
+        let empty = RuleSet {
+            dead: vec![],
+            ..RuleSet::default()
+        };
+        assert_eq!(
+            empty.next_state(&Cell::Dead, &alive_neighbors(3)),
+            Cell::Dead
+        );
+    }
+
+    #[test]
+    fn state_counts() {
+        // Comes alive with exactly 1 or 2 `Cell::Alive` neighbors, like a WireWorld conductor
+        let rule_set = RuleSet {
+            dead: vec![(
+                Rules::Count {
+                    state: Cell::Alive,
+                    counts: Box::new(Rules::Range(1..=2)),
+                },
+                Action::Live,
+            )],
+            ..RuleSet::default()
+        };
+        let dying = Cell::dying_cell();
+        let neighbors = |cells: &[Cell]| cells.iter().collect::<NeighborCounts>();
+        assert_eq!(
+            rule_set.next_state(&Cell::Dead, &neighbors(&[Cell::Alive, dying.clone()])),
+            Cell::Alive
+        );
+        assert_eq!(
+            rule_set.next_state(&Cell::Dead, &neighbors(&[dying.clone(), dying.clone()])),
+            Cell::Dead
+        );
+        assert_eq!(rule_set.birth(), vec![1, 2]);
+
+        // Rules counting dying neighbors are reached by neighborhoods without alive ones
+        let by_dying = |counts| Rules::Count {
+            state: dying.clone(),
+            counts: Box::new(Rules::Singles(counts)),
+        };
+        let rule_set = RuleSet {
+            dead: vec![
+                (by_dying(vec![2]), Action::Live),
+                (by_dying(vec![2, 3]), Action::Die),
+                (by_dying(vec![2]), Action::Keep),
+            ],
+            ..RuleSet::default()
+        };
+        assert_eq!(
+            rule_set.conflicts(),
+            vec![
+                Conflict::Overlap {
+                    state: Cell::Dead,
+                    first: 0,
+                    second: 1,
+                    counts: vec![2],
+                },
+                Conflict::Overlap {
+                    state: Cell::Dead,
+                    first: 0,
+                    second: 2,
+                    counts: vec![2],
+                },
+                Conflict::Overlap {
+                    state: Cell::Dead,
+                    first: 1,
+                    second: 2,
+                    counts: vec![2],
+                },
+                Conflict::Unreachable {
+                    state: Cell::Dead,
+                    rule: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn counting() {
+        let neighbors = [Cell::Alive, Cell::dying_cell(), Cell::dying_cell()]
+            .iter()
+            .collect::<NeighborCounts>();
+        let mut conway = RuleSet::default();
+        assert_eq!(conway.next_state(&Cell::Dead, &neighbors), Cell::Dead);
+        conway.counting = Counting::AliveAndDying;
+        assert_eq!(conway.next_state(&Cell::Dead, &neighbors), Cell::Alive);
+    }
+
+    #[test]
+    fn actions() {
+        let dying = Cell::Dying {
+            ticks_till_death: 2,
+        };
+        assert_eq!(Action::Die.apply(&Cell::Alive), Cell::Dead);
+        assert_eq!(Action::StartDying { ticks: 2 }.apply(&Cell::Alive), dying);
+        assert_eq!(
+            Action::StartDying { ticks: 0 }.apply(&Cell::Alive),
+            Cell::Dead
+        );
+        assert_eq!(Action::Set(dying.clone()).apply(&Cell::Dead), dying);
+        assert_eq!(Action::Keep.apply(&dying), dying);
+
+        // Explicit dying doesn't make the dying Cells count as survivors
+        let brians_brain = RuleSet::life_like(vec![2], vec![], 1);
+        assert_eq!(brians_brain.alive[1].1, Action::StartDying { ticks: 1 });
+        assert!(brians_brain.survival().is_empty());
+    }
+}
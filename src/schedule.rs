@@ -0,0 +1,258 @@
+//! Rule changes scheduled during a run, e.g. switching from Seeds to Life at generation 500.
+//!
+//! A change may ramp in over several generations, during which a growing random share of the
+//! Cells already follows the new rules.
+
+use crate::{
+    rules::{NotationError, RuleSet},
+    transition, Automaton, RuleTable,
+};
+use rand::Rng;
+use serde::Deserialize;
+use std::{error, fmt, fs, io, path::Path};
+
+/// Errors that can occur while loading a `Schedule`
+/// - `Io` => The file couldn't be read
+/// - `Toml` => The file isn't a valid TOML schedule, e.g. one of the rules isn't B/S notation
+#[derive(Debug)]
+pub enum ScheduleError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't read schedule: {e}"),
+            Self::Toml(e) => write!(f, "Invalid schedule: {e}"),
+        }
+    }
+}
+
+impl error::Error for ScheduleError {}
+
+impl From<io::Error> for ScheduleError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<toml::de::Error> for ScheduleError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Toml(value)
+    }
+}
+
+/// A change as written in TOML, before its rules are parsed
+#[derive(Deserialize)]
+struct RawRuleChange {
+    generation: u64,
+    rules: String,
+    #[serde(default)]
+    ramp: u64,
+}
+
+/// Switch to `rule_set` when stepping from `generation` on.
+///
+/// With a `ramp` of `n` generations the share of Cells following the new rules grows by `1/n`
+/// every generation, the others still follow the rules in effect before.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(try_from = "RawRuleChange")]
+pub struct RuleChange {
+    pub generation: u64,
+    pub rule_set: RuleSet,
+    pub ramp: u64,
+}
+
+impl TryFrom<RawRuleChange> for RuleChange {
+    type Error = NotationError;
+
+    fn try_from(value: RawRuleChange) -> Result<Self, Self::Error> {
+        Ok(Self {
+            generation: value.generation,
+            rule_set: value.rules.parse()?,
+            ramp: value.ramp,
+        })
+    }
+}
+
+impl RuleChange {
+    /// Share of the Cells following the new rules when stepping from `generation`,
+    /// `0` before the change
+    #[allow(clippy::cast_precision_loss)]
+    fn share(&self, generation: u64) -> f64 {
+        match generation.checked_sub(self.generation) {
+            None => 0.0,
+            Some(elapsed) if elapsed >= self.ramp => 1.0,
+            Some(elapsed) => (elapsed + 1) as f64 / self.ramp as f64,
+        }
+    }
+}
+
+/// Rule changes ordered by generation, loadable from TOML:
+///
+/// ```toml
+/// [[changes]]
+/// generation = 500
+/// rules = "B3/S23"
+///
+/// [[changes]]
+/// generation = 1000
+/// rules = "B36/S23"
+/// ramp = 200
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(from = "RawSchedule")]
+pub struct Schedule {
+    changes: Vec<RuleChange>,
+}
+
+#[derive(Deserialize)]
+struct RawSchedule {
+    #[serde(default)]
+    changes: Vec<RuleChange>,
+}
+
+impl From<RawSchedule> for Schedule {
+    fn from(value: RawSchedule) -> Self {
+        Self::new(value.changes)
+    }
+}
+
+impl Schedule {
+    pub fn new(mut changes: Vec<RuleChange>) -> Self {
+        changes.sort_by_key(|change| change.generation);
+        Self { changes }
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScheduleError> {
+        Ok(Self::from_toml(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn changes(&self) -> &[RuleChange] {
+        &self.changes
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The rules before and after the latest change at or before `generation` and the share
+    /// of Cells following the latter, starting from the `initial` rules.
+    /// A change taking effect during the ramp of the one before cuts the ramp short.
+    pub fn rules_at<'a>(
+        &'a self,
+        initial: &'a RuleSet,
+        generation: u64,
+    ) -> (&'a RuleSet, &'a RuleSet, f64) {
+        let mut rules = (initial, initial, 1.0);
+        for change in &self.changes {
+            if change.generation > generation {
+                break;
+            }
+            rules = (rules.1, &change.rule_set, change.share(generation));
+        }
+        rules
+    }
+
+    /// Steps `automaton` with the rules scheduled for its generation, starting from the
+    /// `initial` rules. Its `rule_set` is set to the rules being ramped in, so stepping on
+    /// without the schedule keeps the latest rules.
+    pub fn step(&self, automaton: &mut Automaton, initial: &RuleSet, rng: &mut impl Rng) {
+        let (before, after, share) = self.rules_at(initial, automaton.generation.0);
+        if automaton.rule_set != *after {
+            automaton.rule_set = after.clone();
+        }
+        if share >= 1.0 {
+            automaton.step();
+            return;
+        }
+        // Compiled once per generation, every Cell picks one of them
+        let tables = [before, after].map(RuleTable::new);
+        let grid = &automaton.grid;
+        let next = grid
+            .iter()
+            .enumerate()
+            .map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(col, cell)| {
+                        let neighbors =
+                            transition::neighbors(grid, automaton.neighborhood_type, row, col);
+                        tables[usize::from(rng.gen_bool(share))].next_state(cell, neighbors)
+                    })
+                    .collect()
+            })
+            .collect();
+        automaton.replace_grid(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        schedule::{RuleChange, Schedule},
+        seeding, Automaton, RuleSet,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn from_toml() {
+        let schedule = Schedule::from_toml(
+            "[[changes]]\ngeneration = 10\nrules = \"B36/S23\"\nramp = 4\n\n\
+             [[changes]]\ngeneration = 2\nrules = \"B3/S23\"\n",
+        )
+        .unwrap();
+        assert_eq!(schedule.changes()[0].rule_set, "B3/S23".parse().unwrap());
+        assert_eq!(schedule.changes()[1].ramp, 4);
+        assert!(Schedule::from_toml("[[changes]]\ngeneration = 1\nrules = \"B3\"\n").is_err());
+        assert!(Schedule::from_toml("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn ramped_changes() {
+        let seeds: RuleSet = "B2/S".parse().unwrap();
+        let life: RuleSet = "B3/S23".parse().unwrap();
+        let high_life: RuleSet = "B36/S23".parse().unwrap();
+        let schedule = Schedule::new(vec![
+            RuleChange {
+                generation: 10,
+                rule_set: high_life.clone(),
+                ramp: 4,
+            },
+            RuleChange {
+                generation: 2,
+                rule_set: life.clone(),
+                ramp: 0,
+            },
+        ]);
+        assert_eq!(schedule.rules_at(&seeds, 1), (&seeds, &seeds, 1.0));
+        assert_eq!(schedule.rules_at(&seeds, 2), (&seeds, &life, 1.0));
+        assert_eq!(schedule.rules_at(&seeds, 10), (&life, &high_life, 0.25));
+        assert_eq!(schedule.rules_at(&seeds, 12), (&life, &high_life, 0.75));
+        assert_eq!(schedule.rules_at(&seeds, 13), (&life, &high_life, 1.0));
+
+        let run = || {
+            let mut automaton = Automaton::builder()
+                .row_count(16)
+                .col_count(16)
+                .grid(seeding::soup(7, 16, 16, 0.4))
+                .rule_set(seeds.clone())
+                .build();
+            let mut rng = StdRng::seed_from_u64(7);
+            for _ in 0..16 {
+                schedule.step(&mut automaton, &seeds, &mut rng);
+            }
+            automaton
+        };
+        let automaton = run();
+        assert_eq!(automaton.rule_set, high_life);
+        // The same seed mixes the rules the same way
+        assert_eq!(automaton.grid, run().grid);
+    }
+}
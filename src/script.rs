@@ -0,0 +1,223 @@
+use crate::{Automaton, Cell, Grid, NeighborCounts};
+use rhai::{
+    CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, ParseError, Scope, Variant, AST, INT,
+};
+use std::{error, fmt, fs, io, path::Path};
+
+/// Errors that can occur while loading or running a `Script`
+#[derive(Debug)]
+pub enum ScriptError {
+    /// The file couldn't be read
+    Io(io::Error),
+    /// The script doesn't compile
+    Parse(ParseError),
+    /// A function of the script failed or returned something unexpected
+    Eval(Box<EvalAltResult>),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't read script: {e}"),
+            Self::Parse(e) => write!(f, "Invalid script: {e}"),
+            Self::Eval(e) => write!(f, "Script failed: {e}"),
+        }
+    }
+}
+
+impl error::Error for ScriptError {}
+
+impl From<io::Error> for ScriptError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ParseError> for ScriptError {
+    fn from(value: ParseError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+impl From<Box<EvalAltResult>> for ScriptError {
+    fn from(value: Box<EvalAltResult>) -> Self {
+        Self::Eval(value)
+    }
+}
+
+/// Automaton behavior written in Rhai and loaded at runtime.
+/// Every function is optional:
+///
+/// ```rhai
+/// // Transition replacing the rules, e.g. Seeds (B2/S)
+/// fn next_state(cell, neighbors) {
+///     if cell.is_dead() && neighbors.alive == 2 { Alive() } else { Dead() }
+/// }
+///
+/// // Initial state of the Cell at (row, col)
+/// fn seed(row, col) {
+///     if random() < 0.3 { Alive() } else { Dead() }
+/// }
+///
+/// // Called after every generation
+/// fn on_generation(generation, population) {
+///     print(`${generation}: ${population}`);
+/// }
+/// ```
+///
+/// Cells are created with `Dead()`, `Alive()` and `Dying(ticks)` and inspected with
+/// `is_dead()`, `is_alive()`, `is_dying()` and `ticks`.
+/// Neighbors provide `alive`, `dying` and `of(cell)`, see `NeighborCounts`.
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+}
+
+impl Script {
+    pub fn new(source: &str) -> Result<Self, ScriptError> {
+        let engine = engine();
+        let ast = engine.compile(source)?;
+        Ok(Self { engine, ast })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScriptError> {
+        Self::new(&fs::read_to_string(path)?)
+    }
+
+    /// Whether the script defines the function `name` taking `arity` arguments
+    pub fn defines(&self, name: &str, arity: usize) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|function| function.name == name && function.params.len() == arity)
+    }
+
+    fn call<T: Variant + Clone>(&self, name: &str, args: impl FuncArgs) -> Result<T, ScriptError> {
+        // The top level statements only run once while compiling, not for every call
+        let options = CallFnOptions::new().eval_ast(false);
+        Ok(self
+            .engine
+            .call_fn_with_options(options, &mut Scope::new(), &self.ast, name, args)?)
+    }
+
+    /// State of `cell` in the next generation according to the script's `next_state`
+    pub fn next_state(&self, cell: &Cell, neighbors: &NeighborCounts) -> Result<Cell, ScriptError> {
+        self.call("next_state", (cell.clone(), neighbors.clone()))
+    }
+
+    /// Grid populated by the script's `seed`
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn seed(&self, row_count: usize, col_count: usize) -> Result<Grid, ScriptError> {
+        (0..row_count)
+            .map(|row| {
+                (0..col_count)
+                    .map(|col| self.call("seed", (row as INT, col as INT)))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect()
+    }
+
+    /// Steps `automaton` with the script's `next_state`, or its `rule_set` if there is none,
+    /// and calls the script's `on_generation` afterwards
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn step(&self, automaton: &mut Automaton) -> Result<(), ScriptError> {
+        if self.defines("next_state", 2) {
+            automaton.try_step_with(|cell, neighbors| self.next_state(cell, neighbors))?;
+        } else {
+            automaton.step();
+        }
+        if self.defines("on_generation", 2) {
+            self.call::<Dynamic>(
+                "on_generation",
+                (automaton.generation.0 as INT, automaton.population() as INT),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Engine knowing about Cells and neighbor counts
+#[allow(clippy::cast_possible_wrap)]
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .register_type_with_name::<Cell>("Cell")
+        .register_fn("Dead", || Cell::Dead)
+        .register_fn("Alive", || Cell::Alive)
+        .register_fn("Dying", |ticks: INT| Cell::Dying {
+            ticks_till_death: usize::try_from(ticks).unwrap_or_default(),
+        })
+        .register_fn("is_dead", |cell: &mut Cell| cell.is_dead())
+        .register_fn("is_alive", |cell: &mut Cell| cell.is_alive())
+        .register_fn("is_dying", |cell: &mut Cell| cell.is_dying())
+        .register_get("ticks", |cell: &mut Cell| match cell {
+            Cell::Dying { ticks_till_death } => *ticks_till_death as INT,
+            Cell::Dead | Cell::Alive => 0,
+        })
+        .register_fn("==", |left: Cell, right: Cell| left == right)
+        .register_fn("!=", |left: Cell, right: Cell| left != right)
+        .register_fn("to_string", |cell: &mut Cell| cell.to_string())
+        .register_type_with_name::<NeighborCounts>("NeighborCounts")
+        .register_get("alive", |neighbors: &mut NeighborCounts| {
+            neighbors.alive() as INT
+        })
+        .register_get("dying", |neighbors: &mut NeighborCounts| {
+            neighbors.dying() as INT
+        })
+        .register_fn("of", |neighbors: &mut NeighborCounts, state: Cell| {
+            neighbors.of(&state) as INT
+        })
+        .register_fn("random", rand::random::<f64>);
+    engine
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{script::Script, Automaton, Cell, RuleSet};
+
+    #[test]
+    fn matches_rules() {
+        let script = Script::new(
+            "
+            fn next_state(cell, neighbors) {
+                if cell.is_dead() && neighbors.alive == 2 { Alive() } else { Dead() }
+            }
+            fn seed(row, col) {
+                if row == col { Alive() } else { Dead() }
+            }
+            ",
+        )
+        .unwrap();
+        assert!(script.defines("next_state", 2));
+        assert!(!script.defines("on_generation", 2));
+
+        let grid = script.seed(6, 6).unwrap();
+        assert_eq!(grid[2][2], Cell::Alive);
+        assert_eq!(grid[2][3], Cell::Dead);
+
+        let mut scripted = Automaton::builder()
+            .row_count(6)
+            .col_count(6)
+            .grid(grid)
+            .build();
+        let mut seeds = Automaton {
+            rule_set: RuleSet::life_like(vec![2], vec![], 0),
+            ..scripted.clone()
+        };
+        for _ in 0..4 {
+            script.step(&mut scripted).unwrap();
+            seeds.step();
+            assert_eq!(scripted.grid, seeds.grid);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert!(Script::new("fn next_state(cell, neighbors) {").is_err());
+
+        let script = Script::new("fn next_state(cell, neighbors) { 42 }").unwrap();
+        let mut automaton = Automaton::default();
+        let grid = automaton.grid.clone();
+        assert!(script.step(&mut automaton).is_err());
+        assert_eq!(automaton.grid, grid);
+    }
+}
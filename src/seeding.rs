@@ -0,0 +1,361 @@
+use crate::{grid, library, Cell, Grid};
+use clap::ValueEnum;
+use itertools::iproduct;
+use noise::{NoiseFn, OpenSimplex, Perlin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt;
+
+/// Strategy used to populate a fresh `Grid`
+///
+/// - `Uniform` => Every Cell is alive with the same probability `density`
+/// - `Perlin` => Cells are alive wherever Perlin noise exceeds `threshold`
+/// - `Simplex` => Same as `Perlin` but sampling `OpenSimplex` noise
+/// - `Radial` => The probability of being alive fades linearly
+///   from `center` in the middle of the grid to `edge` in its corners
+/// - `Symmetric` => A `Uniform` soup that is invariant under `symmetry`
+/// - `Scatter` => `count` randomly oriented embedded patterns at random positions,
+///   see `library::scatter`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Seeding {
+    Uniform {
+        density: f64,
+    },
+    Perlin {
+        scale: f64,
+        threshold: f64,
+        seed: u32,
+    },
+    Simplex {
+        scale: f64,
+        threshold: f64,
+        seed: u32,
+    },
+    Radial {
+        center: f64,
+        edge: f64,
+    },
+    Symmetric {
+        density: f64,
+        symmetry: Symmetry,
+    },
+    Scatter {
+        count: usize,
+    },
+}
+
+impl Default for Seeding {
+    fn default() -> Self {
+        Self::Uniform { density: 0.5 }
+    }
+}
+
+impl Seeding {
+    pub fn populate(&self, row_count: usize, col_count: usize) -> Grid {
+        match *self {
+            Self::Uniform { density } => {
+                Self::populate_with(row_count, col_count, |_, _| Self::random_cell(density))
+            }
+            Self::Perlin {
+                scale,
+                threshold,
+                seed,
+            } => Self::noise_population(&Perlin::new(seed), row_count, col_count, scale, threshold),
+            Self::Simplex {
+                scale,
+                threshold,
+                seed,
+            } => Self::noise_population(
+                &OpenSimplex::new(seed),
+                row_count,
+                col_count,
+                scale,
+                threshold,
+            ),
+            Self::Radial { center, edge } => {
+                Self::radial_population(row_count, col_count, center, edge)
+            }
+            Self::Symmetric { density, symmetry } => {
+                symmetry.symmetric_population(row_count, col_count, density)
+            }
+            Self::Scatter { count } => {
+                let mut grid = vec![vec![Cell::Dead; col_count]; row_count];
+                library::scatter(
+                    &mut grid,
+                    &library::embedded(),
+                    count,
+                    &mut rand::thread_rng(),
+                );
+                grid
+            }
+        }
+    }
+
+    fn populate_with(
+        row_count: usize,
+        col_count: usize,
+        mut cell_at: impl FnMut(usize, usize) -> Cell,
+    ) -> Grid {
+        (0..row_count)
+            .map(|row| (0..col_count).map(|col| cell_at(row, col)).collect())
+            .collect()
+    }
+
+    fn random_cell(density: f64) -> Cell {
+        if rand::thread_rng().gen_bool(density.clamp(0.0, 1.0)) {
+            Cell::Alive
+        } else {
+            Cell::default()
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn noise_population(
+        noise: &impl NoiseFn<f64, 2>,
+        row_count: usize,
+        col_count: usize,
+        scale: f64,
+        threshold: f64,
+    ) -> Grid {
+        Self::populate_with(row_count, col_count, |row, col| {
+            if noise.get([col as f64 * scale, row as f64 * scale]) > threshold {
+                Cell::Alive
+            } else {
+                Cell::default()
+            }
+        })
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn radial_population(row_count: usize, col_count: usize, center: f64, edge: f64) -> Grid {
+        let center_row = (row_count as f64 - 1.0) / 2.0;
+        let center_col = (col_count as f64 - 1.0) / 2.0;
+        let max_distance = center_row.hypot(center_col).max(f64::EPSILON);
+        Self::populate_with(row_count, col_count, |row, col| {
+            let distance = (row as f64 - center_row).hypot(col as f64 - center_col);
+            let density = (edge - center).mul_add(distance / max_distance, center);
+            Self::random_cell(density)
+        })
+    }
+}
+
+/// Random grid where every Cell is alive with probability `density`,
+/// the same `seed` always yields the same soup
+pub fn soup(seed: u64, row_count: usize, col_count: usize, density: f64) -> Grid {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..row_count)
+        .map(|_| {
+            (0..col_count)
+                .map(|_| {
+                    if rng.gen_bool(density) {
+                        Cell::Alive
+                    } else {
+                        Cell::Dead
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Symmetry groups of a pattern, also used for `Seeding::Symmetric` soups
+/// and for mirroring edits in the GUI
+/// - `C2` => Invariant under a 180° rotation
+/// - `C4` => Invariant under 90° rotations
+/// - `D2` => Invariant under a reflection across the vertical axis
+/// - `D4` => Invariant under reflections across the vertical and the horizontal axis
+/// - `D8` => Invariant under 90° rotations and reflections
+///
+/// `C4` and `D8` can only be exact on square grids. Other grids are cut out
+/// of the center of a symmetric square soup.
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, ValueEnum)]
+pub enum Symmetry {
+    #[default]
+    C2,
+    C4,
+    D2,
+    D4,
+    D8,
+}
+
+impl Symmetry {
+    /// Every group, larger groups before the ones they contain
+    pub const ALL: [Self; 5] = [Self::D8, Self::D4, Self::C4, Self::D2, Self::C2];
+
+    /// Images of (`row`, `col`) under every element of the group, rotating and reflecting
+    /// around the center of a `row_count` x `col_count` grid. Images that aren't Cells of the grid
+    /// are left out, which only happens for `C4` and `D8` on grids that aren't square.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn images(
+        self,
+        row_count: usize,
+        col_count: usize,
+        row: usize,
+        col: usize,
+    ) -> Vec<(usize, usize)> {
+        // Coordinates relative to the center, doubled to stay integral
+        let (last_row, last_col) = (row_count as i64 - 1, col_count as i64 - 1);
+        let (r, c) = (2 * row as i64 - last_row, 2 * col as i64 - last_col);
+        let rotations = [(r, c), (-r, -c), (c, -r), (-c, r)];
+        let reflections = [(r, -c), (-r, c), (c, r), (-c, -r)];
+        let transformed = match self {
+            Self::C2 => rotations[..2].to_vec(),
+            Self::C4 => rotations.to_vec(),
+            Self::D2 => vec![rotations[0], reflections[0]],
+            Self::D4 => vec![rotations[0], rotations[1], reflections[0], reflections[1]],
+            Self::D8 => rotations.into_iter().chain(reflections).collect(),
+        };
+        transformed
+            .into_iter()
+            .filter_map(|(r, c)| {
+                let (row, col) = (r + last_row, c + last_col);
+                let inside = (0..=2 * last_row).contains(&row) && (0..=2 * last_col).contains(&col);
+                (inside && row % 2 == 0 && col % 2 == 0)
+                    .then_some((row as usize / 2, col as usize / 2))
+            })
+            .collect()
+    }
+
+    /// Whether every Cell of `grid` has the same state as all of its images,
+    /// never for `C4` and `D8` if the grid isn't square
+    pub fn is_invariant(self, grid: &[Vec<Cell>]) -> bool {
+        let (row_count, col_count) = (grid.len(), grid.first().map_or(0, Vec::len));
+        if matches!(self, Self::C4 | Self::D8) && row_count != col_count {
+            return false;
+        }
+        iproduct!(0..row_count, 0..col_count).all(|(row, col)| {
+            self.images(row_count, col_count, row, col)
+                .into_iter()
+                .all(|(image_row, image_col)| grid[image_row][image_col] == grid[row][col])
+        })
+    }
+
+    /// The largest group the alive Cells are invariant under, regardless of where they are
+    /// on the grid. A reflection across the horizontal axis or a diagonal also counts as `D2`,
+    /// reflections across both diagonals as `D4`. `None` if there's no symmetry or no alive Cell.
+    pub fn detect(grid: &[Vec<Cell>]) -> Option<Self> {
+        let bounds = grid::bounding_box(grid)?;
+        let pattern = grid[bounds.top..=bounds.bottom]
+            .iter()
+            .map(|row| row[bounds.left..=bounds.right].to_vec())
+            .collect::<Grid>();
+        let rotated = grid::rotate(&pattern);
+        let size = pattern.len();
+        // Diagonals only map a bounding box onto itself if it's square
+        let mirrored = |image: fn(usize, usize, usize) -> (usize, usize)| {
+            pattern.iter().all(|cells| cells.len() == size)
+                && iproduct!(0..size, 0..size).all(|(row, col)| {
+                    let (image_row, image_col) = image(size - 1, row, col);
+                    pattern[image_row][image_col] == pattern[row][col]
+                })
+        };
+        let diagonal = mirrored(|_, row, col| (col, row));
+        let anti_diagonal = mirrored(|last, row, col| (last - col, last - row));
+        Self::ALL.into_iter().find(|symmetry| {
+            symmetry.is_invariant(&pattern)
+                || match symmetry {
+                    Self::D4 => diagonal && anti_diagonal,
+                    Self::D2 => diagonal || anti_diagonal || symmetry.is_invariant(&rotated),
+                    _ => false,
+                }
+        })
+    }
+
+    fn symmetric_population(self, row_count: usize, col_count: usize, density: f64) -> Grid {
+        let size = row_count.max(col_count);
+        let soup = Seeding::Uniform { density }.populate(size, size);
+        let row_offset = (size - row_count) / 2;
+        let col_offset = (size - col_count) / 2;
+        Seeding::populate_with(row_count, col_count, |row, col| {
+            let (source_row, source_col) = self
+                .images(size, size, row + row_offset, col + col_offset)
+                .into_iter()
+                .min()
+                .unwrap_or_default();
+            soup[source_row][source_col].clone()
+        })
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::C2 => "C2",
+            Self::C4 => "C4",
+            Self::D2 => "D2",
+            Self::D4 => "D4",
+            Self::D8 => "D8",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rle, Cell, Seeding, Symmetry};
+    use itertools::iproduct;
+
+    #[test]
+    fn symmetric_seeding() {
+        for symmetry in Symmetry::ALL {
+            let grid = Seeding::Symmetric {
+                density: 0.5,
+                symmetry,
+            }
+            .populate(6, 6);
+            for (row, col) in iproduct!(0..6, 0..6) {
+                for (irow, icol) in symmetry.images(6, 6, row, col) {
+                    assert_eq!(grid[row][col], grid[irow][icol]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn symmetry_detection() {
+        let pattern = |rle: &str| {
+            let mut grid = vec![vec![Cell::Dead; 12]; 10];
+            for (row, cells) in rle::parse(rle).unwrap().into_iter().enumerate() {
+                for (col, cell) in cells.into_iter().enumerate() {
+                    grid[row + 2][col + 1] = cell;
+                }
+            }
+            grid
+        };
+        // Block, glider, R-pentomino, beehive, standing beehive, T-tetromino, Z-tetromino
+        assert_eq!(Symmetry::detect(&pattern("2o$2o!")), Some(Symmetry::D8));
+        assert_eq!(Symmetry::detect(&pattern("bo$2bo$3o!")), None);
+        assert_eq!(Symmetry::detect(&pattern("b2o$2o$bo!")), None);
+        assert_eq!(
+            Symmetry::detect(&pattern("b2o$o2bo$b2o!")),
+            Some(Symmetry::D4)
+        );
+        assert_eq!(
+            Symmetry::detect(&pattern("bo$obo$obo$bo!")),
+            Some(Symmetry::D4)
+        );
+        assert_eq!(Symmetry::detect(&pattern("3o$bo!")), Some(Symmetry::D2));
+        assert_eq!(Symmetry::detect(&pattern("o$2o$bo!")), Some(Symmetry::C2));
+        assert_eq!(Symmetry::detect(&vec![vec![Cell::Dead; 3]; 3]), None);
+
+        // Boats are symmetric across one diagonal, a ship and a diagonal line across both
+        for boat in ["2o$obo$bo!", "bo$obo$b2o!", "b2o$obo$bo!"] {
+            assert_eq!(Symmetry::detect(&pattern(boat)), Some(Symmetry::D2));
+        }
+        assert_eq!(
+            Symmetry::detect(&pattern("2o$obo$b2o!")),
+            Some(Symmetry::D4)
+        );
+        assert_eq!(Symmetry::detect(&pattern("o$bo$2bo!")), Some(Symmetry::D4));
+
+        // Images on a wide grid that have no rotated counterpart are left out
+        assert_eq!(Symmetry::C4.images(2, 4, 0, 0), vec![(0, 0), (1, 3)]);
+        assert_eq!(
+            Symmetry::D4.images(3, 4, 0, 1),
+            vec![(0, 1), (2, 2), (0, 2), (2, 1)]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Headless simulation shared between remote clients, see `remote` for the protocol
+
+mod chat;
+#[cfg(feature = "http-api")]
+mod http;
+mod metrics;
+mod websocket;
+
+pub use chat::{
+    listen_chat, pattern, pattern_names, privmsg, ChatCommand, ChatConfig, ChatError, Moderation,
+};
+#[cfg(feature = "http-api")]
+pub use http::{listen_http, router};
+pub use metrics::Metrics;
+pub use websocket::listen;
+
+use crate::{
+    remote::{diff, Change, Frame, Request},
+    Automaton, Cell, CellAccess, ColIdx, Grid, RowIdx,
+};
+use std::{
+    sync::{mpsc, Mutex, MutexGuard, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Most generations a single request of a client steps, keeping one client from blocking the others
+pub const MAX_STEP: usize = 10_000;
+
+/// The simulation and the clients watching it
+pub struct Session {
+    pub automaton: Automaton,
+    /// Whether `run` stops stepping, `Session::step` still works
+    pub paused: bool,
+    pub metrics: Metrics,
+    /// Encoded frames for every connected client, dropped once the client is gone
+    subscribers: Vec<mpsc::Sender<Vec<u8>>>,
+}
+
+impl Session {
+    pub fn new(automaton: Automaton) -> Self {
+        Self {
+            automaton,
+            paused: false,
+            metrics: Metrics::default(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Registers a client, which receives a `Frame::Full` first and `Frame::Diff`s afterwards
+    pub fn subscribe(&mut self) -> mpsc::Receiver<Vec<u8>> {
+        let (sender, frames) = mpsc::channel();
+        // The receiver is still alive
+        let _ = sender.send(self.full_frame().encode());
+        self.subscribers.push(sender);
+        frames
+    }
+
+    /// Steps `generations` times and sends the Cells that changed to every client
+    pub fn step(&mut self, generations: usize) {
+        let before = self.automaton.grid.clone();
+        for _ in 0..generations {
+            let start = Instant::now();
+            self.automaton.step();
+            self.metrics.observe_step(&self.automaton, start.elapsed());
+        }
+        let changes = diff(&before, &self.automaton.grid);
+        self.broadcast(changes);
+    }
+
+    /// Replaces the grid, clients receive it as a whole
+    pub fn set_grid(&mut self, grid: Grid) {
+        self.automaton.row_count = grid.len();
+        self.automaton.col_count = grid.first().map_or(0, Vec::len);
+        self.automaton.grid = grid;
+        let frame = self.full_frame().encode();
+        self.subscribers
+            .retain(|subscriber| subscriber.send(frame.clone()).is_ok());
+    }
+
+    /// `Automaton::stamp` that sends the Cells that changed to every client
+    pub fn stamp(&mut self, pattern: &[Vec<Cell>], row: usize, col: usize) {
+        let before = self.automaton.grid.clone();
+        self.automaton.stamp(pattern, row, col);
+        let changes = diff(&before, &self.automaton.grid);
+        self.broadcast(changes);
+    }
+
+    /// Applies a request of a client, steps are capped at `MAX_STEP` generations
+    pub fn handle(&mut self, request: Request) {
+        match request {
+            Request::Pause => self.paused = true,
+            Request::Resume => self.paused = false,
+            Request::Step { generations } => {
+                self.step(
+                    usize::try_from(generations)
+                        .map_or(MAX_STEP, |generations| generations.min(MAX_STEP)),
+                );
+            }
+            Request::SetCell(change) => {
+                let (row, col) = (RowIdx(change.row as usize), ColIdx(change.col as usize));
+                if self
+                    .automaton
+                    .grid
+                    .set_cell(row, col, change.cell.clone())
+                    .is_some()
+                {
+                    self.broadcast(vec![change]);
+                }
+            }
+        }
+    }
+
+    fn full_frame(&self) -> Frame {
+        Frame::Full {
+            generation: self.automaton.generation,
+            grid: self.automaton.grid.clone(),
+        }
+    }
+
+    fn broadcast(&mut self, changes: Vec<Change>) {
+        if changes.is_empty() {
+            return;
+        }
+        let frame = Frame::Diff {
+            generation: self.automaton.generation,
+            changes,
+        }
+        .encode();
+        self.subscribers
+            .retain(|subscriber| subscriber.send(frame.clone()).is_ok());
+    }
+}
+
+/// Locks `session`, a panicking client can't leave it in an inconsistent state
+pub fn lock(session: &Mutex<Session>) -> MutexGuard<'_, Session> {
+    session.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Steps `session` every `interval` unless it's paused
+pub fn run(session: &Mutex<Session>, interval: Duration) -> ! {
+    loop {
+        thread::sleep(interval);
+        let mut session = lock(session);
+        if !session.paused {
+            session.step(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        remote::{Change, Frame, Request},
+        server::{Session, MAX_STEP},
+        Automaton, Cell,
+    };
+
+    #[test]
+    fn broadcasts_changes() {
+        let mut blinker = vec![vec![Cell::Dead; 5]; 5];
+        blinker[2][1..=3].fill(Cell::Alive);
+        let mut session = Session::new(
+            Automaton::builder()
+                .row_count(5)
+                .col_count(5)
+                .grid(blinker)
+                .build(),
+        );
+        let frames = session.subscribe();
+        assert!(matches!(
+            Frame::decode(&frames.recv().unwrap()),
+            Ok(Frame::Full { .. })
+        ));
+
+        session.handle(Request::Pause);
+        assert!(session.paused);
+        let change = Change {
+            row: 0,
+            col: 0,
+            cell: Cell::Alive,
+        };
+        session.handle(Request::SetCell(change.clone()));
+        let Ok(Frame::Diff { changes, .. }) = Frame::decode(&frames.recv().unwrap()) else {
+            panic!("Expected a diff");
+        };
+        assert_eq!(changes, vec![change]);
+
+        session.handle(Request::SetCell(Change {
+            row: 99,
+            col: 0,
+            cell: Cell::Alive,
+        }));
+        session.handle(Request::Step { generations: 2 });
+        assert_eq!(session.automaton.generation.0, 2);
+        session.handle(Request::Step {
+            generations: u32::MAX,
+        });
+        assert_eq!(session.automaton.generation.0, 2 + MAX_STEP as u64);
+        frames.try_iter().for_each(drop);
+        session.set_grid(vec![vec![Cell::Alive; 3]; 2]);
+        assert_eq!(session.automaton.col_count, 3);
+        assert!(matches!(
+            Frame::decode(&frames.recv().unwrap()),
+            Ok(Frame::Full { grid, .. }) if grid.len() == 2
+        ));
+        drop(frames);
+        session.step(1);
+        assert!(session.subscribers.is_empty());
+    }
+}
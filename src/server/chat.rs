@@ -0,0 +1,310 @@
+use crate::{
+    rle,
+    server::{lock, Session},
+    Grid, RuleSet,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+    io::{self, BufRead, BufReader, Write},
+    net::TcpStream,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Patterns viewers can spawn, in RLE
+const PATTERNS: [(&str, &str); 7] = [
+    ("glider", "bo$2bo$3o!"),
+    ("blinker", "3o!"),
+    ("lwss", "bo2bo$o4b$o3bo$4o!"),
+    ("rpentomino", "b2o$2o$bo!"),
+    ("acorn", "bo5b$3bo3b$2o2b3o!"),
+    ("diehard", "6bob$2o6b$bo3b3o!"),
+    ("pulsar", "2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!"),
+];
+
+/// Names of the patterns `!spawn` accepts
+pub fn pattern_names() -> impl Iterator<Item = &'static str> {
+    PATTERNS.iter().map(|(name, _)| *name)
+}
+
+/// The built in pattern called `name`, ignoring case
+pub fn pattern(name: &str) -> Option<Grid> {
+    PATTERNS
+        .iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+        .and_then(|(_, pattern)| rle::parse(pattern).ok())
+}
+
+/// Errors in chat commands, sent back to the viewer
+/// - `Usage` => Wrong arguments, contains the expected ones
+/// - `UnknownPattern` => `!spawn` of a pattern that isn't built in
+/// - `Rules` => `!rule` with invalid B/S notation
+/// - `NotAllowed` => The command is reserved for the allowlist
+/// - `RateLimited` => The viewer has to wait before the next command
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ChatError {
+    Usage(&'static str),
+    UnknownPattern(String),
+    Rules(String),
+    NotAllowed,
+    RateLimited(Duration),
+}
+
+impl fmt::Display for ChatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Usage(usage) => write!(f, "Usage: {usage}"),
+            Self::UnknownPattern(name) => write!(
+                f,
+                "Unknown pattern {name}, try one of {}",
+                pattern_names().collect::<Vec<_>>().join(", ")
+            ),
+            Self::Rules(e) => write!(f, "Invalid rules: {e}"),
+            Self::NotAllowed => write!(f, "Only moderators can do that"),
+            Self::RateLimited(wait) => {
+                write!(f, "Slow down, try again in {}s", wait.as_secs().max(1))
+            }
+        }
+    }
+}
+
+impl error::Error for ChatError {}
+
+/// Commands of viewers
+/// - `!spawn <pattern> <row> <col>` => `Spawn`, places a built in pattern with its top left there
+/// - `!rule <B/S notation>` => `Rule`, switches the rules
+/// - `!pause`, `!resume` => `Pause`, `Resume`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ChatCommand {
+    Spawn {
+        pattern: Grid,
+        row: usize,
+        col: usize,
+    },
+    Rule(RuleSet),
+    Pause,
+    Resume,
+}
+
+impl ChatCommand {
+    /// The command in `message`, `None` for regular chat
+    pub fn parse(message: &str) -> Option<Result<Self, ChatError>> {
+        let mut words = message.split_whitespace();
+        let command = match words.next()? {
+            "!spawn" => {
+                const USAGE: &str = "!spawn <pattern> <row> <col>";
+                let (Some(name), Some(row), Some(col), None) =
+                    (words.next(), words.next(), words.next(), words.next())
+                else {
+                    return Some(Err(ChatError::Usage(USAGE)));
+                };
+                let (Ok(row), Ok(col)) = (row.parse(), col.parse()) else {
+                    return Some(Err(ChatError::Usage(USAGE)));
+                };
+                let Some(pattern) = pattern(name) else {
+                    return Some(Err(ChatError::UnknownPattern(name.to_string())));
+                };
+                Self::Spawn { pattern, row, col }
+            }
+            "!rule" => {
+                let (Some(notation), None) = (words.next(), words.next()) else {
+                    return Some(Err(ChatError::Usage("!rule <B/S notation>")));
+                };
+                match notation.parse() {
+                    Ok(rule_set) => Self::Rule(rule_set),
+                    Err(e) => return Some(Err(ChatError::Rules(e.to_string()))),
+                }
+            }
+            "!pause" => Self::Pause,
+            "!resume" => Self::Resume,
+            _ => return None,
+        };
+        Some(Ok(command))
+    }
+
+    /// Changing the rules or pausing affects every viewer, so only the allowlist may
+    pub const fn needs_moderator(&self) -> bool {
+        !matches!(self, Self::Spawn { .. })
+    }
+}
+
+/// Who may use which commands how often.
+/// Users on the allowlist may use every command without limits,
+/// everyone else may only spawn patterns, once per `cooldown`.
+#[derive(Debug, Clone)]
+pub struct Moderation {
+    allowlist: HashSet<String>,
+    cooldown: Duration,
+    last_command: HashMap<String, Instant>,
+}
+
+impl Moderation {
+    /// Chat names are compared ignoring case
+    pub fn new<'a>(allowlist: impl IntoIterator<Item = &'a str>, cooldown: Duration) -> Self {
+        Self {
+            allowlist: allowlist.into_iter().map(str::to_lowercase).collect(),
+            cooldown,
+            last_command: HashMap::new(),
+        }
+    }
+
+    /// Whether `user` may run `command` at `now`, which counts towards their rate limit
+    pub fn check(
+        &mut self,
+        user: &str,
+        command: &ChatCommand,
+        now: Instant,
+    ) -> Result<(), ChatError> {
+        let user = user.to_lowercase();
+        if self.allowlist.contains(&user) {
+            return Ok(());
+        }
+        if command.needs_moderator() {
+            return Err(ChatError::NotAllowed);
+        }
+        if let Some(last) = self.last_command.get(&user) {
+            let elapsed = now.saturating_duration_since(*last);
+            if elapsed < self.cooldown {
+                return Err(ChatError::RateLimited(
+                    self.cooldown.saturating_sub(elapsed),
+                ));
+            }
+        }
+        self.last_command.insert(user, now);
+        Ok(())
+    }
+}
+
+/// Connection to an IRC server, e.g. Twitch's at `irc.chat.twitch.tv:6667`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ChatConfig {
+    pub server: String,
+    /// Twitch allows reading anonymously as `justinfan` followed by any number
+    pub nick: String,
+    /// `oauth:` token on Twitch, needed for replies
+    pub password: Option<String>,
+    /// Without the leading `#`
+    pub channel: String,
+}
+
+/// Sender and text of a chat message in an IRC line, Twitch tags are skipped
+pub fn privmsg(line: &str) -> Option<(&str, &str)> {
+    let line = if line.starts_with('@') {
+        line.split_once(' ')?.1
+    } else {
+        line
+    };
+    let (prefix, rest) = line.strip_prefix(':')?.split_once(' ')?;
+    let (_, text) = rest.strip_prefix("PRIVMSG ")?.split_once(" :")?;
+    let nick = prefix.split_once('!').map_or(prefix, |(nick, _)| nick);
+    Some((nick, text))
+}
+
+/// Joins the channel of `config` and applies the commands of its viewers to `session`
+/// until the connection is closed
+pub fn listen_chat(
+    config: &ChatConfig,
+    moderation: &mut Moderation,
+    session: &Mutex<Session>,
+) -> io::Result<()> {
+    let mut stream = TcpStream::connect(&config.server)?;
+    if let Some(password) = &config.password {
+        writeln!(stream, "PASS {password}\r")?;
+    }
+    writeln!(stream, "NICK {}\r", config.nick)?;
+    writeln!(stream, "JOIN #{}\r", config.channel)?;
+    let reader = BufReader::new(stream.try_clone()?);
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim_end();
+        if let Some(server) = line.strip_prefix("PING ") {
+            writeln!(stream, "PONG {server}\r")?;
+            continue;
+        }
+        let Some((user, text)) = privmsg(line) else {
+            continue;
+        };
+        let Some(command) = ChatCommand::parse(text) else {
+            continue;
+        };
+        let result = command.and_then(|command| {
+            moderation.check(user, &command, Instant::now())?;
+            apply(&mut lock(session), command);
+            Ok(())
+        });
+        if let Err(e) = result {
+            writeln!(stream, "PRIVMSG #{} :@{user} {e}\r", config.channel)?;
+        }
+    }
+    Ok(())
+}
+
+fn apply(session: &mut Session, command: ChatCommand) {
+    match command {
+        ChatCommand::Spawn { pattern, row, col } => session.stamp(&pattern, row, col),
+        ChatCommand::Rule(rule_set) => session.automaton.rule_set = rule_set,
+        ChatCommand::Pause => session.paused = true,
+        ChatCommand::Resume => session.paused = false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::{pattern, privmsg, ChatCommand, ChatError, Moderation};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn commands() {
+        assert_eq!(
+            ChatCommand::parse("!spawn Glider 10 20"),
+            Some(Ok(ChatCommand::Spawn {
+                pattern: pattern("glider").unwrap(),
+                row: 10,
+                col: 20,
+            }))
+        );
+        assert!(matches!(
+            ChatCommand::parse("!rule B36/S23"),
+            Some(Ok(ChatCommand::Rule(_)))
+        ));
+        assert_eq!(ChatCommand::parse("hello !spawn"), None);
+        assert!(matches!(
+            ChatCommand::parse("!spawn glider 10"),
+            Some(Err(ChatError::Usage(_)))
+        ));
+        assert!(matches!(
+            ChatCommand::parse("!spawn ship 1 2"),
+            Some(Err(ChatError::UnknownPattern(_)))
+        ));
+        assert!(matches!(
+            ChatCommand::parse("!rule B3"),
+            Some(Err(ChatError::Rules(_)))
+        ));
+
+        assert_eq!(
+            privmsg("@badges=;color= :alice!alice@alice.tmi.twitch.tv PRIVMSG #life :!pause"),
+            Some(("alice", "!pause"))
+        );
+        assert_eq!(privmsg(":tmi.twitch.tv 001 justinfan1 :Welcome"), None);
+
+        let mut moderation = Moderation::new(["Mod"], Duration::from_secs(10));
+        let spawn = ChatCommand::parse("!spawn blinker 1 1").unwrap().unwrap();
+        let now = Instant::now();
+        assert_eq!(moderation.check("bob", &spawn, now), Ok(()));
+        assert_eq!(
+            moderation.check("Bob", &spawn, now + Duration::from_secs(4)),
+            Err(ChatError::RateLimited(Duration::from_secs(6)))
+        );
+        assert_eq!(
+            moderation.check("bob", &spawn, now + Duration::from_secs(10)),
+            Ok(())
+        );
+        assert_eq!(
+            moderation.check("bob", &ChatCommand::Pause, now),
+            Err(ChatError::NotAllowed)
+        );
+        assert_eq!(moderation.check("mod", &ChatCommand::Pause, now), Ok(()));
+        assert_eq!(moderation.check("mod", &spawn, now), Ok(()));
+    }
+}
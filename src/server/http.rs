@@ -0,0 +1,270 @@
+use crate::{
+    grid,
+    server::{lock, Session, MAX_STEP},
+    Cell, Grid, RuleSet, Theme,
+};
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use image::ImageOutputFormat;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, Cursor},
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+type Shared = State<Arc<Mutex<Session>>>;
+
+/// Largest `scale` of snapshots, keeping their size reasonable
+const MAX_SCALE: u32 = 32;
+
+/// Generation and grid, Cells as their `Cell::state_number`
+#[derive(Debug, Serialize)]
+struct GridState {
+    generation: u64,
+    population: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+/// New grid, Cells as their `Cell::state_number`
+#[derive(Debug, Deserialize)]
+struct NewGrid {
+    cells: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StepQuery {
+    generations: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnapshotQuery {
+    /// Pixels per Cell in both directions
+    scale: Option<u32>,
+    /// Name of a built in theme
+    theme: Option<String>,
+}
+
+/// Routes of the HTTP API:
+/// - `GET /grid` => Generation, population and Cells as JSON
+/// - `PUT /grid` => Replaces the grid with `{"cells": [[0, 1], [1, 0]]}`
+/// - `GET /rules` => Rules in B/S notation
+/// - `PUT /rules` => Switches to the rules in B/S notation of the body
+/// - `POST /step?generations=N` => Steps N generations, 1 by default and at most `MAX_STEP`,
+///   and returns the grid
+/// - `GET /snapshot.png?scale=N&theme=Ocean` => The grid as a PNG with N x N pixels per Cell
+/// - `GET /metrics` => `Metrics` for Prometheus
+pub fn router(session: &Arc<Mutex<Session>>) -> Router {
+    Router::new()
+        .route("/grid", get(get_grid).put(put_grid))
+        .route("/rules", get(get_rules).put(put_rules))
+        .route("/step", post(step))
+        .route("/snapshot.png", get(snapshot))
+        .route("/metrics", get(metrics))
+        .with_state(Arc::clone(session))
+}
+
+/// Serves the HTTP API on `address` until the process is stopped, see `router`
+pub fn listen_http(address: SocketAddr, session: &Arc<Mutex<Session>>) -> io::Result<()> {
+    let app = router(session);
+    tokio::runtime::Runtime::new()?.block_on(async {
+        axum::Server::try_bind(&address)
+            .map_err(|e| io::Error::new(io::ErrorKind::AddrInUse, e))?
+            .serve(app.into_make_service())
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    })
+}
+
+fn grid_state(session: &Session) -> GridState {
+    let automaton = &session.automaton;
+    GridState {
+        generation: automaton.generation.0,
+        population: automaton.population(),
+        cells: automaton
+            .grid
+            .iter()
+            .map(|row| row.iter().map(Cell::state_number).collect())
+            .collect(),
+    }
+}
+
+async fn get_grid(State(session): Shared) -> Json<GridState> {
+    Json(grid_state(&lock(&session)))
+}
+
+async fn put_grid(
+    State(session): Shared,
+    Json(new): Json<NewGrid>,
+) -> Result<Json<GridState>, (StatusCode, &'static str)> {
+    let col_count = new.cells.first().map_or(0, Vec::len);
+    if col_count == 0 || new.cells.iter().any(|row| row.len() != col_count) {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "The grid has to be a non-empty rectangle",
+        ));
+    }
+    let grid: Grid = new
+        .cells
+        .into_iter()
+        .map(|row| row.into_iter().map(Cell::from_state_number).collect())
+        .collect();
+    let mut session = lock(&session);
+    session.set_grid(grid);
+    Ok(Json(grid_state(&session)))
+}
+
+async fn get_rules(State(session): Shared) -> String {
+    lock(&session).automaton.rule_set.to_string()
+}
+
+async fn put_rules(
+    State(session): Shared,
+    notation: String,
+) -> Result<String, (StatusCode, String)> {
+    let rule_set = notation
+        .parse::<RuleSet>()
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?;
+    let mut session = lock(&session);
+    session.automaton.rule_set = rule_set;
+    Ok(session.automaton.rule_set.to_string())
+}
+
+async fn step(
+    State(session): Shared,
+    Query(query): Query<StepQuery>,
+) -> Result<Json<GridState>, (StatusCode, String)> {
+    let generations = query.generations.unwrap_or(1);
+    if generations > MAX_STEP {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("At most {MAX_STEP} generations can be stepped at once"),
+        ));
+    }
+    // Stepping blocks, the runtime keeps serving other requests meanwhile
+    tokio::task::spawn_blocking(move || {
+        let mut session = lock(&session);
+        session.step(generations);
+        grid_state(&session)
+    })
+    .await
+    .map(Json)
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+async fn snapshot(State(session): Shared, Query(query): Query<SnapshotQuery>) -> impl IntoResponse {
+    let theme = query
+        .theme
+        .as_deref()
+        .and_then(Theme::named)
+        .unwrap_or_default();
+    let image = {
+        let session = lock(&session);
+        let automaton = &session.automaton;
+        grid::to_image(
+            &automaton.grid,
+            &theme,
+            automaton.rule_set.decay,
+            query.scale.unwrap_or(1).min(MAX_SCALE),
+        )
+    };
+    let mut png = Vec::new();
+    match image.write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png) {
+        Ok(()) => Ok(([(header::CONTENT_TYPE, "image/png")], png)),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+    }
+}
+
+async fn metrics(State(session): Shared) -> impl IntoResponse {
+    let session = lock(&session);
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        session.metrics.render(&session.automaton),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        server::{router, Session, MAX_STEP},
+        Automaton, Cell,
+    };
+    use std::{
+        io::{Read, Write},
+        net::{SocketAddr, TcpStream},
+        sync::{Arc, Mutex},
+    };
+
+    /// Status code of a request with `body` to the server at `address`
+    fn send(address: SocketAddr, method: &str, path: &str, body: &str) -> u16 {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+            .split(' ')
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .unwrap()
+    }
+
+    #[test]
+    fn routes() {
+        let mut blinker = vec![vec![Cell::Dead; 5]; 5];
+        blinker[2][1..=3].fill(Cell::Alive);
+        let session = Arc::new(Mutex::new(Session::new(
+            Automaton::builder()
+                .row_count(5)
+                .col_count(5)
+                .grid(blinker)
+                .build(),
+        )));
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = {
+            let _guard = runtime.enter();
+            axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+                .serve(router(&session).into_make_service())
+        };
+        let address = server.local_addr();
+        runtime.spawn(server);
+        let generation = || session.lock().unwrap().automaton.generation.0;
+
+        assert_eq!(send(address, "POST", "/step", ""), 200);
+        assert_eq!(generation(), 1);
+        assert_eq!(send(address, "POST", "/step?generations=3", ""), 200);
+        assert_eq!(generation(), 4);
+        let too_many = format!("/step?generations={}", MAX_STEP + 1);
+        assert_eq!(send(address, "POST", &too_many, ""), 400);
+        assert_eq!(generation(), 4);
+
+        let grid = r#"{"cells": [[0, 1], [1, 0], [1, 1]]}"#;
+        assert_eq!(send(address, "PUT", "/grid", grid), 200);
+        assert_eq!(
+            session.lock().unwrap().automaton.grid[2],
+            vec![Cell::Alive; 2]
+        );
+        assert_eq!(
+            send(address, "PUT", "/grid", r#"{"cells": [[0, 1], [1]]}"#),
+            422
+        );
+        assert_eq!(session.lock().unwrap().automaton.row_count, 3);
+
+        assert_eq!(send(address, "PUT", "/rules", "B36/S23"), 200);
+        assert_eq!(
+            session.lock().unwrap().automaton.rule_set.to_string(),
+            "B36/S23"
+        );
+        assert_eq!(send(address, "PUT", "/rules", "Not rules"), 422);
+    }
+}
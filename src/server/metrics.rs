@@ -0,0 +1,143 @@
+use crate::{Automaton, Cell};
+use std::{
+    fmt::{self, Write},
+    fs, mem,
+    time::Duration,
+};
+
+/// Upper bounds in seconds of the step duration histogram buckets
+const STEP_BUCKETS: [f64; 10] = [
+    0.000_01, 0.000_1, 0.000_5, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0,
+];
+
+/// Statistics of a `Session` for monitoring, rendered in the Prometheus text format
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Metrics {
+    births: u64,
+    deaths: u64,
+    /// Steps per bucket of `STEP_BUCKETS`, the last one counts slower steps
+    step_buckets: [u64; STEP_BUCKETS.len() + 1],
+    step_seconds: f64,
+    steps: u64,
+}
+
+impl Metrics {
+    /// Records a generation `automaton` was just stepped to, which took `duration`
+    pub fn observe_step(&mut self, automaton: &Automaton, duration: Duration) {
+        self.births += automaton.births as u64;
+        self.deaths += automaton.deaths as u64;
+        let seconds = duration.as_secs_f64();
+        let bucket = STEP_BUCKETS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(STEP_BUCKETS.len());
+        self.step_buckets[bucket] += 1;
+        self.step_seconds += seconds;
+        self.steps += 1;
+    }
+
+    /// Metrics together with the current state of `automaton`.
+    /// Births and deaths are counters, Grafana shows them per second with `rate()`.
+    pub fn render(&self, automaton: &Automaton) -> String {
+        let mut text = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: &dyn fmt::Display| {
+            let _ = writeln!(text, "# HELP {name} {help}");
+            let _ = writeln!(text, "# TYPE {name} {kind}");
+            let _ = writeln!(text, "{name} {value}");
+        };
+        metric(
+            "automaton_generation",
+            "counter",
+            "Generations stepped so far",
+            &automaton.generation,
+        );
+        metric(
+            "automaton_population",
+            "gauge",
+            "Alive Cells",
+            &automaton.population(),
+        );
+        metric(
+            "automaton_births_total",
+            "counter",
+            "Cells that came alive",
+            &self.births,
+        );
+        metric(
+            "automaton_deaths_total",
+            "counter",
+            "Cells that stopped being alive",
+            &self.deaths,
+        );
+        let cells = automaton.grid.iter().map(Vec::len).sum::<usize>();
+        metric(
+            "automaton_grid_bytes",
+            "gauge",
+            "Memory taken by the Cells of the grid",
+            &(cells * mem::size_of::<Cell>()),
+        );
+        if let Some(bytes) = resident_memory() {
+            metric(
+                "process_resident_memory_bytes",
+                "gauge",
+                "Resident memory of the process",
+                &bytes,
+            );
+        }
+
+        let name = "automaton_step_duration_seconds";
+        let _ = writeln!(text, "# HELP {name} Time taken by a single step");
+        let _ = writeln!(text, "# TYPE {name} histogram");
+        let mut cumulative = 0;
+        for (bound, count) in STEP_BUCKETS.iter().zip(self.step_buckets) {
+            cumulative += count;
+            let _ = writeln!(text, "{name}_bucket{{le=\"{bound}\"}} {cumulative}");
+        }
+        let _ = writeln!(text, "{name}_bucket{{le=\"+Inf\"}} {}", self.steps);
+        let _ = writeln!(text, "{name}_sum {}", self.step_seconds);
+        let _ = writeln!(text, "{name}_count {}", self.steps);
+        text
+    }
+}
+
+/// Resident set size of this process, only known on Linux
+fn resident_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{server::Metrics, Automaton};
+    use std::time::Duration;
+
+    #[test]
+    fn exposition() {
+        let mut automaton = Automaton::default();
+        let mut metrics = Metrics::default();
+        automaton.step();
+        metrics.observe_step(&automaton, Duration::from_micros(200));
+        automaton.step();
+        metrics.observe_step(&automaton, Duration::from_secs(2));
+
+        let text = metrics.render(&automaton);
+        assert!(text.contains("automaton_generation 2\n"));
+        assert!(text.contains(&format!(
+            "automaton_population {}\n",
+            automaton.population()
+        )));
+        assert!(text.contains("automaton_step_duration_seconds_bucket{le=\"0.0001\"} 0\n"));
+        assert!(text.contains("automaton_step_duration_seconds_bucket{le=\"0.0005\"} 1\n"));
+        assert!(text.contains("automaton_step_duration_seconds_bucket{le=\"1\"} 1\n"));
+        assert!(text.contains("automaton_step_duration_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("automaton_step_duration_seconds_count 2\n"));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    remote::Request,
+    server::{lock, Session},
+};
+use std::{
+    io,
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use tungstenite::{HandshakeError, Message};
+
+/// How long a client's thread waits for requests before forwarding new frames
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Accepts WebSocket clients on `address`, each one on its own thread.
+/// Clients receive binary `remote::Frame`s and send binary `remote::Request`s.
+pub fn listen(address: impl ToSocketAddrs, session: &Arc<Mutex<Session>>) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let session = Arc::clone(session);
+        thread::spawn(move || {
+            let peer = stream.peer_addr();
+            if let Err(e) = serve_client(stream, &session) {
+                eprintln!("Client {peer:?} disconnected: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn serve_client(stream: TcpStream, session: &Mutex<Session>) -> tungstenite::Result<()> {
+    let mut socket = tungstenite::accept(stream).map_err(|e| match e {
+        HandshakeError::Failure(e) => e,
+        // Only happens for non-blocking streams, the timeout is set afterwards
+        HandshakeError::Interrupted(_) => io::Error::from(io::ErrorKind::WouldBlock).into(),
+    })?;
+    // Reading only blocks briefly, so frames are forwarded while the client is silent
+    socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+    let frames = lock(session).subscribe();
+    loop {
+        for frame in frames.try_iter() {
+            socket.write_message(Message::Binary(frame))?;
+        }
+        match socket.read_message() {
+            Ok(Message::Binary(bytes)) => match Request::decode(&bytes) {
+                Ok(request) => lock(session).handle(request),
+                Err(e) => eprintln!("Ignoring invalid request: {e}"),
+            },
+            Ok(Message::Close(_)) | Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e)) if is_timeout(&e) => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn is_timeout(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
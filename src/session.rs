@@ -0,0 +1,170 @@
+//! Everything needed to pick up an app session where it was left, saved as gzipped JSON
+
+use crate::{
+    annotation::Annotation,
+    history::{History, Snapshot},
+    Automaton, Neighborhood, RuleSet,
+};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::{
+    error, fmt,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// Errors that can occur while saving or loading a session
+/// - `Io` => The file couldn't be read or written
+/// - `Json` => The file isn't a session
+#[derive(Debug)]
+pub enum SessionError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't access session: {e}"),
+            Self::Json(e) => write!(f, "Invalid session: {e}"),
+        }
+    }
+}
+
+impl error::Error for SessionError {}
+
+impl From<io::Error> for SessionError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for SessionError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+/// How the simulation was shown and run, up to the frontend to interpret
+#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct View {
+    /// Position of the center of the screen
+    pub camera: [f32; 2],
+    pub zoom: f32,
+    /// Name of the `Theme`
+    pub theme: String,
+    /// Generations per second, `None` if unlimited
+    pub speed: Option<f32>,
+    /// Generations per rendered step
+    pub stride: usize,
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// The current generation
+    pub snapshot: Snapshot,
+    pub rule_set: RuleSet,
+    pub neighborhood: Neighborhood,
+    /// Past generations for rewinding
+    pub history: History,
+    pub view: View,
+    /// Labels on the grid, missing from sessions saved before they existed
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+impl Session {
+    pub fn new(automaton: &Automaton, history: History, view: View) -> Self {
+        Self {
+            snapshot: Snapshot::new(automaton),
+            rule_set: automaton.rule_set.clone(),
+            neighborhood: automaton.neighborhood_type,
+            history,
+            view,
+            annotations: Vec::new(),
+        }
+    }
+
+    /// Puts `automaton` into the saved generation with the saved rules
+    pub fn restore(&self, automaton: &mut Automaton) {
+        self.snapshot.restore(automaton);
+        automaton.rule_set = self.rule_set.clone();
+        automaton.neighborhood_type = self.neighborhood;
+    }
+
+    /// Writes to a temporary file next to `path` first,
+    /// so a crash while saving doesn't destroy the previous session
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let path = path.as_ref();
+        let temporary = path.with_extension("tmp");
+        let mut encoder = GzEncoder::new(
+            BufWriter::new(File::create(&temporary)?),
+            Compression::default(),
+        );
+        serde_json::to_writer(&mut encoder, self)?;
+        encoder.finish()?.flush()?;
+        fs::rename(temporary, path)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SessionError> {
+        let decoder = GzDecoder::new(BufReader::new(File::open(path)?));
+        Ok(serde_json::from_reader(decoder)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        annotation::Annotation,
+        history::History,
+        rle,
+        session::{Session, View},
+        Automaton, Cell, Generation, Neighborhood, RuleSet,
+    };
+
+    #[test]
+    fn round_trip() {
+        let mut automaton = Automaton::builder()
+            .row_count(16)
+            .col_count(16)
+            .grid(vec![vec![Cell::Dead; 16]; 16])
+            .rule_set(RuleSet::life_like(vec![3, 6], vec![2, 3], 0))
+            .neighborhood_type(Neighborhood::Moore)
+            .build();
+        automaton.stamp(&rle::parse("bo$2bo$3o!").unwrap(), 1, 1);
+        let mut history = History::new(8);
+        for _ in 0..10 {
+            history.record(&automaton);
+            automaton.step();
+        }
+        let view = View {
+            camera: [12.0, -4.5],
+            zoom: 0.5,
+            theme: "Classic".into(),
+            speed: None,
+            stride: 4,
+            paused: true,
+        };
+        let path = std::env::temp_dir().join("cellular_automata_session.json.gz");
+        let mut session = Session::new(&automaton, history, view.clone());
+        session.annotations = vec![Annotation::new(2, 3, "Glider")];
+        session.save(&path).unwrap();
+
+        let session = Session::load(&path).unwrap();
+        let mut restored = Automaton::default();
+        session.restore(&mut restored);
+        assert_eq!(restored.grid, automaton.grid);
+        assert_eq!(restored.generation, automaton.generation);
+        assert_eq!(restored.rule_set, automaton.rule_set);
+        assert_eq!(session.view, view);
+        assert_eq!(session.annotations, [Annotation::new(2, 3, "Glider")]);
+        let mut history = session.history;
+        assert_eq!(history.len(), 8);
+        assert!(history.rewind(&mut restored));
+        assert_eq!(restored.generation, Generation(9));
+        std::fs::remove_file(path).unwrap();
+    }
+}
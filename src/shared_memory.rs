@@ -0,0 +1,220 @@
+//! Live grid in a memory mapped file, e.g. in `/dev/shm`, for external visualizers and analysis
+//! tools in any language. Readers never block the simulation, a seqlock tells them to retry
+//! if they read while a new generation was being written.
+//!
+//! Layout, integers in native byte order:
+//! - `0..8` => Magic bytes `CAGRID\0\x01`
+//! - `8..16` => Sequence as u64, odd while a generation is being written
+//! - `16..24` => Generation as u64
+//! - `24..28` => Rows as u32
+//! - `28..32` => Columns as u32
+//! - `64..` => Rows * columns `Cell::state_byte`s, row by row
+//!
+//! Reading a consistent generation: load the sequence and retry while it's odd,
+//! copy the generation and the Cells, then retry if the sequence has changed in the meantime.
+
+use crate::{Cell, Generation, Grid};
+use memmap2::{Mmap, MmapMut};
+use std::{
+    error, fmt,
+    fs::{File, OpenOptions},
+    hint, io,
+    path::Path,
+    ptr,
+    sync::atomic::{self, AtomicU64, Ordering},
+};
+
+const MAGIC: [u8; 8] = *b"CAGRID\0\x01";
+const SEQUENCE_OFFSET: usize = 8;
+const GENERATION_OFFSET: usize = 16;
+const ROWS_OFFSET: usize = 24;
+const COLS_OFFSET: usize = 28;
+const HEADER_LEN: usize = 64;
+
+/// Errors that can occur while mapping a shared grid
+/// - `Io` => The file couldn't be created, opened or mapped
+/// - `Format` => The file isn't a shared grid
+#[derive(Debug)]
+pub enum SharedMemoryError {
+    Io(io::Error),
+    Format,
+}
+
+impl fmt::Display for SharedMemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't map the shared grid: {e}"),
+            Self::Format => write!(f, "Not a shared grid"),
+        }
+    }
+}
+
+impl error::Error for SharedMemoryError {}
+
+impl From<io::Error> for SharedMemoryError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// The sequence of the seqlock within a mapping
+///
+/// # Safety
+/// `base` has to point to a mapping of at least `HEADER_LEN` bytes that outlives the result,
+/// mappings are page aligned so the sequence is aligned as well
+#[allow(clippy::cast_ptr_alignment)]
+const unsafe fn sequence<'a>(base: *const u8) -> &'a AtomicU64 {
+    &*base.add(SEQUENCE_OFFSET).cast::<AtomicU64>()
+}
+
+/// Writing end, owned by the simulation
+pub struct SharedGrid {
+    mmap: MmapMut,
+    rows: usize,
+    cols: usize,
+}
+
+impl SharedGrid {
+    /// Creates or truncates the file at `path` for a grid of `rows` x `cols` Cells
+    pub fn create(
+        path: impl AsRef<Path>,
+        rows: usize,
+        cols: usize,
+    ) -> Result<Self, SharedMemoryError> {
+        let (Ok(row_count), Ok(col_count)) = (u32::try_from(rows), u32::try_from(cols)) else {
+            return Err(SharedMemoryError::Format);
+        };
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len((HEADER_LEN + rows * cols) as u64)?;
+        // Safety: the file was just sized, other processes only ever read it
+        let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+        mmap[..MAGIC.len()].copy_from_slice(&MAGIC);
+        mmap[ROWS_OFFSET..COLS_OFFSET].copy_from_slice(&row_count.to_ne_bytes());
+        mmap[COLS_OFFSET..COLS_OFFSET + 4].copy_from_slice(&col_count.to_ne_bytes());
+        Ok(Self { mmap, rows, cols })
+    }
+
+    /// Publishes `grid`, parts outside of the dimensions the file was created with are clipped
+    pub fn publish(&mut self, generation: Generation, grid: &[Vec<Cell>]) {
+        let base = self.mmap.as_mut_ptr();
+        // Safety: the mapping is at least `HEADER_LEN` bytes long and lives as long as `self`
+        let sequence = unsafe { sequence(base) };
+        let start = sequence.load(Ordering::Relaxed);
+        sequence.store(start.wrapping_add(1), Ordering::Relaxed);
+        atomic::fence(Ordering::Release);
+        // Safety: every offset lies within the header or the `rows * cols` Cells after it
+        unsafe {
+            ptr::write_unaligned(base.add(GENERATION_OFFSET).cast::<u64>(), generation.0);
+            for (row, cells) in grid.iter().take(self.rows).enumerate() {
+                for (col, cell) in cells.iter().take(self.cols).enumerate() {
+                    base.add(HEADER_LEN + row * self.cols + col)
+                        .write_volatile(cell.state_byte());
+                }
+            }
+        }
+        sequence.store(start.wrapping_add(2), Ordering::Release);
+    }
+}
+
+/// Reading end, e.g. in a visualizer. Tools in other languages follow the protocol of the module.
+pub struct SharedGridReader {
+    mmap: Mmap,
+    rows: usize,
+    cols: usize,
+}
+
+impl SharedGridReader {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SharedMemoryError> {
+        let file = File::open(path)?;
+        // Safety: the writer never shrinks the file while it's mapped
+        let mmap = unsafe { Mmap::map(&file)? };
+        let header = mmap.get(..HEADER_LEN).ok_or(SharedMemoryError::Format)?;
+        if header[..MAGIC.len()] != MAGIC {
+            return Err(SharedMemoryError::Format);
+        }
+        let dimension = |offset: usize| {
+            header[offset..offset + 4]
+                .try_into()
+                .map(|bytes| u32::from_ne_bytes(bytes) as usize)
+                .map_err(|_| SharedMemoryError::Format)
+        };
+        let (rows, cols) = (dimension(ROWS_OFFSET)?, dimension(COLS_OFFSET)?);
+        if mmap.len() < HEADER_LEN + rows * cols {
+            return Err(SharedMemoryError::Format);
+        }
+        Ok(Self { mmap, rows, cols })
+    }
+
+    /// The latest generation, waits while the writer is in the middle of publishing one
+    pub fn read(&self) -> (Generation, Grid) {
+        let base = self.mmap.as_ptr();
+        // Safety: `open` checked the length of the mapping, which lives as long as `self`
+        let sequence = unsafe { sequence(base) };
+        let mut cells = vec![0; self.rows * self.cols];
+        loop {
+            let before = sequence.load(Ordering::Acquire);
+            if before % 2 == 1 {
+                hint::spin_loop();
+                continue;
+            }
+            // Safety: `open` checked that the Cells lie within the mapping.
+            // Torn reads are possible and discarded by comparing the sequence afterwards.
+            let generation = unsafe {
+                for (index, cell) in cells.iter_mut().enumerate() {
+                    *cell = base.add(HEADER_LEN + index).read_volatile();
+                }
+                ptr::read_unaligned(base.add(GENERATION_OFFSET).cast::<u64>())
+            };
+            atomic::fence(Ordering::Acquire);
+            if sequence.load(Ordering::Relaxed) == before {
+                let grid = cells
+                    .chunks(self.cols.max(1))
+                    .take(self.rows)
+                    .map(|row| {
+                        row.iter()
+                            .map(|&state| Cell::from_state_number(state.into()))
+                            .collect()
+                    })
+                    .collect();
+                return (Generation(generation), grid);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        shared_memory::{SharedGrid, SharedGridReader, SharedMemoryError},
+        Automaton,
+    };
+    use std::fs;
+
+    #[test]
+    fn publishes_generations() {
+        let path = std::env::temp_dir().join("cellular_automata_shared_grid");
+        let mut automaton = Automaton::default();
+        let mut shared =
+            SharedGrid::create(&path, automaton.row_count, automaton.col_count).unwrap();
+        let reader = SharedGridReader::open(&path).unwrap();
+        for _ in 0..3 {
+            shared.publish(automaton.generation, &automaton.grid);
+            assert_eq!(
+                reader.read(),
+                (automaton.generation, automaton.grid.clone())
+            );
+            automaton.step();
+        }
+
+        fs::write(&path, b"not a grid").unwrap();
+        assert!(matches!(
+            SharedGridReader::open(&path),
+            Err(SharedMemoryError::Format)
+        ));
+    }
+}
@@ -0,0 +1,224 @@
+//! Makes automata audible: births per column become notes of a scale, population sets their
+//! volume and the tempo.
+//!
+//! The notes go to a MIDI port, see `MidiPlayer`, or are synthesized into a WAV file, see
+//! `WavWriter`. The same synthesis makes the sound effects of the Bevy app.
+
+mod audio;
+#[cfg(feature = "midi")]
+mod midi;
+
+pub use audio::{decay, synthesize, wav_bytes, WavWriter};
+#[cfg(feature = "midi")]
+pub use midi::{MidiError, MidiPlayer};
+
+use crate::{Automaton, Grid};
+use clap::ValueEnum;
+use itertools::izip;
+use std::time::Duration;
+
+/// Scales notes are picked from
+/// - `Major` => Ionian
+/// - `Minor` => Natural minor
+/// - `Pentatonic` => Major pentatonic, never sounds dissonant
+/// - `Blues` => Minor pentatonic with the blue note
+/// - `Chromatic` => All twelve semitones
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum Scale {
+    Major,
+    Minor,
+    #[default]
+    Pentatonic,
+    Blues,
+    Chromatic,
+}
+
+impl Scale {
+    /// Semitones above the root within one octave
+    pub const fn intervals(self) -> &'static [u8] {
+        match self {
+            Self::Major => &[0, 2, 4, 5, 7, 9, 11],
+            Self::Minor => &[0, 2, 3, 5, 7, 8, 10],
+            Self::Pentatonic => &[0, 2, 4, 7, 9],
+            Self::Blues => &[0, 3, 5, 6, 7, 10],
+            Self::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+
+    /// MIDI key of the `degree`th note above `root`, spanning octaves
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn key(self, root: u8, degree: usize) -> u8 {
+        let intervals = self.intervals();
+        let octave = (degree / intervals.len()).min(10) as u8;
+        root.saturating_add(octave * 12)
+            .saturating_add(intervals[degree % intervals.len()])
+            .min(127)
+    }
+}
+
+/// How a generation is turned into notes
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SonificationConfig {
+    pub scale: Scale,
+    /// MIDI key of the leftmost column, 48 is C3
+    pub root: u8,
+    /// Octaves the columns are spread over
+    pub octaves: u8,
+    /// Most notes sounding at once, the ones with the most births win
+    pub max_notes: usize,
+    /// Beat length of an empty grid
+    pub slowest_beat: Duration,
+    /// Beat length once half of the grid is alive
+    pub fastest_beat: Duration,
+}
+
+impl Default for SonificationConfig {
+    fn default() -> Self {
+        Self {
+            scale: Scale::default(),
+            root: 48,
+            octaves: 3,
+            max_notes: 6,
+            slowest_beat: Duration::from_millis(600),
+            fastest_beat: Duration::from_millis(150),
+        }
+    }
+}
+
+/// A note to play for one beat
+/// - `key` => MIDI key, 60 is middle C
+/// - `velocity` => MIDI velocity from 1 to 127
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Note {
+    pub key: u8,
+    pub velocity: u8,
+}
+
+impl Note {
+    /// Frequency in Hz in equal temperament, A4 being 440 Hz
+    pub fn frequency(self) -> f32 {
+        440.0 * ((f32::from(self.key) - 69.0) / 12.0).exp2()
+    }
+}
+
+/// Remembers the previous generation to find the births of the next one
+#[derive(Debug, Clone)]
+pub struct Sonifier {
+    pub config: SonificationConfig,
+    previous: Grid,
+}
+
+impl Sonifier {
+    pub const fn new(config: SonificationConfig) -> Self {
+        Self {
+            config,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Notes of the generation `automaton` is at, silent for the first one observed
+    /// or after the dimensions of the grid changed
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn observe(&mut self, automaton: &Automaton) -> Vec<Note> {
+        let config = &self.config;
+        let degrees = config.scale.intervals().len() * usize::from(config.octaves.max(1));
+        let mut births = vec![0_usize; degrees];
+        if self.previous.len() == automaton.grid.len() {
+            for (before, after) in izip!(&self.previous, &automaton.grid) {
+                let cols = after.len().max(1);
+                for (col, (before, after)) in izip!(before, after).enumerate() {
+                    if after.is_alive() && !before.is_alive() {
+                        births[col * degrees / cols] += 1;
+                    }
+                }
+            }
+        }
+        self.previous.clone_from(&automaton.grid);
+
+        let velocity = 126.0_f64.mul_add((2.0 * density(automaton)).min(1.0), 1.0) as u8;
+        let mut degrees = births
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .collect::<Vec<_>>();
+        degrees.sort_by_key(|(_, &count)| std::cmp::Reverse(count));
+        let mut notes = degrees
+            .into_iter()
+            .take(config.max_notes)
+            .map(|(degree, _)| Note {
+                key: config.scale.key(config.root, degree),
+                velocity,
+            })
+            .collect::<Vec<_>>();
+        notes.sort_by_key(|note| note.key);
+        notes.dedup_by_key(|note| note.key);
+        notes
+    }
+
+    /// Beat length of the generation `automaton` is at, busier grids play faster
+    pub fn beat(&self, automaton: &Automaton) -> Duration {
+        let busy = (2.0 * density(automaton)).min(1.0);
+        let (slowest, fastest) = (self.config.slowest_beat, self.config.fastest_beat);
+        slowest.saturating_sub(slowest.saturating_sub(fastest).mul_f64(busy))
+    }
+}
+
+/// Alive Cells per Cell of the grid
+#[allow(clippy::cast_precision_loss)]
+fn density(automaton: &Automaton) -> f64 {
+    let cells = automaton.grid.iter().map(Vec::len).sum::<usize>();
+    if cells == 0 {
+        0.0
+    } else {
+        automaton.population() as f64 / cells as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        sonification::{Scale, SonificationConfig, Sonifier},
+        Automaton, Cell,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn births_become_notes() {
+        assert_eq!(Scale::Major.key(60, 0), 60);
+        assert_eq!(Scale::Major.key(60, 7), 72);
+        assert_eq!(Scale::Pentatonic.key(60, 3), 67);
+        assert_eq!(Scale::Chromatic.key(120, 30), 127);
+
+        // Vertical blinker in column 4, turns horizontal with births in columns 3 and 5
+        let mut grid = vec![vec![Cell::Dead; 10]; 10];
+        for row in &mut grid[3..6] {
+            row[4] = Cell::Alive;
+        }
+        let mut automaton = Automaton::builder()
+            .row_count(10)
+            .col_count(10)
+            .grid(grid)
+            .build();
+        let config = SonificationConfig {
+            scale: Scale::Chromatic,
+            root: 60,
+            octaves: 1,
+            ..SonificationConfig::default()
+        };
+        let mut sonifier = Sonifier::new(config);
+        assert!(sonifier.observe(&automaton).is_empty());
+        automaton.step();
+        let notes = sonifier.observe(&automaton);
+        let keys = notes.iter().map(|note| note.key).collect::<Vec<_>>();
+        assert_eq!(keys, [60 + 3 * 12 / 10, 60 + 5 * 12 / 10]);
+        assert!(notes.iter().all(|note| note.velocity == notes[0].velocity));
+
+        assert_eq!(sonifier.beat(&automaton), Duration::from_millis(573));
+        automaton.grid = vec![vec![Cell::Alive; 10]; 10];
+        assert_eq!(sonifier.beat(&automaton), Duration::from_millis(150));
+    }
+}
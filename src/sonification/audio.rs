@@ -0,0 +1,154 @@
+use crate::sonification::Note;
+use std::{
+    f32::consts::TAU,
+    fs::File,
+    io::{self, BufWriter, Seek, SeekFrom, Write},
+    path::Path,
+    time::Duration,
+};
+
+/// Fade in and out of every note, avoids clicks between beats
+const FADE_SECONDS: f32 = 0.005;
+
+/// Mono 16 bit samples of `notes` played as sine waves for `beat`
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn synthesize(notes: &[Note], beat: Duration, sample_rate: u32) -> Vec<i16> {
+    let len = (beat.as_secs_f32() * sample_rate as f32) as usize;
+    let fade = (FADE_SECONDS * sample_rate as f32).max(1.0);
+    let voices = notes.len().max(1) as f32;
+    (0..len)
+        .map(|index| {
+            let time = index as f32 / sample_rate as f32;
+            let envelope = (index as f32 / fade)
+                .min((len - index) as f32 / fade)
+                .min(1.0);
+            let sample = notes
+                .iter()
+                .map(|note| {
+                    f32::from(note.velocity) / 127.0 * (TAU * note.frequency() * time).sin()
+                })
+                .sum::<f32>()
+                / voices;
+            (sample * envelope * f32::from(i16::MAX)) as i16
+        })
+        .collect()
+}
+
+/// Fades `samples` out exponentially, halving their amplitude every `half_life`,
+/// turning a held note into a plucked or struck one
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub fn decay(samples: &mut [i16], half_life: Duration, sample_rate: u32) {
+    let half_life = (half_life.as_secs_f32() * sample_rate as f32).max(1.0);
+    for (index, sample) in samples.iter_mut().enumerate() {
+        *sample = (f32::from(*sample) * (-(index as f32) / half_life).exp2()) as i16;
+    }
+}
+
+/// `samples` as a whole mono 16 bit PCM WAV file, e.g. for audio players taking bytes
+pub fn wav_bytes(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    #[allow(clippy::cast_possible_truncation)]
+    let data_bytes = (samples.len() * 2) as u32;
+    let mut bytes = header(sample_rate, data_bytes);
+    bytes.extend(samples.iter().flat_map(|sample| sample.to_le_bytes()));
+    bytes
+}
+
+/// RIFF header of a mono 16 bit PCM WAV file with `data_bytes` of samples
+fn header(sample_rate: u32, data_bytes: u32) -> Vec<u8> {
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(36 + data_bytes).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16_u32.to_le_bytes());
+    // PCM, mono
+    header.extend_from_slice(&1_u16.to_le_bytes());
+    header.extend_from_slice(&1_u16.to_le_bytes());
+    header.extend_from_slice(&sample_rate.to_le_bytes());
+    header.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    // Bytes per frame, bits per sample
+    header.extend_from_slice(&2_u16.to_le_bytes());
+    header.extend_from_slice(&16_u16.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_bytes.to_le_bytes());
+    header
+}
+
+/// Mono 16 bit PCM WAV file that's valid after every `write`,
+/// so an endless simulation can be stopped at any time
+pub struct WavWriter {
+    file: BufWriter<File>,
+    sample_rate: u32,
+    data_bytes: u32,
+}
+
+impl WavWriter {
+    pub fn create(path: impl AsRef<Path>, sample_rate: u32) -> io::Result<Self> {
+        let mut wav = Self {
+            file: BufWriter::new(File::create(path)?),
+            sample_rate,
+            data_bytes: 0,
+        };
+        wav.write_header()?;
+        Ok(wav)
+    }
+
+    pub fn write(&mut self, samples: &[i16]) -> io::Result<()> {
+        self.file.seek(SeekFrom::End(0))?;
+        for sample in samples {
+            self.file.write_all(&sample.to_le_bytes())?;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let bytes = (samples.len() * 2) as u32;
+        self.data_bytes = self.data_bytes.saturating_add(bytes);
+        self.write_header()
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file
+            .write_all(&header(self.sample_rate, self.data_bytes))?;
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sonification::{decay, synthesize, wav_bytes, Note, WavWriter};
+    use std::{fs, time::Duration};
+
+    #[test]
+    fn wav() {
+        let notes = [Note {
+            key: 69,
+            velocity: 127,
+        }];
+        let samples = synthesize(&notes, Duration::from_millis(100), 8000);
+        assert_eq!(samples.len(), 800);
+        assert_eq!(samples[0], 0);
+        assert!(samples.iter().any(|&sample| sample > 30_000));
+        assert!(synthesize(&[], Duration::from_millis(100), 8000)
+            .iter()
+            .all(|&sample| sample == 0));
+
+        let path = std::env::temp_dir().join("cellular_automata_sonification.wav");
+        let mut wav = WavWriter::create(&path, 8000).unwrap();
+        wav.write(&samples).unwrap();
+        wav.write(&samples).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        assert_eq!(bytes.len(), 44 + 3200);
+        assert_eq!(&bytes[..4], b"RIFF");
+        assert_eq!(bytes[40..44], 3200_u32.to_le_bytes());
+        let twice = [samples.as_slice(), samples.as_slice()].concat();
+        assert_eq!(wav_bytes(&twice, 8000), bytes);
+
+        let mut decayed = vec![i16::MAX; 200];
+        decay(&mut decayed, Duration::from_millis(10), 8000);
+        assert_eq!(decayed[0], i16::MAX);
+        assert_eq!(decayed[80], i16::MAX / 2);
+        assert!(decayed[160] < i16::MAX / 4 + 1);
+    }
+}
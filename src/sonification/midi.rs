@@ -0,0 +1,90 @@
+use crate::sonification::Note;
+use midir::{MidiOutput, MidiOutputConnection};
+use std::{error, fmt};
+
+const NOTE_ON: u8 = 0x90;
+const NOTE_OFF: u8 = 0x80;
+
+/// Errors that can occur while talking to a MIDI port
+/// - `Init` => The MIDI backend of the system isn't available
+/// - `NoPort` => There's no output port, or none with the requested name
+/// - `Connect` => The port refused the connection
+/// - `Send` => A message couldn't be sent
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MidiError {
+    Init(String),
+    NoPort(Option<String>),
+    Connect(String),
+    Send(String),
+}
+
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Init(e) => write!(f, "MIDI isn't available: {e}"),
+            Self::NoPort(None) => write!(f, "There's no MIDI output port"),
+            Self::NoPort(Some(name)) => write!(f, "There's no MIDI output port named {name:?}"),
+            Self::Connect(e) => write!(f, "Couldn't connect to the MIDI port: {e}"),
+            Self::Send(e) => write!(f, "Couldn't send MIDI: {e}"),
+        }
+    }
+}
+
+impl error::Error for MidiError {}
+
+/// Plays the notes of each beat on a MIDI output port, e.g. a software synthesizer
+pub struct MidiPlayer {
+    connection: MidiOutputConnection,
+    channel: u8,
+    /// Keys that are on since the previous beat
+    sounding: Vec<u8>,
+}
+
+impl MidiPlayer {
+    /// Connects to the first output port whose name contains `port`, or the first one at all
+    pub fn connect(port: Option<&str>, channel: u8) -> Result<Self, MidiError> {
+        let output =
+            MidiOutput::new("cellular_automata").map_err(|e| MidiError::Init(e.to_string()))?;
+        let found = output.ports().into_iter().find(|candidate| {
+            port.is_none()
+                || output
+                    .port_name(candidate)
+                    .is_ok_and(|name| port.is_some_and(|port| name.contains(port)))
+        });
+        let Some(found) = found else {
+            return Err(MidiError::NoPort(port.map(String::from)));
+        };
+        let connection = output
+            .connect(&found, "cellular_automata")
+            .map_err(|e| MidiError::Connect(e.to_string()))?;
+        Ok(Self {
+            connection,
+            channel: channel.min(15),
+            sounding: Vec::new(),
+        })
+    }
+
+    /// Stops the notes of the previous beat and starts `notes`
+    pub fn play(&mut self, notes: &[Note]) -> Result<(), MidiError> {
+        for key in std::mem::take(&mut self.sounding) {
+            self.send(&[NOTE_OFF | self.channel, key, 0])?;
+        }
+        for note in notes {
+            self.send(&[NOTE_ON | self.channel, note.key, note.velocity])?;
+            self.sounding.push(note.key);
+        }
+        Ok(())
+    }
+
+    fn send(&mut self, message: &[u8]) -> Result<(), MidiError> {
+        self.connection
+            .send(message)
+            .map_err(|e| MidiError::Send(e.to_string()))
+    }
+}
+
+impl Drop for MidiPlayer {
+    fn drop(&mut self) {
+        let _ = self.play(&[]);
+    }
+}
@@ -0,0 +1,134 @@
+//! Duplicate state detection on top of `Automaton::state_hash`, for loop detection,
+//! caching and deduplication of generations
+
+use crate::{analysis::Period, Automaton, Generation};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Hashes of the states of the last `capacity` recorded generations, each mapped to the latest
+/// generation it occurred at.
+///
+/// Memory stays bounded by `capacity` however long the run is. `H` is `u64` for
+/// `Automaton::state_hash` or `u128` for `Automaton::state_hash128`.
+#[derive(Debug, Clone)]
+pub struct StateHistory<H = u64> {
+    latest: HashMap<H, Generation>,
+    /// The recorded generations, the newest at the back
+    window: VecDeque<(H, Generation)>,
+    capacity: usize,
+}
+
+impl<H: Hash + Eq + Copy> StateHistory<H> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            latest: HashMap::new(),
+            window: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Records that the state hashed to `hash` occurred at `generation`, dropping the oldest
+    /// recorded generation if it's full. Returns the generation the state occurred at before
+    /// if it was among the recorded ones.
+    pub fn insert(&mut self, hash: H, generation: Generation) -> Option<Generation> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let previous = self.get(&hash);
+        if self.window.len() == self.capacity {
+            if let Some((oldest, oldest_generation)) = self.window.pop_front() {
+                // Only forget the hash if it didn't occur again since
+                if self.latest.get(&oldest) == Some(&oldest_generation) {
+                    self.latest.remove(&oldest);
+                }
+            }
+        }
+        self.window.push_back((hash, generation));
+        self.latest.insert(hash, generation);
+        previous
+    }
+
+    /// The latest recorded generation with the state hashed to `hash`
+    pub fn get(&self, hash: &H) -> Option<Generation> {
+        self.latest.get(hash).copied()
+    }
+
+    pub fn contains(&self, hash: &H) -> bool {
+        self.latest.contains_key(hash)
+    }
+
+    /// Recorded generations, at most `capacity`
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.latest.clear();
+        self.window.clear();
+    }
+}
+
+impl StateHistory {
+    /// Records the current generation of `automaton`, the `Period` since its state last
+    /// occurred if it was among the recorded ones
+    pub fn observe(&mut self, automaton: &Automaton) -> Option<Period> {
+        self.insert(automaton.state_hash(), automaton.generation)
+            .map(|start| Period {
+                start,
+                period: automaton.generation - start,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{analysis::Period, rle, state_history::StateHistory, Automaton, Cell, Generation};
+
+    #[test]
+    fn repetitions_within_capacity() {
+        let mut automaton = Automaton::builder()
+            .row_count(5)
+            .col_count(5)
+            .grid(vec![vec![Cell::Dead; 5]; 5])
+            .build();
+        automaton.stamp(&rle::parse("3o!").unwrap(), 2, 1);
+        let mut history = StateHistory::new(2);
+        assert_eq!(history.observe(&automaton), None);
+        automaton.step();
+        assert_eq!(history.observe(&automaton), None);
+        automaton.step();
+        assert_eq!(
+            history.observe(&automaton),
+            Some(Period {
+                start: Generation(0),
+                period: 2
+            })
+        );
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(&automaton.state_hash()), Some(Generation(2)));
+        assert_ne!(automaton.state_hash128(), 0);
+
+        // Evicted states are forgotten, unless they occurred again since
+        let mut history = StateHistory::<u128>::new(2);
+        assert_eq!(history.insert(1, Generation(0)), None);
+        assert_eq!(history.insert(2, Generation(1)), None);
+        assert_eq!(history.insert(3, Generation(2)), None);
+        assert!(!history.contains(&1));
+        assert_eq!(history.insert(3, Generation(3)), Some(Generation(2)));
+        assert_eq!(history.insert(4, Generation(4)), None);
+        assert_eq!(history.get(&3), Some(Generation(3)));
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(StateHistory::<u64>::new(0).insert(1, Generation(0)), None);
+    }
+}
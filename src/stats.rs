@@ -0,0 +1,234 @@
+use crate::{
+    grid::{self, BoundingBox},
+    metrics::Metrics,
+    table::{Table, Values},
+    Automaton, Generation,
+};
+use std::{collections::VecDeque, io};
+
+/// Glyphs of a sparkline, from lowest to highest
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Statistics of a single generation
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Sample {
+    pub generation: Generation,
+    pub population: usize,
+    pub births: usize,
+    pub deaths: usize,
+}
+
+impl From<&Automaton> for Sample {
+    fn from(value: &Automaton) -> Self {
+        Self {
+            generation: value.generation,
+            population: value.population(),
+            births: value.births,
+            deaths: value.deaths,
+        }
+    }
+}
+
+/// Ring buffer keeping the `Sample`s of the most recent generations
+#[derive(Debug, Clone)]
+pub struct Stats {
+    samples: VecDeque<Sample>,
+    capacity: usize,
+}
+
+impl Stats {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Records the current generation, evicting the oldest `Sample` once full
+    pub fn record(&mut self, automaton: &Automaton) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(automaton.into());
+    }
+
+    /// Recorded `Sample`s from oldest to newest
+    pub fn samples(&self) -> impl DoubleEndedIterator<Item = &Sample> + ExactSizeIterator {
+        self.samples.iter()
+    }
+
+    pub fn latest(&self) -> Option<&Sample> {
+        self.samples.back()
+    }
+}
+
+/// Statistics of a single generation for exporting, see `TimeSeries`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Record {
+    pub sample: Sample,
+    pub metrics: Metrics,
+    /// `None` once every Cell is dead
+    pub bounding_box: Option<BoundingBox>,
+}
+
+impl From<&Automaton> for Record {
+    fn from(value: &Automaton) -> Self {
+        Self {
+            sample: value.into(),
+            metrics: value.metrics(),
+            bounding_box: grid::bounding_box(&value.grid),
+        }
+    }
+}
+
+/// `Record`s of every `stride`-th generation of a run, for plotting in pandas or R
+#[derive(Debug, Clone)]
+pub struct TimeSeries {
+    stride: u64,
+    records: Vec<Record>,
+    /// Records already written by `TimeSeries::write_csv`
+    written: usize,
+}
+
+impl TimeSeries {
+    /// A `stride` of `0` is treated as `1`
+    pub fn new(stride: u64) -> Self {
+        Self {
+            stride: stride.max(1),
+            records: Vec::new(),
+            written: 0,
+        }
+    }
+
+    /// Records the current generation if it's a multiple of the stride
+    pub fn record(&mut self, automaton: &Automaton) {
+        if automaton.generation.0.is_multiple_of(self.stride) {
+            self.records.push(automaton.into());
+        }
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Columns `generation`, `population`, `births`, `deaths`, the `Metrics` and the inclusive `top`, `left`, `bottom` and `right` of the bounding box, `-1` without alive Cells
+    pub fn table(&self) -> Table {
+        table(&self.records)
+    }
+
+    /// Appends the records that weren't written yet as CSV, starting with the header,
+    /// so a file stays up to date during a run that is never stopped gracefully
+    pub fn write_csv(&mut self, mut writer: impl io::Write) -> io::Result<()> {
+        let pending = &self.records[self.written..];
+        if pending.is_empty() {
+            return Ok(());
+        }
+        table(pending).write_csv_rows(&mut writer, self.written == 0)?;
+        writer.flush()?;
+        self.written = self.records.len();
+        Ok(())
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn table(records: &[Record]) -> Table {
+    let int = |value: fn(&Record) -> u64| {
+        Values::Int(records.iter().map(|record| value(record) as i64).collect())
+    };
+    let float = |value: fn(&Record) -> f64| Values::Float(records.iter().map(value).collect());
+    let bound = |side: fn(&BoundingBox) -> usize| {
+        Values::Int(
+            records
+                .iter()
+                .map(|record| {
+                    record
+                        .bounding_box
+                        .as_ref()
+                        .map_or(-1, |bounds| side(bounds) as i64)
+                })
+                .collect(),
+        )
+    };
+    Table::default()
+        .column("generation", int(|record| record.sample.generation.0))
+        .column("population", int(|record| record.sample.population as u64))
+        .column("births", int(|record| record.sample.births as u64))
+        .column("deaths", int(|record| record.sample.deaths as u64))
+        .column("entropy", float(|record| record.metrics.entropy))
+        .column(
+            "block_entropy",
+            float(|record| record.metrics.block_entropy),
+        )
+        .column(
+            "compression_ratio",
+            float(|record| record.metrics.compression_ratio),
+        )
+        .column("activity", float(|record| record.metrics.activity))
+        .column("top", bound(|bounds| bounds.top))
+        .column("left", bound(|bounds| bounds.left))
+        .column("bottom", bound(|bounds| bounds.bottom))
+        .column("right", bound(|bounds| bounds.right))
+}
+
+/// Renders `values` as a row of block glyphs scaled to the largest value
+pub fn sparkline(values: impl IntoIterator<Item = usize>) -> String {
+    let values = values.into_iter().collect::<Vec<_>>();
+    let max = values.iter().copied().max().unwrap_or_default().max(1);
+    values
+        .into_iter()
+        .map(|value| SPARKS[value * (SPARKS.len() - 1) / max])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        stats::{self, TimeSeries},
+        Automaton, Generation, Stats,
+    };
+
+    #[test]
+    fn ring_buffer() {
+        let mut automaton = Automaton::default();
+        let mut stats = Stats::with_capacity(3);
+        for _ in 0..5 {
+            stats.record(&automaton);
+            automaton.step();
+        }
+        let generations = stats.samples().map(|sample| sample.generation);
+        assert_eq!(
+            generations.collect::<Vec<_>>(),
+            vec![Generation(2), Generation(3), Generation(4)]
+        );
+    }
+
+    #[test]
+    fn time_series() {
+        let mut automaton = Automaton::default();
+        let mut series = TimeSeries::new(2);
+        let mut csv = Vec::new();
+        for _ in 0..5 {
+            series.record(&automaton);
+            series.write_csv(&mut csv).unwrap();
+            automaton.step();
+        }
+        assert_eq!(series.records().len(), 3);
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "generation,population,births,deaths,entropy,block_entropy,\
+                 compression_ratio,activity,top,left,bottom,right"
+            )
+        );
+        assert_eq!(lines.count(), 3);
+        assert_eq!(series.table().row_count(), 3);
+    }
+
+    #[test]
+    fn sparkline() {
+        assert_eq!(stats::sparkline([0, 4, 8]), "▁▄█");
+        assert_eq!(stats::sparkline([0, 0]), "▁▁");
+    }
+}
@@ -0,0 +1,265 @@
+//! Conditions ending headless runs on their own, `no_bevy_2d run --headless --stop-on ..`
+
+use crate::{
+    analysis::Period, state_history::StateHistory, zobrist::Zobrist, Automaton, Generation,
+};
+use std::{error, fmt, str::FromStr};
+
+/// When to stop a run, written like `period<=3`
+/// - `Extinction` => `extinction`, no alive Cells are left
+/// - `Stable` => `stable`, the grid didn't change during the last step
+/// - `Period` => `period<=K`, the grid repeats with a period of at most K generations
+/// - `Generation` => `generation=N`, generation N was reached
+/// - `Population` => `population>X`, more than X Cells are alive
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StopCondition {
+    Extinction,
+    Stable,
+    Period(u64),
+    Generation(u64),
+    Population(usize),
+}
+
+impl StopCondition {
+    /// Exit status of a run stopped by this condition, distinct for every kind of condition.
+    /// `1` and `2` are left for errors and unfinished analyses.
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::Generation(_) => 0,
+            Self::Extinction => 3,
+            Self::Stable => 4,
+            Self::Period(_) => 5,
+            Self::Population(_) => 6,
+        }
+    }
+
+    /// Generations of grids that have to be remembered to detect this condition
+    const fn history(&self) -> u64 {
+        match self {
+            Self::Stable => 1,
+            Self::Period(max_period) => *max_period,
+            Self::Extinction | Self::Generation(_) | Self::Population(_) => 0,
+        }
+    }
+}
+
+impl fmt::Display for StopCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Extinction => write!(f, "extinction"),
+            Self::Stable => write!(f, "stable"),
+            Self::Period(max_period) => write!(f, "period<={max_period}"),
+            Self::Generation(generation) => write!(f, "generation={generation}"),
+            Self::Population(population) => write!(f, "population>{population}"),
+        }
+    }
+}
+
+/// Errors that can occur while parsing a `StopCondition`
+/// - `Unknown` => Not one of the conditions
+/// - `Number` => The number after the comparison is invalid
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StopConditionError {
+    Unknown(String),
+    Number(String),
+}
+
+impl fmt::Display for StopConditionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(condition) => write!(
+                f,
+                "Unknown stop condition {condition:?}, expected extinction, stable, \
+                 period<=K, generation=N or population>X"
+            ),
+            Self::Number(number) => write!(f, "Invalid number {number:?}"),
+        }
+    }
+}
+
+impl error::Error for StopConditionError {}
+
+/// Parses the notation written by `Display`, ignoring case and whitespace
+impl FromStr for StopCondition {
+    type Err = StopConditionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let condition = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let number = |number: &str| {
+            number
+                .parse()
+                .map_err(|_| StopConditionError::Number(number.to_owned()))
+        };
+        if let Some(max_period) = condition.strip_prefix("period<=") {
+            return match number(max_period)? {
+                0 => Err(StopConditionError::Number(max_period.to_owned())),
+                max_period => Ok(Self::Period(max_period)),
+            };
+        }
+        if let Some(generation) = condition.strip_prefix("generation=") {
+            return Ok(Self::Generation(number(generation)?));
+        }
+        if let Some(population) = condition.strip_prefix("population>") {
+            return Ok(Self::Population(
+                usize::try_from(number(population)?)
+                    .map_err(|_| StopConditionError::Number(population.to_owned()))?,
+            ));
+        }
+        match condition.as_str() {
+            "extinction" => Ok(Self::Extinction),
+            "stable" => Ok(Self::Stable),
+            _ => Err(StopConditionError::Unknown(s.to_owned())),
+        }
+    }
+}
+
+/// A condition that fired
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stopped {
+    pub condition: StopCondition,
+    pub generation: Generation,
+    pub population: usize,
+    /// Repetition of the grid, for `StopCondition::Stable` and `StopCondition::Period`
+    pub period: Option<Period>,
+}
+
+/// Summary of the run, e.g. `Stopped at generation 42 (population 12): period<=3 fired,
+/// period 2 since generation 40`
+impl fmt::Display for Stopped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Stopped at generation {} (population {}): {} fired",
+            self.generation, self.population, self.condition
+        )?;
+        if let Some(period) = self.period {
+            write!(
+                f,
+                ", period {} since generation {}",
+                period.period, period.start
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks a list of `StopCondition`s against every generation of a run.
+///
+/// Repetitions are detected by comparing `zobrist` hashes of the last generations,
+/// so memory stays bounded by the largest `period<=K`. They're updated incrementally
+/// for automata with `Automaton::track_changes` set.
+#[derive(Debug, Clone)]
+pub struct Stopper {
+    conditions: Vec<StopCondition>,
+    history: StateHistory,
+    zobrist: Option<Zobrist>,
+}
+
+impl Stopper {
+    pub fn new(conditions: Vec<StopCondition>) -> Self {
+        let history_len = conditions
+            .iter()
+            .map(StopCondition::history)
+            .max()
+            .unwrap_or_default();
+        Self {
+            conditions,
+            history: StateHistory::new(usize::try_from(history_len).unwrap_or(usize::MAX)),
+            zobrist: None,
+        }
+    }
+
+    /// The first of the conditions that fired for the current generation of `automaton`,
+    /// to be called once per generation
+    pub fn check(&mut self, automaton: &Automaton) -> Option<Stopped> {
+        let period = if self.history.capacity() > 0 {
+            let hash = match &mut self.zobrist {
+                Some(zobrist) => zobrist.update(automaton),
+                zobrist => zobrist.insert(Zobrist::new(automaton)).hash(),
+            };
+            self.history
+                .insert(hash, automaton.generation)
+                .map(|start| Period {
+                    start,
+                    period: automaton.generation - start,
+                })
+        } else {
+            None
+        };
+        let population = automaton.population();
+        let fired = |condition: &StopCondition| match *condition {
+            StopCondition::Extinction => population == 0,
+            StopCondition::Stable => period.is_some_and(|period| period.period == 1),
+            StopCondition::Period(max_period) => {
+                period.is_some_and(|period| period.period <= max_period)
+            }
+            StopCondition::Generation(generation) => automaton.generation.0 >= generation,
+            StopCondition::Population(max_population) => population > max_population,
+        };
+        let condition = *self.conditions.iter().find(|condition| fired(condition))?;
+        Some(Stopped {
+            condition,
+            generation: automaton.generation,
+            population,
+            period: period.filter(|_| condition.history() > 0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rle,
+        stop::{StopCondition, StopConditionError, Stopper},
+        Automaton, Cell, Generation,
+    };
+
+    #[test]
+    fn conditions() {
+        assert_eq!("period <= 3".parse(), Ok(StopCondition::Period(3)));
+        assert_eq!("Population>100".parse(), Ok(StopCondition::Population(100)));
+        assert_eq!(
+            "period<=0".parse::<StopCondition>(),
+            Err(StopConditionError::Number("0".to_owned()))
+        );
+        assert!(matches!(
+            "forever".parse::<StopCondition>(),
+            Err(StopConditionError::Unknown(_))
+        ));
+        for condition in ["extinction", "stable", "period<=2", "generation=7"] {
+            assert_eq!(
+                condition.parse::<StopCondition>().unwrap().to_string(),
+                condition
+            );
+        }
+
+        let mut automaton = Automaton::builder()
+            .row_count(8)
+            .col_count(8)
+            .grid(vec![vec![Cell::Dead; 8]; 8])
+            .build();
+        automaton.stamp(&rle::parse("3o!").unwrap(), 3, 2);
+        let mut stopper = Stopper::new(vec![StopCondition::Stable, StopCondition::Period(2)]);
+        assert_eq!(stopper.check(&automaton), None);
+        automaton.step();
+        assert_eq!(stopper.check(&automaton), None);
+        automaton.step();
+        let fired = stopper.check(&automaton).unwrap();
+        assert_eq!(fired.condition, StopCondition::Period(2));
+        assert_eq!(fired.generation, Generation(2));
+        assert_eq!(fired.period.map(|period| period.period), Some(2));
+
+        let mut stopper = Stopper::new(vec![StopCondition::Extinction]);
+        automaton.grid = vec![vec![Cell::Dead; 8]; 8];
+        assert_eq!(
+            stopper
+                .check(&automaton)
+                .map(|stopped| stopped.condition.exit_code()),
+            Some(3)
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! Tidy tables of numbers for pandas or R, written as CSV or, with the `parquet` feature, Parquet
+
+use std::{fmt, io};
+
+/// Values of a single column, all of the same type
+#[derive(Debug, PartialEq, Clone)]
+pub enum Values {
+    Int(Vec<i64>),
+    Float(Vec<f64>),
+    Text(Vec<String>),
+}
+
+impl Values {
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::Int(values) => values.len(),
+            Self::Float(values) => values.len(),
+            Self::Text(values) => values.len(),
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The value in `row` as written to CSV, quoted if necessary
+    fn csv(&self, row: usize) -> String {
+        match self {
+            Self::Int(values) => values[row].to_string(),
+            Self::Float(values) => values[row].to_string(),
+            Self::Text(values) => csv_field(&values[row]),
+        }
+    }
+}
+
+/// Named columns of the same length
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Table {
+    columns: Vec<(String, Values)>,
+}
+
+impl Table {
+    /// Appends a column, which must have as many values as the ones before
+    #[must_use]
+    pub fn column(mut self, name: impl Into<String>, values: Values) -> Self {
+        assert!(
+            self.columns
+                .first()
+                .is_none_or(|(_, first)| first.len() == values.len()),
+            "Columns of a table must have the same length"
+        );
+        self.columns.push((name.into(), values));
+        self
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.columns.first().map_or(0, |(_, values)| values.len())
+    }
+
+    /// Writes a header line with the column names followed by one line per row
+    pub fn write_csv(&self, writer: impl io::Write) -> io::Result<()> {
+        self.write_csv_rows(writer, true)
+    }
+
+    /// Writes one line per row, preceded by the header line if `header`,
+    /// to append to an existing CSV file with the same columns
+    pub fn write_csv_rows(&self, mut writer: impl io::Write, header: bool) -> io::Result<()> {
+        if header {
+            let names = self
+                .columns
+                .iter()
+                .map(|(name, _)| csv_field(name))
+                .collect::<Vec<_>>();
+            writeln!(writer, "{}", names.join(","))?;
+        }
+        for row in 0..self.row_count() {
+            let fields = self
+                .columns
+                .iter()
+                .map(|(_, values)| values.csv(row))
+                .collect::<Vec<_>>();
+            writeln!(writer, "{}", fields.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single row group, texts are stored as UTF-8 byte arrays
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet(
+        &self,
+        writer: impl io::Write + Send,
+    ) -> Result<(), parquet::errors::ParquetError> {
+        use parquet::{
+            data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type},
+            file::{properties::WriterProperties, writer::SerializedFileWriter},
+            schema::parser::parse_message_type,
+        };
+        use std::sync::Arc;
+
+        let fields = self
+            .columns
+            .iter()
+            .map(|(name, values)| match values {
+                Values::Int(_) => format!("REQUIRED INT64 {name};"),
+                Values::Float(_) => format!("REQUIRED DOUBLE {name};"),
+                Values::Text(_) => format!("REQUIRED BYTE_ARRAY {name} (UTF8);"),
+            })
+            .collect::<String>();
+        let schema = Arc::new(parse_message_type(&format!(
+            "message table {{ {fields} }}"
+        ))?);
+        let properties = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(writer, schema, properties)?;
+        let mut row_group = writer.next_row_group()?;
+        for (_, values) in &self.columns {
+            let Some(mut column) = row_group.next_column()? else {
+                break;
+            };
+            match values {
+                Values::Int(values) => {
+                    column
+                        .typed::<Int64Type>()
+                        .write_batch(values, None, None)?;
+                }
+                Values::Float(values) => {
+                    column
+                        .typed::<DoubleType>()
+                        .write_batch(values, None, None)?;
+                }
+                Values::Text(values) => {
+                    let values = values
+                        .iter()
+                        .map(|value| ByteArray::from(value.as_str()))
+                        .collect::<Vec<_>>();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, None, None)?;
+                }
+            }
+            column.close()?;
+        }
+        row_group.close()?;
+        writer.close()?;
+        Ok(())
+    }
+}
+
+/// Output formats of a `Table`
+/// - `Csv` => Comma separated values with a header line, `.csv`
+/// - `Parquet` => Apache Parquet, `.parquet`, needs the `parquet` feature
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TableFormat {
+    Csv,
+    Parquet,
+}
+
+impl TableFormat {
+    /// The format of the file at `path` going by its extension, CSV unless it's `.parquet`
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Self {
+        let parquet = path
+            .as_ref()
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("parquet"));
+        if parquet {
+            Self::Parquet
+        } else {
+            Self::Csv
+        }
+    }
+}
+
+impl fmt::Display for TableFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Csv => write!(f, "CSV"),
+            Self::Parquet => write!(f, "Parquet"),
+        }
+    }
+}
+
+/// Quotes `field` if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::table::{Table, TableFormat, Values};
+
+    #[test]
+    fn csv() {
+        let table = Table::default()
+            .column(
+                "rules",
+                Values::Text(vec!["B3/S23".into(), "a,\"b\"".into()]),
+            )
+            .column("density", Values::Float(vec![0.25, 0.5]))
+            .column("population", Values::Int(vec![12, -1]));
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "rules,density,population\nB3/S23,0.25,12\n\"a,\"\"b\"\"\",0.5,-1\n"
+        );
+        assert_eq!(TableFormat::from_path("runs.PARQUET"), TableFormat::Parquet);
+        assert_eq!(TableFormat::from_path("runs.tsv"), TableFormat::Csv);
+    }
+}
@@ -0,0 +1,347 @@
+use crate::Cell;
+use serde::Deserialize;
+use std::{error, fmt, fs, io, path::Path};
+
+/// Color as red, green and blue channels
+pub type Rgb = [u8; 3];
+
+/// Errors that can occur while loading a `Theme` from a file
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file couldn't be read
+    Io(io::Error),
+    /// The file isn't a valid TOML theme
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't read theme: {e}"),
+            Self::Toml(e) => write!(f, "Invalid theme: {e}"),
+        }
+    }
+}
+
+impl error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<toml::de::Error> for LoadError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Toml(value)
+    }
+}
+
+/// Texture of a Cell, so its state can be told apart without seeing its color
+/// - `Solid` => Every pixel is painted in the color of the state
+/// - `Hatched` => Diagonal stripes of the color over the dead color
+/// - `Dotted` => A dot of the color in every other pixel of every other row
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Fill {
+    #[default]
+    Solid,
+    Hatched,
+    Dotted,
+}
+
+impl Fill {
+    /// Whether the pixel at (`x`, `y`) within a Cell takes the color of its state.
+    /// The top left pixel always does, so Cells rendered as a single pixel keep their color.
+    pub const fn covers(self, x: u32, y: u32) -> bool {
+        match self {
+            Self::Solid => true,
+            Self::Hatched => (x + y).is_multiple_of(3),
+            Self::Dotted => x.is_multiple_of(2) && y.is_multiple_of(2),
+        }
+    }
+}
+
+/// Colors used to render the grid, loadable from TOML:
+///
+/// ```toml
+/// name = "Sepia"
+/// dead = [40, 30, 20]
+/// alive = [240, 220, 180]
+/// dying = [160, 110, 60]
+/// background = [20, 15, 10]
+/// grid_line = [60, 45, 30]
+/// trail = [120, 90, 50]
+/// age_gradient = [[160, 110, 60], [70, 50, 30]]
+/// pattern_fills = true
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    pub dead: Rgb,
+    pub alive: Rgb,
+    /// Color of `Cell::Dying` if there's no `age_gradient`
+    pub dying: Rgb,
+    /// Everything around the grid
+    pub background: Rgb,
+    pub grid_line: Rgb,
+    /// Tint of recently dead Cells
+    pub trail: Rgb,
+    /// Colors a `Cell::Dying` passes through from its death until it turns `Cell::Dead`
+    #[serde(default)]
+    pub age_gradient: Vec<Rgb>,
+    /// Whether dying Cells are hatched and then dotted as they age, see `Theme::fill`
+    #[serde(default)]
+    pub pattern_fills: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Theme {
+    pub fn classic() -> Self {
+        Self {
+            name: "Classic".into(),
+            dead: [0, 0, 0],
+            alive: [235, 235, 235],
+            dying: [140, 90, 50],
+            background: [38, 38, 38],
+            grid_line: [38, 38, 38],
+            trail: [60, 110, 255],
+            age_gradient: vec![[140, 90, 50], [60, 35, 20]],
+            pattern_fills: false,
+        }
+    }
+
+    /// Pure colors that stay distinguishable on any display
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "High Contrast".into(),
+            dead: [0, 0, 0],
+            alive: [255, 255, 255],
+            dying: [255, 255, 0],
+            background: [0, 0, 0],
+            grid_line: [128, 128, 128],
+            trail: [0, 255, 255],
+            age_gradient: vec![[255, 255, 0], [255, 0, 0]],
+            pattern_fills: true,
+        }
+    }
+
+    pub fn ocean() -> Self {
+        Self {
+            name: "Ocean".into(),
+            dead: [8, 24, 48],
+            alive: [120, 220, 255],
+            dying: [40, 110, 160],
+            background: [4, 12, 24],
+            grid_line: [20, 40, 70],
+            trail: [30, 70, 120],
+            age_gradient: vec![[40, 110, 160], [15, 45, 80]],
+            pattern_fills: false,
+        }
+    }
+
+    /// Blue and orange from the Okabe-Ito palette, which red-green color blindness
+    /// with missing green cones doesn't confuse
+    pub fn deuteranopia() -> Self {
+        Self {
+            name: "Deuteranopia".into(),
+            dead: [0, 0, 0],
+            alive: [86, 180, 233],
+            dying: [230, 159, 0],
+            background: [24, 24, 24],
+            grid_line: [56, 56, 56],
+            trail: [0, 114, 178],
+            age_gradient: vec![[230, 159, 0], [115, 80, 0]],
+            pattern_fills: true,
+        }
+    }
+
+    /// Yellow and blue from the Okabe-Ito palette, avoiding the reds that look dark
+    /// without red cones
+    pub fn protanopia() -> Self {
+        Self {
+            name: "Protanopia".into(),
+            dead: [0, 0, 0],
+            alive: [240, 228, 66],
+            dying: [0, 114, 178],
+            background: [24, 24, 24],
+            grid_line: [56, 56, 56],
+            trail: [86, 180, 233],
+            age_gradient: vec![[0, 114, 178], [0, 57, 89]],
+            pattern_fills: true,
+        }
+    }
+
+    pub fn built_in() -> Vec<Self> {
+        vec![
+            Self::classic(),
+            Self::high_contrast(),
+            Self::ocean(),
+            Self::deuteranopia(),
+            Self::protanopia(),
+        ]
+    }
+
+    /// Built in theme with the given name, ignoring case
+    pub fn named(name: &str) -> Option<Self> {
+        Self::built_in()
+            .into_iter()
+            .find(|theme| theme.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        Ok(Self::from_toml(&fs::read_to_string(path)?)?)
+    }
+
+    /// Color of `cell`, a `Cell::Dying` is placed on the `age_gradient`
+    /// according to how many of the `decay` generations it has been dying
+    #[allow(clippy::cast_precision_loss)]
+    pub fn cell_color(&self, cell: &Cell, decay: usize) -> Rgb {
+        match cell {
+            Cell::Dead => self.dead,
+            Cell::Alive => self.alive,
+            Cell::Dying { ticks_till_death } if !self.age_gradient.is_empty() => {
+                let age = decay.saturating_sub(*ticks_till_death);
+                gradient(
+                    &self.age_gradient,
+                    age as f32 / decay.saturating_sub(1).max(1) as f32,
+                )
+            }
+            Cell::Dying { .. } => self.dying,
+        }
+    }
+
+    /// Texture of `cell` with `pattern_fills`, dying Cells are hatched during the first
+    /// half of the `decay` generations and dotted during the second
+    pub const fn fill(&self, cell: &Cell, decay: usize) -> Fill {
+        match cell {
+            Cell::Dying { ticks_till_death } if self.pattern_fills => {
+                if *ticks_till_death * 2 > decay {
+                    Fill::Hatched
+                } else {
+                    Fill::Dotted
+                }
+            }
+            _ => Fill::Solid,
+        }
+    }
+
+    /// Color of the pixel at (`x`, `y`) within `cell`, the `fill` shows the color of its
+    /// state over the dead color
+    pub fn pixel_color(&self, cell: &Cell, decay: usize, x: u32, y: u32) -> Rgb {
+        if self.fill(cell, decay).covers(x, y) {
+            self.cell_color(cell, decay)
+        } else {
+            self.dead
+        }
+    }
+}
+
+/// Linear interpolation from `from` (`t = 0.0`) to `to` (`t = 1.0`)
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_lossless
+)]
+pub fn blend(from: Rgb, to: Rgb, t: f32) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    let mut blended = [0; 3];
+    for (channel, (from, to)) in blended.iter_mut().zip(from.into_iter().zip(to)) {
+        *channel = (f32::from(to) - f32::from(from))
+            .mul_add(t, f32::from(from))
+            .round() as u8;
+    }
+    blended
+}
+
+/// Color at `t` between `0.0` and `1.0` along evenly spaced `stops`
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn gradient(stops: &[Rgb], t: f32) -> Rgb {
+    match stops {
+        [] => [0; 3],
+        [only] => *only,
+        _ => {
+            let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+            let segment = (position as usize).min(stops.len() - 2);
+            blend(
+                stops[segment],
+                stops[segment + 1],
+                position - segment as f32,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        theme::{blend, gradient, Fill},
+        Cell, Theme,
+    };
+
+    #[test]
+    fn blending() {
+        assert_eq!(blend([0; 3], [100; 3], 0.0), [0; 3]);
+        assert_eq!(blend([0; 3], [100; 3], 1.0), [100; 3]);
+        assert_eq!(blend([0; 3], [100; 3], 0.5), [50; 3]);
+        assert_eq!(gradient(&[[0; 3], [100; 3], [0; 3]], 0.75), [50; 3]);
+    }
+
+    #[test]
+    fn age_gradient() {
+        let theme = Theme {
+            age_gradient: vec![[200; 3], [100; 3]],
+            ..Theme::classic()
+        };
+        let dying = |ticks_till_death| Cell::Dying { ticks_till_death };
+        assert_eq!(theme.cell_color(&dying(3), 3), [200; 3]);
+        assert_eq!(theme.cell_color(&dying(2), 3), [150; 3]);
+        assert_eq!(theme.cell_color(&dying(1), 3), [100; 3]);
+        assert_eq!(Theme::named("high contrast"), Some(Theme::high_contrast()));
+    }
+
+    #[test]
+    fn pattern_fills() {
+        let theme = Theme::deuteranopia();
+        let dying = |ticks_till_death| Cell::Dying { ticks_till_death };
+        assert_eq!(theme.fill(&Cell::Alive, 4), Fill::Solid);
+        assert_eq!(theme.fill(&dying(4), 4), Fill::Hatched);
+        assert_eq!(theme.fill(&dying(2), 4), Fill::Dotted);
+        assert_eq!(Theme::classic().fill(&dying(4), 4), Fill::Solid);
+        assert_eq!(theme.pixel_color(&dying(4), 4, 0, 0), theme.dying);
+        assert_eq!(theme.pixel_color(&dying(4), 4, 1, 0), theme.dead);
+        assert_eq!(theme.pixel_color(&dying(2), 4, 1, 1), theme.dead);
+        assert_eq!(theme.pixel_color(&Cell::Alive, 4, 1, 1), theme.alive);
+    }
+
+    #[test]
+    fn from_toml() {
+        let theme = Theme::from_toml(
+            r#"
+            name = "Mono"
+            dead = [0, 0, 0]
+            alive = [255, 255, 255]
+            dying = [128, 128, 128]
+            background = [0, 0, 0]
+            grid_line = [32, 32, 32]
+            trail = [64, 64, 64]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(theme.name, "Mono");
+        assert!(theme.age_gradient.is_empty());
+        assert!(Theme::from_toml("name = 1").is_err());
+    }
+}
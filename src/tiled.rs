@@ -0,0 +1,415 @@
+//! Stepping on a fixed pool of threads, each owning some cache-sized tiles of the grid.
+//!
+//! Every tile keeps a ring of one Cell around it, its halo. Before each generation the tiles
+//! send their edges to the owners of the neighboring tiles, which copy them into their halos.
+//! Workers only wait for the halos they need, so they run ahead of each other within a
+//! generation instead of meeting at a barrier. Like `distributed` it only exchanges messages,
+//! which is what NUMA-aware and distributed modes need as well.
+
+use crate::{Automaton, Cell, Generation, Grid, Neighborhood, RuleTable};
+use std::{
+    array, mem,
+    sync::mpsc::{self, Receiver, Sender},
+    thread::{self, JoinHandle},
+};
+
+/// Rows and columns of a tile, 16 KiB of Cells fit into the L1 cache of most CPUs
+pub const DEFAULT_TILE_SIZE: usize = 128;
+
+const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+const VON_NEUMANN: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Message from the pool to a worker
+/// - `Advance` => Steps the tiles of the worker this many generations
+/// - `Gather` => Asks for the Cells of every tile of the worker
+enum Command {
+    Advance(u64),
+    Gather,
+}
+
+/// Message from a worker to the pool
+/// - `Advanced` => The worker finished an `Advance`
+/// - `Tiles` => Index and Cells without halo of every tile of the worker, answering `Gather`
+enum Report {
+    Advanced,
+    Tiles(Vec<(usize, Vec<u8>)>),
+}
+
+/// Edge of a tile, sent to the neighboring tile in generation `generation`
+struct Halo {
+    generation: u64,
+    tile: usize,
+    /// Where the sending tile lies, seen from the receiving one
+    from: (isize, isize),
+    cells: Vec<u8>,
+}
+
+/// Grid split into tiles stepped by a fixed pool of threads, the edges don't wrap around.
+/// The threads are stopped when the pool is dropped.
+pub struct TilePool {
+    row_count: usize,
+    col_count: usize,
+    tile_size: usize,
+    commands: Vec<Sender<Command>>,
+    reports: Receiver<Report>,
+    workers: Vec<JoinHandle<()>>,
+    generation: Generation,
+}
+
+impl TilePool {
+    /// Splits the grid of `automaton` into tiles of `tile_size` x `tile_size` Cells,
+    /// which are dealt round-robin to `threads` threads
+    pub fn new(automaton: &Automaton, tile_size: usize, threads: usize) -> Self {
+        let row_count = automaton.grid.len();
+        let col_count = automaton.grid.first().map_or(0, Vec::len);
+        let tile_size = tile_size.max(1);
+        let (tile_rows, tile_cols) = (row_count.div_ceil(tile_size), col_count.div_ceil(tile_size));
+        let threads = threads.clamp(1, (tile_rows * tile_cols).max(1));
+        let mut owned = (0..threads).map(|_| Vec::new()).collect::<Vec<_>>();
+        for index in 0..tile_rows * tile_cols {
+            let (first_row, first_col) =
+                (index / tile_cols * tile_size, index % tile_cols * tile_size);
+            let rows = tile_size.min(row_count - first_row);
+            let cols = tile_size.min(col_count - first_col);
+            let mut cells = vec![Cell::Dead.state_byte(); (rows + 2) * (cols + 2)];
+            for (row, grid_row) in automaton.grid[first_row..first_row + rows]
+                .iter()
+                .enumerate()
+            {
+                for (col, cell) in grid_row[first_col..first_col + cols].iter().enumerate() {
+                    cells[(row + 1) * (cols + 2) + col + 1] = cell.state_byte();
+                }
+            }
+            owned[index % threads].push(Tile {
+                index,
+                first_row,
+                first_col,
+                rows,
+                cols,
+                next: cells.clone(),
+                cells,
+            });
+        }
+        let (halo_senders, inboxes): (Vec<_>, Vec<_>) =
+            (0..threads).map(|_| mpsc::channel()).unzip();
+        let (report_sender, reports) = mpsc::channel();
+        let mut commands = Vec::with_capacity(threads);
+        let workers = owned
+            .into_iter()
+            .zip(inboxes)
+            .map(|(tiles, inbox)| {
+                let (command_sender, command_receiver) = mpsc::channel();
+                commands.push(command_sender);
+                let mut worker = Worker {
+                    tiles,
+                    row_count,
+                    col_count,
+                    tile_rows,
+                    tile_cols,
+                    neighborhood: automaton.neighborhood_type,
+                    table: RuleTable::new(&automaton.rule_set),
+                    halos: halo_senders.clone(),
+                    inbox,
+                    pending: Vec::new(),
+                    generation: 0,
+                };
+                let reports = report_sender.clone();
+                thread::spawn(move || worker.run(&command_receiver, &reports))
+            })
+            .collect();
+        Self {
+            row_count,
+            col_count,
+            tile_size,
+            commands,
+            reports,
+            workers,
+            generation: automaton.generation,
+        }
+    }
+
+    pub const fn generation(&self) -> Generation {
+        self.generation
+    }
+
+    /// Steps every tile `generations` times, the workers only synchronize through halos meanwhile
+    pub fn advance(&mut self, generations: u64) {
+        for commands in &self.commands {
+            // A worker only stops once the pool is dropped
+            commands.send(Command::Advance(generations)).ok();
+        }
+        for _ in 0..self.commands.len() {
+            self.reports.recv().ok();
+        }
+        self.generation.0 += generations;
+    }
+
+    /// Assembles the current grid out of all tiles
+    pub fn grid(&self) -> Grid {
+        let mut grid = vec![vec![Cell::Dead; self.col_count]; self.row_count];
+        let tile_cols = self.col_count.div_ceil(self.tile_size);
+        for commands in &self.commands {
+            commands.send(Command::Gather).ok();
+        }
+        for _ in 0..self.commands.len() {
+            let Ok(Report::Tiles(tiles)) = self.reports.recv() else {
+                continue;
+            };
+            for (index, cells) in tiles {
+                let first_row = index / tile_cols * self.tile_size;
+                let first_col = index % tile_cols * self.tile_size;
+                let cols = self.tile_size.min(self.col_count - first_col);
+                for (row, tile_row) in cells.chunks(cols).enumerate() {
+                    for (col, &byte) in tile_row.iter().enumerate() {
+                        grid[first_row + row][first_col + col] =
+                            Cell::from_state_number(usize::from(byte));
+                    }
+                }
+            }
+        }
+        grid
+    }
+}
+
+impl Drop for TilePool {
+    fn drop(&mut self) {
+        // Workers return once their commands are disconnected
+        self.commands.clear();
+        for worker in self.workers.drain(..) {
+            worker.join().ok();
+        }
+    }
+}
+
+/// Part of the grid with a ring of one halo Cell around it
+struct Tile {
+    index: usize,
+    first_row: usize,
+    first_col: usize,
+    rows: usize,
+    cols: usize,
+    /// `Cell::state_byte`s including the halo, row by row
+    cells: Vec<u8>,
+    next: Vec<u8>,
+}
+
+impl Tile {
+    const fn width(&self) -> usize {
+        self.cols + 2
+    }
+
+    /// Positions within `cells` of the edge facing `direction`, or of the halo if `halo`
+    fn ring(&self, (row_offset, col_offset): (isize, isize), halo: bool) -> Vec<usize> {
+        let span = |offset: isize, len: usize| match (offset, halo) {
+            (-1, false) => 1..=1,
+            (-1, true) => 0..=0,
+            (1, false) => len..=len,
+            (1, true) => len + 1..=len + 1,
+            _ => 1..=len,
+        };
+        let cols = span(col_offset, self.cols);
+        span(row_offset, self.rows)
+            .flat_map(|row| cols.clone().map(move |col| row * self.width() + col))
+            .collect()
+    }
+
+    fn interior(&self) -> Vec<u8> {
+        (1..=self.rows)
+            .flat_map(|row| {
+                let start = row * self.width() + 1;
+                self.cells[start..start + self.cols].iter().copied()
+            })
+            .collect()
+    }
+}
+
+/// State of a thread of the pool
+struct Worker {
+    tiles: Vec<Tile>,
+    row_count: usize,
+    col_count: usize,
+    tile_rows: usize,
+    tile_cols: usize,
+    neighborhood: Neighborhood,
+    table: RuleTable,
+    /// Halo inbox of every worker, tile `index` belongs to worker `index % halos.len()`
+    halos: Vec<Sender<Halo>>,
+    inbox: Receiver<Halo>,
+    /// Halos of later generations from workers that are ahead
+    pending: Vec<Halo>,
+    generation: u64,
+}
+
+impl Worker {
+    fn run(&mut self, commands: &Receiver<Command>, reports: &Sender<Report>) {
+        while let Ok(command) = commands.recv() {
+            let report = match command {
+                Command::Advance(generations) => {
+                    for _ in 0..generations {
+                        self.exchange_halos();
+                        self.step();
+                    }
+                    Report::Advanced
+                }
+                Command::Gather => Report::Tiles(
+                    self.tiles
+                        .iter()
+                        .map(|tile| (tile.index, tile.interior()))
+                        .collect(),
+                ),
+            };
+            if reports.send(report).is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Neighboring tiles of tile `index` and where they lie
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = (usize, (isize, isize))> + '_ {
+        let (tile_row, tile_col) = (index / self.tile_cols, index % self.tile_cols);
+        MOORE
+            .into_iter()
+            .filter_map(move |(row_offset, col_offset)| {
+                let row = tile_row.checked_add_signed(row_offset)?;
+                let col = tile_col.checked_add_signed(col_offset)?;
+                (row < self.tile_rows && col < self.tile_cols)
+                    .then_some((row * self.tile_cols + col, (row_offset, col_offset)))
+            })
+    }
+
+    /// Sends the edges of every tile to its neighbors and fills the halos with theirs
+    fn exchange_halos(&mut self) {
+        let mut expected = 0;
+        for tile in &self.tiles {
+            for (neighbor, direction) in self.neighbors(tile.index) {
+                let cells = tile
+                    .ring(direction, false)
+                    .into_iter()
+                    .map(|position| tile.cells[position])
+                    .collect();
+                let halo = Halo {
+                    generation: self.generation,
+                    tile: neighbor,
+                    from: (-direction.0, -direction.1),
+                    cells,
+                };
+                self.halos[neighbor % self.halos.len()].send(halo).ok();
+                expected += 1;
+            }
+        }
+        let mut received = 0;
+        let mut pending = mem::take(&mut self.pending);
+        while received < expected {
+            let halo = match pending
+                .iter()
+                .position(|halo| halo.generation == self.generation)
+            {
+                Some(position) => pending.swap_remove(position),
+                None => match self.inbox.recv() {
+                    Ok(halo) if halo.generation == self.generation => halo,
+                    Ok(halo) => {
+                        pending.push(halo);
+                        continue;
+                    }
+                    // Never, the worker holds a sender of its own inbox
+                    Err(_) => return,
+                },
+            };
+            let offset = self.tiles.partition_point(|tile| tile.index < halo.tile);
+            let tile = &mut self.tiles[offset];
+            for (position, byte) in tile.ring(halo.from, true).into_iter().zip(halo.cells) {
+                tile.cells[position] = byte;
+            }
+            received += 1;
+        }
+        self.pending = pending;
+    }
+
+    fn step(&mut self) {
+        let offsets: &[(isize, isize)] = match self.neighborhood {
+            Neighborhood::Moore => &MOORE,
+            Neighborhood::VonNeumann => &VON_NEUMANN,
+        };
+        let mut neighbors: [Cell; 8] = array::from_fn(|_| Cell::Dead);
+        for tile in &mut self.tiles {
+            let width = tile.width();
+            for row in 1..=tile.rows {
+                for col in 1..=tile.cols {
+                    let mut count = 0;
+                    for &(row_offset, col_offset) in offsets {
+                        let grid_row = (tile.first_row + row - 1).checked_add_signed(row_offset);
+                        let grid_col = (tile.first_col + col - 1).checked_add_signed(col_offset);
+                        // Halo Cells outside of the grid aren't neighbors
+                        if grid_row.is_some_and(|grid_row| grid_row < self.row_count)
+                            && grid_col.is_some_and(|grid_col| grid_col < self.col_count)
+                        {
+                            let position = row.wrapping_add_signed(row_offset) * width
+                                + col.wrapping_add_signed(col_offset);
+                            neighbors[count] =
+                                Cell::from_state_number(usize::from(tile.cells[position]));
+                            count += 1;
+                        }
+                    }
+                    let current =
+                        Cell::from_state_number(usize::from(tile.cells[row * width + col]));
+                    tile.next[row * width + col] = self
+                        .table
+                        .next_state(&current, &neighbors[..count])
+                        .state_byte();
+                }
+            }
+            mem::swap(&mut tile.cells, &mut tile.next);
+        }
+        self.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{seeding::soup, tiled::TilePool, Automaton, Counting, Neighborhood, RuleSet};
+
+    #[test]
+    fn matches_automaton() {
+        let brians_brain = RuleSet {
+            counting: Counting::AliveAndDying,
+            ..RuleSet::life_like(vec![2], vec![], 1)
+        };
+        for (rule_set, neighborhood) in [
+            (RuleSet::default(), Neighborhood::Moore),
+            (brians_brain, Neighborhood::Moore),
+            (
+                RuleSet::life_like(vec![1], vec![1, 2], 0),
+                Neighborhood::VonNeumann,
+            ),
+        ] {
+            let mut automaton = Automaton::builder()
+                .row_count(30)
+                .col_count(41)
+                .grid(soup(7, 30, 41, 0.4))
+                .rule_set(rule_set)
+                .neighborhood_type(neighborhood)
+                .build();
+            // Tiles of different sizes at the right and bottom edges, more tiles than threads
+            let mut pool = TilePool::new(&automaton, 8, 3);
+            for _ in 0..3 {
+                pool.advance(10);
+                automaton.advance(10);
+                assert_eq!(pool.grid(), automaton.grid);
+            }
+            assert_eq!(pool.generation(), automaton.generation);
+        }
+        let empty = Automaton::builder().grid(Vec::new()).build();
+        let mut pool = TilePool::new(&empty, 8, 4);
+        pool.advance(1);
+        assert!(pool.grid().is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+//! Recording every K-th generation of a running simulation as numbered PNG frames
+//! or as an animated GIF
+
+use crate::{grid, Automaton, Cell, Generation, Theme};
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, DynamicImage, Frame, ImageResult,
+};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+/// Which generations a timelapse captures: the first one it sees, then the first one at or
+/// after each multiple of `every`.
+///
+/// Generations skipped by stepping several at once, like with a stride, can't be captured, so
+/// the one after them is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cadence {
+    every: u64,
+    last: Option<Generation>,
+}
+
+impl Cadence {
+    pub fn new(every: u64) -> Self {
+        Self {
+            every: every.max(1),
+            last: None,
+        }
+    }
+
+    /// Whether `generation` is captured, marking it as the last capture if it is
+    pub fn tick(&mut self, generation: Generation) -> bool {
+        let due = self
+            .last
+            .is_none_or(|last| generation.0 / self.every > last.0 / self.every);
+        if due {
+            self.last = Some(generation);
+        }
+        due
+    }
+}
+
+/// Where the frames of a `Timelapse` go
+/// - `Frames` => PNG files in a directory, named after their generation
+/// - `Gif` => An animated GIF looping forever
+enum Sink {
+    Frames(PathBuf),
+    Gif(Box<GifEncoder<BufWriter<File>>>),
+}
+
+/// Renders captured generations with `scale` x `scale` pixels per Cell, see `grid::to_image`
+pub struct Timelapse {
+    sink: Sink,
+    cadence: Cadence,
+    theme: Theme,
+    scale: u32,
+    /// Time each frame of a GIF is shown
+    delay: Delay,
+    frame_count: usize,
+}
+
+impl Timelapse {
+    /// Records into an animated GIF if `path` ends in `.gif`,
+    /// otherwise into numbered PNG files in the directory `path`, which is created if needed
+    pub fn create(
+        path: impl AsRef<Path>,
+        every: u64,
+        theme: Theme,
+        scale: u32,
+        delay_ms: u32,
+    ) -> ImageResult<Self> {
+        let path = path.as_ref();
+        let is_gif = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+        let sink = if is_gif {
+            let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+            encoder.set_repeat(Repeat::Infinite)?;
+            Sink::Gif(Box::new(encoder))
+        } else {
+            fs::create_dir_all(path)?;
+            Sink::Frames(path.to_path_buf())
+        };
+        Ok(Self {
+            sink,
+            cadence: Cadence::new(every),
+            theme,
+            scale,
+            delay: Delay::from_numer_denom_ms(delay_ms, 1),
+            frame_count: 0,
+        })
+    }
+
+    /// Writes the grid of `automaton` as the next frame if its generation is due,
+    /// whether it was
+    pub fn capture(&mut self, automaton: &Automaton) -> ImageResult<bool> {
+        if !self.cadence.tick(automaton.generation) {
+            return Ok(false);
+        }
+        self.write_frame(
+            automaton.generation,
+            &automaton.grid,
+            automaton.rule_set.decay,
+        )?;
+        Ok(true)
+    }
+
+    /// Writes `grid` as the next frame regardless of the cadence,
+    /// for callers picking the generations with their own `Cadence`
+    pub fn write_frame(
+        &mut self,
+        generation: Generation,
+        grid: &[Vec<Cell>],
+        decay: usize,
+    ) -> ImageResult<()> {
+        let image = grid::to_image(grid, &self.theme, decay, self.scale);
+        match &mut self.sink {
+            Sink::Frames(directory) => {
+                image.save(directory.join(format!("{:08}.png", generation.0)))?;
+            }
+            Sink::Gif(encoder) => encoder.encode_frame(Frame::from_parts(
+                DynamicImage::ImageRgb8(image).into_rgba8(),
+                0,
+                0,
+                self.delay,
+            ))?,
+        }
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    pub const fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rle,
+        timelapse::{Cadence, Timelapse},
+        Automaton, Cell, Generation, Theme,
+    };
+    use std::fs;
+
+    #[test]
+    fn cadence_and_frames() {
+        let mut cadence = Cadence::new(10);
+        let captured = [3, 5, 9, 10, 11, 25, 26, 40]
+            .into_iter()
+            .filter(|&generation| cadence.tick(Generation(generation)))
+            .collect::<Vec<_>>();
+        assert_eq!(captured, [3, 10, 25, 40]);
+
+        let mut automaton = Automaton::builder()
+            .row_count(8)
+            .col_count(8)
+            .grid(vec![vec![Cell::Dead; 8]; 8])
+            .build();
+        automaton.stamp(&rle::parse("bo$2bo$3o!").unwrap(), 1, 1);
+        let directory = std::env::temp_dir().join("cellular_automata_timelapse");
+        let mut timelapse = Timelapse::create(&directory, 4, Theme::classic(), 2, 100).unwrap();
+        for _ in 0..10 {
+            timelapse.capture(&automaton).unwrap();
+            automaton.step();
+        }
+        assert_eq!(timelapse.frame_count(), 3);
+        let frame = image::open(directory.join("00000004.png")).unwrap();
+        assert_eq!((frame.width(), frame.height()), (16, 16));
+        fs::remove_dir_all(&directory).unwrap();
+
+        let path = std::env::temp_dir().join("cellular_automata_timelapse.gif");
+        let mut timelapse = Timelapse::create(&path, 1, Theme::classic(), 1, 50).unwrap();
+        for _ in 0..3 {
+            assert!(timelapse.capture(&automaton).unwrap());
+            automaton.step();
+        }
+        drop(timelapse);
+        assert!(fs::metadata(&path).unwrap().len() > 0);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{Cell, Grid};
+use itertools::izip;
+
+/// Render side memory of recently alive Cells, so moving patterns leave fading trails
+///
+/// Doesn't influence the simulation in any way.
+#[derive(Debug, Clone)]
+pub struct Trail {
+    frames_since_alive: Vec<Vec<usize>>,
+    length: usize,
+}
+
+impl Trail {
+    /// `length` is the number of frames it takes a trail to fade out completely
+    pub const fn new(length: usize) -> Self {
+        Self {
+            frames_since_alive: Vec::new(),
+            length,
+        }
+    }
+
+    /// Advances the trails by one frame showing `grid`.
+    /// A `grid` of different dimensions clears all trails.
+    pub fn observe(&mut self, grid: &Grid) {
+        let same_size = self.frames_since_alive.len() == grid.len()
+            && izip!(&self.frames_since_alive, grid).all(|(frames, row)| frames.len() == row.len());
+        if !same_size {
+            self.frames_since_alive = grid.iter().map(|row| vec![usize::MAX; row.len()]).collect();
+        }
+        for (frames_row, row) in izip!(&mut self.frames_since_alive, grid) {
+            for (frames, cell) in izip!(frames_row, row) {
+                *frames = if *cell == Cell::Alive {
+                    0
+                } else {
+                    frames.saturating_add(1)
+                };
+            }
+        }
+    }
+
+    /// Opacity of the trail at (`row`, `col`), from `1.0` right after the Cell stopped
+    /// being alive down to `0.0` once the trail faded out or while the Cell is alive
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fade(&self, row: usize, col: usize) -> f32 {
+        let frames = self
+            .frames_since_alive
+            .get(row)
+            .and_then(|frames_row| frames_row.get(col))
+            .copied()
+            .unwrap_or(usize::MAX);
+        if frames == 0 || frames > self.length {
+            0.0
+        } else {
+            (self.length + 1 - frames) as f32 / (self.length + 1) as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, Trail};
+
+    #[test]
+    fn trail_fades_out() {
+        let mut trail = Trail::new(2);
+        trail.observe(&vec![vec![Cell::Alive]]);
+        assert!(trail.fade(0, 0) < f32::EPSILON);
+
+        let dead = vec![vec![Cell::Dead]];
+        let fades = (0..3)
+            .map(|_| {
+                trail.observe(&dead);
+                trail.fade(0, 0)
+            })
+            .collect::<Vec<_>>();
+        assert!(fades[0] > fades[1] && fades[1] > 0.0);
+        assert!(fades[2] < f32::EPSILON);
+    }
+}
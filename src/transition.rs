@@ -0,0 +1,120 @@
+//! Stepping core shared by every frontend, it only needs `alloc`
+//! and builds without the `std` feature, e.g. for microcontrollers driving LED matrices.
+
+use crate::{
+    Cell, CellAccess, ColIdx, Grid, NeighborCounts, Neighborhood, RowIdx, RuleSet, RuleTable,
+};
+use alloc::vec::Vec;
+
+/// Positions of the neighbors of the Cell at (`row`, `col`), including ones past the bottom
+/// and right edges of the grid
+pub fn neighbor_positions(
+    neighborhood: Neighborhood,
+    row: usize,
+    col: usize,
+) -> impl Iterator<Item = (usize, usize)> + Clone {
+    (row.saturating_sub(1)..=row.saturating_add(1))
+        .flat_map(move |irow| {
+            (col.saturating_sub(1)..=col.saturating_add(1)).map(move |icol| (irow, icol))
+        })
+        .filter(move |&(irow, icol)| match neighborhood {
+            Neighborhood::Moore => irow != row || icol != col,
+            Neighborhood::VonNeumann => (irow == row) != (icol == col),
+        })
+}
+
+/// Neighbors of the Cell at (`row`, `col`), the edges of the grid don't wrap around
+pub fn neighbors(
+    grid: &[Vec<Cell>],
+    neighborhood: Neighborhood,
+    row: usize,
+    col: usize,
+) -> impl Iterator<Item = &Cell> {
+    neighbor_positions(neighborhood, row, col)
+        .filter_map(|(irow, icol)| grid.cell(RowIdx(irow), ColIdx(icol)))
+}
+
+/// Grid of the next generation, every Cell is transitioned by `next_state`.
+/// Stops at the first Cell whose transition fails.
+pub fn try_next_grid<E>(
+    grid: &[Vec<Cell>],
+    neighborhood: Neighborhood,
+    mut next_state: impl FnMut(&Cell, &NeighborCounts) -> Result<Cell, E>,
+) -> Result<Grid, E> {
+    grid.iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(|(col, cell)| {
+                    let neighbors = neighbors(grid, neighborhood, row, col).collect();
+                    next_state(cell, &neighbors)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Grid of the next generation according to `rule_set`, compiled into a `RuleTable` first.
+/// Prefer `compiled_next_grid` when stepping the same rules repeatedly.
+pub fn next_grid(grid: &[Vec<Cell>], neighborhood: Neighborhood, rule_set: &RuleSet) -> Grid {
+    compiled_next_grid(grid, neighborhood, &RuleTable::new(rule_set))
+}
+
+/// Grid of the next generation according to the rules compiled into `table`
+pub fn compiled_next_grid(
+    grid: &[Vec<Cell>],
+    neighborhood: Neighborhood,
+    table: &RuleTable,
+) -> Grid {
+    grid.iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(|(col, cell)| table.next_state(cell, neighbors(grid, neighborhood, row, col)))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        transition::{neighbors, next_grid},
+        Cell, Neighborhood, RuleSet,
+    };
+
+    #[test]
+    fn blinker() {
+        let horizontal = vec![
+            vec![Cell::Dead, Cell::Dead, Cell::Dead],
+            vec![Cell::Alive, Cell::Alive, Cell::Alive],
+            vec![Cell::Dead, Cell::Dead, Cell::Dead],
+        ];
+        let vertical = vec![vec![Cell::Dead, Cell::Alive, Cell::Dead]; 3];
+        let rule_set = RuleSet::default();
+        assert_eq!(
+            next_grid(&horizontal, Neighborhood::Moore, &rule_set),
+            vertical
+        );
+        assert_eq!(
+            next_grid(&vertical, Neighborhood::Moore, &rule_set),
+            horizontal
+        );
+
+        assert_eq!(neighbors(&horizontal, Neighborhood::Moore, 0, 0).count(), 3);
+        assert_eq!(
+            neighbors(&horizontal, Neighborhood::VonNeumann, 1, 1).count(),
+            4
+        );
+        assert_eq!(
+            neighbors(&horizontal, Neighborhood::VonNeumann, 1, 1)
+                .filter(|cell| **cell == Cell::Alive)
+                .count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{localization::Localizer, theme::blend, Automaton, Cell, Theme, Trail};
+use std::fmt::Write;
+
+/// Width of the row labels in front of every row when rendering axes
+const ROW_LABEL_WIDTH: usize = 4;
+
+/// One line summary of the generation, population and rules of `automaton`
+pub fn status_line(automaton: &Automaton) -> String {
+    localized_status_line(automaton, &Localizer::default())
+}
+
+/// `status_line` in the language of `localizer`
+pub fn localized_status_line(automaton: &Automaton, localizer: &Localizer) -> String {
+    localizer.format(
+        "status-line",
+        [
+            ("generation", automaton.generation.to_string().into()),
+            ("population", automaton.population().into()),
+            ("rules", automaton.rule_set.to_string().into()),
+        ],
+    )
+}
+
+/// Glyph of each state in the emoji renderer
+pub const fn glyph(cell: &Cell) -> &'static str {
+    match cell {
+        Cell::Dead => "⬛",
+        Cell::Alive => "⬜",
+        Cell::Dying { .. } => "🟫",
+    }
+}
+
+/// Renders the grid with one emoji per Cell, each row enclosed in brackets.
+/// With `axes` every row is prefixed by its index and a column index header is added.
+pub fn render_emoji(automaton: &Automaton, axes: bool) -> String {
+    let mut out = String::new();
+    if axes {
+        out.push_str(&column_axis(automaton.col_count, ROW_LABEL_WIDTH + 1));
+    }
+    for (row, cells) in automaton.grid.iter().enumerate() {
+        if axes {
+            out.push_str(&row_label(row));
+        }
+        out.push('[');
+        out.extend(cells.iter().map(glyph));
+        out.push_str("]\n");
+    }
+    out
+}
+
+/// Two character glyph of each state in the monochrome renderer, which tells dying Cells
+/// apart by shade instead of color. They get lighter during the `decay` generations.
+pub const fn monochrome_glyph(cell: &Cell, decay: usize) -> &'static str {
+    match cell {
+        Cell::Dead => "  ",
+        Cell::Alive => "██",
+        Cell::Dying { ticks_till_death } if *ticks_till_death * 3 > decay * 2 => "▓▓",
+        Cell::Dying { ticks_till_death } if *ticks_till_death * 3 > decay => "▒▒",
+        Cell::Dying { .. } => "░░",
+    }
+}
+
+/// Renders the grid without colors or emoji for terminals lacking them or users who can't tell
+/// them apart, see `monochrome_glyph`.
+///
+/// The grid is framed as the dead Cells are blank. `axes` adds row and column indices like in
+/// `render_emoji`.
+pub fn render_monochrome(automaton: &Automaton, axes: bool) -> String {
+    let mut out = String::new();
+    // The frame starts where the row labels end
+    let indent = if axes { ROW_LABEL_WIDTH } else { 0 };
+    if axes {
+        out.push_str(&column_axis(automaton.col_count, indent + 1));
+    }
+    let border = "─".repeat(automaton.col_count * 2);
+    // Writing to a String can't fail
+    let _ = writeln!(out, "{:indent$}┌{border}┐", "");
+    for (row, cells) in automaton.grid.iter().enumerate() {
+        if axes {
+            out.push_str(&row_label(row));
+        }
+        out.push('│');
+        out.extend(
+            cells
+                .iter()
+                .map(|cell| monochrome_glyph(cell, automaton.rule_set.decay)),
+        );
+        out.push_str("│\n");
+    }
+    // Writing to a String can't fail
+    let _ = writeln!(out, "{:indent$}└{border}┘", "");
+    out
+}
+
+/// Renders the XOR `difference` of two grids, differing Cells are red
+pub fn render_difference(difference: &[Vec<bool>], axes: bool) -> String {
+    let mut out = String::new();
+    if axes {
+        let col_count = difference.iter().map(Vec::len).max().unwrap_or_default();
+        out.push_str(&column_axis(col_count, ROW_LABEL_WIDTH + 1));
+    }
+    for (row, cells) in difference.iter().enumerate() {
+        if axes {
+            out.push_str(&row_label(row));
+        }
+        out.push('[');
+        out.extend(
+            cells
+                .iter()
+                .map(|&differs| if differs { "🟥" } else { "⬛" }),
+        );
+        out.push_str("]\n");
+    }
+    out
+}
+
+/// Renders the grid with 24 bit ANSI background colors of the `theme`, two columns per Cell.
+///
+/// Dead Cells with a `trail` are tinted according to how recently they died.
+/// `axes` adds row and column indices like in `render_emoji`.
+pub fn render_color(
+    automaton: &Automaton,
+    theme: &Theme,
+    trail: Option<&Trail>,
+    axes: bool,
+) -> String {
+    let mut out = String::new();
+    if axes {
+        out.push_str(&column_axis(automaton.col_count, ROW_LABEL_WIDTH));
+    }
+    for (row, cells) in automaton.grid.iter().enumerate() {
+        if axes {
+            out.push_str(&row_label(row));
+        }
+        for (col, cell) in cells.iter().enumerate() {
+            let [r, g, b] = match cell {
+                Cell::Dead => {
+                    let fade = trail.map_or(0.0, |trail| trail.fade(row, col));
+                    blend(theme.dead, theme.trail, fade)
+                }
+                cell => theme.cell_color(cell, automaton.rule_set.decay),
+            };
+            // Writing to a String can't fail
+            let _ = write!(out, "\x1b[48;2;{r};{g};{b}m  ");
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Column indices modulo 100, two characters per column
+fn column_axis(col_count: usize, indent: usize) -> String {
+    let mut axis = " ".repeat(indent);
+    for col in 0..col_count {
+        // Writing to a String can't fail
+        let _ = write!(axis, "{:>2}", col % 100);
+    }
+    axis.push('\n');
+    axis
+}
+
+fn row_label(row: usize) -> String {
+    format!("{row:>width$} ", width = ROW_LABEL_WIDTH - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_emoji, render_monochrome};
+    use crate::{Automaton, Cell, RuleSet};
+
+    #[test]
+    fn emoji_axes() {
+        let automaton = Automaton::builder()
+            .row_count(2)
+            .col_count(2)
+            .grid(vec![vec![Cell::Alive, Cell::Dead]; 2])
+            .build();
+        assert_eq!(render_emoji(&automaton, false), "[⬜⬛]\n[⬜⬛]\n");
+        assert_eq!(
+            render_emoji(&automaton, true),
+            "      0 1\n  0 [⬜⬛]\n  1 [⬜⬛]\n"
+        );
+    }
+
+    #[test]
+    fn monochrome() {
+        let mut automaton = Automaton::builder()
+            .row_count(1)
+            .col_count(3)
+            .grid(vec![vec![
+                Cell::Alive,
+                Cell::Dying {
+                    ticks_till_death: 3,
+                },
+                Cell::Dying {
+                    ticks_till_death: 1,
+                },
+            ]])
+            .build();
+        automaton.rule_set = RuleSet::life_like(vec![3], vec![2, 3], 3);
+        assert_eq!(
+            render_monochrome(&automaton, false),
+            "┌──────┐\n│██▓▓░░│\n└──────┘\n"
+        );
+        assert_eq!(
+            render_monochrome(&automaton, true),
+            "      0 1 2\n    ┌──────┐\n  0 │██▓▓░░│\n    └──────┘\n"
+        );
+    }
+}
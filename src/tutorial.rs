@@ -0,0 +1,264 @@
+//! Scripted tutorials teaching newcomers the editor one step at a time, loaded from RON:
+//!
+//! ```ron
+//! (
+//!     name: "Gliders",
+//!     steps: [
+//!         (
+//!             text: "Open the Patterns window and place a glider",
+//!             highlight: Some("patterns"),
+//!             goal: Pattern("bo$2bo$3o!"),
+//!         ),
+//!         (
+//!             text: "Change the rule to HighLife in the Rules window",
+//!             highlight: Some("rules"),
+//!             goal: Rule("B36/S23"),
+//!         ),
+//!     ],
+//! )
+//! ```
+
+use crate::{
+    analysis::{self, canonical},
+    rle,
+    rules::NotationError,
+    Automaton, Grid, RuleSet,
+};
+use serde::Deserialize;
+use std::{error, fmt, fs, io, path::Path};
+
+/// Shipped with the crate, introducing the controls and the editor
+const GETTING_STARTED: &str = r#"(
+    name: "Getting started",
+    steps: [
+        (
+            text: "Welcome! The grid evolves by itself, every Cell lives or dies depending on how many of its 8 neighbors are alive. Press Space to pause and resume it, N to step once while paused.",
+            goal: Continue,
+        ),
+        (
+            text: "Scroll to zoom, drag with the middle mouse button to pan and press F to fit the living Cells into the window.",
+            goal: Continue,
+        ),
+        (
+            text: "Open the Patterns window, pick the glider and click on the grid to place it.",
+            highlight: Some("patterns"),
+            goal: Pattern("bo$2bo$3o!"),
+        ),
+        (
+            text: "Gliders travel diagonally. Now change the rule to HighLife, B36/S23, in the Rules window.",
+            highlight: Some("rules"),
+            goal: Rule("B36/S23"),
+        ),
+        (
+            text: "HighLife has a replicator. Let the simulation run until generation 500 and watch what the soup turns into.",
+            goal: Generation(500),
+        ),
+    ],
+)"#;
+
+/// Errors that can occur while loading a `Tutorial`
+/// - `Io` => The file couldn't be read
+/// - `Ron` => The file isn't a valid RON tutorial, or one of its goals is invalid
+#[derive(Debug)]
+pub enum TutorialError {
+    Io(io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for TutorialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't read tutorial: {e}"),
+            Self::Ron(e) => write!(f, "Invalid tutorial: {e}"),
+        }
+    }
+}
+
+impl error::Error for TutorialError {}
+
+impl From<io::Error> for TutorialError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ron::error::SpannedError> for TutorialError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Ron(value)
+    }
+}
+
+/// Errors that can occur while reading a `Goal`
+/// - `Pattern` => The pattern isn't valid RLE
+/// - `Objects` => The pattern isn't a single object but this many, see `analysis::objects`
+/// - `Rule` => The rule isn't valid B/S notation
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GoalError {
+    Pattern(rle::ParseError),
+    Objects(usize),
+    Rule(NotationError),
+}
+
+impl fmt::Display for GoalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pattern(e) => write!(f, "Invalid goal pattern: {e}"),
+            Self::Objects(count) => {
+                write!(f, "Goal pattern has {count} objects instead of one")
+            }
+            Self::Rule(e) => write!(f, "Invalid goal rule: {e}"),
+        }
+    }
+}
+
+impl error::Error for GoalError {}
+
+/// A goal as written in RON, before its pattern or rule is parsed
+#[derive(Deserialize)]
+enum RawGoal {
+    Continue,
+    Pattern(String),
+    Rule(String),
+    Generation(u64),
+    Population(usize),
+}
+
+/// What has to happen for a step to be done
+/// - `Continue` => Confirmed by the user after reading the step
+/// - `Pattern` => An object shaped like this RLE pattern in any rotation or reflection is on
+///   the grid, kept in the `analysis::canonical` orientation
+/// - `Rule` => The rules are switched to this B/S notation, however they're written
+/// - `Generation` => This generation was reached
+/// - `Population` => At least this many Cells are alive
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(try_from = "RawGoal")]
+pub enum Goal {
+    Continue,
+    Pattern(Grid),
+    Rule(RuleSet),
+    Generation(u64),
+    Population(usize),
+}
+
+impl TryFrom<RawGoal> for Goal {
+    type Error = GoalError;
+
+    fn try_from(value: RawGoal) -> Result<Self, Self::Error> {
+        Ok(match value {
+            RawGoal::Continue => Self::Continue,
+            RawGoal::Pattern(text) => {
+                let pattern = rle::parse(&text).map_err(GoalError::Pattern)?;
+                match analysis::objects(&pattern).as_slice() {
+                    [object] => Self::Pattern(canonical(object)),
+                    objects => return Err(GoalError::Objects(objects.len())),
+                }
+            }
+            RawGoal::Rule(notation) => Self::Rule(notation.parse().map_err(GoalError::Rule)?),
+            RawGoal::Generation(generation) => Self::Generation(generation),
+            RawGoal::Population(population) => Self::Population(population),
+        })
+    }
+}
+
+impl Goal {
+    /// Whether `automaton` fulfills the goal, never for `Goal::Continue`
+    pub fn reached(&self, automaton: &Automaton) -> bool {
+        match self {
+            Self::Continue => false,
+            Self::Pattern(pattern) => analysis::objects(&automaton.grid)
+                .iter()
+                .any(|object| canonical(object) == *pattern),
+            Self::Rule(rule_set) => automaton.rule_set.to_string() == rule_set.to_string(),
+            Self::Generation(generation) => automaton.generation.0 >= *generation,
+            Self::Population(population) => automaton.population() >= *population,
+        }
+    }
+}
+
+/// One instruction of a tutorial
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Step {
+    pub text: String,
+    /// Id of the window the step is about, outlined while the step is shown, e.g. `patterns`
+    #[serde(default)]
+    pub highlight: Option<String>,
+    pub goal: Goal,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Tutorial {
+    pub name: String,
+    pub steps: Vec<Step>,
+}
+
+impl Tutorial {
+    pub fn from_ron(text: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(text)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TutorialError> {
+        Ok(Self::from_ron(&fs::read_to_string(path)?)?)
+    }
+
+    /// The tutorial shipped with the crate
+    pub fn getting_started() -> Self {
+        Self::from_ron(GETTING_STARTED).expect("The embedded tutorial is valid")
+    }
+
+    /// Index of the step following `step` once `automaton` reached its goal, or `step` itself.
+    /// Steps are done in order, so `Goal::Continue` steps hold it until confirmed.
+    pub fn advance(&self, step: usize, automaton: &Automaton) -> usize {
+        self.steps
+            .get(step)
+            .filter(|current| current.goal.reached(automaton))
+            .map_or(step, |_| step + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rle,
+        tutorial::{Goal, Tutorial},
+        Automaton, Cell, RuleSet,
+    };
+
+    #[test]
+    fn steps_from_ron() {
+        let tutorial = Tutorial::getting_started();
+        assert_eq!(tutorial.steps.len(), 5);
+        assert_eq!(tutorial.steps[2].highlight.as_deref(), Some("patterns"));
+        assert!(
+            Tutorial::from_ron("(name: \"Bad\", steps: [(text: \"\", goal: Rule(\"B9\"))])")
+                .is_err()
+        );
+        assert!(Tutorial::from_ron(
+            "(name: \"Bad\", steps: [(text: \"\", goal: Pattern(\"o3bo!\"))])"
+        )
+        .is_err());
+
+        let mut automaton = Automaton::builder()
+            .row_count(10)
+            .col_count(10)
+            .grid(vec![vec![Cell::Dead; 10]; 10])
+            .build();
+        assert_eq!(tutorial.advance(0, &automaton), 0);
+        assert_eq!(tutorial.advance(2, &automaton), 2);
+        // Any orientation of the glider counts
+        automaton.stamp(&rle::parse("3o$o$bo!").unwrap(), 4, 4);
+        assert_eq!(tutorial.advance(2, &automaton), 3);
+        assert_eq!(tutorial.advance(3, &automaton), 3);
+        automaton.rule_set = RuleSet::life_like(vec![3, 6], vec![2, 3], 0);
+        assert_eq!(tutorial.advance(3, &automaton), 4);
+        assert!(!Goal::Population(6).reached(&automaton));
+        assert!(Goal::Population(5).reached(&automaton));
+    }
+
+    #[test]
+    fn rules_reached_however_written() {
+        let automaton = Automaton::builder().row_count(4).col_count(4).build();
+        // The default rules are written with ranges, the goal with single counts
+        assert!(Goal::Rule("B3/S23".parse().unwrap()).reached(&automaton));
+        assert!(!Goal::Rule("B36/S23".parse().unwrap()).reached(&automaton));
+    }
+}
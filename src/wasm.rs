@@ -0,0 +1,103 @@
+use crate::{Automaton, Cell, CellAccess, ColIdx, RowIdx, RuleSet, Seeding};
+use wasm_bindgen::prelude::*;
+
+/// The engine for web pages drawing on their own canvases, exported as `Automaton`
+#[wasm_bindgen(js_name = Automaton)]
+pub struct WasmAutomaton {
+    automaton: Automaton,
+}
+
+#[wasm_bindgen(js_class = Automaton)]
+impl WasmAutomaton {
+    /// Conway's Game of Life on a grid where every Cell is alive with probability `density`
+    #[wasm_bindgen(constructor)]
+    pub fn new(rows: usize, cols: usize, density: f64) -> Self {
+        Self {
+            automaton: Automaton::builder()
+                .row_count(rows)
+                .col_count(cols)
+                .seeding(Seeding::Uniform { density })
+                .build(),
+        }
+    }
+
+    /// Switches to Life-like rules, see `RuleSet::life_like`
+    #[wasm_bindgen(js_name = setRules)]
+    pub fn set_rules(&mut self, birth: Vec<usize>, survival: Vec<usize>, decay: usize) {
+        self.automaton.rule_set = RuleSet::life_like(birth, survival, decay);
+    }
+
+    /// Rules in B/S notation
+    pub fn rules(&self) -> String {
+        self.automaton.rule_set.to_string()
+    }
+
+    pub fn step(&mut self) {
+        self.automaton.step();
+    }
+
+    pub fn advance(&mut self, generations: usize) {
+        self.automaton.advance(generations);
+    }
+
+    /// Generation as a JavaScript number instead of a `BigInt`
+    #[allow(clippy::cast_precision_loss)]
+    #[wasm_bindgen(getter)]
+    pub fn generation(&self) -> f64 {
+        self.automaton.generation.0 as f64
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rows(&self) -> usize {
+        self.automaton.row_count
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn cols(&self) -> usize {
+        self.automaton.col_count
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn population(&self) -> usize {
+        self.automaton.population()
+    }
+
+    /// The grid row by row as an `Uint8Array` of one byte per Cell:
+    /// `0` is dead, `1` alive and `n >= 2` dying with `n - 1` generations left
+    pub fn cells(&self) -> Vec<u8> {
+        self.automaton
+            .grid
+            .iter()
+            .flatten()
+            .map(Cell::state_byte)
+            .collect()
+    }
+
+    /// Makes the Cell at (`row`, `col`) alive or dead, ignored outside of the grid
+    #[wasm_bindgen(js_name = setAlive)]
+    pub fn set_alive(&mut self, row: usize, col: usize, alive: bool) {
+        let cell = if alive { Cell::Alive } else { Cell::Dead };
+        self.automaton.grid.set_cell(RowIdx(row), ColIdx(col), cell);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wasm::WasmAutomaton;
+
+    #[test]
+    fn cells() {
+        let mut automaton = WasmAutomaton::new(3, 3, 0.0);
+        for col in 0..3 {
+            automaton.set_alive(1, col, true);
+        }
+        automaton.set_alive(5, 5, true);
+        assert_eq!(automaton.cells(), [0, 0, 0, 1, 1, 1, 0, 0, 0]);
+
+        automaton.set_rules(vec![3], vec![2], 2);
+        automaton.step();
+        assert_eq!(automaton.cells(), [0, 1, 0, 3, 1, 3, 0, 1, 0]);
+        assert_eq!(automaton.generation(), 1.0);
+        assert_eq!(automaton.population(), 3);
+    }
+}
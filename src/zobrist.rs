@@ -0,0 +1,133 @@
+//! Zobrist hashing of grids: every state at every position has a pseudo random key and a grid
+//! hashes to the XOR of the keys of its Cells.
+//!
+//! A generation only XORs the keys of the Cells it changed in and out, so the hash of a huge
+//! grid stays cheap to keep up to date. Dead Cells have no key, so sparse grids are cheap to
+//! hash from scratch too.
+
+use crate::{Automaton, Cell, Generation};
+
+/// One round of `SplitMix64`, spreads every input bit over the whole output
+const fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Key of `cell` at (`row`, `col`), `0` for `Cell::Dead`.
+/// The keys are derived rather than drawn, so hashes are the same in every run and release.
+pub fn key(row: usize, col: usize, cell: &Cell) -> u64 {
+    match cell.state_byte() {
+        0 => 0,
+        state => splitmix64(splitmix64(splitmix64(row as u64) ^ col as u64) ^ u64::from(state)),
+    }
+}
+
+/// Key of the size of the grid, so equal Cells on grids of different sizes hash differently
+const fn size_key(row_count: usize, col_count: usize) -> u64 {
+    splitmix64(splitmix64(!(row_count as u64)) ^ col_count as u64)
+}
+
+/// Hash of `grid` from scratch
+pub fn hash(grid: &[Vec<Cell>]) -> u64 {
+    let col_count = grid.first().map_or(0, Vec::len);
+    let cells = grid.iter().enumerate().flat_map(|(row, cells)| {
+        cells
+            .iter()
+            .enumerate()
+            .map(move |(col, cell)| key(row, col, cell))
+    });
+    cells.fold(size_key(grid.len(), col_count), |hash, key| hash ^ key)
+}
+
+/// The hash of a grid as of some generation, kept up to date with the `Automaton::changes` of
+/// following generations.
+///
+/// Grids edited without stepping have to be rehashed with `Zobrist::new`, as the changes only
+/// cover stepping.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Zobrist {
+    hash: u64,
+    generation: Generation,
+    size: (usize, usize),
+}
+
+impl Zobrist {
+    pub fn new(automaton: &Automaton) -> Self {
+        Self {
+            hash: hash(&automaton.grid),
+            generation: automaton.generation,
+            size: (automaton.row_count, automaton.col_count),
+        }
+    }
+
+    pub const fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Catches up with the current generation of `automaton`. Only XORs the `changes` if it's
+    /// the generation following the hashed one and has `track_changes` set, otherwise the
+    /// whole grid is rehashed.
+    pub fn update(&mut self, automaton: &Automaton) -> u64 {
+        let incremental = automaton.track_changes
+            && automaton.generation.0 == self.generation.0 + 1
+            && (automaton.row_count, automaton.col_count) == self.size;
+        if incremental {
+            for change in &automaton.changes {
+                let after = &automaton.grid[change.row][change.col];
+                self.hash ^= key(change.row, change.col, &change.before)
+                    ^ key(change.row, change.col, after);
+            }
+            self.generation = automaton.generation;
+        } else if automaton.generation != self.generation
+            || (automaton.row_count, automaton.col_count) != self.size
+        {
+            *self = Self::new(automaton);
+        }
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rle,
+        zobrist::{self, Zobrist},
+        Automaton, Cell,
+    };
+
+    #[test]
+    fn incremental_equals_from_scratch() {
+        let mut automaton = Automaton::builder()
+            .row_count(16)
+            .col_count(16)
+            .grid(vec![vec![Cell::Dead; 16]; 16])
+            .track_changes(true)
+            .build();
+        automaton.stamp(&rle::parse("bo$2bo$3o!").unwrap(), 1, 1);
+        automaton.stamp(&rle::parse("3o!").unwrap(), 10, 10);
+        let mut zobrist = Zobrist::new(&automaton);
+        let start = zobrist.hash();
+        for _ in 0..8 {
+            automaton.step();
+            assert_eq!(zobrist.update(&automaton), zobrist::hash(&automaton.grid));
+        }
+        assert_ne!(zobrist.hash(), start);
+        // The glider and the blinker each change 4 Cells per generation
+        assert_eq!(automaton.changes.len(), 8);
+
+        // Skipped generations and untracked changes are rehashed
+        automaton.advance(2);
+        automaton.track_changes = false;
+        automaton.step();
+        assert!(automaton.changes.is_empty());
+        assert_eq!(zobrist.update(&automaton), zobrist::hash(&automaton.grid));
+
+        assert_eq!(zobrist::key(3, 4, &Cell::Dead), 0);
+        assert_ne!(
+            zobrist::hash(&vec![vec![Cell::Dead; 2]; 3]),
+            zobrist::hash(&vec![vec![Cell::Dead; 3]; 2])
+        );
+    }
+}
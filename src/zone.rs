@@ -0,0 +1,301 @@
+//! Zones of the grid following rules of their own, so patterns can be watched migrating between
+//! regimes in one universe. Cells outside of every zone follow the rules of the automaton.
+
+use crate::{
+    grid::BoundingBox, rules::NotationError, transition, Automaton, Cell, Grid, Neighborhood,
+    RuleSet, RuleTable,
+};
+use serde::Deserialize;
+use std::{error, fmt, fs, io, path::Path};
+
+/// Errors that can occur while loading `Zones`
+/// - `Io` => The file couldn't be read
+/// - `Toml` => The file isn't valid TOML zones, e.g. one of the rules isn't B/S notation
+#[derive(Debug)]
+pub enum ZonesError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for ZonesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Couldn't read zones: {e}"),
+            Self::Toml(e) => write!(f, "Invalid zones: {e}"),
+        }
+    }
+}
+
+impl error::Error for ZonesError {}
+
+impl From<io::Error> for ZonesError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<toml::de::Error> for ZonesError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::Toml(value)
+    }
+}
+
+/// How Cells interact across the border between two zones, or a zone and the rest of the grid
+/// - `Open` => Neighbors in other zones count, only the rules differ
+/// - `Closed` => Neighbors in other zones don't count, as if the zone was walled in by dead Cells
+/// - `Absorbing` => Cells with a neighbor in another zone die, nothing crosses the border
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Border {
+    #[default]
+    Open,
+    Closed,
+    Absorbing,
+}
+
+impl Border {
+    pub const ALL: [Self; 3] = [Self::Open, Self::Closed, Self::Absorbing];
+}
+
+impl fmt::Display for Border {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Closed => write!(f, "closed"),
+            Self::Absorbing => write!(f, "absorbing"),
+        }
+    }
+}
+
+/// A zone as written in TOML, before its rules are parsed
+#[derive(Deserialize)]
+struct RawZone {
+    top: usize,
+    left: usize,
+    bottom: usize,
+    right: usize,
+    rules: String,
+}
+
+/// Inclusive rows and columns following `rule_set`
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(try_from = "RawZone")]
+pub struct Zone {
+    pub bounds: BoundingBox,
+    pub rule_set: RuleSet,
+}
+
+impl TryFrom<RawZone> for Zone {
+    type Error = NotationError;
+
+    fn try_from(value: RawZone) -> Result<Self, Self::Error> {
+        let bounds = BoundingBox {
+            top: value.top,
+            left: value.left,
+            bottom: value.bottom,
+            right: value.right,
+        };
+        Ok(Self::new(bounds, value.rules.parse()?))
+    }
+}
+
+impl Zone {
+    pub const fn new(bounds: BoundingBox, rule_set: RuleSet) -> Self {
+        Self { bounds, rule_set }
+    }
+
+    pub const fn contains(&self, row: usize, col: usize) -> bool {
+        self.bounds.top <= row
+            && row <= self.bounds.bottom
+            && self.bounds.left <= col
+            && col <= self.bounds.right
+    }
+}
+
+/// Zones laid over the grid, later ones cover earlier ones where they overlap.
+/// Loadable from TOML:
+///
+/// ```toml
+/// border = "closed"
+///
+/// [[zones]]
+/// top = 0
+/// left = 32
+/// bottom = 63
+/// right = 63
+/// rules = "B36/S23"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize)]
+pub struct Zones {
+    #[serde(default)]
+    pub border: Border,
+    #[serde(default)]
+    pub zones: Vec<Zone>,
+}
+
+impl Zones {
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ZonesError> {
+        Ok(Self::from_toml(&fs::read_to_string(path)?)?)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// Index into `zones` of the zone covering (`row`, `col`), `None` outside of every zone
+    pub fn zone_at(&self, row: usize, col: usize) -> Option<usize> {
+        self.zones.iter().rposition(|zone| zone.contains(row, col))
+    }
+
+    pub fn step(&self, automaton: &mut Automaton) {
+        self.advance(automaton, 1);
+    }
+
+    /// Steps `automaton` `generations` times, each Cell with the rules of its zone.
+    /// Without zones it's the same as `Automaton::advance`.
+    pub fn advance(&self, automaton: &mut Automaton, generations: usize) {
+        if self.is_empty() {
+            automaton.advance(generations);
+            return;
+        }
+        let mut tables = self
+            .zones
+            .iter()
+            .map(|zone| RuleTable::new(&zone.rule_set))
+            .collect::<Vec<_>>();
+        // The rest of the grid comes last, at the index `labels` gives it
+        tables.push(RuleTable::new(&automaton.rule_set));
+        let labels = (0..automaton.row_count)
+            .map(|row| {
+                (0..automaton.col_count)
+                    .map(|col| self.zone_at(row, col).unwrap_or(self.zones.len()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..generations {
+            let next = self.next_grid(
+                &automaton.grid,
+                automaton.neighborhood_type,
+                &tables,
+                &labels,
+            );
+            automaton.replace_grid(next);
+        }
+    }
+
+    fn next_grid(
+        &self,
+        grid: &[Vec<Cell>],
+        neighborhood: Neighborhood,
+        tables: &[RuleTable],
+        labels: &[Vec<usize>],
+    ) -> Grid {
+        let label = |row: usize, col: usize| labels.get(row).and_then(|row| row.get(col));
+        grid.iter()
+            .enumerate()
+            .map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(col, cell)| {
+                        let zone = label(row, col).copied().unwrap_or(self.zones.len());
+                        let positions = transition::neighbor_positions(neighborhood, row, col)
+                            .filter(|&(irow, icol)| irow < grid.len() && icol < cells.len());
+                        let inside =
+                            |&(irow, icol): &(usize, usize)| label(irow, icol) == Some(&zone);
+                        if self.border == Border::Absorbing
+                            && !positions.clone().all(|position| inside(&position))
+                        {
+                            return Cell::Dead;
+                        }
+                        let counted = positions
+                            .filter(|position| self.border != Border::Closed || inside(position));
+                        tables[zone].next_state(cell, counted.map(|(irow, icol)| &grid[irow][icol]))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        grid::BoundingBox,
+        rle,
+        zone::{Border, Zone, Zones},
+        Automaton, Cell, RuleSet,
+    };
+
+    #[test]
+    fn from_toml() {
+        let zones = Zones::from_toml(
+            "border = \"absorbing\"\n\n[[zones]]\ntop = 1\nleft = 2\nbottom = 3\nright = 4\n\
+             rules = \"B36/S23\"\n",
+        )
+        .unwrap();
+        assert_eq!(zones.border, Border::Absorbing);
+        assert_eq!(zones.zones[0].bounds.right, 4);
+        assert!(Zones::from_toml(
+            "[[zones]]\ntop = 0\nleft = 0\nbottom = 1\nright = 1\nrules = \"S23\"\n"
+        )
+        .is_err());
+        assert_eq!(Zones::from_toml("").unwrap(), Zones::default());
+    }
+
+    #[test]
+    fn borders() {
+        let right_half = BoundingBox {
+            top: 0,
+            left: 4,
+            bottom: 7,
+            right: 7,
+        };
+        let life: RuleSet = "B3/S23".parse().unwrap();
+        let mut automaton = Automaton::builder()
+            .row_count(8)
+            .col_count(8)
+            .grid(vec![vec![Cell::Dead; 8]; 8])
+            .rule_set(life.clone())
+            .build();
+        // A blinker across the border and a block well within the zone
+        automaton.stamp(&rle::parse("3o!").unwrap(), 4, 3);
+        automaton.stamp(&rle::parse("2o$2o!").unwrap(), 0, 6);
+        let mut zones = Zones {
+            border: Border::Open,
+            zones: vec![Zone::new(right_half, life)],
+        };
+        assert_eq!(zones.zone_at(4, 4), Some(0));
+        assert_eq!(zones.zone_at(4, 3), None);
+
+        let run = |zones: &Zones, generations| {
+            let mut stepped = automaton.clone();
+            zones.advance(&mut stepped, generations);
+            stepped
+        };
+        let mut expected = automaton.clone();
+        expected.advance(2);
+        assert_eq!(run(&zones, 2).grid, expected.grid);
+        assert_eq!(run(&zones, 2).generation, expected.generation);
+
+        // Split in two, neither part of the blinker has enough neighbors left
+        zones.border = Border::Closed;
+        assert_eq!(run(&zones, 1).population(), 4);
+        zones.border = Border::Absorbing;
+        let absorbed = run(&zones, 1);
+        assert_eq!(absorbed.population(), 4);
+        assert!(absorbed
+            .grid
+            .iter()
+            .all(|row| row[3..=4] == [Cell::Dead, Cell::Dead]));
+
+        // Nothing survives in the zone, nor is born next to it
+        zones.border = Border::Open;
+        zones.zones[0].rule_set = "B/S".parse().unwrap();
+        assert_eq!(run(&zones, 1).population(), 0);
+    }
+}